@@ -0,0 +1,198 @@
+// Filename: accessibility.rs
+// Project: EntropicRust
+// Description: Accessibility mode for class demos. Switching systems, pausing and resuming,
+//              and parameter changes are announced: spoken through the platform's speech
+//              synthesizer (speech-dispatcher or eSpeak on Linux, `say` on macOS, the .NET
+//              System.Speech voice on Windows) and shown as large white-on-black captions
+//              along the bottom of the window, which otherwise carry the current system and
+//              its parameter values. A held parameter key is announced once, with its final
+//              value, when the key has settled. Without a synthesizer, captions remain.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::process::{Child, Command, Stdio};
+use std::time::Instant;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::locale::Locale;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::system_parameters::{get_parameter_info, SystemParameters};
+
+// Seconds a parameter has to stay put before its new value is announced.
+const SETTLE_SECS: f32 = 0.6;
+// Seconds an announcement stays in the caption band.
+const CAPTION_SECS: f32 = 6.0;
+const CAPTION_SIZE: f32 = 28.0;
+// Padding around the captions, in pixels.
+const MARGIN: f32 = 10.0;
+
+// Speech synthesizers to try, in order of preference.
+#[cfg(windows)]
+const VOICES: &[&str] = &["powershell"];
+#[cfg(target_os = "macos")]
+const VOICES: &[&str] = &["say"];
+#[cfg(not(any(windows, target_os = "macos")))]
+const VOICES: &[&str] = &["spd-say", "espeak-ng", "espeak"];
+// The text reaches PowerShell through the environment, so it is never parsed as script.
+const SPEECH_VARIABLE: &str = "ENTROPICRUST_SPEAK";
+const POWERSHELL_SPEAK: &str = "Add-Type -AssemblyName System.Speech; \
+    (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:ENTROPICRUST_SPEAK)";
+
+pub struct Announcer {
+    system_type: SystemType,
+    parameters: SystemParameters,
+    paused: bool,
+    // When the parameters last changed without having been announced yet.
+    changed_at: Option<Instant>,
+    // Parameters as last announced, to tell which ones a change touched.
+    announced: SystemParameters,
+    caption: Option<(String, Instant)>,
+    // Index into VOICES of the synthesizer in use; None once none could be started.
+    voice: Option<usize>,
+    speaking: Option<Child>,
+}
+
+impl Announcer {
+    pub fn new(system_type: SystemType, parameters: SystemParameters, paused: bool, speech: bool) -> Self {
+        Announcer {
+            system_type,
+            parameters,
+            paused,
+            changed_at: None,
+            announced: parameters,
+            caption: None,
+            voice: speech.then_some(0),
+            speaking: None,
+        }
+    }
+
+    // Compares the state with the last frame's and announces what changed. Returns an error
+    // once, when no speech synthesizer could be started.
+    pub fn observe(
+        &mut self,
+        system_type: SystemType,
+        parameters: &SystemParameters,
+        paused: bool,
+        locale: Locale,
+    ) -> Result<(), String> {
+        if system_type != self.system_type {
+            self.system_type = system_type;
+            self.parameters = *parameters;
+            self.announced = *parameters;
+            self.changed_at = None;
+            return self.announce(format!("{} system. {}", system_type.name(), self.summary(locale)));
+        }
+        if paused != self.paused {
+            self.paused = paused;
+            return self.announce(if paused { "Paused" } else { "Running" }.to_string());
+        }
+        if *parameters != self.parameters {
+            self.parameters = *parameters;
+            self.changed_at = Some(Instant::now());
+        }
+        if self.changed_at.is_some_and(|at| at.elapsed().as_secs_f32() >= SETTLE_SECS) {
+            self.changed_at = None;
+            let changed: Vec<String> = get_parameter_info(system_type)
+                .iter()
+                .filter(|p| parameters.get(p.name) != self.announced.get(p.name))
+                .map(|p| format!("{} {}", p.label, locale.significant(parameters.get(p.name).unwrap_or(0.0))))
+                .collect();
+            self.announced = *parameters;
+            if !changed.is_empty() {
+                return self.announce(changed.join(". "));
+            }
+        }
+        Ok(())
+    }
+
+    // The current system's parameters, as read out after a switch.
+    fn summary(&self, locale: Locale) -> String {
+        get_parameter_info(self.system_type)
+            .iter()
+            .map(|p| format!("{} {}", p.label, locale.significant(self.parameters.get(p.name).unwrap_or(0.0))))
+            .collect::<Vec<_>>()
+            .join(". ")
+    }
+
+    fn announce(&mut self, text: String) -> Result<(), String> {
+        self.caption = Some((text.clone(), Instant::now()));
+        self.speak(&text)
+    }
+
+    // Speaks `text`, cutting off whatever was still being said.
+    fn speak(&mut self, text: &str) -> Result<(), String> {
+        if let Some(mut child) = self.speaking.take() {
+            // It may already have finished; either way it is reaped here.
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        while let Some(index) = self.voice {
+            let program = VOICES[index];
+            let mut command = Command::new(program);
+            if program == "powershell" {
+                command.args(["-NoProfile", "-Command", POWERSHELL_SPEAK]).env(SPEECH_VARIABLE, text);
+            } else {
+                command.arg(text);
+            }
+            match command.stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+                Ok(child) => {
+                    self.speaking = Some(child);
+                    return Ok(());
+                }
+                Err(_) if index + 1 < VOICES.len() => self.voice = Some(index + 1),
+                Err(e) => {
+                    self.voice = None;
+                    return Err(format!("No speech synthesizer ({}): {}; showing captions only", VOICES.join(", "), e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Caption band along the bottom: the latest announcement while it is fresh, otherwise
+    // the current system and parameters. Long captions wrap and the band grows with them.
+    pub fn draw(&self, ctx: &mut Context, locale: Locale) -> GameResult {
+        let text = match &self.caption {
+            Some((text, at)) if at.elapsed().as_secs_f32() < CAPTION_SECS => text.clone(),
+            _ => format!("{}: {}", self.system_type.name(), self.summary(locale)),
+        };
+        let mut caption = graphics::Text::new(
+            graphics::TextFragment::new(text)
+                .font(graphics::Font::default())
+                .scale(graphics::PxScale::from(CAPTION_SIZE)),
+        );
+        caption.set_bounds(Point2 { x: SCREEN_WIDTH - 2.0 * MARGIN, y: f32::INFINITY }, graphics::Align::Left);
+        let height = caption.dimensions(ctx).h + 2.0 * MARGIN;
+        let band = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(0.0, SCREEN_HEIGHT - height, SCREEN_WIDTH, height),
+            graphics::Color::BLACK,
+        )?;
+        graphics::draw(ctx, &band, graphics::DrawParam::default())?;
+        graphics::draw(
+            ctx,
+            &caption,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: MARGIN, y: SCREEN_HEIGHT - height + MARGIN })
+                .color(graphics::Color::WHITE),
+        )
+    }
+}
+
+impl Drop for Announcer {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.speaking.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
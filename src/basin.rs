@@ -0,0 +1,208 @@
+// Filename: basin.rs
+// Project: EntropicRust
+// Description: Basin-of-attraction maps. A grid of initial conditions covering the view
+//              in a chosen coordinate plane is integrated in parallel; each run is
+//              summarised by the time-average of its late trajectory, which is then
+//              clustered to tell coexisting attractors and fixed points apart. The
+//              result is an RGBA image with basin boundaries darkened.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rayon::prelude::*;
+
+use crate::dynamics;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_initial_ranges, get_scale_factor};
+
+pub const BASIN_WIDTH: usize = 200;
+pub const BASIN_HEIGHT: usize = 150;
+const DIVERGENCE_LIMIT: f32 = 1.0e4;
+
+// How the runs of a system are summarised and told apart.
+struct Summary {
+    settle_steps: usize,
+    average_steps: usize,
+    // Coordinates whose averages are compared.
+    axes: [bool; 3],
+    // Two runs belong to the same attractor when their late-time averages are within this
+    // many screen pixels of each other.
+    tolerance_px: f32,
+}
+
+// Newton-Leipnik's two attractors are each symmetric under (x, y) → (-x, -y), so their x
+// and y averages wander for a long time after a slow transient; their heights alone set
+// them apart.
+fn summary(system_type: SystemType) -> Summary {
+    match system_type {
+        SystemType::NewtonLeipnik => {
+            Summary { settle_steps: 10_000, average_steps: 2_000, axes: [false, false, true], tolerance_px: 50.0 }
+        }
+        _ => Summary { settle_steps: 2_500, average_steps: 500, axes: [true; 3], tolerance_px: 12.0 },
+    }
+}
+
+pub const PALETTE: [[u8; 3]; 8] = [
+    [66, 135, 245],
+    [245, 96, 66],
+    [80, 200, 120],
+    [230, 200, 60],
+    [170, 90, 220],
+    [60, 210, 210],
+    [240, 130, 190],
+    [150, 150, 150],
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BasinPlane {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl BasinPlane {
+    pub fn next(self) -> BasinPlane {
+        match self {
+            BasinPlane::XY => BasinPlane::XZ,
+            BasinPlane::XZ => BasinPlane::YZ,
+            BasinPlane::YZ => BasinPlane::XY,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BasinPlane::XY => "x-y",
+            BasinPlane::XZ => "x-z",
+            BasinPlane::YZ => "y-z",
+        }
+    }
+}
+
+pub struct BasinMap {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+    pub attractors: usize,
+}
+
+pub fn compute(
+    system_type: SystemType,
+    params: SystemParameters,
+    plane: BasinPlane,
+    dt: f32,
+) -> BasinMap {
+    let scale = get_scale_factor(system_type);
+    let summary = summary(system_type);
+    let outcomes: Vec<Option<[f32; 3]>> = (0..BASIN_WIDTH * BASIN_HEIGHT)
+        .into_par_iter()
+        .map(|cell| {
+            let start = grid_start(system_type, plane, cell, BASIN_WIDTH, BASIN_HEIGHT);
+            settle(system_type, &params, start, dt, &summary)
+        })
+        .collect();
+
+    let tolerance = summary.tolerance_px / scale;
+    let mut centers: Vec<[f32; 3]> = Vec::new();
+    let labels: Vec<Option<usize>> = outcomes
+        .iter()
+        .map(|outcome| {
+            let average = (*outcome)?;
+            let found = centers.iter().position(|c| {
+                let dx = c[0] - average[0];
+                let dy = c[1] - average[1];
+                let dz = c[2] - average[2];
+                (dx * dx + dy * dy + dz * dz).sqrt() < tolerance
+            });
+            Some(found.unwrap_or_else(|| {
+                centers.push(average);
+                centers.len() - 1
+            }))
+        })
+        .collect();
+
+    BasinMap {
+        width: BASIN_WIDTH as u16,
+        height: BASIN_HEIGHT as u16,
+        rgba: paint(&labels, BASIN_WIDTH, BASIN_HEIGHT),
+        attractors: centers.len(),
+    }
+}
+
+// RGBA image of a grid of basin labels, row by row, with cells that never settled left
+// black and the cells along a boundary darkened.
+pub fn paint(labels: &[Option<usize>], width: usize, height: usize) -> Vec<u8> {
+    let mut rgba = vec![0u8; width * height * 4];
+    for row in 0..height {
+        for col in 0..width {
+            let i = row * width + col;
+            let label = labels[i];
+            let mut color = match label {
+                Some(k) => PALETTE[k % PALETTE.len()],
+                None => [0, 0, 0],
+            };
+            let is_boundary =
+                (col + 1 < width && labels[i + 1] != label) || (row + 1 < height && labels[i + width] != label);
+            if is_boundary {
+                color = [color[0] / 3, color[1] / 3, color[2] / 3];
+            }
+            rgba[i * 4..i * 4 + 4].copy_from_slice(&[color[0], color[1], color[2], 200]);
+        }
+    }
+    rgba
+}
+
+// Initial condition for one cell of a grid laid over the view in `plane`. The remaining
+// coordinate is held at the centre of the system's initial range.
+pub fn grid_start(system_type: SystemType, plane: BasinPlane, cell: usize, width: usize, height: usize) -> [f32; 3] {
+    let scale = get_scale_factor(system_type);
+    let (x_range, y_range, z_range) = get_initial_ranges(system_type);
+    let slice = [
+        (x_range.start + x_range.end) / 2.0,
+        (y_range.start + y_range.end) / 2.0,
+        (z_range.start + z_range.end) / 2.0,
+    ];
+    let (col, row) = (cell % width, cell / width);
+    let u = ((col as f32 + 0.5) / width as f32 * SCREEN_WIDTH - SCREEN_WIDTH / 2.0) / scale;
+    let v = ((row as f32 + 0.5) / height as f32 * SCREEN_HEIGHT - SCREEN_HEIGHT / 2.0) / scale;
+    match plane {
+        BasinPlane::XY => [u, v, slice[2]],
+        BasinPlane::XZ => [u, slice[1], v],
+        BasinPlane::YZ => [slice[0], u, v],
+    }
+}
+
+fn settle(
+    system_type: SystemType,
+    params: &SystemParameters,
+    start: [f32; 3],
+    dt: f32,
+    summary: &Summary,
+) -> Option<[f32; 3]> {
+    let mut state = start;
+    for _ in 0..summary.settle_steps {
+        state = dynamics::step(system_type, params, state, dt);
+        if !state.iter().all(|v| v.is_finite() && v.abs() < DIVERGENCE_LIMIT) {
+            return None;
+        }
+    }
+
+    let mut sum = [0.0f32; 3];
+    for _ in 0..summary.average_steps {
+        state = dynamics::step(system_type, params, state, dt);
+        if !state.iter().all(|v| v.is_finite() && v.abs() < DIVERGENCE_LIMIT) {
+            return None;
+        }
+        sum[0] += state[0];
+        sum[1] += state[1];
+        sum[2] += state[2];
+    }
+    let n = summary.average_steps as f32;
+    let average = |i: usize| if summary.axes[i] { sum[i] / n } else { 0.0 };
+    Some([average(0), average(1), average(2)])
+}
@@ -0,0 +1,266 @@
+// Filename: camera.rs
+// Project: EntropicRust
+// Description: 2D camera over the projected view. The simulation still projects onto the
+//              fixed 800×600 view centred on the origin; the camera picks the part of
+//              that view shown in the window (a target point at the window centre and a
+//              zoom), applied as the screen coordinates of everything drawn in the
+//              attractor layer. Poses can be bookmarked into numbered slots and kept in
+//              the config as `bookmark_<slot> = name x y zoom`, and camera moves can be
+//              recorded as paths and written to or read from a text file with one
+//              `time x y zoom` key per line, in seconds of wall-clock time:
+//
+//                  # time  x    y    zoom
+//                  0       400  300  1
+//                  4       520  260  3.5
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::versioning::{self, Migration};
+
+pub const CAMERA_PATH: &str = "camera_path.txt";
+// Version 1 added the header; the lines are as before.
+const PATH_MIGRATIONS: &[Migration<String>] = &[versioning::unchanged];
+pub const MIN_ZOOM: f32 = 0.1;
+pub const MAX_ZOOM: f32 = 500.0;
+pub const BOOKMARK_SLOTS: usize = 9;
+// Fraction of the visible width moved per pan key press.
+pub const PAN_STEP: f32 = 0.1;
+// Drags shorter than this on either side are clicks, not zoom boxes.
+pub const MIN_BOX_PX: f32 = 8.0;
+// Space left around the cloud when fitting the view to it.
+const FIT_MARGIN: f32 = 1.1;
+// Seconds between keys while recording a path.
+const RECORD_INTERVAL: f32 = 0.1;
+// How quickly a following camera closes the gap to its subject, per second.
+const FOLLOW_RATE: f32 = 3.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Camera {
+    // Point of the projected view shown at the window centre.
+    pub target: Point2<f32>,
+    pub zoom: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera { target: Point2 { x: SCREEN_WIDTH / 2.0, y: SCREEN_HEIGHT / 2.0 }, zoom: 1.0 }
+    }
+}
+
+impl Camera {
+    // Part of the projected view visible in the window.
+    pub fn view(&self) -> graphics::Rect {
+        let (w, h) = (SCREEN_WIDTH / self.zoom, SCREEN_HEIGHT / self.zoom);
+        graphics::Rect::new(self.target.x - w / 2.0, self.target.y - h / 2.0, w, h)
+    }
+
+    pub fn apply(&self, ctx: &mut Context) -> GameResult {
+        graphics::set_screen_coordinates(ctx, self.view())
+    }
+
+    // Window pixel to the point of the projected view under it.
+    pub fn unproject(&self, x: f32, y: f32) -> Point2<f32> {
+        let view = self.view();
+        Point2 { x: view.x + x / self.zoom, y: view.y + y / self.zoom }
+    }
+
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        self.target.x += dx * SCREEN_WIDTH * PAN_STEP / self.zoom;
+        self.target.y += dy * SCREEN_HEIGHT * PAN_STEP / self.zoom;
+    }
+
+    // Eases the target towards `point` over `dt` seconds of wall-clock time, so the
+    // subject stays centred without every jolt of its motion shaking the view.
+    pub fn follow(&mut self, point: Point2<f32>, dt: f32) {
+        if !point.x.is_finite() || !point.y.is_finite() {
+            return;
+        }
+        let t = 1.0 - (-FOLLOW_RATE * dt).exp();
+        self.target.x += (point.x - self.target.x) * t;
+        self.target.y += (point.y - self.target.y) * t;
+    }
+
+    // Shows `area` of the projected view, as large as fits in the window.
+    pub fn frame(&mut self, area: graphics::Rect) {
+        if area.w <= 0.0 && area.h <= 0.0 {
+            return;
+        }
+        self.target = Point2 { x: area.x + area.w / 2.0, y: area.y + area.h / 2.0 };
+        self.zoom = (SCREEN_WIDTH / area.w).min(SCREEN_HEIGHT / area.h).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    // Frames every finite point, with a margin so the outermost ones are not on the edge.
+    pub fn fit(&mut self, points: impl Iterator<Item = Point2<f32>>) {
+        let (mut lo, mut hi) = ([f32::MAX; 2], [f32::MIN; 2]);
+        for p in points.filter(|p| p.x.is_finite() && p.y.is_finite()) {
+            lo = [lo[0].min(p.x), lo[1].min(p.y)];
+            hi = [hi[0].max(p.x), hi[1].max(p.y)];
+        }
+        if lo[0] > hi[0] {
+            return;
+        }
+        let (w, h) = ((hi[0] - lo[0]) * FIT_MARGIN, (hi[1] - lo[1]) * FIT_MARGIN);
+        let center = [(lo[0] + hi[0]) / 2.0, (lo[1] + hi[1]) / 2.0];
+        self.frame(graphics::Rect::new(center[0] - w / 2.0, center[1] - h / 2.0, w, h));
+    }
+
+    // Zooms by `factor`, keeping the point under window pixel (x, y) where it is.
+    pub fn zoom_at(&mut self, factor: f32, x: f32, y: f32) {
+        let anchor = self.unproject(x, y);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let moved = self.unproject(x, y);
+        self.target.x += anchor.x - moved.x;
+        self.target.y += anchor.y - moved.y;
+    }
+}
+
+// Screen coordinates for the HUD and panels, which never move with the camera.
+pub fn reset(ctx: &mut Context) -> GameResult {
+    graphics::set_screen_coordinates(ctx, graphics::Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT))
+}
+
+#[derive(Clone)]
+pub struct Bookmark {
+    pub name: String,
+    pub camera: Camera,
+}
+
+impl Bookmark {
+    // `name x y zoom`; the name is a single word.
+    pub fn parse(value: &str) -> Result<Bookmark, String> {
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        let [name, x, y, zoom] = fields[..] else {
+            return Err(format!("expected `name x y zoom`, got `{}`", value));
+        };
+        let number = |text: &str| text.parse::<f32>().map_err(|_| format!("expected a number, got `{}`", text));
+        Ok(Bookmark {
+            name: name.to_string(),
+            camera: Camera {
+                target: Point2 { x: number(x)?, y: number(y)? },
+                zoom: number(zoom)?.clamp(MIN_ZOOM, MAX_ZOOM),
+            },
+        })
+    }
+
+    pub fn to_setting(&self) -> String {
+        format!("{} {} {} {}", self.name, self.camera.target.x, self.camera.target.y, self.camera.zoom)
+    }
+}
+
+pub struct CameraPath {
+    // Sorted by time.
+    keys: Vec<(f32, Camera)>,
+    // Seconds since recording or playback started.
+    pub elapsed: f32,
+    pub playing: bool,
+}
+
+impl CameraPath {
+    pub fn new() -> Self {
+        CameraPath { keys: Vec::new(), elapsed: 0.0, playing: false }
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |&(time, _)| time)
+    }
+
+    // Starts a new recording, replacing the current path.
+    pub fn record(&mut self) {
+        self.keys.clear();
+        self.elapsed = 0.0;
+        self.playing = false;
+    }
+
+    pub fn play(&mut self) {
+        self.elapsed = 0.0;
+        self.playing = !self.keys.is_empty();
+    }
+
+    // Eased like timeline keys; zoom is interpolated on a log scale so a zoom from 1x to
+    // 100x passes 10x halfway through.
+    fn camera_at(&self, time: f32) -> Option<Camera> {
+        let (first, last) = (self.keys.first()?, self.keys.last()?);
+        if time <= first.0 {
+            return Some(first.1);
+        }
+        if time >= last.0 {
+            return Some(last.1);
+        }
+        let i = self.keys.iter().position(|&(t, _)| t > time)?;
+        let ((t0, a), (t1, b)) = (self.keys[i - 1], self.keys[i]);
+        let s = (time - t0) / (t1 - t0);
+        let s = s * s * (3.0 - 2.0 * s);
+        Some(Camera {
+            target: Point2 {
+                x: a.target.x + (b.target.x - a.target.x) * s,
+                y: a.target.y + (b.target.y - a.target.y) * s,
+            },
+            zoom: a.zoom * (b.zoom / a.zoom).powf(s),
+        })
+    }
+
+    // Moves the clock on by `dt` seconds, sampling `camera` while `recording` and steering it
+    // while playing. Playback stops at the last key.
+    pub fn advance(&mut self, camera: &mut Camera, dt: f32, recording: bool) {
+        if recording {
+            if self.keys.last().is_none_or(|&(time, _)| self.elapsed - time >= RECORD_INTERVAL) {
+                self.keys.push((self.elapsed, *camera));
+            }
+        } else if self.playing {
+            if let Some(at) = self.camera_at(self.elapsed) {
+                *camera = at;
+            }
+            if self.elapsed >= self.duration() {
+                self.playing = false;
+            }
+        } else {
+            return;
+        }
+        self.elapsed += dt;
+    }
+
+    pub fn load(path: &str) -> Result<CameraPath, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let text = versioning::upgrade_text(&text, "camera-path", PATH_MIGRATIONS)?;
+        let mut camera_path = CameraPath::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let values: Result<Vec<f32>, _> = line.split_whitespace().map(str::parse).collect();
+            let Ok(&[time, x, y, zoom]) = values.as_deref() else {
+                return Err(format!("line {}: expected `time x y zoom`", line_no + 1));
+            };
+            let camera = Camera { target: Point2 { x, y }, zoom: zoom.clamp(MIN_ZOOM, MAX_ZOOM) };
+            let index = camera_path.keys.partition_point(|&(t, _)| t <= time);
+            camera_path.keys.insert(index, (time.max(0.0), camera));
+        }
+        Ok(camera_path)
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<usize> {
+        let mut text = versioning::header("camera-path", PATH_MIGRATIONS);
+        text += "# time  x  y  zoom\n";
+        for (time, camera) in &self.keys {
+            text += &format!("{:.3} {} {} {}\n", time, camera.target.x, camera.target.y, camera.zoom);
+        }
+        fs::write(path, text)?;
+        Ok(self.keys.len())
+    }
+}
@@ -0,0 +1,94 @@
+// Filename: color_range.rs
+// Project: EntropicRust
+// Description: Auto-normalizing colour scales. The particle colourings that map a value onto
+//              a scale (local error, neighbour density, speed) track the smallest and largest
+//              value drawn in each frame and ease a running range towards them, so the whole
+//              scale stays in use as parameter changes stretch or shrink the dynamic range.
+//              Smoothing keeps the colours from flickering with single outliers. With
+//              `auto_color_range = false` every colouring keeps its fixed range instead.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+// Fraction of the way to each frame's extremes the running range moves per frame.
+const SMOOTHING: f32 = 0.05;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorScale {
+    // log10 of the local error in pixels per step.
+    Error,
+    // Neighbours within the density radius.
+    Density,
+    // Phase-space speed in pixels per unit of simulated time.
+    Speed,
+}
+
+impl ColorScale {
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorScale::Error => "log10 error px",
+            ColorScale::Density => "neighbours",
+            ColorScale::Speed => "speed px/t",
+        }
+    }
+
+    // Range the scale spans when it is not normalized.
+    pub fn fixed_range(self) -> (f32, f32) {
+        match self {
+            ColorScale::Error => (-2.0, 0.0),
+            ColorScale::Density => (0.0, 8.0),
+            ColorScale::Speed => (0.0, 1000.0),
+        }
+    }
+
+    // Ends of the scale in the classic palette.
+    pub fn colors(self) -> ((f32, f32, f32), (f32, f32, f32)) {
+        match self {
+            ColorScale::Error => ((0.2, 1.0, 0.3), (1.0, 0.3, 0.3)),
+            ColorScale::Density => ((0.2, 0.4, 1.0), (1.0, 0.3, 0.2)),
+            ColorScale::Speed => ((0.3, 0.2, 0.8), (1.0, 0.9, 0.3)),
+        }
+    }
+}
+
+pub struct ColorRange {
+    // Scale the range was tracked for; a different one starts over.
+    pub scale: Option<ColorScale>,
+    pub low: f32,
+    pub high: f32,
+}
+
+impl ColorRange {
+    pub fn new() -> Self {
+        ColorRange { scale: None, low: 0.0, high: 1.0 }
+    }
+
+    // Folds in the values drawn this frame.
+    pub fn observe(&mut self, scale: ColorScale, values: &[f32]) {
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        let (low, high) = finite.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        if low > high {
+            return;
+        }
+        if self.scale != Some(scale) {
+            *self = ColorRange { scale: Some(scale), low, high };
+            return;
+        }
+        self.low += (low - self.low) * SMOOTHING;
+        self.high += (high - self.high) * SMOOTHING;
+    }
+
+    // Position of `value` along the running range, in [0, 1].
+    pub fn normalize(&self, value: f32) -> f32 {
+        let span = self.high - self.low;
+        if span <= f32::EPSILON {
+            return 0.5;
+        }
+        ((value - self.low) / span).clamp(0.0, 1.0)
+    }
+}
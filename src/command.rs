@@ -0,0 +1,430 @@
+// Filename: command.rs
+// Project: EntropicRust
+// Description: Typed commands for everything the user can do to the running scene. Input
+//              handlers only translate keys and mouse actions into commands and hand them to
+//              MainState::apply, so every source of input (keyboard today; remapped keys,
+//              recorded sessions, scripts or a network remote later) goes through the same
+//              code path and cannot drift apart.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::graphics;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+
+use crate::camera;
+use crate::mode::Mechanics;
+use crate::particle::SystemType;
+use crate::particle_pool::ParticleId;
+
+// Ctrl saves the camera into the slot of each key, Shift recalls it.
+const BOOKMARK_KEYS: [KeyCode; camera::BOOKMARK_SLOTS] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Command {
+    // Systems and parameters.
+    SwitchSystem(SystemType),
+    ToggleGroup(SystemType),
+    FocusNextGroup,
+    // `direction` is +1 or -1 steps of the parameter in `slot` of the key rows.
+    StepParameter { slot: usize, direction: f32, fine: bool },
+    ResetParameters,
+    ToggleParameterJitter,
+    CycleLink,
+    ToggleHistoryScrubber,
+    ScrubHistory(isize),
+    // Position along the history bar, 0 to 1.
+    SeekHistory(f32),
+    ToggleRewind,
+    ScrubRewind(isize),
+    // Position along the rewind bar, 0 to 1.
+    SeekRewind(f32),
+    ToggleXyPad,
+    NextPadPair,
+    // Position across the XY pad, 0 to 1 on both axes with y pointing up.
+    SetPadPoint { x: f32, y: f32 },
+    ToggleDrift,
+    NextDriftParameter,
+    CycleLfo,
+    NextLfoParameter,
+    AdjustLfo { up: bool, depth: bool },
+    StepNoise(i32),
+    StepTimeStep(i32),
+    StepTimeScale(i32),
+    NextIntegrator,
+    NextComparisonIntegrator,
+    ToggleComparison,
+    ToggleIntegratorOverlay,
+    ToggleSoftClamp,
+    // At twice the configured poster size when `double`.
+    ExportPoster { double: bool },
+    ExportTiledPoster,
+    TogglePause,
+
+    // Particles and seeding.
+    Restart,
+    MoreParticles,
+    FewerParticles,
+    EnterParticleCount,
+    NextSeedPattern,
+    ScaleSeedExtent(f32),
+    ToggleLifetime,
+    NextEmitterShape,
+    // In simulation coordinates.
+    AddEmitter { x: f32, y: f32 },
+    ClearEmitters,
+    ToggleSpawnMode,
+    // In simulation coordinates.
+    SpawnParticle { x: f32, y: f32 },
+    CycleNetwork,
+    StepNetworkStrength(i32),
+    CycleCoupling,
+    StepCouplingStrength(i32),
+
+    // Leader, pins and recorded trajectories.
+    CycleLeader { forward: bool },
+    ToggleFollowLeader,
+    PinLeader,
+    TogglePin(ParticleId),
+    ReleasePins,
+    ToggleTrajectory { x: f32, y: f32 },
+    ExportTrajectory { json: bool },
+
+    // Camera.
+    Pan { dx: f32, dy: f32 },
+    // Zooms by `factor` about a point on the window.
+    Zoom { factor: f32, x: f32, y: f32 },
+    // Frames a region given in simulation coordinates.
+    FrameRegion(graphics::Rect),
+    FitCamera,
+    ResetCamera,
+    SaveBookmark(usize),
+    RecallBookmark(usize),
+    ToggleCameraRecording,
+    ToggleCameraPlayback,
+    SaveCameraPath,
+    LoadCameraPath,
+
+    // Presets, timeline, tour and shared state.
+    SavePreset,
+    LoadNextPreset,
+    CopyState,
+    PasteState,
+    CaptureKeyframe,
+    SaveTimeline,
+    ToggleTimelinePlayback,
+    ClearTimeline,
+    ToggleTour,
+    SkipTourStep,
+    ToggleDoublePendulum,
+    ToggleMagneticPendulum,
+    ToggleThreeBody,
+    // Inside the mechanics modes; each mode answers the ones it has a use for.
+    MechanicsPause,
+    MechanicsRestart,
+    MechanicsNext,
+    MechanicsAdjust(i32),
+    MechanicsClear,
+
+    // Looks.
+    ToggleTrails,
+    ToggleRibbons,
+    NextTrailWidth,
+    NextTrailGradient,
+    ToggleTrailMode,
+    NextParticleShape,
+    ToggleHalo,
+    StepHalo(i32),
+    ToggleDensityColoring,
+    ToggleErrorColoring,
+    ToggleSpeedColoring,
+    NextPalette,
+    NextTheme,
+    ToggleHud,
+    ToggleAccessibility,
+    ToggleMessageLog,
+    ToggleKeyReference,
+    TurnKeyReference(isize),
+
+    // Analysis overlays.
+    CycleEntropyAxis,
+    ComputeLyapunov,
+    ToggleFixedPoints,
+    ToggleNullclines,
+    ToggleSensitivityDemo,
+    ToggleEmbedding,
+    AdjustEmbeddingDelay(isize),
+    AdjustEmbeddingDimension(isize),
+    ToggleSymbolic,
+    ToggleSymbolEcho,
+    TogglePeriodicOrbit,
+    ToggleStroboscope,
+    SwapStroboscopeAxes,
+    ToggleMeasure,
+    ExportMeasure,
+    ToggleRegimes,
+    LoadRegime(usize),
+    ToggleScan,
+    NextScanPair,
+    // Picks the parameters under a point of the scan image, as fractions of the window.
+    PickScanPoint { x: f32, y: f32 },
+    ToggleFtle,
+    NextFtlePlane,
+    ToggleEscapeMap,
+    NextEscapePlane,
+    ToggleBasinMap,
+    NextBasinPlane,
+    CycleHistograms,
+    ToggleSpectrum,
+    ToggleCorrelation,
+    ToggleDetachedPanels,
+
+    Quit,
+}
+
+// The parts of the scene that change what a key means.
+pub struct KeyContext {
+    pub system_type: SystemType,
+    // The history bar is up, so Left/Right scrub it instead of panning.
+    pub scrubbing: bool,
+    // The rewind bar is up; it takes Left/Right before the history bar.
+    pub rewinding: bool,
+    // A mechanics mode has the window and takes every key for itself.
+    pub mechanics: Option<Mechanics>,
+    // The key reference is open; Left/Right turn its pages and Escape closes it.
+    pub key_reference: bool,
+    // Number of parameters on the key rows; R/F only step a fourth one if there is one.
+    pub key_parameters: usize,
+}
+
+// The command a key press stands for, if any.
+pub fn from_key(keycode: KeyCode, keymod: KeyMods, context: &KeyContext) -> Option<Command> {
+    let ctrl = keymod.contains(KeyMods::CTRL);
+    let shift = keymod.contains(KeyMods::SHIFT);
+    let fine = keymod.contains(KeyMods::ALT);
+    if let Some(mechanics) = context.mechanics {
+        return from_mechanics_key(keycode, ctrl, shift, mechanics);
+    }
+    let command = match keycode {
+        KeyCode::Key1 | KeyCode::Key2 | KeyCode::Key3 | KeyCode::Key4 | KeyCode::Key5 | KeyCode::Key6 => {
+            // Shift reaches the systems past the sixth.
+            let system_type = SystemType::from_digit(keycode, shift)?;
+            if ctrl {
+                Command::ToggleGroup(system_type)
+            } else if system_type != context.system_type {
+                Command::SwitchSystem(system_type)
+            } else {
+                return None;
+            }
+        }
+        KeyCode::Tab if ctrl => Command::FocusNextGroup,
+        KeyCode::J if ctrl => Command::ToggleParameterJitter,
+        KeyCode::K if ctrl => Command::SavePreset,
+        KeyCode::C if ctrl => Command::CopyState,
+        KeyCode::V if ctrl => Command::PasteState,
+        KeyCode::Y if ctrl && shift => Command::SaveTimeline,
+        KeyCode::Y if ctrl => Command::CaptureKeyframe,
+        KeyCode::O if ctrl && shift => Command::ClearTimeline,
+        KeyCode::O if ctrl => Command::ToggleTimelinePlayback,
+        KeyCode::L if ctrl => Command::LoadNextPreset,
+        KeyCode::F if ctrl && shift => Command::NextLfoParameter,
+        KeyCode::F if ctrl => Command::CycleLfo,
+        KeyCode::I if ctrl => Command::CycleLink,
+        KeyCode::H if ctrl => Command::ToggleHistoryScrubber,
+        KeyCode::Z if ctrl => Command::ToggleRewind,
+        KeyCode::D if ctrl => Command::ToggleComparison,
+        KeyCode::Q if ctrl => Command::ToggleIntegratorOverlay,
+        KeyCode::Slash if ctrl && shift => Command::ToggleKeyReference,
+        KeyCode::Slash if ctrl => Command::ToggleSoftClamp,
+        KeyCode::A if ctrl => Command::NextPalette,
+        KeyCode::X if ctrl => Command::ToggleMessageLog,
+        KeyCode::R if ctrl => Command::NextTheme,
+        KeyCode::W if ctrl && shift => Command::SkipTourStep,
+        KeyCode::W if ctrl => Command::ToggleTour,
+        KeyCode::B if ctrl && shift => Command::ToggleCameraPlayback,
+        KeyCode::B if ctrl => Command::ToggleCameraRecording,
+        KeyCode::E if ctrl && shift => Command::LoadCameraPath,
+        KeyCode::E if ctrl => Command::SaveCameraPath,
+        KeyCode::N if ctrl => Command::CycleLeader { forward: false },
+        KeyCode::M if ctrl => Command::CycleLeader { forward: true },
+        KeyCode::P if ctrl => Command::PinLeader,
+        KeyCode::U if ctrl => Command::ReleasePins,
+        KeyCode::S if ctrl => Command::ExportTrajectory { json: shift },
+        KeyCode::Left | KeyCode::Right if context.key_reference => {
+            Command::TurnKeyReference(if keycode == KeyCode::Left { -1 } else { 1 })
+        }
+        KeyCode::Escape if context.key_reference => Command::ToggleKeyReference,
+        KeyCode::Left | KeyCode::Right if context.rewinding => {
+            let delta = if shift { 10 } else { 1 };
+            Command::ScrubRewind(if keycode == KeyCode::Left { -delta } else { delta })
+        }
+        KeyCode::Left | KeyCode::Right if context.scrubbing => {
+            let delta = if shift { 10 } else { 1 };
+            Command::ScrubHistory(if keycode == KeyCode::Left { -delta } else { delta })
+        }
+        KeyCode::Left => Command::Pan { dx: -1.0, dy: 0.0 },
+        KeyCode::Right => Command::Pan { dx: 1.0, dy: 0.0 },
+        KeyCode::Up => Command::Pan { dx: 0.0, dy: -1.0 },
+        KeyCode::Down => Command::Pan { dx: 0.0, dy: 1.0 },
+        KeyCode::Q | KeyCode::W | KeyCode::E => {
+            let slot = [KeyCode::Q, KeyCode::W, KeyCode::E].iter().position(|&k| k == keycode).unwrap_or(0);
+            Command::StepParameter { slot, direction: 1.0, fine }
+        }
+        KeyCode::A | KeyCode::S | KeyCode::D => {
+            let slot = [KeyCode::A, KeyCode::S, KeyCode::D].iter().position(|&k| k == keycode).unwrap_or(0);
+            Command::StepParameter { slot, direction: -1.0, fine }
+        }
+        KeyCode::R if shift => Command::ResetParameters,
+        // With three parameters or fewer, R and F restart the particles.
+        KeyCode::R | KeyCode::F if context.key_parameters <= 3 => Command::Restart,
+        KeyCode::R => Command::StepParameter { slot: 3, direction: 1.0, fine },
+        KeyCode::F => Command::StepParameter { slot: 3, direction: -1.0, fine },
+        KeyCode::Back => Command::Restart,
+        KeyCode::Grave if shift => Command::ExportMeasure,
+        KeyCode::Grave => Command::ToggleMeasure,
+        KeyCode::Insert if ctrl => Command::ToggleSpawnMode,
+        KeyCode::Insert => Command::NextSeedPattern,
+        KeyCode::PageUp if ctrl => Command::ToggleMagneticPendulum,
+        KeyCode::PageUp => Command::ScaleSeedExtent(1.25),
+        KeyCode::PageDown if ctrl => Command::ToggleThreeBody,
+        KeyCode::PageDown => Command::ScaleSeedExtent(0.8),
+        KeyCode::End if ctrl && shift => Command::NextPadPair,
+        KeyCode::End if ctrl => Command::ToggleXyPad,
+        KeyCode::End => Command::NextEmitterShape,
+        KeyCode::Delete => Command::ClearEmitters,
+        KeyCode::Home if ctrl => Command::ToggleDoublePendulum,
+        KeyCode::Home => Command::ToggleLifetime,
+        KeyCode::F11 if shift => Command::NextComparisonIntegrator,
+        KeyCode::F11 => Command::NextIntegrator,
+        KeyCode::F12 if ctrl && fine => Command::ExportTiledPoster,
+        KeyCode::F12 if ctrl => Command::ExportPoster { double: shift },
+        KeyCode::F12 => Command::ToggleErrorColoring,
+        KeyCode::Tab if shift => Command::NextDriftParameter,
+        KeyCode::Tab => Command::ToggleDrift,
+        KeyCode::Key7 if shift => Command::StepNetworkStrength(1),
+        KeyCode::Key7 if ctrl => Command::StepNetworkStrength(-1),
+        KeyCode::Key7 => Command::CycleNetwork,
+        KeyCode::Key8 if shift => Command::StepCouplingStrength(1),
+        KeyCode::Key8 if ctrl => Command::StepCouplingStrength(-1),
+        KeyCode::Key8 => Command::CycleCoupling,
+        KeyCode::Key9 if ctrl => Command::FitCamera,
+        KeyCode::Key9 => Command::NextParticleShape,
+        KeyCode::Key0 if ctrl => Command::ResetCamera,
+        KeyCode::Key0 => Command::ToggleHalo,
+        KeyCode::Equals if ctrl => Command::StepHalo(1),
+        KeyCode::Minus if ctrl => Command::StepHalo(-1),
+        KeyCode::Equals => Command::StepNoise(1),
+        KeyCode::Minus => Command::StepNoise(-1),
+        KeyCode::Z if shift => Command::StepTimeStep(1),
+        KeyCode::X if shift => Command::StepTimeStep(-1),
+        KeyCode::Z => Command::StepTimeScale(1),
+        KeyCode::X => Command::StepTimeScale(-1),
+        KeyCode::C => Command::MoreParticles,
+        KeyCode::V => Command::FewerParticles,
+        KeyCode::Return => Command::EnterParticleCount,
+        KeyCode::T if ctrl => Command::ToggleRibbons,
+        KeyCode::T if shift => Command::NextTrailWidth,
+        KeyCode::T => Command::ToggleTrails,
+        KeyCode::G if ctrl => Command::ToggleFollowLeader,
+        KeyCode::G if shift => Command::NextTrailGradient,
+        KeyCode::G => Command::ToggleTrailMode,
+        KeyCode::H if shift => Command::ToggleAccessibility,
+        KeyCode::H => Command::ToggleHud,
+        KeyCode::N if shift => Command::ToggleSpeedColoring,
+        KeyCode::N => Command::ToggleDensityColoring,
+        KeyCode::Space => Command::TogglePause,
+        KeyCode::Y => Command::CycleEntropyAxis,
+        KeyCode::L => Command::ComputeLyapunov,
+        KeyCode::O => Command::ToggleFixedPoints,
+        KeyCode::U => Command::ToggleNullclines,
+        KeyCode::J => Command::ToggleSensitivityDemo,
+        KeyCode::Period => Command::ToggleEmbedding,
+        KeyCode::Backslash if shift => Command::ToggleSymbolEcho,
+        KeyCode::Backslash => Command::ToggleSymbolic,
+        KeyCode::Comma => Command::TogglePeriodicOrbit,
+        KeyCode::Slash if shift => Command::SwapStroboscopeAxes,
+        KeyCode::Slash => Command::ToggleStroboscope,
+        KeyCode::LBracket | KeyCode::RBracket if ctrl => {
+            Command::AdjustLfo { up: keycode == KeyCode::RBracket, depth: shift }
+        }
+        KeyCode::LBracket | KeyCode::RBracket => {
+            let delta = if keycode == KeyCode::RBracket { 1 } else { -1 };
+            if shift { Command::AdjustEmbeddingDimension(delta) } else { Command::AdjustEmbeddingDelay(delta) }
+        }
+        KeyCode::F10 if ctrl => Command::ToggleDetachedPanels,
+        KeyCode::F10 => Command::ToggleRegimes,
+        KeyCode::F1
+        | KeyCode::F2
+        | KeyCode::F3
+        | KeyCode::F4
+        | KeyCode::F5
+        | KeyCode::F6
+        | KeyCode::F7
+        | KeyCode::F8
+        | KeyCode::F9 => {
+            let slot = BOOKMARK_KEYS.iter().position(|&k| k == keycode).unwrap_or(0);
+            if ctrl {
+                Command::SaveBookmark(slot)
+            } else if shift {
+                Command::RecallBookmark(slot)
+            } else {
+                Command::LoadRegime(slot)
+            }
+        }
+        KeyCode::I if shift => Command::NextScanPair,
+        KeyCode::I => Command::ToggleScan,
+        KeyCode::M => Command::CycleHistograms,
+        KeyCode::Semicolon if shift => Command::NextFtlePlane,
+        KeyCode::Semicolon => Command::ToggleFtle,
+        KeyCode::Apostrophe if shift => Command::NextEscapePlane,
+        KeyCode::Apostrophe => Command::ToggleEscapeMap,
+        KeyCode::B if shift => Command::NextBasinPlane,
+        KeyCode::B => Command::ToggleBasinMap,
+        KeyCode::P => Command::ToggleSpectrum,
+        KeyCode::K => Command::ToggleCorrelation,
+        KeyCode::Escape => Command::Quit,
+        _ => return None,
+    };
+    Some(command)
+}
+
+// Keys while the pendulums or the three bodies have the window; everything else waits until
+// they are left.
+fn from_mechanics_key(keycode: KeyCode, ctrl: bool, shift: bool, mechanics: Mechanics) -> Option<Command> {
+    let command = match keycode {
+        KeyCode::Home if ctrl => Command::ToggleDoublePendulum,
+        KeyCode::PageUp if ctrl => Command::ToggleMagneticPendulum,
+        KeyCode::PageDown if ctrl => Command::ToggleThreeBody,
+        KeyCode::Escape => match mechanics {
+            Mechanics::Pendulum => Command::ToggleDoublePendulum,
+            Mechanics::Magnets => Command::ToggleMagneticPendulum,
+            Mechanics::ThreeBody => Command::ToggleThreeBody,
+        },
+        KeyCode::Space => Command::MechanicsPause,
+        KeyCode::R => Command::MechanicsRestart,
+        KeyCode::Tab if mechanics == Mechanics::ThreeBody => Command::MechanicsNext,
+        KeyCode::C if mechanics == Mechanics::Magnets => Command::MechanicsClear,
+        // Shift adds or removes ten pendulums at a time.
+        KeyCode::Up | KeyCode::Down if mechanics != Mechanics::ThreeBody => {
+            let delta = if shift && mechanics == Mechanics::Pendulum { 10 } else { 1 };
+            Command::MechanicsAdjust(if keycode == KeyCode::Down { -delta } else { delta })
+        }
+        _ => return None,
+    };
+    Some(command)
+}
@@ -0,0 +1,144 @@
+// Filename: comparison.rs
+// Project: EntropicRust
+// Description: Side-by-side comparison. A copy of the main cloud, started from exactly
+//              the same particle states, runs on the right half of the screen with the
+//              parameters the main system had when the split was opened and its own
+//              integrator, while the main system keeps responding to the controls on the
+//              left. The mean distance between each particle and its twin is recorded on
+//              a log scale to show how fast the two runs part ways.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::dynamics::Integrator;
+use crate::groups::SystemGroup;
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::shapes::ParticleShape;
+use crate::system_parameters::SystemParameters;
+use crate::theme::Theme;
+use crate::trail_gradient::{TrailGradient, TrailWidth};
+
+const MAX_SAMPLES: usize = 2_000;
+
+pub struct Comparison {
+    pub copy: SystemGroup,
+    pub integrator: Integrator,
+    // Main particle and its twin in the copy.
+    pairs: Vec<(ParticleId, ParticleId)>,
+    pub elapsed: f32,
+    pub separation: Option<f32>,
+    pub samples: Vec<[f32; 2]>,
+    canvas: Option<graphics::Canvas>,
+}
+
+impl Comparison {
+    pub fn new(
+        particles: &ParticlePool,
+        system_type: SystemType,
+        parameters: SystemParameters,
+        integrator: Integrator,
+    ) -> Self {
+        let mut comparison = Comparison {
+            copy: SystemGroup {
+                system_type,
+                parameters,
+                particles: ParticlePool::with_capacity(particles.len()),
+                shape: ParticleShape::Circle,
+            },
+            integrator,
+            pairs: Vec::new(),
+            elapsed: 0.0,
+            separation: None,
+            samples: Vec::new(),
+            canvas: None,
+        };
+        comparison.reseed(particles, system_type);
+        comparison
+    }
+
+    // Restarts the copy from the current main states, keeping its parameters and integrator.
+    pub fn reseed(&mut self, particles: &ParticlePool, system_type: SystemType) {
+        self.copy.system_type = system_type;
+        self.copy.particles.clear();
+        self.pairs.clear();
+        for (id, particle) in particles.iter_with_ids() {
+            let twin = self.copy.particles.spawn(particle.x, particle.y, particle.z);
+            if let Some(copy) = self.copy.particles.get_mut(twin) {
+                copy.color = particle.color;
+            }
+            self.pairs.push((id, twin));
+        }
+        self.elapsed = 0.0;
+        self.separation = None;
+        self.samples.clear();
+    }
+
+    pub fn step(&mut self, particles: &ParticlePool, dt: f32, noise: f32, record_trail: bool) {
+        self.copy.step(dt, self.integrator, noise, record_trail);
+        self.elapsed += dt;
+        // Particles respawned or removed since the split have no twin and are left out.
+        let (sum, count) = self
+            .pairs
+            .iter()
+            .filter_map(|&(id, twin)| Some((particles.get(id)?, self.copy.particles.get(twin)?)))
+            .map(|(a, b)| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt())
+            .filter(|d| d.is_finite())
+            .fold((0.0, 0usize), |(sum, count), d| (sum + d, count + 1));
+        self.separation = (count > 0).then(|| sum / count as f32);
+        if let Some(separation) = self.separation
+            && separation > 0.0
+            && self.samples.len() < MAX_SAMPLES
+        {
+            self.samples.push([self.elapsed, separation.log10()]);
+        }
+    }
+
+    // Draws the copy through `view` into the right half of the window, clipped to it.
+    pub fn draw(
+        &mut self,
+        ctx: &mut Context,
+        view: graphics::Rect,
+        trails: bool,
+        gradient: TrailGradient,
+        width: TrailWidth,
+        theme: &Theme,
+    ) -> GameResult {
+        if self.canvas.is_none() {
+            self.canvas = Some(graphics::Canvas::with_window_size(ctx)?);
+        }
+        let Some(canvas) = &self.canvas else {
+            return Ok(());
+        };
+        let window = graphics::screen_coordinates(ctx);
+        graphics::set_canvas(ctx, Some(canvas));
+        graphics::clear(ctx, theme.background);
+        graphics::set_screen_coordinates(ctx, view)?;
+        self.copy.draw(ctx, trails, gradient, width, theme, None)?;
+        graphics::set_screen_coordinates(ctx, window)?;
+        graphics::set_canvas(ctx, None);
+
+        // The middle half of the canvas holds the centre of the view, matching the left side.
+        graphics::draw(
+            ctx,
+            canvas,
+            graphics::DrawParam::default()
+                .src(graphics::Rect::new(0.25, 0.0, 0.5, 1.0))
+                .dest([window.x + window.w / 2.0, window.y]),
+        )?;
+        let divider = graphics::Mesh::new_line(
+            ctx,
+            &[[window.x + window.w / 2.0, window.y], [window.x + window.w / 2.0, window.y + window.h]],
+            1.0,
+            theme.frame,
+        )?;
+        graphics::draw(ctx, &divider, graphics::DrawParam::default())
+    }
+}
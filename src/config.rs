@@ -0,0 +1,463 @@
+// Filename: config.rs
+// Project: EntropicRust
+// Description: Application configuration. Settings are read from an optional
+//              `entropicrust.conf` file (one `key = value` per line) and can then be
+//              overridden on the command line with `--key value` or `--key=value`.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+
+use crate::camera::{BOOKMARK_SLOTS, Bookmark};
+use crate::coupling::{self, Coupling, CouplingKind};
+use crate::dynamics::Integrator;
+use crate::events::EventRule;
+use crate::halo;
+use crate::locale::Locale;
+use crate::main_state::MAX_PARTICLES;
+use crate::network::{self, Topology};
+use crate::palette::Palette;
+use crate::poster;
+use crate::screensaver;
+use crate::seeding::{self, SeedPattern};
+use crate::shapes::ParticleShape;
+use crate::soft_clamp;
+use crate::system_parameters::{ParameterInfo, find_parameter};
+use crate::trail_gradient::{TrailGradient, TrailWidth};
+use crate::wallpaper;
+
+pub const CONFIG_PATH: &str = "entropicrust.conf";
+// Relative spread of per-particle parameters when jitter is switched on (±1%).
+const DEFAULT_JITTER: f32 = 0.01;
+
+#[derive(Clone)]
+pub struct AppConfig {
+    pub idle_throttle: bool,
+    pub idle_fps: u32,
+    pub vsync: bool,
+    pub max_fps: Option<u32>,
+    pub double_buffer: bool,
+    pub particles: usize,
+    pub noise: f32,
+    pub parameter_jitter: f32,
+    pub integrator: Integrator,
+    pub lifetime: Option<f32>,
+    pub emitter_rate: f32,
+    pub seed_pattern: SeedPattern,
+    pub seed_extent: f32,
+    pub trail_gradient: TrailGradient,
+    // Overrides the theme's palette.
+    pub palette: Option<Palette>,
+    pub trail_width: TrailWidth,
+    // Fit error, density and speed colours to the range currently on screen.
+    pub auto_color_range: bool,
+    pub ribbons: bool,
+    pub halo: Option<f32>,
+    // Radius in pixels beyond which runaway particles are slowed down.
+    pub soft_clamp: Option<f32>,
+    // Pixel size of exported posters, and their trail width in window pixels.
+    pub poster_size: (u32, u32),
+    pub tiled_poster_size: (u32, u32),
+    pub poster_trail_width: f32,
+    pub particle_shape: ParticleShape,
+    pub sprite: Option<String>,
+    pub coupling: Option<CouplingKind>,
+    pub coupling_strength: f32,
+    pub coupling_radius: f32,
+    pub network: Option<Topology>,
+    pub network_strength: f32,
+    pub events: Vec<EventRule>,
+    pub osc_target: Option<String>,
+    pub timeline: Option<String>,
+    pub camera_path: Option<String>,
+    // Apply edits to the config, theme and preset files while running.
+    pub hot_reload: bool,
+    // Show the start screen rather than going straight into a system.
+    pub start_menu: bool,
+    // Show the first-run tutorial; turned off once it is finished or dismissed.
+    pub tutorial: bool,
+    // Start in screensaver mode; input then quits.
+    pub screensaver: bool,
+    // Seconds without input before the screensaver starts.
+    pub screensaver_idle: Option<f32>,
+    pub screensaver_interval: f32,
+    // Announce state changes in large captions, and aloud when `speech` is on too.
+    pub accessibility: bool,
+    pub speech: bool,
+    // Rumble connected gamepads on leader lobe switches and section crossings.
+    pub haptics: bool,
+    // Decimal separator and list style for numbers in the HUD.
+    pub locale: Locale,
+    // Reset diverged clouds and shed particles when frames stay slow.
+    pub watchdog: bool,
+    // Run as the desktop background: borderless, behind every window and click-through.
+    pub wallpaper: bool,
+    // Tour file to start with.
+    pub tour: Option<String>,
+    // Theme to start with, by file name in the themes directory.
+    pub theme: Option<String>,
+    // Parameter pairs to ratio-lock at startup, as `leader:follower`.
+    pub links: Vec<(String, String)>,
+    // Per-parameter key step overrides, from `step_<parameter>` settings.
+    pub steps: Vec<(&'static str, f32)>,
+    // Shared state string to start from.
+    pub state: Option<String>,
+    // Camera bookmarks by slot, from `bookmark_<slot>` settings.
+    pub bookmarks: Vec<Option<Bookmark>>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            idle_throttle: true,
+            idle_fps: 5,
+            vsync: true,
+            max_fps: None,
+            double_buffer: false,
+            particles: 50,
+            noise: 0.0,
+            parameter_jitter: DEFAULT_JITTER,
+            integrator: Integrator::Euler,
+            lifetime: None,
+            emitter_rate: 20.0,
+            seed_pattern: SeedPattern::RandomBox,
+            seed_extent: seeding::DEFAULT_EXTENT_PX,
+            trail_gradient: TrailGradient::Solid,
+            palette: None,
+            trail_width: TrailWidth::Constant,
+            auto_color_range: true,
+            ribbons: false,
+            halo: None,
+            soft_clamp: None,
+            poster_size: poster::DEFAULT_SIZE,
+            tiled_poster_size: poster::DEFAULT_TILED_SIZE,
+            poster_trail_width: poster::DEFAULT_TRAIL_WIDTH,
+            particle_shape: ParticleShape::Circle,
+            sprite: None,
+            coupling: None,
+            coupling_strength: coupling::DEFAULT_STRENGTH,
+            coupling_radius: coupling::DEFAULT_RADIUS_PX,
+            network: None,
+            network_strength: network::DEFAULT_STRENGTH,
+            events: Vec::new(),
+            osc_target: None,
+            timeline: None,
+            camera_path: None,
+            hot_reload: true,
+            start_menu: true,
+            tutorial: true,
+            screensaver: false,
+            screensaver_idle: None,
+            screensaver_interval: screensaver::DEFAULT_INTERVAL_SECS,
+            accessibility: false,
+            speech: true,
+            haptics: true,
+            locale: Locale::default(),
+            watchdog: true,
+            wallpaper: false,
+            tour: None,
+            theme: None,
+            links: Vec::new(),
+            steps: Vec::new(),
+            state: None,
+            bookmarks: vec![None; BOOKMARK_SLOTS],
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn coupling(&self, kind: CouplingKind) -> Coupling {
+        Coupling {
+            kind,
+            strength: self.coupling_strength,
+            radius_px: self.coupling_radius,
+        }
+    }
+
+    pub fn step(&self, info: &ParameterInfo) -> f32 {
+        self.steps.iter().find(|(name, _)| *name == info.name).map_or(info.step, |&(_, step)| step)
+    }
+
+    // The settings, and a message for every line or flag that was rejected.
+    pub fn load() -> (Self, Vec<String>) {
+        let mut config = AppConfig::default();
+        let mut problems = Vec::new();
+
+        if let Ok(text) = fs::read_to_string(CONFIG_PATH) {
+            for (line_no, line) in text.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let Some((key, value)) = line.split_once('=') else {
+                    problems.push(format!("{}:{}: expected `key = value`", CONFIG_PATH, line_no + 1));
+                    continue;
+                };
+                if let Err(e) = config.set(key.trim(), value.trim()) {
+                    problems.push(format!("{}:{}: {}", CONFIG_PATH, line_no + 1, e));
+                }
+            }
+        }
+
+        let mut args = std::env::args().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                problems.push(format!("Ignoring unexpected argument: {}", arg));
+                continue;
+            };
+            let (key, value) = match flag.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => match args.peek() {
+                    Some(next) if !next.starts_with("--") => (flag.to_string(), args.next().unwrap_or_default()),
+                    _ => (flag.to_string(), "true".to_string()),
+                },
+            };
+            if let Err(e) = config.set(&key.replace('-', "_"), &value) {
+                problems.push(format!("--{}: {}", key, e));
+            }
+        }
+
+        (config, problems)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "idle_throttle" => self.idle_throttle = parse_bool(value)?,
+            "no_idle_throttle" => self.idle_throttle = !parse_bool(value)?,
+            "idle_fps" => self.idle_fps = parse_number::<u32>(value)?.max(1),
+            "vsync" => self.vsync = parse_bool(value)?,
+            "max_fps" => {
+                self.max_fps = match value {
+                    "uncapped" | "none" | "0" => None,
+                    _ => Some(parse_number::<u32>(value)?),
+                }
+            }
+            "uncapped" => {
+                if parse_bool(value)? {
+                    self.vsync = false;
+                    self.max_fps = None;
+                }
+            }
+            "double_buffer" => self.double_buffer = parse_bool(value)?,
+            "particles" => self.particles = parse_number::<usize>(value)?.clamp(1, MAX_PARTICLES),
+            "parameter_jitter" => self.parameter_jitter = parse_number::<f32>(value)?.clamp(0.0, 0.5),
+            "noise" => self.noise = parse_number::<f32>(value)?.max(0.0),
+            "integrator" => {
+                self.integrator = Integrator::from_name(value)
+                    .ok_or_else(|| format!("unknown integrator `{}` (expected euler or heun)", value))?
+            }
+            "lifetime" => {
+                self.lifetime = match value {
+                    "none" | "off" | "0" => None,
+                    _ => Some(parse_number::<f32>(value)?).filter(|l| *l > 0.0),
+                }
+            }
+            "emitter_rate" => self.emitter_rate = parse_number::<f32>(value)?.max(0.0),
+            "seed_pattern" => {
+                self.seed_pattern = SeedPattern::from_name(value)
+                    .ok_or_else(|| format!("unknown seed pattern `{}` (random, line, grid, circle, shell)", value))?
+            }
+            "seed_extent" => self.seed_extent = parse_number::<f32>(value)?.max(1.0),
+            "palette" => {
+                self.palette = Some(
+                    Palette::from_name(value)
+                        .ok_or_else(|| format!("unknown palette `{}` (classic, okabe-ito, cividis)", value))?,
+                )
+            }
+            "trail_gradient" => {
+                self.trail_gradient = TrailGradient::from_name(value)
+                    .ok_or_else(|| {
+                        format!("unknown trail gradient `{}` (solid, fade, dusk, fire, ice, viridis)", value)
+                    })?
+            }
+            "trail_width" => {
+                self.trail_width = TrailWidth::from_name(value)
+                    .ok_or_else(|| format!("unknown trail width `{}` (constant, inverse, direct)", value))?
+            }
+            "auto_color_range" => self.auto_color_range = parse_bool(value)?,
+            "ribbons" => self.ribbons = parse_bool(value)?,
+            "halo" => {
+                self.halo = match value {
+                    "off" | "none" | "0" => None,
+                    "on" => Some(halo::DEFAULT_HALO_RADIUS),
+                    _ => Some(parse_number::<f32>(value)?.clamp(halo::MIN_HALO_RADIUS, halo::MAX_HALO_RADIUS)),
+                }
+            }
+            "soft_clamp" => {
+                self.soft_clamp = match value {
+                    "off" | "none" | "0" => None,
+                    "on" => Some(soft_clamp::DEFAULT_RADIUS_PX),
+                    _ => Some(parse_number::<f32>(value)?.max(soft_clamp::MIN_RADIUS_PX)),
+                }
+            }
+            "poster_size" => self.poster_size = poster::parse_size(value)?,
+            "tiled_poster_size" => self.tiled_poster_size = poster::parse_size(value)?,
+            "poster_trail_width" => self.poster_trail_width = parse_number::<f32>(value)?.clamp(0.5, 20.0),
+            "particle_shape" => {
+                self.particle_shape = ParticleShape::from_name(value)
+                    .ok_or_else(|| format!("unknown particle shape `{}` (circle, square, triangle, sprite)", value))?
+            }
+            "sprite" => {
+                self.sprite = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            "coupling" => {
+                self.coupling = match value {
+                    "off" | "none" => None,
+                    _ => Some(
+                        CouplingKind::from_name(value)
+                            .ok_or_else(|| format!("unknown coupling `{}` (off, spring, alignment)", value))?,
+                    ),
+                }
+            }
+            "coupling_strength" => {
+                self.coupling_strength =
+                    parse_number::<f32>(value)?.clamp(coupling::STRENGTH_SCALE.min, coupling::STRENGTH_SCALE.max)
+            }
+            "coupling_radius" => self.coupling_radius = parse_number::<f32>(value)?.max(1.0),
+            "network" => {
+                self.network = match value {
+                    "off" | "none" => None,
+                    _ => Some(
+                        Topology::from_name(value)
+                            .ok_or_else(|| format!("unknown network `{}` (off, ring, small-world, all)", value))?,
+                    ),
+                }
+            }
+            "network_strength" => {
+                self.network_strength =
+                    parse_number::<f32>(value)?.clamp(network::STRENGTH_SCALE.min, network::STRENGTH_SCALE.max)
+            }
+            // Repeatable: each `event` line adds one rule.
+            "event" => self.events.push(EventRule::parse(value)?),
+            "link" => {
+                let Some((leader, follower)) = value.split_once(':') else {
+                    return Err(format!("expected `leader:follower`, got `{}`", value));
+                };
+                self.links.push((leader.trim().to_string(), follower.trim().to_string()));
+            }
+            "state" => self.state = Some(value.to_string()),
+            "timeline" => {
+                self.timeline = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            "camera_path" => {
+                self.camera_path = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            "hot_reload" => self.hot_reload = parse_bool(value)?,
+            "start_menu" => self.start_menu = parse_bool(value)?,
+            "tutorial" => self.tutorial = parse_bool(value)?,
+            "screensaver" => self.screensaver = parse_bool(value)?,
+            "screensaver_idle" => {
+                self.screensaver_idle = match value {
+                    "none" | "off" | "0" => None,
+                    _ => Some(parse_number::<f32>(value)?).filter(|s| *s > 0.0),
+                }
+            }
+            "screensaver_interval" => self.screensaver_interval = parse_number::<f32>(value)?.max(1.0),
+            "accessibility" => self.accessibility = parse_bool(value)?,
+            "speech" => self.speech = parse_bool(value)?,
+            "haptics" => self.haptics = parse_bool(value)?,
+            "locale" => self.locale = Locale::from_name(value)?,
+            "watchdog" => self.watchdog = parse_bool(value)?,
+            // The wallpaper is the screensaver's loop, kept light and never throttled, since a
+            // window behind all others counts as unfocused.
+            "wallpaper" => {
+                self.wallpaper = parse_bool(value)?;
+                if self.wallpaper {
+                    self.screensaver = true;
+                    self.idle_throttle = false;
+                    self.max_fps = Some(wallpaper::DEFAULT_FPS);
+                    self.particles = wallpaper::DEFAULT_PARTICLES;
+                    self.start_menu = false;
+                    self.tutorial = false;
+                }
+            }
+            "tour" => {
+                self.tour = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            "theme" => {
+                self.theme = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            "osc_target" => {
+                self.osc_target = match value {
+                    "none" | "" => None,
+                    _ => Some(value.to_string()),
+                }
+            }
+            _ if key.starts_with("bookmark_") => {
+                let slot = parse_number::<usize>(&key["bookmark_".len()..])?;
+                if !(1..=BOOKMARK_SLOTS).contains(&slot) {
+                    return Err(format!("bookmark slot must be 1 to {}, got {}", BOOKMARK_SLOTS, slot));
+                }
+                self.bookmarks[slot - 1] = Some(Bookmark::parse(value)?);
+            }
+            _ if key.starts_with("step_") => {
+                let Some(name) = find_parameter(&key["step_".len()..]).map(|info| info.name) else {
+                    return Err(format!("unknown parameter in `{}`", key));
+                };
+                let step = parse_number::<f32>(value)?;
+                if step <= 0.0 || !step.is_finite() {
+                    return Err(format!("step must be positive, got `{}`", value));
+                }
+                self.steps.retain(|(n, _)| *n != name);
+                self.steps.push((name, step));
+            }
+            _ => return Err(format!("unknown setting `{}`", key)),
+        }
+        Ok(())
+    }
+}
+
+// Writes one setting into the config file, replacing an existing line for the key or
+// appending one, and leaving every other line (comments included) as it was.
+pub fn save_setting(key: &str, value: &str) -> std::io::Result<()> {
+    let text = fs::read_to_string(CONFIG_PATH).unwrap_or_default();
+    let line = format!("{} = {}", key, value);
+    let mut replaced = false;
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|l| match l.split_once('=') {
+            Some((k, _)) if k.trim() == key && !l.trim_start().starts_with('#') => {
+                replaced = true;
+                line.clone()
+            }
+            _ => l.to_string(),
+        })
+        .collect();
+    if !replaced {
+        lines.push(line);
+    }
+    fs::write(CONFIG_PATH, lines.join("\n") + "\n")
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "on" | "yes" | "1" => Ok(true),
+        "false" | "off" | "no" | "0" => Ok(false),
+        _ => Err(format!("expected on/off, got `{}`", value)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("expected a number, got `{}`", value))
+}
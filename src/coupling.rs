@@ -0,0 +1,118 @@
+// Filename: coupling.rs
+// Project: EntropicRust
+// Description: Optional interaction between particles. After each dynamics step, every
+//              particle is nudged by its neighbours within a screen-space radius (found via
+//              the spatial hash): either a diffusive spring pulling it towards their mean
+//              position, or an alignment term pulling its velocity towards theirs.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::dynamics;
+use crate::log_scale::LogScale;
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::spatial_hash::SpatialHash;
+use crate::system_parameters::{SystemParameters, get_scale_factor};
+
+pub const DEFAULT_STRENGTH: f32 = 0.5;
+pub const DEFAULT_RADIUS_PX: f32 = 30.0;
+pub const STRENGTH_SCALE: LogScale = LogScale { min: 0.01, max: 50.0, steps_per_decade: 6.0 };
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CouplingKind {
+    Spring,
+    Alignment,
+}
+
+impl CouplingKind {
+    pub fn from_name(name: &str) -> Option<CouplingKind> {
+        match name.to_ascii_lowercase().as_str() {
+            "spring" | "diffusive" => Some(CouplingKind::Spring),
+            "alignment" | "align" => Some(CouplingKind::Alignment),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CouplingKind::Spring => "spring",
+            CouplingKind::Alignment => "alignment",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Coupling {
+    pub kind: CouplingKind,
+    pub strength: f32,
+    pub radius_px: f32,
+}
+
+impl Coupling {
+    pub fn radius(&self, system_type: SystemType) -> f32 {
+        self.radius_px / get_scale_factor(system_type)
+    }
+
+    // `hash` must have been rebuilt from `particles` with a cell size of `self.radius()`.
+    pub fn apply(
+        &self,
+        particles: &mut ParticlePool,
+        hash: &SpatialHash,
+        system_type: SystemType,
+        params: &SystemParameters,
+        dt: f32,
+    ) {
+        let radius = self.radius(system_type);
+        // The driving phase of forced systems is shared time, not a coordinate to couple.
+        let axes = if system_type.is_driven() { 2 } else { 3 };
+
+        let nudges: Vec<(ParticleId, [f32; 3])> = particles
+            .iter_with_ids()
+            .filter(|(_, p)| !p.pinned)
+            .filter_map(|(id, p)| {
+                let pos = [p.x, p.y, p.z];
+                let mut sum = [0.0f32; 3];
+                let mut count = 0usize;
+                hash.for_each_neighbor(pos, radius, |other_id, other, _| {
+                    if other_id == id {
+                        return;
+                    }
+                    let term = match self.kind {
+                        CouplingKind::Spring => other,
+                        CouplingKind::Alignment => dynamics::derivative(system_type, params, other),
+                    };
+                    for (total, value) in sum.iter_mut().zip(term) {
+                        *total += value;
+                    }
+                    count += 1;
+                });
+                if count == 0 {
+                    return None;
+                }
+                let own = match self.kind {
+                    CouplingKind::Spring => pos,
+                    CouplingKind::Alignment => dynamics::derivative(system_type, params, pos),
+                };
+                // Mean-field form: the pull does not grow with the number of neighbours.
+                let nudge = std::array::from_fn(|axis| {
+                    if axis < axes { self.strength * dt * (sum[axis] / count as f32 - own[axis]) } else { 0.0 }
+                });
+                Some((id, nudge))
+            })
+            .collect();
+
+        for (id, [dx, dy, dz]) in nudges {
+            if let Some(p) = particles.get_mut(id) {
+                p.x += dx;
+                p.y += dy;
+                p.z += dz;
+            }
+        }
+    }
+}
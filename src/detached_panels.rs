@@ -0,0 +1,254 @@
+// Filename: detached_panels.rs
+// Project: EntropicRust
+// Description: Analysis panels in a window of their own, so the main view stays clean while
+//              the plots live elsewhere. ggez 0.7 drives a single window per process, so the
+//              panels are detached into a second process: while they are detached the main
+//              window leaves them off its own view and writes them to `panels.txt` twice a
+//              second, and the viewer it starts reads the file and lays them out in a grid.
+//              Closing the viewer brings the panels back to the main window.
+//
+//              Usage: entropicrust panels [--file panels.txt]
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::process::{Child, Command};
+use std::time::{Duration, Instant, SystemTime};
+
+use ggez::{conf, event, graphics, timer, Context, GameError, GameResult};
+
+use crate::config::AppConfig;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::plot_panel::PlotPanel;
+use crate::theme::{self, Theme};
+use crate::toast::Toasts;
+use crate::versioning::{self, Migration};
+
+pub const PANELS_PATH: &str = "panels.txt";
+const MIGRATIONS: &[Migration<String>] = &[versioning::unchanged];
+// How often the main window writes the panels and the viewer looks for a new file.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+// The viewer only redraws what it read, so it does not need to run flat out.
+const VIEWER_FRAME: Duration = Duration::from_millis(50);
+const COLUMNS: usize = 3;
+const MARGIN: f32 = 20.0;
+
+// The viewer process of the running main window.
+pub struct DetachedPanels {
+    viewer: Child,
+    last_export: Option<Instant>,
+}
+
+impl DetachedPanels {
+    pub fn open() -> Result<Self, String> {
+        let exe = std::env::current_exe().map_err(|e| format!("Failed to find the program to open panels: {}", e))?;
+        let viewer = Command::new(exe)
+            .arg("panels")
+            .spawn()
+            .map_err(|e| format!("Failed to open the panel window: {}", e))?;
+        Ok(DetachedPanels { viewer, last_export: None })
+    }
+
+    pub fn viewer_closed(&mut self) -> bool {
+        !matches!(self.viewer.try_wait(), Ok(None))
+    }
+
+    // Writes the panels unless they were written less than REFRESH_INTERVAL ago.
+    pub fn export(&mut self, panels: &[PlotPanel]) -> Result<(), String> {
+        if self.last_export.is_some_and(|at| at.elapsed() < REFRESH_INTERVAL) {
+            return Ok(());
+        }
+        self.last_export = Some(Instant::now());
+        // Written aside and renamed, so the viewer never reads half a file.
+        let partial = format!("{}.partial", PANELS_PATH);
+        fs::write(&partial, to_text(panels))
+            .and_then(|()| fs::rename(&partial, PANELS_PATH))
+            .map_err(|e| format!("Failed to write {}: {}", PANELS_PATH, e))
+    }
+}
+
+impl Drop for DetachedPanels {
+    fn drop(&mut self) {
+        let _ = self.viewer.kill();
+        let _ = self.viewer.wait();
+        let _ = fs::remove_file(PANELS_PATH);
+    }
+}
+
+// One `panel` line per panel, followed by its colour, its fit line if it has one and its
+// points.
+fn to_text(panels: &[PlotPanel]) -> String {
+    let mut text = versioning::header("panels", MIGRATIONS);
+    for panel in panels {
+        let color = panel.color;
+        text += &format!("panel {}\n", panel.title);
+        text += &format!("color {} {} {} {}\n", color.r, color.g, color.b, color.a);
+        if let Some((from, to)) = panel.overlay {
+            text += &format!("fit {} {} {} {}\n", from[0], from[1], to[0], to[1]);
+        }
+        for point in panel.series.iter() {
+            text += &format!("point {} {}\n", point[0], point[1]);
+        }
+    }
+    text
+}
+
+// Panels without a place yet; the viewer gives each one its cell of the grid.
+fn parse(text: &str) -> Result<Vec<PlotPanel<'static>>, String> {
+    let text = versioning::upgrade_text(text, "panels", MIGRATIONS)?;
+    let mut panels: Vec<PlotPanel> = Vec::new();
+    for (line_no, line) in text.lines().enumerate().filter(|(_, line)| !line.starts_with('#')) {
+        let error = |what: &str| format!("line {}: {}", line_no + 1, what);
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if keyword == "panel" {
+            panels.push(PlotPanel {
+                area: graphics::Rect::default(),
+                title: rest.to_string(),
+                series: Cow::Owned(Vec::new()),
+                overlay: None,
+                color: graphics::Color::WHITE,
+            });
+            continue;
+        }
+        let panel = panels.last_mut().ok_or_else(|| error("expected a `panel` line first"))?;
+        let numbers = rest
+            .split_whitespace()
+            .map(|field| field.parse::<f32>().map_err(|_| error(&format!("expected a number, got `{}`", field))))
+            .collect::<Result<Vec<f32>, String>>()?;
+        match (keyword, &numbers[..]) {
+            ("color", &[r, g, b, a]) => panel.color = graphics::Color::new(r, g, b, a),
+            ("fit", &[x0, y0, x1, y1]) => panel.overlay = Some(([x0, y0], [x1, y1])),
+            ("point", &[x, y]) => panel.series.to_mut().push([x, y]),
+            _ => return Err(error(&format!("unexpected `{}`", line))),
+        }
+    }
+    Ok(panels)
+}
+
+struct PanelViewer {
+    panels: Vec<PlotPanel<'static>>,
+    path: String,
+    // Modification time of the file last read, so it is only read again when it changes.
+    read_at: Option<SystemTime>,
+    last_check: Instant,
+    theme: Theme,
+    toasts: Toasts,
+}
+
+impl PanelViewer {
+    fn refresh(&mut self) {
+        let modified = match fs::metadata(&self.path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => modified,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.panels.clear();
+                self.read_at = None;
+                return;
+            }
+            Err(e) => {
+                self.toasts.error(format!("Failed to read {}: {}", self.path, e));
+                return;
+            }
+        };
+        if self.read_at == Some(modified) {
+            return;
+        }
+        self.read_at = Some(modified);
+        match fs::read_to_string(&self.path).map_err(|e| e.to_string()).and_then(|text| parse(&text)) {
+            Ok(panels) => self.panels = panels,
+            Err(e) => self.toasts.error(format!("{}: {}", self.path, e)),
+        }
+    }
+}
+
+impl event::EventHandler for PanelViewer {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        if self.last_check.elapsed() >= REFRESH_INTERVAL {
+            self.last_check = Instant::now();
+            self.refresh();
+        }
+        timer::sleep(VIEWER_FRAME);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.theme.background);
+        if self.panels.is_empty() {
+            let text = graphics::Text::new(
+                graphics::TextFragment::new("No analysis panels are open in the main window")
+                    .font(graphics::Font::default())
+                    .scale(graphics::PxScale::from(16.0)),
+            );
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest([MARGIN, MARGIN]).color(self.theme.text))?;
+        }
+        let rows = self.panels.len().div_ceil(COLUMNS).max(1);
+        let width = (SCREEN_WIDTH - MARGIN) / COLUMNS as f32 - MARGIN;
+        let height = (SCREEN_HEIGHT - MARGIN) / rows as f32 - MARGIN;
+        for (i, panel) in self.panels.iter_mut().enumerate() {
+            let (column, row) = ((i % COLUMNS) as f32, (i / COLUMNS) as f32);
+            let (x, y) = (MARGIN + column * (width + MARGIN), MARGIN + row * (height + MARGIN));
+            panel.area = graphics::Rect::new(x, y, width, height);
+            panel.draw(ctx, &self.theme)?;
+        }
+        self.toasts.draw(ctx, &self.theme)?;
+        graphics::present(ctx)
+    }
+}
+
+pub fn run_viewer(args: &[String]) -> GameResult {
+    let mut path = PANELS_PATH.to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => path = args.next().ok_or(GameError::CustomError("--file needs a path".to_string()))?.clone(),
+            other => return Err(GameError::CustomError(format!("unknown argument `{}`", other))),
+        }
+    }
+
+    let (config, problems) = AppConfig::load();
+    let (theme, theme_problems) = theme::configured(config.theme.as_deref(), config.palette);
+    let mut toasts = Toasts::default();
+    for problem in problems.into_iter().chain(theme_problems) {
+        toasts.error(problem);
+    }
+    let (ctx, event_loop) = ggez::ContextBuilder::new("entropicrust", "emanuellcs")
+        .window_setup(conf::WindowSetup::default().title("EntropicRust panels").vsync(config.vsync))
+        .window_mode(conf::WindowMode::default().dimensions(SCREEN_WIDTH, SCREEN_HEIGHT))
+        .build()?;
+    let mut viewer = PanelViewer { panels: Vec::new(), path, read_at: None, last_check: Instant::now(), theme, toasts };
+    viewer.refresh();
+    event::run(ctx, event_loop, viewer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panels_survive_the_file() {
+        let series = [[0.0, 1.5], [1.0, f32::NAN], [2.0, -3.25]];
+        let panel = PlotPanel {
+            area: graphics::Rect::new(1.0, 2.0, 3.0, 4.0),
+            title: "log C(r) vs log r   D2 ≈ 2,05".to_string(),
+            series: Cow::Borrowed(&series),
+            overlay: Some(([0.0, 1.0], [2.0, 3.0])),
+            color: graphics::Color::new(0.5, 0.8, 1.0, 1.0),
+        };
+        let read = parse(&to_text(&[panel])).unwrap();
+
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].title, "log C(r) vs log r   D2 ≈ 2,05");
+        assert_eq!(read[0].overlay, Some(([0.0, 1.0], [2.0, 3.0])));
+        assert_eq!(read[0].color, graphics::Color::new(0.5, 0.8, 1.0, 1.0));
+        assert_eq!(read[0].series.len(), 3);
+        assert_eq!(read[0].series[2], [2.0, -3.25]);
+        assert!(read[0].series[1][1].is_nan());
+    }
+}
@@ -0,0 +1,213 @@
+// Filename: double_pendulum.rs
+// Project: EntropicRust
+// Description: Double pendulum mode. A row of pendulums is released from almost the same
+//              angles, a ten-thousandth of a radian apart, and drawn as the swinging rods
+//              on the left and as a portrait of the two angles on the right, where the
+//              paths that began together visibly part after a few swings.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
+
+use entropicrust::systems::double_pendulum::{self, Params, State};
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::locale::Locale;
+use crate::main_state::SCREEN_HEIGHT;
+use crate::theme::Theme;
+use crate::trail_gradient::{self, TrailGradient, TrailWidth};
+
+pub const DEFAULT_COUNT: usize = 12;
+pub const MAX_COUNT: usize = 200;
+// Metre-long rods, unit masses and Earth's gravity, so a swing looks like one.
+const PARAMS: Params = Params { gravity: 9.81, length1: 1.0, length2: 1.0, mass1: 1.0, mass2: 1.0 };
+// Both rods raised to 120 degrees, high enough to flip the lower one over.
+const START: State = [2.0 * PI / 3.0, 2.0 * PI / 3.0, 0.0, 0.0];
+// Difference in the upper angle between neighbouring pendulums, in radians.
+const SPREAD: f32 = 1.0e-4;
+// Runge-Kutta steps per frame.
+const SUBSTEPS: usize = 4;
+const TRAIL_LENGTH: usize = 120;
+const PORTRAIT_LENGTH: usize = 600;
+const PIVOT: Point2<f32> = Point2 { x: 210.0, y: 270.0 };
+const PIXELS_PER_METRE: f32 = 105.0;
+const PORTRAIT: graphics::Rect = graphics::Rect { x: 430.0, y: 120.0, w: 340.0, h: 340.0 };
+// The first and last pendulum's colours; the rest blend between them.
+const FIRST_COLOR: (f32, f32, f32) = (1.0, 0.5, 0.3);
+const LAST_COLOR: (f32, f32, f32) = (0.5, 0.6, 1.0);
+
+struct Pendulum {
+    state: State,
+    // Recent positions of the lower bob, in screen pixels.
+    trail: VecDeque<Point2<f32>>,
+    // Recent (θ1, θ2), placed in the portrait.
+    portrait: VecDeque<Point2<f32>>,
+    color: graphics::Color,
+}
+
+pub struct DoublePendulum {
+    pendulums: Vec<Pendulum>,
+    pub paused: bool,
+    time: f32,
+    // Of the first pendulum at release, for the drift readout.
+    initial_energy: f32,
+}
+
+impl DoublePendulum {
+    pub fn new(count: usize) -> Self {
+        let count = count.clamp(1, MAX_COUNT);
+        let pendulums = (0..count)
+            .map(|i| {
+                let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.0 };
+                let mix = |a: f32, b: f32| a + (b - a) * t;
+                let [t1, t2, w1, w2] = START;
+                Pendulum {
+                    state: [t1 + i as f32 * SPREAD, t2, w1, w2],
+                    trail: VecDeque::with_capacity(TRAIL_LENGTH),
+                    portrait: VecDeque::with_capacity(PORTRAIT_LENGTH),
+                    color: graphics::Color::new(
+                        mix(FIRST_COLOR.0, LAST_COLOR.0),
+                        mix(FIRST_COLOR.1, LAST_COLOR.1),
+                        mix(FIRST_COLOR.2, LAST_COLOR.2),
+                        1.0,
+                    ),
+                }
+            })
+            .collect();
+        DoublePendulum {
+            pendulums,
+            paused: false,
+            time: 0.0,
+            initial_energy: double_pendulum::energy(START, &PARAMS),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.pendulums.len()
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        let h = dt / SUBSTEPS as f32;
+        for pendulum in &mut self.pendulums {
+            for _ in 0..SUBSTEPS {
+                pendulum.state = double_pendulum::step(pendulum.state, &PARAMS, h);
+            }
+            if pendulum.trail.len() == TRAIL_LENGTH {
+                pendulum.trail.pop_front();
+            }
+            pendulum.trail.push_back(to_screen(double_pendulum::bobs(pendulum.state, &PARAMS)[1]));
+            if pendulum.portrait.len() == PORTRAIT_LENGTH {
+                pendulum.portrait.pop_front();
+            }
+            pendulum.portrait.push_back(portrait_point(pendulum.state));
+        }
+        self.time += dt;
+    }
+
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        theme: &Theme,
+        gradient: TrailGradient,
+        width: TrailWidth,
+        locale: Locale,
+    ) -> GameResult {
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), PORTRAIT, theme.panel)?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), PORTRAIT, theme.frame)?;
+        // Both rods hanging straight down.
+        let centre = Point2 { x: PORTRAIT.x + PORTRAIT.w / 2.0, y: PORTRAIT.y + PORTRAIT.h / 2.0 };
+        let axis = graphics::Color { a: 0.4, ..theme.frame };
+        let across = [Point2 { x: PORTRAIT.x, y: centre.y }, Point2 { x: PORTRAIT.right(), y: centre.y }];
+        let down = [Point2 { x: centre.x, y: PORTRAIT.y }, Point2 { x: centre.x, y: PORTRAIT.bottom() }];
+        builder.line(&across, 1.0, axis)?;
+        builder.line(&down, 1.0, axis)?;
+
+        for pendulum in &self.pendulums {
+            let color = theme.palette.particle_color(pendulum.color);
+            let points: Vec<Point2<f32>> = pendulum.trail.iter().copied().collect();
+            trail_gradient::append_trail(&mut builder, &points, 1.0, color, gradient, width, theme.trail_alpha)?;
+            // Angles wrap at ±π, so the path is broken where it leaves one edge for the other.
+            let faint = graphics::Color { a: 0.6, ..color };
+            let mut run: Vec<Point2<f32>> = Vec::new();
+            for &p in &pendulum.portrait {
+                if let Some(last) = run.last()
+                    && ((p.x - last.x).abs() > PORTRAIT.w / 2.0 || (p.y - last.y).abs() > PORTRAIT.h / 2.0)
+                {
+                    if run.len() >= 2 {
+                        builder.line(&run, 1.0, faint)?;
+                    }
+                    run.clear();
+                }
+                run.push(p);
+            }
+            if run.len() >= 2 {
+                builder.line(&run, 1.0, faint)?;
+            }
+        }
+        // Rods and bobs go over every trail.
+        for pendulum in &self.pendulums {
+            let color = theme.palette.particle_color(pendulum.color);
+            let [upper, lower] = double_pendulum::bobs(pendulum.state, &PARAMS).map(to_screen);
+            builder.line(&[PIVOT, upper, lower], 2.0, graphics::Color { a: 0.7, ..color })?;
+            builder.circle(graphics::DrawMode::fill(), upper, 6.0, 0.2, color)?;
+            builder.circle(graphics::DrawMode::fill(), lower, 6.0, 0.2, color)?;
+            builder.circle(graphics::DrawMode::fill(), portrait_point(pendulum.state), 3.0, 0.2, color)?;
+        }
+        builder.circle(graphics::DrawMode::fill(), PIVOT, 4.0, 0.2, theme.text)?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let energy = self.pendulums.first().map_or(0.0, |p| double_pendulum::energy(p.state, &PARAMS));
+        let drift = (energy - self.initial_energy) / self.initial_energy.abs();
+        let status = format!(
+            "{} pendulums, released {} rad apart   t = {} s   energy drift {}{}",
+            self.count(),
+            locale.scientific(SPREAD, 0),
+            locale.fixed(self.time, 1),
+            locale.scientific(drift, 1),
+            if self.paused { "   (paused)" } else { "" }
+        );
+        draw_text(ctx, "Double pendulum", [20.0, 20.0], 22.0, theme.text)?;
+        draw_text(ctx, &status, [20.0, 50.0], 14.0, theme.text)?;
+        draw_text(ctx, "θ1 across, θ2 up, each from -π to π", [PORTRAIT.x, PORTRAIT.y - 24.0], 16.0, theme.text)?;
+        let hint = "Up/Down: pendulums (Shift: ten at a time), R: release again, Space: pause, Esc or Ctrl+Home: back";
+        draw_text(ctx, hint, [20.0, SCREEN_HEIGHT - 40.0], 14.0, theme.frame)
+    }
+}
+
+// Text in the default font, for the modes that draw their own screen.
+pub fn draw_text(ctx: &mut Context, text: &str, at: [f32; 2], size: f32, color: graphics::Color) -> GameResult {
+    let text = graphics::Text::new(
+        graphics::TextFragment::new(text).font(graphics::Font::default()).scale(graphics::PxScale::from(size)),
+    );
+    graphics::draw(ctx, &text, graphics::DrawParam::default().dest(at).color(color))
+}
+
+fn to_screen([x, y]: [f32; 2]) -> Point2<f32> {
+    Point2 { x: PIVOT.x + x * PIXELS_PER_METRE, y: PIVOT.y + y * PIXELS_PER_METRE }
+}
+
+fn wrap(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+// θ1 across and θ2 up, each over (-π, π].
+fn portrait_point(state: State) -> Point2<f32> {
+    let [t1, t2, _, _] = state;
+    Point2 {
+        x: PORTRAIT.x + (wrap(t1) + PI) / TAU * PORTRAIT.w,
+        y: PORTRAIT.y + (PI - wrap(t2)) / TAU * PORTRAIT.h,
+    }
+}
+
@@ -0,0 +1,48 @@
+// Filename: drift.rs
+// Project: EntropicRust
+// Description: Slow parameter drift ("annealing"). One parameter sweeps back and forth
+//              across a range around its starting value while the simulation runs, so
+//              bifurcation cascades unfold on screen without user input.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::param_scan::ScanAxis;
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_parameter_names};
+
+// Simulated time for one pass from one end of the range to the other.
+pub const DRIFT_DURATION: f32 = 150.0;
+
+pub struct ParameterDrift {
+    pub axis: ScanAxis,
+    // Position along the back-and-forth cycle, in [0, 2).
+    phase: f32,
+}
+
+impl ParameterDrift {
+    pub fn new(system_type: SystemType, params: &SystemParameters, index: usize) -> Self {
+        let names = get_parameter_names(system_type);
+        let axis = ScanAxis::around(names[index % names.len()], params);
+        // Start where the parameter currently sits, in the middle of the range.
+        ParameterDrift { axis, phase: 0.5 }
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.phase <= 1.0 { self.phase } else { 2.0 - self.phase }
+    }
+
+    pub fn advance(&mut self, params: &mut SystemParameters, dt: f32) {
+        self.phase = (self.phase + dt / DRIFT_DURATION).rem_euclid(2.0);
+        params.set(self.axis.name, self.axis.value_at(self.progress()));
+    }
+
+    pub fn descending(&self) -> bool {
+        self.phase > 1.0
+    }
+}
@@ -0,0 +1,250 @@
+// Filename: dynamics.rs
+// Project: EntropicRust
+// Description: Equations of motion for the supported chaotic systems, their analytic
+//              Jacobians and the step function that advances a single state. Kept free of
+//              any rendering state so it can run on worker threads. The equations and the
+//              plain step themselves live in the library's `systems` module; this picks the
+//              system and its parameters out of the application's types.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+use entropicrust::systems::{
+    aizawa, chen_lee, duffing, food_chain, four_wing, lorenz, newton_leipnik, rabinovich_fabrikant, rossler, sakarya,
+    van_der_pol,
+};
+use rand::Rng;
+
+use crate::particle::SystemType;
+use crate::system_parameters::SystemParameters;
+
+pub fn derivative(system_type: SystemType, params: &SystemParameters, state: [f32; 3]) -> [f32; 3] {
+    match system_type {
+        SystemType::Lorenz => lorenz::derivative(state, &params.lorenz()),
+        SystemType::Rossler => rossler::derivative(state, &params.rossler()),
+        SystemType::Aizawa => aizawa::derivative(state, &params.aizawa()),
+        SystemType::ChenLee => chen_lee::derivative(state, &params.chen_lee()),
+        SystemType::Duffing => duffing::derivative(state, &params.duffing()),
+        SystemType::VanDerPol => van_der_pol::derivative(state, &params.van_der_pol()),
+        SystemType::RabinovichFabrikant => {
+            rabinovich_fabrikant::derivative(state, &params.rabinovich_fabrikant())
+        }
+        SystemType::NewtonLeipnik => newton_leipnik::derivative(state, &params.newton_leipnik()),
+        SystemType::FourWing => four_wing::derivative(state, &params.four_wing()),
+        SystemType::Sakarya => sakarya::derivative(state, &params.sakarya()),
+        SystemType::FoodChain => food_chain::derivative(state, &params.food_chain()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Integrator {
+    Euler,
+    // Heun's method with the Euler predictor as its embedded lower-order solution.
+    Heun,
+}
+
+impl Integrator {
+    pub fn from_name(name: &str) -> Option<Integrator> {
+        match name.to_ascii_lowercase().as_str() {
+            "euler" => Some(Integrator::Euler),
+            "heun" | "heun-euler" => Some(Integrator::Heun),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Integrator::Euler => "Euler",
+            Integrator::Heun => "Heun-Euler",
+        }
+    }
+
+    pub fn next(self) -> Integrator {
+        match self {
+            Integrator::Euler => Integrator::Heun,
+            Integrator::Heun => Integrator::Euler,
+        }
+    }
+}
+
+pub fn step(system_type: SystemType, params: &SystemParameters, state: [f32; 3], dt: f32) -> [f32; 3] {
+    match system_type {
+        SystemType::Lorenz => lorenz::step(state, &params.lorenz(), dt),
+        SystemType::Rossler => rossler::step(state, &params.rossler(), dt),
+        SystemType::Aizawa => aizawa::step(state, &params.aizawa(), dt),
+        SystemType::ChenLee => chen_lee::step(state, &params.chen_lee(), dt),
+        SystemType::Duffing => duffing::step(state, &params.duffing(), dt),
+        SystemType::VanDerPol => van_der_pol::step(state, &params.van_der_pol(), dt),
+        SystemType::RabinovichFabrikant => rabinovich_fabrikant::step(state, &params.rabinovich_fabrikant(), dt),
+        SystemType::NewtonLeipnik => newton_leipnik::step(state, &params.newton_leipnik(), dt),
+        SystemType::FourWing => four_wing::step(state, &params.four_wing(), dt),
+        SystemType::Sakarya => sakarya::step(state, &params.sakarya(), dt),
+        SystemType::FoodChain => food_chain::step(state, &params.food_chain(), dt),
+    }
+}
+
+// Euler–Maruyama step for dX = f(X) dt + σ dW. The driving phase of forced systems is a
+// clock, not a state variable, and is left noise-free.
+// The second value is the local error estimate, available only from embedded integrators.
+pub fn step_noisy<R: Rng>(
+    system_type: SystemType,
+    params: &SystemParameters,
+    state: [f32; 3],
+    dt: f32,
+    integrator: Integrator,
+    noise: f32,
+    rng: &mut R,
+) -> ([f32; 3], Option<f32>) {
+    let (mut next, error) = match integrator {
+        Integrator::Euler => (step(system_type, params, state, dt), None),
+        Integrator::Heun => {
+            let (next, error) = step_heun(system_type, params, state, dt);
+            (next, Some(error))
+        }
+    };
+    if noise <= 0.0 {
+        return (next, error);
+    }
+    let amplitude = noise * dt.sqrt();
+    let noisy_axes = if system_type.is_driven() { 2 } else { 3 };
+    for value in next.iter_mut().take(noisy_axes) {
+        *value += amplitude * standard_normal(rng);
+    }
+    (confine(system_type, next), error)
+}
+
+// Keeps population models in the positive octant, which noise or a long step can leave;
+// a negative abundance would grow without bound.
+fn confine(system_type: SystemType, state: [f32; 3]) -> [f32; 3] {
+    if system_type.is_population() { state.map(|v| v.max(0.0)) } else { state }
+}
+
+// Heun step plus the distance to the embedded Euler solution, which estimates the local
+// error of the lower-order method.
+pub fn step_heun(system_type: SystemType, params: &SystemParameters, state: [f32; 3], dt: f32) -> ([f32; 3], f32) {
+    let k1 = derivative(system_type, params, state);
+    let euler = [state[0] + k1[0] * dt, state[1] + k1[1] * dt, state[2] + k1[2] * dt];
+    let k2 = derivative(system_type, params, euler);
+    let mut heun = [
+        state[0] + 0.5 * (k1[0] + k2[0]) * dt,
+        state[1] + 0.5 * (k1[1] + k2[1]) * dt,
+        state[2] + 0.5 * (k1[2] + k2[2]) * dt,
+    ];
+    let error = (0..3).map(|i| (heun[i] - euler[i]).powi(2)).sum::<f32>().sqrt();
+    if system_type.is_driven() {
+        heun[2] = heun[2].rem_euclid(TAU);
+    }
+    (confine(system_type, heun), error)
+}
+
+// Classical fourth-order Runge–Kutta step, the accurate run the cheaper integrators are
+// measured against.
+pub fn step_rk4(system_type: SystemType, params: &SystemParameters, state: [f32; 3], dt: f32) -> [f32; 3] {
+    let f = |s: [f32; 3]| derivative(system_type, params, s);
+    let along = |s: [f32; 3], k: [f32; 3], h: f32| [s[0] + k[0] * h, s[1] + k[1] * h, s[2] + k[2] * h];
+    let k1 = f(state);
+    let k2 = f(along(state, k1, dt / 2.0));
+    let k3 = f(along(state, k2, dt / 2.0));
+    let k4 = f(along(state, k3, dt));
+    let mut next: [f32; 3] =
+        std::array::from_fn(|i| state[i] + dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]));
+    if system_type.is_driven() {
+        next[2] = next[2].rem_euclid(TAU);
+    }
+    confine(system_type, next)
+}
+
+// Box–Muller transform; one of the pair is discarded to keep the call stateless.
+fn standard_normal<R: Rng>(rng: &mut R) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+pub fn jacobian(system_type: SystemType, params: &SystemParameters, state: [f32; 3]) -> [[f32; 3]; 3] {
+    let [x, y, z] = state;
+
+    match system_type {
+        SystemType::Lorenz => [
+            [-params.sigma, params.sigma, 0.0],
+            [params.rho - z, -1.0, -x],
+            [y, x, -params.beta],
+        ],
+        SystemType::Rossler => [
+            [0.0, -1.0, -1.0],
+            [1.0, params.a, 0.0],
+            [z, 0.0, x - params.c],
+        ],
+        SystemType::Aizawa => [
+            [z - params.gamma, -params.delta, x],
+            [params.delta, z - params.gamma, y],
+            [
+                -2.0 * x * (1.0 + params.epsilon * z) + 3.0 * params.delta * z * x * x,
+                -2.0 * y * (1.0 + params.epsilon * z),
+                params.beta - z * z - params.epsilon * (x * x + y * y) + params.delta * x * x * x,
+            ],
+        ],
+        SystemType::ChenLee => [
+            [params.p, -z, -y],
+            [z, params.q, x],
+            [y / 3.0, x / 3.0, params.r],
+        ],
+        SystemType::Duffing => [
+            [0.0, 1.0, 0.0],
+            [
+                -params.duffing_alpha - 3.0 * params.duffing_beta * x * x,
+                -params.duffing_delta,
+                -params.duffing_gamma * z.sin(),
+            ],
+            [0.0, 0.0, 0.0],
+        ],
+        SystemType::VanDerPol => [
+            [0.0, 1.0, 0.0],
+            [
+                -2.0 * params.vdp_mu * x * y - 1.0,
+                params.vdp_mu * (1.0 - x * x),
+                params.vdp_amplitude * z.cos(),
+            ],
+            [0.0, 0.0, 0.0],
+        ],
+        SystemType::RabinovichFabrikant => [
+            [2.0 * x * y + params.rf_gamma, z - 1.0 + x * x, y],
+            [3.0 * z + 1.0 - 3.0 * x * x, params.rf_gamma, 3.0 * x],
+            [-2.0 * z * y, -2.0 * z * x, -2.0 * (params.rf_alpha + x * y)],
+        ],
+        SystemType::NewtonLeipnik => [
+            [-params.nl_a, 1.0 + 10.0 * z, 10.0 * y],
+            [-1.0 + 5.0 * z, -0.4, 5.0 * x],
+            [-5.0 * y, -5.0 * x, params.nl_b],
+        ],
+        SystemType::FourWing => [
+            [params.fw_a, z, y],
+            [params.fw_b - z, params.fw_c, -x],
+            [-y, -x, -1.0],
+        ],
+        SystemType::Sakarya => [
+            [-1.0, 1.0 + z, y],
+            [-1.0 + params.sk_a * z, -1.0, params.sk_a * x],
+            [-params.sk_b * y, -params.sk_b * x, 1.0],
+        ],
+        SystemType::FoodChain => {
+            let (a1, b1, a2, b2) = (params.fc_a1, params.fc_b1, params.fc_a2, params.fc_b2);
+            // Saturation denominators of the two appetites.
+            let (prey, predator) = (1.0 + b1 * x, 1.0 + b2 * y);
+            let (eaten, hunted) = (a1 / (prey * prey), a2 / (predator * predator));
+            let rows = [
+                [1.0 - 2.0 * x - eaten * y, -a1 * x / prey, 0.0],
+                [eaten * y, a1 * x / prey - hunted * z - params.fc_d1, -a2 * y / predator],
+                [0.0, hunted * z, a2 * y / predator - params.fc_d2],
+            ];
+            rows.map(|row| row.map(|v| v * food_chain::TIME_UNIT))
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Filename: embedding.rs
+// Project: EntropicRust
+// Description: Takens delay-embedding reconstruction. Only x(t) of the reference particle
+//              is kept; delay vectors (x(t), x(t-τ), x(t-2τ), ...) are built from it and
+//              their first three components are projected for display next to the real
+//              attractor.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+pub const EMBEDDING_POINTS: usize = 1_500;
+pub const MAX_DELAY: usize = 100;
+pub const MAX_DIMENSION: usize = 6;
+
+pub struct DelayEmbedding {
+    history: VecDeque<f32>,
+    pub delay: usize,
+    pub dimension: usize,
+}
+
+impl DelayEmbedding {
+    pub fn new() -> Self {
+        DelayEmbedding {
+            history: VecDeque::with_capacity(EMBEDDING_POINTS + MAX_DELAY * MAX_DIMENSION),
+            delay: 8,
+            dimension: 3,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    pub fn push(&mut self, x: f32) {
+        if !x.is_finite() {
+            return;
+        }
+        if self.history.len() >= EMBEDDING_POINTS + MAX_DELAY * MAX_DIMENSION {
+            self.history.pop_front();
+        }
+        self.history.push_back(x);
+    }
+
+    pub fn adjust_delay(&mut self, delta: isize) {
+        self.delay = self.delay.saturating_add_signed(delta).clamp(1, MAX_DELAY);
+    }
+
+    pub fn adjust_dimension(&mut self, delta: isize) {
+        self.dimension = self.dimension.saturating_add_signed(delta).clamp(2, MAX_DIMENSION);
+    }
+
+    pub fn projected_points(&self) -> Vec<[f32; 2]> {
+        let span = self.delay * (self.dimension - 1);
+        let len = self.history.len();
+        if len <= span {
+            return Vec::new();
+        }
+
+        let first = span.max(len.saturating_sub(EMBEDDING_POINTS));
+        (first..len)
+            .map(|t| {
+                let lag = |k: usize| if k < self.dimension { self.history[t - k * self.delay] } else { 0.0 };
+                let (a, b, c) = (lag(0), lag(1), lag(2));
+                // Oblique projection keeps the 2D case exact and gives depth cues in 3D.
+                [a + 0.5 * c, b + 0.35 * c]
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,83 @@
+// Filename: emitter.rs
+// Project: EntropicRust
+// Description: Particle emitters. An emitter sits at a point in state space and releases
+//              new particles at a steady rate from that point, a short line segment or a
+//              small sphere shell around it, so the cloud is fed continuously instead of
+//              being seeded once.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+// Segment half-length and shell radius, in screen pixels.
+pub const EMITTER_EXTENT_PX: f32 = 30.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EmitterShape {
+    Point,
+    Segment,
+    Shell,
+}
+
+impl EmitterShape {
+    pub fn next(self) -> EmitterShape {
+        match self {
+            EmitterShape::Point => EmitterShape::Segment,
+            EmitterShape::Segment => EmitterShape::Shell,
+            EmitterShape::Shell => EmitterShape::Point,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EmitterShape::Point => "point",
+            EmitterShape::Segment => "segment",
+            EmitterShape::Shell => "sphere shell",
+        }
+    }
+}
+
+pub struct Emitter {
+    pub origin: [f32; 3],
+    pub shape: EmitterShape,
+    // Extent in state-space units.
+    pub extent: f32,
+    pending: f32,
+}
+
+impl Emitter {
+    pub fn new(origin: [f32; 3], shape: EmitterShape, extent: f32) -> Self {
+        Emitter { origin, shape, extent, pending: 0.0 }
+    }
+
+    // Start states for the particles due after `dt` of simulated time at `rate` per unit time.
+    pub fn emit<R: Rng>(&mut self, dt: f32, rate: f32, rng: &mut R) -> Vec<[f32; 3]> {
+        self.pending += dt * rate;
+        let count = self.pending.floor();
+        self.pending -= count;
+        (0..count as usize).map(|_| self.sample(rng)).collect()
+    }
+
+    fn sample<R: Rng>(&self, rng: &mut R) -> [f32; 3] {
+        let [x, y, z] = self.origin;
+        match self.shape {
+            EmitterShape::Point => self.origin,
+            EmitterShape::Segment => [x + rng.gen_range(-self.extent..=self.extent), y, z],
+            EmitterShape::Shell => {
+                // Uniform on the sphere: uniform height and azimuth.
+                let h: f32 = rng.gen_range(-1.0..=1.0);
+                let phi = rng.gen_range(0.0..TAU);
+                let ring = (1.0 - h * h).sqrt();
+                [x + self.extent * ring * phi.cos(), y + self.extent * ring * phi.sin(), z + self.extent * h]
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Filename: entropy.rs
+// Project: EntropicRust
+// Description: Sliding-window entropy estimates for one coordinate of the reference
+//              particle: normalized permutation entropy (Bandt–Pompe) and sample entropy
+//              (Richman–Moorman).
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+pub const ENTROPY_WINDOW: usize = 400;
+pub const PERMUTATION_ORDER: usize = 4;
+pub const PERMUTATION_DELAY: usize = 2;
+pub const SAMPLE_EMBEDDING: usize = 2;
+pub const SAMPLE_TOLERANCE: f32 = 0.2;
+const SAMPLE_ENTROPY_INTERVAL: usize = 15;
+
+pub struct EntropyMonitor {
+    pub axis: usize,
+    window: VecDeque<f32>,
+    scratch: Vec<f32>,
+    updates: usize,
+    pub permutation: Option<f32>,
+    pub sample: Option<f32>,
+}
+
+impl EntropyMonitor {
+    pub fn new() -> Self {
+        EntropyMonitor {
+            axis: 0,
+            window: VecDeque::with_capacity(ENTROPY_WINDOW),
+            scratch: Vec::with_capacity(ENTROPY_WINDOW),
+            updates: 0,
+            permutation: None,
+            sample: None,
+        }
+    }
+
+    pub fn cycle_axis(&mut self) {
+        self.axis = (self.axis + 1) % 3;
+        self.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.permutation = None;
+        self.sample = None;
+    }
+
+    pub fn push(&mut self, state: [f32; 3]) {
+        let value = state[self.axis];
+        if !value.is_finite() {
+            return;
+        }
+        if self.window.len() >= ENTROPY_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < ENTROPY_WINDOW {
+            return;
+        }
+
+        self.scratch.clear();
+        self.scratch.extend(self.window.iter().copied());
+        self.permutation = permutation_entropy(&self.scratch, PERMUTATION_ORDER, PERMUTATION_DELAY);
+
+        // Sample entropy is quadratic in the window length, so refresh it less often.
+        self.updates += 1;
+        if self.sample.is_none() || self.updates.is_multiple_of(SAMPLE_ENTROPY_INTERVAL) {
+            self.sample = sample_entropy(&self.scratch, SAMPLE_EMBEDDING, SAMPLE_TOLERANCE);
+        }
+    }
+}
+
+pub fn permutation_entropy(series: &[f32], order: usize, delay: usize) -> Option<f32> {
+    if order < 2 {
+        return None;
+    }
+    let span = (order - 1) * delay;
+    if series.len() <= span {
+        return None;
+    }
+
+    let patterns: usize = (1..=order).product();
+    let mut counts = vec![0usize; patterns];
+    let mut indices: Vec<usize> = Vec::with_capacity(order);
+    let mut total = 0usize;
+
+    for start in 0..series.len() - span {
+        indices.clear();
+        indices.extend(0..order);
+        indices.sort_by(|&a, &b| series[start + a * delay].total_cmp(&series[start + b * delay]));
+        counts[lehmer_code(&indices)] += 1;
+        total += 1;
+    }
+
+    let entropy: f32 = counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f32 / total as f32;
+            -p * p.ln()
+        })
+        .sum();
+    Some(entropy / (patterns as f32).ln())
+}
+
+pub fn sample_entropy(series: &[f32], m: usize, tolerance: f32) -> Option<f32> {
+    let n = series.len();
+    if n <= m + 1 {
+        return None;
+    }
+    let mean = series.iter().sum::<f32>() / n as f32;
+    let std = (series.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / n as f32).sqrt();
+    if std <= 0.0 {
+        return None;
+    }
+    let r = tolerance * std;
+
+    let templates = n - m;
+    let mut matches_m = 0u64;
+    let mut matches_m1 = 0u64;
+    for i in 0..templates {
+        for j in i + 1..templates {
+            if (0..m).all(|k| (series[i + k] - series[j + k]).abs() <= r) {
+                matches_m += 1;
+                if (series[i + m] - series[j + m]).abs() <= r {
+                    matches_m1 += 1;
+                }
+            }
+        }
+    }
+
+    if matches_m == 0 || matches_m1 == 0 {
+        return None;
+    }
+    Some(-((matches_m1 as f64 / matches_m as f64).ln()) as f32)
+}
+
+fn lehmer_code(permutation: &[usize]) -> usize {
+    let mut code = 0;
+    for i in 0..permutation.len() {
+        let smaller_after = permutation[i + 1..].iter().filter(|&&p| p < permutation[i]).count();
+        code = code * (permutation.len() - i) + smaller_after;
+    }
+    code
+}
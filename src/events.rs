@@ -0,0 +1,196 @@
+// Filename: events.rs
+// Project: EntropicRust
+// Description: Event hooks on particle trajectories. Rules such as `x up 0 -> flash, log`
+//              or `z exceeds 40 -> osc` are checked on every step; when a coordinate crosses
+//              its threshold the rule's actions fire: highlight the particle, log the
+//              simulated time, or send an OSC message over UDP for sonification.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::net::UdpSocket;
+
+pub const OSC_ADDRESS: &str = "/entropicrust/event";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Edge {
+    Up,
+    Down,
+    Either,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EventAction {
+    Flash,
+    Log,
+    Osc,
+}
+
+#[derive(Clone)]
+pub struct EventRule {
+    pub spec: String,
+    pub axis: usize,
+    pub edge: Edge,
+    pub threshold: f32,
+    pub actions: Vec<EventAction>,
+}
+
+impl EventRule {
+    // Grammar: `<x|y|z> <up|down|cross> <threshold> [-> action, ...]`, with `rises`/`exceeds`
+    // as aliases for up and `falls` for down. Without actions a rule only logs.
+    pub fn parse(spec: &str) -> Result<EventRule, String> {
+        let (condition, actions) = match spec.split_once("->") {
+            Some((condition, actions)) => (condition, Some(actions)),
+            None => (spec, None),
+        };
+        let words: Vec<&str> = condition.split_whitespace().collect();
+        let [axis, edge, threshold] = words[..] else {
+            return Err(format!("expected `<axis> <up|down|cross> <value> -> <actions>`, got `{}`", spec.trim()));
+        };
+        let axis = match axis {
+            "x" => 0,
+            "y" => 1,
+            "z" => 2,
+            _ => return Err(format!("unknown axis `{}`", axis)),
+        };
+        let edge = match edge {
+            "up" | "rises" | "exceeds" => Edge::Up,
+            "down" | "falls" => Edge::Down,
+            "cross" | "crosses" => Edge::Either,
+            _ => return Err(format!("unknown crossing `{}`", edge)),
+        };
+        let threshold = threshold
+            .parse::<f32>()
+            .map_err(|_| format!("`{}` is not a number", threshold))?;
+        let actions = match actions {
+            None => vec![EventAction::Log],
+            Some(list) => list
+                .split(',')
+                .map(|action| match action.trim() {
+                    "flash" => Ok(EventAction::Flash),
+                    "log" => Ok(EventAction::Log),
+                    "osc" => Ok(EventAction::Osc),
+                    other => Err(format!("unknown action `{}`", other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        Ok(EventRule { spec: spec.trim().to_string(), axis, edge, threshold, actions })
+    }
+
+    fn fires(&self, old: [f32; 3], new: [f32; 3]) -> bool {
+        let (before, after) = (old[self.axis] - self.threshold, new[self.axis] - self.threshold);
+        let up = before < 0.0 && after >= 0.0;
+        let down = before >= 0.0 && after < 0.0;
+        match self.edge {
+            Edge::Up => up,
+            Edge::Down => down,
+            Edge::Either => up || down,
+        }
+    }
+}
+
+pub struct EventHooks {
+    rules: Vec<EventRule>,
+    osc_target: Option<String>,
+    socket: Option<UdpSocket>,
+    osc_failed: bool,
+    // Failures since the last `take_problems`, for the on-screen messages.
+    problems: Vec<String>,
+    pub clock: f32,
+    pub fired: u64,
+}
+
+impl EventHooks {
+    pub fn new(rules: Vec<EventRule>, osc_target: Option<String>) -> Self {
+        EventHooks {
+            rules,
+            osc_target,
+            socket: None,
+            osc_failed: false,
+            problems: Vec::new(),
+            clock: 0.0,
+            fired: 0,
+        }
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn take_problems(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.problems)
+    }
+
+    pub fn advance(&mut self, dt: f32) {
+        self.clock += dt;
+    }
+
+    // Returns true when a rule asks for the particle to be highlighted.
+    pub fn check(&mut self, old: [f32; 3], new: [f32; 3]) -> bool {
+        let mut flash = false;
+        for index in 0..self.rules.len() {
+            if !self.rules[index].fires(old, new) {
+                continue;
+            }
+            self.fired += 1;
+            for action in self.rules[index].actions.clone() {
+                match action {
+                    EventAction::Flash => flash = true,
+                    EventAction::Log => println!(
+                        "t={:.3} event {} ({}) at ({:.3}, {:.3}, {:.3})",
+                        self.clock, index, self.rules[index].spec, new[0], new[1], new[2]
+                    ),
+                    EventAction::Osc => self.send_osc(index, new),
+                }
+            }
+        }
+        flash
+    }
+
+    fn send_osc(&mut self, index: usize, state: [f32; 3]) {
+        let Some(target) = self.osc_target.as_deref() else {
+            if !self.osc_failed {
+                self.problems.push("Event hooks: `osc` action needs osc_target = host:port in the config".to_string());
+                self.osc_failed = true;
+            }
+            return;
+        };
+        if self.socket.is_none() {
+            self.socket = UdpSocket::bind("0.0.0.0:0").ok();
+        }
+        let Some(socket) = &self.socket else {
+            return;
+        };
+        let message = osc_message(index as i32, self.clock, state);
+        if let Err(e) = socket.send_to(&message, target)
+            && !self.osc_failed
+        {
+            self.problems.push(format!("Event hooks: failed to send OSC to {}: {}", target, e));
+            self.osc_failed = true;
+        }
+    }
+}
+
+// OSC 1.0 message: padded address, type tags `,iffff`, then big-endian arguments.
+fn osc_message(rule: i32, time: f32, state: [f32; 3]) -> Vec<u8> {
+    fn push_padded(out: &mut Vec<u8>, text: &str) {
+        out.extend_from_slice(text.as_bytes());
+        out.push(0);
+        while !out.len().is_multiple_of(4) {
+            out.push(0);
+        }
+    }
+    let mut out = Vec::with_capacity(48);
+    push_padded(&mut out, OSC_ADDRESS);
+    push_padded(&mut out, ",iffff");
+    out.extend_from_slice(&rule.to_be_bytes());
+    for value in [time, state[0], state[1], state[2]] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    out
+}
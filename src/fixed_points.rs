@@ -0,0 +1,186 @@
+// Filename: fixed_points.rs
+// Project: EntropicRust
+// Description: Equilibrium finder. Newton's method on the analytic Jacobian is started
+//              from a spread of seeds, converged roots are de-duplicated and each one is
+//              classified from the eigenvalues of its Jacobian.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::dynamics;
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_initial_ranges, get_scale_factor};
+
+const NEWTON_ITERATIONS: usize = 60;
+const RESIDUAL_TOLERANCE: f64 = 1.0e-6;
+const SEEDS_PER_AXIS: i32 = 5;
+const EIGEN_EPSILON: f64 = 1.0e-7;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Stability {
+    StableNode,
+    UnstableNode,
+    Saddle,
+    StableFocus,
+    UnstableFocus,
+    SaddleFocus,
+    Degenerate,
+}
+
+impl Stability {
+    pub fn label(self) -> &'static str {
+        match self {
+            Stability::StableNode => "stable node",
+            Stability::UnstableNode => "unstable node",
+            Stability::Saddle => "saddle",
+            Stability::StableFocus => "stable focus",
+            Stability::UnstableFocus => "unstable focus",
+            Stability::SaddleFocus => "saddle-focus",
+            Stability::Degenerate => "non-hyperbolic",
+        }
+    }
+}
+
+pub struct FixedPoint {
+    pub position: [f32; 3],
+    pub stability: Stability,
+}
+
+pub fn find(system_type: SystemType, params: &SystemParameters) -> Vec<FixedPoint> {
+    // Seed Newton over a box roughly the size of the visible attractor.
+    let extent = 300.0 / get_scale_factor(system_type);
+    let (_, _, z_range) = get_initial_ranges(system_type);
+    let z_center = (z_range.start + z_range.end) / 2.0;
+    let mut found: Vec<FixedPoint> = Vec::new();
+
+    for i in -SEEDS_PER_AXIS..=SEEDS_PER_AXIS {
+        for j in -SEEDS_PER_AXIS..=SEEDS_PER_AXIS {
+            for k in -SEEDS_PER_AXIS..=SEEDS_PER_AXIS {
+                let s = extent / SEEDS_PER_AXIS as f32;
+                let seed = [i as f32 * s, j as f32 * s, z_center + k as f32 * s];
+                let Some(root) = newton(system_type, params, seed) else {
+                    continue;
+                };
+                let duplicate = found.iter().any(|fp| {
+                    let d: f32 = (0..3).map(|a| (fp.position[a] - root[a]).powi(2)).sum();
+                    d.sqrt() < 1.0e-3 * extent
+                });
+                if !duplicate {
+                    let j = dynamics::jacobian(system_type, params, root).map(|row| row.map(f64::from));
+                    let eigenvalues = eigenvalues(&j);
+                    found.push(FixedPoint {
+                        position: root,
+                        stability: classify(&eigenvalues),
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+fn newton(system_type: SystemType, params: &SystemParameters, seed: [f32; 3]) -> Option<[f32; 3]> {
+    let mut x = seed.map(f64::from);
+    for _ in 0..NEWTON_ITERATIONS {
+        let state = x.map(|v| v as f32);
+        let f = dynamics::derivative(system_type, params, state).map(f64::from);
+        let residual = (f[0] * f[0] + f[1] * f[1] + f[2] * f[2]).sqrt();
+        if residual < RESIDUAL_TOLERANCE {
+            return Some(state);
+        }
+        let j = dynamics::jacobian(system_type, params, state).map(|row| row.map(f64::from));
+        let delta = solve3(&j, &f)?;
+        for a in 0..3 {
+            x[a] -= delta[a];
+        }
+        if !x.iter().all(|v| v.is_finite() && v.abs() < 1.0e6) {
+            return None;
+        }
+    }
+
+    // f32 evaluation limits how small the residual can get; accept near-converged roots.
+    let state = x.map(|v| v as f32);
+    let f = dynamics::derivative(system_type, params, state);
+    let residual = (f[0] * f[0] + f[1] * f[1] + f[2] * f[2]).sqrt();
+    (residual < 1.0e-3).then_some(state)
+}
+
+fn solve3(m: &[[f64; 3]; 3], b: &[f64; 3]) -> Option<[f64; 3]> {
+    let det = determinant(m);
+    if det.abs() < 1.0e-12 {
+        return None;
+    }
+    let mut result = [0.0; 3];
+    for (col, out) in result.iter_mut().enumerate() {
+        let mut replaced = *m;
+        for row in 0..3 {
+            replaced[row][col] = b[row];
+        }
+        *out = determinant(&replaced) / det;
+    }
+    Some(result)
+}
+
+fn determinant(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+pub fn eigenvalues(m: &[[f64; 3]; 3]) -> [(f64, f64); 3] {
+    // Characteristic polynomial λ³ + a λ² + b λ + c.
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let minors = m[0][0] * m[1][1] - m[0][1] * m[1][0]
+        + m[0][0] * m[2][2] - m[0][2] * m[2][0]
+        + m[1][1] * m[2][2] - m[1][2] * m[2][1];
+    let (a, b, c) = (-trace, minors, -determinant(m));
+
+    // Depressed cubic t³ + p t + q with λ = t - a/3.
+    let shift = a / 3.0;
+    let p = b - a * a / 3.0;
+    let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    if discriminant > EIGEN_EPSILON {
+        let sqrt_d = discriminant.sqrt();
+        let u = (-q / 2.0 + sqrt_d).cbrt();
+        let v = (-q / 2.0 - sqrt_d).cbrt();
+        let real = u + v - shift;
+        let re = -(u + v) / 2.0 - shift;
+        let im = (u - v).abs() * 3.0f64.sqrt() / 2.0;
+        [(real, 0.0), (re, im), (re, -im)]
+    } else if p.abs() < EIGEN_EPSILON {
+        let root = (-q).cbrt() - shift;
+        [(root, 0.0); 3]
+    } else {
+        let r = (-p / 3.0).sqrt();
+        let phi = (-q / (2.0 * r * r * r)).clamp(-1.0, 1.0).acos();
+        let mut roots = [0.0; 3];
+        for (k, root) in roots.iter_mut().enumerate() {
+            *root = 2.0 * r * ((phi + 2.0 * std::f64::consts::PI * k as f64) / 3.0).cos() - shift;
+        }
+        roots.map(|root| (root, 0.0))
+    }
+}
+
+pub fn classify(eigenvalues: &[(f64, f64); 3]) -> Stability {
+    if eigenvalues.iter().any(|(re, _)| re.abs() < EIGEN_EPSILON) {
+        return Stability::Degenerate;
+    }
+    let stable = eigenvalues.iter().filter(|(re, _)| *re < 0.0).count();
+    let complex = eigenvalues.iter().any(|(_, im)| im.abs() > EIGEN_EPSILON);
+
+    match (stable, complex) {
+        (3, false) => Stability::StableNode,
+        (0, false) => Stability::UnstableNode,
+        (_, false) => Stability::Saddle,
+        (3, true) => Stability::StableFocus,
+        (0, true) => Stability::UnstableFocus,
+        (_, true) => Stability::SaddleFocus,
+    }
+}
@@ -0,0 +1,57 @@
+// Filename: flat_toml.rs
+// Project: EntropicRust
+// Description: The flat subset of TOML that presets and themes are written in: `key = value`
+//              lines under optional `[section]` headers, with `#` comments. A `#` inside
+//              double quotes is part of the value, so colours such as `"#ff8000"` need no
+//              special care. Each file format reads the entries and checks its own keys.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+// One `key = value` line, with quotes around the value removed.
+pub struct Entry<'a> {
+    // Counted from 1, for error messages.
+    pub line: usize,
+    // Empty before the first header.
+    pub section: &'a str,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+pub fn parse(text: &str) -> Result<Vec<Entry<'_>>, String> {
+    let mut entries = Vec::new();
+    let mut section = "";
+    for (line_no, line) in text.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = header.trim();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected `key = value`", line_no + 1));
+        };
+        entries.push(Entry { line: line_no + 1, section, key: key.trim(), value: value.trim().trim_matches('"') });
+    }
+    Ok(entries)
+}
+
+// Everything before the first `#` that is not inside a quoted string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
@@ -0,0 +1,122 @@
+// Filename: ftle.rs
+// Project: EntropicRust
+// Description: Finite-time Lyapunov exponent field. Each initial condition on a grid in
+//              the chosen plane carries its deformation gradient along a short horizon;
+//              the largest stretching rate of the Cauchy-Green tensor is the FTLE, and its
+//              ridges trace the Lagrangian coherent structures of the flow.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rayon::prelude::*;
+
+use crate::basin::{self, BasinPlane};
+use crate::dynamics;
+use crate::particle::SystemType;
+use crate::system_parameters::SystemParameters;
+
+pub const FTLE_WIDTH: usize = 200;
+pub const FTLE_HEIGHT: usize = 150;
+pub const FTLE_STEPS: usize = 200;
+const POWER_ITERATIONS: usize = 30;
+
+pub struct FtleField {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+    pub max_exponent: f32,
+}
+
+pub fn compute(system_type: SystemType, params: SystemParameters, plane: BasinPlane, dt: f32) -> FtleField {
+    let exponents: Vec<Option<f32>> = (0..FTLE_WIDTH * FTLE_HEIGHT)
+        .into_par_iter()
+        .map(|cell| {
+            let start = basin::grid_start(system_type, plane, cell, FTLE_WIDTH, FTLE_HEIGHT);
+            exponent(system_type, &params, start, dt)
+        })
+        .collect();
+
+    let max_exponent = exponents.iter().flatten().fold(0.0f32, |m, &e| m.max(e));
+    let mut rgba = Vec::with_capacity(FTLE_WIDTH * FTLE_HEIGHT * 4);
+    for value in exponents {
+        let color = match value {
+            None => [0, 0, 0],
+            Some(e) => {
+                let t = if max_exponent > 0.0 { (e / max_exponent).clamp(0.0, 1.0) } else { 0.0 };
+                [(20.0 + 235.0 * t) as u8, (20.0 + 210.0 * t * t) as u8, (70.0 + 120.0 * t.powi(4)) as u8]
+            }
+        };
+        rgba.extend_from_slice(&[color[0], color[1], color[2], 220]);
+    }
+
+    FtleField {
+        width: FTLE_WIDTH as u16,
+        height: FTLE_HEIGHT as u16,
+        rgba,
+        max_exponent,
+    }
+}
+
+fn exponent(system_type: SystemType, params: &SystemParameters, start: [f32; 3], dt: f32) -> Option<f32> {
+    let mut state = start;
+    let mut gradient = [[1.0f64, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let dt64 = dt as f64;
+
+    for _ in 0..FTLE_STEPS {
+        let j = dynamics::jacobian(system_type, params, state).map(|row| row.map(f64::from));
+        let jf = mat_mul(&j, &gradient);
+        for (row, jrow) in gradient.iter_mut().zip(jf) {
+            for (g, jg) in row.iter_mut().zip(jrow) {
+                *g += dt64 * jg;
+            }
+        }
+        state = dynamics::step(system_type, params, state, dt);
+        if !state.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+    }
+
+    // Largest eigenvalue of C = FᵀF by power iteration.
+    let mut cauchy_green = [[0.0f64; 3]; 3];
+    for (i, row) in cauchy_green.iter_mut().enumerate() {
+        for (k, c) in row.iter_mut().enumerate() {
+            *c = (0..3).map(|m| gradient[m][i] * gradient[m][k]).sum();
+        }
+    }
+    let mut v = [1.0f64, 1.0, 1.0];
+    let mut lambda = 0.0;
+    for _ in 0..POWER_ITERATIONS {
+        let cv = mat_vec(&cauchy_green, &v);
+        lambda = (cv[0] * cv[0] + cv[1] * cv[1] + cv[2] * cv[2]).sqrt();
+        if lambda <= 0.0 || !lambda.is_finite() {
+            return None;
+        }
+        v = cv.map(|c| c / lambda);
+    }
+
+    let horizon = FTLE_STEPS as f64 * dt64;
+    Some((0.5 * lambda.ln() / horizon) as f32)
+}
+
+fn mat_mul(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (k, o) in row.iter_mut().enumerate() {
+            *o = (0..3).map(|m| a[i][m] * b[m][k]).sum();
+        }
+    }
+    out
+}
+
+fn mat_vec(m: &[[f64; 3]; 3], v: &[f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
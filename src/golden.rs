@@ -0,0 +1,168 @@
+// Filename: golden.rs
+// Project: EntropicRust
+// Description: Golden-trajectory check. Reference initial conditions of every system are
+//              integrated with its default parameters, once with a fine classical RK4 step
+//              as an accurate account of the equations and once with each integrator the
+//              application offers at the system's own time step, and the sampled states are
+//              compared with the ones stored in `golden.txt`. A change to an equation or an
+//              integrator that moves any sample beyond the tolerance fails the run.
+//
+//              Usage: entropicrust validate [--bless] [--file golden.txt]
+//
+//              --bless writes the current trajectories as the new golden file, for changes
+//              to the dynamics that are meant to change them.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::dynamics::{self, Integrator};
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_default_time_step, get_initial_ranges};
+
+pub const GOLDEN_PATH: &str = "golden.txt";
+// Fractions of the way through each system's seeding box.
+const INITIAL_CONDITIONS: [f32; 2] = [0.25, 0.75];
+// Short runs, so the trajectories have not yet come apart through chaos and the samples
+// stay comparable across platforms.
+const DURATION: f32 = 2.0;
+const SAMPLES: usize = 10;
+const REFERENCE_DT: f32 = 0.001;
+// Relative, with absolute below 1.
+const TOLERANCE: f32 = 1.0e-3;
+const REFERENCE: &str = "rk4";
+
+// Integrator, initial condition and sample index of one stored state.
+type Key = (SystemType, String, usize, usize);
+
+// SAMPLES states evenly spaced over DURATION, after `advance` has taken steps of `dt`.
+fn trajectory(state: [f32; 3], dt: f32, mut advance: impl FnMut([f32; 3]) -> [f32; 3]) -> Vec<[f32; 3]> {
+    let steps_per_sample = (DURATION / SAMPLES as f32 / dt).round() as usize;
+    let mut state = state;
+    (0..SAMPLES)
+        .map(|_| {
+            for _ in 0..steps_per_sample {
+                state = advance(state);
+            }
+            state
+        })
+        .collect()
+}
+
+fn compute() -> Vec<(Key, [f32; 3])> {
+    let params = SystemParameters::new();
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut samples = Vec::new();
+    for system_type in SystemType::ALL {
+        let (x, y, z) = get_initial_ranges(system_type);
+        for (ic, &t) in INITIAL_CONDITIONS.iter().enumerate() {
+            let at = |range: &Range<f32>| range.start + (range.end - range.start) * t;
+            let start = [at(&x), at(&y), at(&z)];
+            let reference =
+                trajectory(start, REFERENCE_DT, |s| dynamics::step_rk4(system_type, &params, s, REFERENCE_DT));
+            let mut runs = vec![(REFERENCE.to_string(), reference)];
+            let dt = get_default_time_step(system_type);
+            for integrator in [Integrator::Euler, Integrator::Heun] {
+                let run = trajectory(start, dt, |s| {
+                    dynamics::step_noisy(system_type, &params, s, dt, integrator, 0.0, &mut rng).0
+                });
+                runs.push((integrator.name().to_ascii_lowercase(), run));
+            }
+            for (name, run) in runs {
+                let keys = (0..).map(|i| (system_type, name.clone(), ic, i));
+                samples.extend(keys.zip(run));
+            }
+        }
+    }
+    samples
+}
+
+fn write(path: &str, samples: &[(Key, [f32; 3])]) -> std::io::Result<()> {
+    let mut text = String::from("# system integrator initial-condition sample x y z\n");
+    for ((system_type, integrator, ic, sample), [x, y, z]) in samples {
+        text += &format!("{} {} {} {} {} {} {}\n", system_type.config_name(), integrator, ic, sample, x, y, z);
+    }
+    fs::write(path, text)
+}
+
+fn read(path: &str) -> Result<HashMap<Key, [f32; 3]>, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut golden = HashMap::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = || format!("line {}: expected `system integrator ic sample x y z`", line_no + 1);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let &[system, integrator, ic, sample, x, y, z] = fields.as_slice() else {
+            return Err(error());
+        };
+        let system_type = SystemType::from_name(system).ok_or_else(error)?;
+        let index = |value: &str| value.parse::<usize>().map_err(|_| error());
+        let coordinate = |value: &str| value.parse::<f32>().map_err(|_| error());
+        golden.insert(
+            (system_type, integrator.to_string(), index(ic)?, index(sample)?),
+            [coordinate(x)?, coordinate(y)?, coordinate(z)?],
+        );
+    }
+    Ok(golden)
+}
+
+// A coordinate that had diverged in the golden run has to diverge again.
+fn within_tolerance(value: [f32; 3], golden: [f32; 3]) -> bool {
+    (0..3).all(|i| match golden[i].is_finite() {
+        true => (value[i] - golden[i]).abs() <= TOLERANCE * golden[i].abs().max(1.0),
+        false => !value[i].is_finite(),
+    })
+}
+
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let mut bless = false;
+    let mut path = GOLDEN_PATH.to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            "--file" => path = args.next().ok_or("--file needs a path")?.clone(),
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+
+    let samples = compute();
+    if bless {
+        write(&path, &samples).map_err(|e| format!("failed to write {}: {}", path, e))?;
+        println!("Wrote {} golden samples to {}", samples.len(), path);
+        return Ok(());
+    }
+
+    let golden = read(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let mut failures = 0;
+    for ((system_type, integrator, ic, sample), state) in &samples {
+        let key = (*system_type, integrator.clone(), *ic, *sample);
+        let problem = match golden.get(&key) {
+            None => "missing from the golden file".to_string(),
+            Some(&expected) if !within_tolerance(*state, expected) => {
+                format!("got {:?}, expected {:?}", state, expected)
+            }
+            Some(_) => continue,
+        };
+        failures += 1;
+        eprintln!("{} {} initial condition {} sample {}: {}", system_type.name(), integrator, ic, sample, problem);
+    }
+    if failures > 0 {
+        return Err(format!("{} of {} samples differ from {}", failures, samples.len(), path));
+    }
+    println!("All {} samples match {}", samples.len(), path);
+    Ok(())
+}
@@ -0,0 +1,132 @@
+// Filename: groups.rs
+// Project: EntropicRust
+// Description: Additional particle groups. Each group owns its own system, parameters and
+//              particles and is stepped and drawn alongside the main simulation in its own
+//              colour. Focus switching swaps a group with the main one, so every control and
+//              analysis applies to whichever system currently has focus.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+use rand::Rng;
+
+use crate::dynamics::{self, Integrator};
+use crate::particle::SystemType;
+use crate::particle_pool::ParticlePool;
+use crate::seeding::{self, SeedPattern};
+use crate::shapes::{self, ParticleShape, ParticleSprite};
+use crate::system_parameters::{SystemParameters, get_view_preset};
+use crate::theme::Theme;
+use crate::trail_gradient::{self, TrailAlpha, TrailGradient, TrailWidth};
+
+pub const GROUP_PALETTE: [(f32, f32, f32); 4] = [(1.0, 0.5, 0.3), (0.4, 0.9, 0.5), (0.5, 0.6, 1.0), (0.9, 0.4, 0.9)];
+
+pub struct SystemGroup {
+    pub system_type: SystemType,
+    pub parameters: SystemParameters,
+    pub particles: ParticlePool,
+    pub shape: ParticleShape,
+}
+
+impl SystemGroup {
+    pub fn new(
+        system_type: SystemType,
+        count: usize,
+        pattern: SeedPattern,
+        extent_px: f32,
+        palette: usize,
+        shape: ParticleShape,
+    ) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut particles = ParticlePool::with_capacity(count);
+        let (r, g, b) = GROUP_PALETTE[palette % GROUP_PALETTE.len()];
+        for [x, y, z] in seeding::positions(pattern, system_type, count, extent_px, &mut rng) {
+            let id = particles.spawn(x, y, z);
+            if let Some(particle) = particles.get_mut(id) {
+                let shade = rng.gen_range(0.75..1.0);
+                particle.color = graphics::Color::new(r * shade, g * shade, b * shade, 1.0);
+            }
+        }
+        SystemGroup {
+            system_type,
+            parameters: SystemParameters::new(),
+            particles,
+            shape,
+        }
+    }
+
+    pub fn step(&mut self, dt: f32, integrator: Integrator, noise: f32, record_trail: bool) {
+        let mut rng = rand::thread_rng();
+        let view = get_view_preset(self.system_type);
+        for particle in self.particles.iter_mut().filter(|p| !p.pinned) {
+            let ([x, y, z], error) = dynamics::step_noisy(
+                self.system_type,
+                &self.parameters,
+                [particle.x, particle.y, particle.z],
+                dt,
+                integrator,
+                noise,
+                &mut rng,
+            );
+            particle.error = error;
+            particle.age += dt;
+            particle.update(x, y, z, record_trail.then(|| (view.project([x, y, z]), view.depth([x, y, z]))));
+        }
+    }
+
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        trails: bool,
+        gradient: TrailGradient,
+        width: TrailWidth,
+        theme: &Theme,
+        mut sprite: Option<&mut ParticleSprite>,
+    ) -> GameResult {
+        let mut builder = graphics::MeshBuilder::new();
+        let mut any = false;
+        for particle in self.particles.iter() {
+            let color = theme.palette.particle_color(particle.color);
+            if trails && particle.trail.len() >= 2 {
+                let points: Vec<Point2<f32>> = particle.trail.iter().copied().collect();
+                let plain = gradient == TrailGradient::Solid
+                    && width == TrailWidth::Constant
+                    && theme.trail_alpha == TrailAlpha::FLAT;
+                if !plain {
+                    let alpha = theme.trail_alpha;
+                    trail_gradient::append_trail(&mut builder, &points, 1.0, color, gradient, width, alpha)?;
+                    any = true;
+                } else if builder.line(&points, 1.0, color).is_ok() {
+                    any = true;
+                }
+            }
+            let position = particle.get_screen_pos(self.system_type);
+            if !position.x.is_finite() || !position.y.is_finite() {
+                continue;
+            }
+            match sprite.as_deref_mut() {
+                Some(sprite) if self.shape == ParticleShape::Sprite => sprite.add(position, theme.particle_size, color),
+                _ => {
+                    shapes::add_shape(&mut builder, self.shape, position, theme.particle_size, color)?;
+                    any = true;
+                }
+            }
+        }
+        if any {
+            let mesh = builder.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        if self.shape == ParticleShape::Sprite
+            && let Some(sprite) = sprite
+        {
+            sprite.flush(ctx)?;
+        }
+        Ok(())
+    }
+}
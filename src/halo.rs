@@ -0,0 +1,67 @@
+// Filename: halo.rs
+// Project: EntropicRust
+// Description: Soft-sprite particle rendering. A radial-gradient texture is generated once
+//              and every particle is drawn as a tinted, additively blended copy of it in a
+//              single sprite batch, so dense regions of the cloud build up into a glow.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{
+    graphics::{self, spritebatch::SpriteBatch, BlendMode, Drawable},
+    mint::Point2,
+    Context, GameResult,
+};
+
+pub const DEFAULT_HALO_RADIUS: f32 = 8.0;
+pub const MIN_HALO_RADIUS: f32 = 2.0;
+pub const MAX_HALO_RADIUS: f32 = 64.0;
+const TEXTURE_SIZE: u16 = 64;
+// Gaussian falloff; the sprite has faded to ~1% at its edge.
+const FALLOFF: f32 = 4.6;
+
+pub struct HaloSprites {
+    batch: SpriteBatch,
+}
+
+impl HaloSprites {
+    pub fn new(ctx: &mut Context) -> GameResult<Self> {
+        let size = TEXTURE_SIZE as usize;
+        let mut rgba = vec![0u8; size * size * 4];
+        for row in 0..size {
+            for col in 0..size {
+                let dx = (col as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let dy = (row as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let alpha = (-FALLOFF * (dx * dx + dy * dy)).exp();
+                let pixel = &mut rgba[(row * size + col) * 4..][..4];
+                pixel.copy_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+            }
+        }
+        let image = graphics::Image::from_rgba8(ctx, TEXTURE_SIZE, TEXTURE_SIZE, &rgba)?;
+        let mut batch = SpriteBatch::new(image);
+        batch.set_blend_mode(Some(BlendMode::Add));
+        Ok(HaloSprites { batch })
+    }
+
+    // Queues one sprite of the given on-screen radius centred on `position`.
+    pub fn add(&mut self, position: Point2<f32>, radius: f32, color: graphics::Color) {
+        let scale = 2.0 * radius / TEXTURE_SIZE as f32;
+        self.batch.add(
+            graphics::DrawParam::default()
+                .dest(Point2 { x: position.x - radius, y: position.y - radius })
+                .scale([scale, scale])
+                .color(color),
+        );
+    }
+
+    pub fn flush(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::draw(ctx, &self.batch, graphics::DrawParam::default())?;
+        self.batch.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+// Filename: haptics.rs
+// Project: EntropicRust
+// Description: Gamepad rumble on trajectory events. While a gamepad with force feedback is
+//              connected, a short weak pulse marks each lobe switch of the leader particle
+//              and a shorter strong one each crossing of the stroboscopic (Poincaré) plane
+//              of the forced oscillators, so the rhythm of the dynamics can be felt as well
+//              as seen. Gamepads plugged in or out while running are picked up.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::input::gamepad::gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use ggez::input::gamepad::gilrs::{EventType, GamepadId, Gilrs};
+
+#[derive(Clone, Copy)]
+pub enum Pulse {
+    LobeSwitch,
+    PlaneCrossing,
+}
+
+impl Pulse {
+    // Motor, strength out of u16::MAX and length in milliseconds.
+    fn effect(self) -> (BaseEffectType, u32) {
+        match self {
+            Pulse::LobeSwitch => (BaseEffectType::Weak { magnitude: 40_000 }, 70),
+            Pulse::PlaneCrossing => (BaseEffectType::Strong { magnitude: 24_000 }, 40),
+        }
+    }
+}
+
+pub struct Haptics {
+    // A handle of our own: ggez keeps its gamepad context to itself, and effects need one.
+    gilrs: Gilrs,
+    // Built for the gamepads connected at the time; None without any that can rumble.
+    effects: Option<[Effect; 2]>,
+    lobe_switches: u64,
+    crossings: u64,
+}
+
+impl Haptics {
+    pub fn new() -> Result<Self, String> {
+        let gilrs = Gilrs::new().map_err(|e| format!("Gamepad rumble unavailable: {}", e))?;
+        let mut haptics = Haptics { gilrs, effects: None, lobe_switches: 0, crossings: 0 };
+        haptics.build_effects();
+        Ok(haptics)
+    }
+
+    fn build_effects(&mut self) {
+        let pads: Vec<GamepadId> =
+            self.gilrs.gamepads().filter(|(_, pad)| pad.is_ff_supported()).map(|(id, _)| id).collect();
+        self.effects = None;
+        if pads.is_empty() {
+            return;
+        }
+        let mut build = |pulse: Pulse| {
+            let (kind, millis) = pulse.effect();
+            let scheduling = Replay { play_for: Ticks::from_ms(millis), ..Replay::default() };
+            EffectBuilder::new()
+                .add_effect(BaseEffect { kind, scheduling, ..BaseEffect::default() })
+                .repeat(Repeat::For(Ticks::from_ms(millis)))
+                .gamepads(&pads)
+                .finish(&mut self.gilrs)
+                .ok()
+        };
+        if let (Some(lobe), Some(crossing)) = (build(Pulse::LobeSwitch), build(Pulse::PlaneCrossing)) {
+            self.effects = Some([lobe, crossing]);
+        }
+    }
+
+    // Called once a frame with the leader's lobe switch count, if there is a leader, and the
+    // stroboscopic section's crossing count. A pulse is played for whichever went up.
+    pub fn update(&mut self, lobe_switches: Option<u64>, crossings: u64) {
+        let mut changed = false;
+        while let Some(event) = self.gilrs.next_event() {
+            changed |= matches!(event.event, EventType::Connected | EventType::Disconnected);
+        }
+        if changed {
+            self.build_effects();
+        }
+        let lobe_switches = lobe_switches.unwrap_or(0);
+        if lobe_switches > self.lobe_switches {
+            self.pulse(Pulse::LobeSwitch);
+        }
+        if crossings > self.crossings {
+            self.pulse(Pulse::PlaneCrossing);
+        }
+        // Counts that went down belong to a new leader or a cleared section.
+        (self.lobe_switches, self.crossings) = (lobe_switches, crossings);
+    }
+
+    fn pulse(&self, pulse: Pulse) {
+        if let Some(effects) = &self.effects {
+            // A gamepad unplugged mid-pulse reports an error; the disconnect event follows.
+            let _ = effects[pulse as usize].play();
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Filename: histogram.rs
+// Project: EntropicRust
+// Description: Marginal histograms of x, y and z, taken either across the particle
+//              ensemble at the current instant or over a long time window of the
+//              reference particle, as rough estimates of the invariant distribution.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+use crate::particle_pool::ParticlePool;
+
+pub const HISTOGRAM_BINS: usize = 32;
+pub const HISTORY_LENGTH: usize = 5_000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistogramSource {
+    Ensemble,
+    ReferenceHistory,
+}
+
+pub struct CoordinateHistograms {
+    pub source: HistogramSource,
+    history: VecDeque<[f32; 3]>,
+    pub curves: [Vec<[f32; 2]>; 3],
+}
+
+impl CoordinateHistograms {
+    pub fn new(source: HistogramSource) -> Self {
+        CoordinateHistograms {
+            source,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+            curves: [Vec::new(), Vec::new(), Vec::new()],
+        }
+    }
+
+    pub fn record(&mut self, particles: &ParticlePool, reference: Option<[f32; 3]>) {
+        match self.source {
+            HistogramSource::Ensemble => {
+                let samples: Vec<[f32; 3]> = particles.iter().map(|p| [p.x, p.y, p.z]).collect();
+                self.rebuild(samples.iter());
+            }
+            HistogramSource::ReferenceHistory => {
+                if let Some(state) = reference {
+                    if self.history.len() >= HISTORY_LENGTH {
+                        self.history.pop_front();
+                    }
+                    self.history.push_back(state);
+                }
+                let history = std::mem::take(&mut self.history);
+                self.rebuild(history.iter());
+                self.history = history;
+            }
+        }
+    }
+
+    fn rebuild<'a, I: Iterator<Item = &'a [f32; 3]> + Clone>(&mut self, samples: I) {
+        for (axis, curve) in self.curves.iter_mut().enumerate() {
+            curve.clear();
+            let values = samples.clone().map(|s| s[axis]).filter(|v| v.is_finite());
+            let (min, max) = values
+                .clone()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+            if min > max {
+                continue;
+            }
+            let width = ((max - min) / HISTOGRAM_BINS as f32).max(f32::EPSILON);
+            let mut counts = [0u32; HISTOGRAM_BINS];
+            for v in values {
+                let bin = (((v - min) / width) as usize).min(HISTOGRAM_BINS - 1);
+                counts[bin] += 1;
+            }
+            // Emit a step outline so the panel's polyline reads as bars.
+            for (bin, &count) in counts.iter().enumerate() {
+                let left = min + bin as f32 * width;
+                curve.push([left, count as f32]);
+                curve.push([left + width, count as f32]);
+            }
+        }
+    }
+}
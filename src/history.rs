@@ -0,0 +1,129 @@
+// Filename: history.rs
+// Project: EntropicRust
+// Description: Parameter history. Parameter values are sampled against wall-clock time
+//              whenever they change, and a scrubber bar lets the user step or click back
+//              to any earlier moment of the session, restoring the parameters of that
+//              moment while the particles carry on from where they are.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::time::Instant;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::main_state::SCREEN_HEIGHT;
+use crate::system_parameters::SystemParameters;
+
+// Changes closer together than this (drift, LFOs) are merged into one entry.
+const RECORD_INTERVAL: f32 = 0.25;
+const MAX_ENTRIES: usize = 10_000;
+
+pub struct ParameterHistory {
+    started: Instant,
+    // (seconds since start, parameters), oldest first.
+    entries: Vec<(f32, SystemParameters)>,
+    // Entry currently restored while scrubbing.
+    pub cursor: Option<usize>,
+}
+
+impl ParameterHistory {
+    pub fn new() -> Self {
+        ParameterHistory { started: Instant::now(), entries: Vec::new(), cursor: None }
+    }
+
+    pub fn record(&mut self, params: &SystemParameters) {
+        let now = self.started.elapsed().as_secs_f32();
+        let Some(&(last_at, last)) = self.entries.last() else {
+            self.entries.push((now, *params));
+            return;
+        };
+        if last == *params || now - last_at < RECORD_INTERVAL {
+            return;
+        }
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push((now, *params));
+    }
+
+    // Moves the cursor by `delta` entries (starting from the newest) and returns the
+    // parameters there.
+    pub fn step(&mut self, delta: isize) -> Option<SystemParameters> {
+        let last = self.entries.len().checked_sub(1)?;
+        let index = self.cursor.unwrap_or(last).saturating_add_signed(delta).min(last);
+        self.cursor = Some(index);
+        Some(self.entries[index].1)
+    }
+
+    // Jumps to the entry nearest to `fraction` of the recorded span.
+    pub fn seek(&mut self, fraction: f32) -> Option<SystemParameters> {
+        let span = self.entries.last().map_or(0.0, |e| e.0);
+        let target = span * fraction.clamp(0.0, 1.0);
+        let index = self
+            .entries
+            .iter()
+            .enumerate()
+            .min_by(|a, b| (a.1.0 - target).abs().total_cmp(&(b.1.0 - target).abs()))
+            .map(|(i, _)| i)?;
+        self.cursor = Some(index);
+        Some(self.entries[index].1)
+    }
+
+    // Sits above the drift progress bar so both can show at once.
+    pub fn bar() -> graphics::Rect {
+        graphics::Rect::new(20.0, SCREEN_HEIGHT - 70.0, 400.0, 10.0)
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let bar = Self::bar();
+        let span = self.entries.last().map_or(0.0, |e| e.0).max(f32::EPSILON);
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), bar, graphics::Color::new(0.0, 0.0, 0.0, 0.6))?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), bar, graphics::Color::WHITE)?;
+        for (at, _) in &self.entries {
+            let x = bar.x + bar.w * at / span;
+            builder.line(
+                &[Point2 { x, y: bar.y + 2.0 }, Point2 { x, y: bar.y + bar.h - 2.0 }],
+                1.0,
+                graphics::Color::new(0.4, 0.8, 1.0, 0.5),
+            )?;
+        }
+        let Some(index) = self.cursor else {
+            return Ok(());
+        };
+        let at = self.entries[index].0;
+        let x = bar.x + bar.w * at / span;
+        builder.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(x - 2.0, bar.y - 3.0, 4.0, bar.h + 6.0),
+            graphics::Color::new(1.0, 0.8, 0.2, 1.0),
+        )?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let label = graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "Parameter history: {:.0} s of {:.0} s, entry {} of {} (←/→ step, click to jump, Ctrl+H to close)",
+                at,
+                span,
+                index + 1,
+                self.entries.len()
+            ))
+            .font(graphics::Font::default())
+            .scale(graphics::PxScale::from(14.0)),
+        );
+        graphics::draw(
+            ctx,
+            &label,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: bar.x, y: bar.y - 18.0 })
+                .color(graphics::Color::WHITE),
+        )
+    }
+}
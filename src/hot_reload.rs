@@ -0,0 +1,100 @@
+// Filename: hot_reload.rs
+// Project: EntropicRust
+// Description: Picks up edits to the config file, the themes and the presets while the
+//              program runs. The operating system reports changes through the notify
+//              crate; they are gathered and handed over twice a second, so an editor that
+//              saves in several steps causes one reload. Files that appear or disappear
+//              count as changes.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct FileWatcher {
+    // Files, and directories whose files are all watched.
+    roots: Vec<PathBuf>,
+    // Dropping it stops the notifications.
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    last_check: Instant,
+    // Changes before this are the program's own writes.
+    ignore_until: Instant,
+}
+
+impl FileWatcher {
+    // Directories are watched directly; single files, and directories that do not exist
+    // yet, through the working directory they sit in.
+    pub fn new(roots: &[&str]) -> Result<Self, String> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(|e| format!("File watcher: {}", e))?;
+        let roots: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+        let mut watch_working_dir = false;
+        for root in &roots {
+            if root.is_dir() {
+                watcher
+                    .watch(root, RecursiveMode::NonRecursive)
+                    .map_err(|e| format!("File watcher: {}: {}", root.display(), e))?;
+            } else {
+                watch_working_dir = true;
+            }
+        }
+        if watch_working_dir {
+            watcher.watch(Path::new("."), RecursiveMode::NonRecursive).map_err(|e| format!("File watcher: {}", e))?;
+        }
+        let now = Instant::now();
+        Ok(FileWatcher { roots, _watcher: watcher, events, last_check: now, ignore_until: now })
+    }
+
+    // Files added, removed or modified since the last call, handed over at most every
+    // CHECK_INTERVAL.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return Vec::new();
+        }
+        self.last_check = Instant::now();
+        let mut changed: Vec<PathBuf> = Vec::new();
+        for event in self.events.try_iter().filter_map(Result::ok) {
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+            for path in event.paths.iter().map(|path| relative(path)) {
+                let watched = self.roots.iter().any(|root| path == *root || path.parent() == Some(root.as_path()));
+                if watched && !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+        if self.last_check < self.ignore_until {
+            return Vec::new();
+        }
+        changed
+    }
+
+    // Drops what is reported over the next CHECK_INTERVAL, for files the program has
+    // just written itself.
+    pub fn forget_changes(&mut self) {
+        self.ignore_until = Instant::now() + CHECK_INTERVAL;
+    }
+}
+
+pub fn touches(changed: &[PathBuf], root: &str) -> bool {
+    changed.iter().any(|path| path.starts_with(Path::new(root)))
+}
+
+// The path as the roots are written, relative to the working directory.
+fn relative(path: &Path) -> PathBuf {
+    let path = std::env::current_dir().ok().and_then(|dir| path.strip_prefix(dir).ok()).unwrap_or(path);
+    path.components().filter(|component| *component != Component::CurDir).collect()
+}
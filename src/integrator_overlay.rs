@@ -0,0 +1,120 @@
+// Filename: integrator_overlay.rs
+// Project: EntropicRust
+// Description: Integrator error overlay. Every particle carries a twin state started from
+//              the same point and advanced with classical RK4 at the same time step, while
+//              the particle itself keeps running under the selected integrator. The twins
+//              are drawn in their own colour, joined to their particles, and the mean
+//              distance between the two runs is recorded on a log scale, so the error the
+//              cheaper integrator makes can be watched as it grows.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::dynamics;
+use crate::particle::SystemType;
+use crate::particle_pool::ParticlePool;
+use crate::system_parameters::{get_view_preset, SystemParameters};
+
+const MAX_SAMPLES: usize = 2_000;
+pub const TWIN_COLOR: graphics::Color = graphics::Color::new(0.3, 0.9, 1.0, 0.9);
+
+pub struct IntegratorOverlay {
+    pub elapsed: f32,
+    pub separation: Option<f32>,
+    pub samples: Vec<[f32; 2]>,
+}
+
+impl IntegratorOverlay {
+    pub fn new(particles: &mut ParticlePool) -> Self {
+        let mut overlay = IntegratorOverlay { elapsed: 0.0, separation: None, samples: Vec::new() };
+        overlay.reseed(particles);
+        overlay
+    }
+
+    // Restarts every twin from its particle's current state.
+    pub fn reseed(&mut self, particles: &mut ParticlePool) {
+        for particle in particles.iter_mut() {
+            particle.twin = Some([particle.x, particle.y, particle.z]);
+        }
+        self.elapsed = 0.0;
+        self.separation = None;
+        self.samples.clear();
+    }
+
+    // Drops the twins when the overlay closes.
+    pub fn clear(particles: &mut ParticlePool) {
+        for particle in particles.iter_mut() {
+            particle.twin = None;
+        }
+    }
+
+    // Advances the twins by one step of `dt`, after the particles have taken theirs. The twins
+    // run without noise; with noise on, the separation measures it as well as the error.
+    pub fn step(
+        &mut self,
+        particles: &mut ParticlePool,
+        system_type: SystemType,
+        parameters: &SystemParameters,
+        dt: f32,
+    ) {
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        for particle in particles.iter_mut().filter(|p| !p.pinned) {
+            // Particles respawned since the overlay opened start a twin of their own.
+            let Some(twin) = particle.twin else {
+                particle.twin = Some([particle.x, particle.y, particle.z]);
+                continue;
+            };
+            let parameters = particle.jitter.map_or(*parameters, |jitter| parameters.jittered(system_type, &jitter));
+            let next = dynamics::step_rk4(system_type, &parameters, twin, dt);
+            particle.twin = Some(next);
+            let [dx, dy, dz] = [particle.x - next[0], particle.y - next[1], particle.z - next[2]];
+            let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+            if distance.is_finite() {
+                sum += distance;
+                count += 1;
+            }
+        }
+        self.elapsed += dt;
+        self.separation = (count > 0).then(|| sum / count as f32);
+        if let Some(separation) = self.separation
+            && separation > 0.0
+            && self.samples.len() < MAX_SAMPLES
+        {
+            self.samples.push([self.elapsed, separation.log10()]);
+        }
+    }
+
+    // Twins as dots joined to their particles; drawn with the attractor camera applied.
+    pub fn draw(&self, ctx: &mut Context, particles: &ParticlePool, system_type: SystemType) -> GameResult {
+        let view = get_view_preset(system_type);
+        let mut builder = graphics::MeshBuilder::new();
+        let mut any = false;
+        for particle in particles.iter() {
+            let Some(twin) = particle.twin else {
+                continue;
+            };
+            let (from, to) = (particle.get_screen_pos(system_type), view.project(twin));
+            if ![from.x, from.y, to.x, to.y].iter().all(|v| v.is_finite()) {
+                continue;
+            }
+            if from != to {
+                builder.line(&[from, to], 1.0, graphics::Color::new(TWIN_COLOR.r, TWIN_COLOR.g, TWIN_COLOR.b, 0.35))?;
+            }
+            builder.circle(graphics::DrawMode::fill(), to, 1.5, 0.5, TWIN_COLOR)?;
+            any = true;
+        }
+        if any {
+            let mesh = builder.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+// Filename: key_reference.rs
+// Project: EntropicRust
+// Description: Key reference. Every key the scene answers to, grouped into pages that each
+//              fit the window, shown in a panel that is toggled with Ctrl+? and paged with
+//              the arrow keys, so the HUD itself only carries a one-line hint.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::main_state::SCREEN_WIDTH;
+use crate::theme::Theme;
+
+const PADDING: f32 = 12.0;
+const LINE_HEIGHT: f32 = 20.0;
+// Width of the key column.
+const KEY_WIDTH: f32 = 200.0;
+
+struct Page {
+    title: &'static str,
+    // (keys, what they do)
+    lines: &'static [(&'static str, &'static str)],
+}
+
+const PAGES: [Page; 5] = [
+    Page {
+        title: "Basics",
+        lines: &[
+            ("H", "hide the UI"),
+            ("Shift+H", "spoken announcements and captions"),
+            ("Space", "pause"),
+            ("Esc", "quit"),
+            ("1-6, Shift+1-6", "choose a system"),
+            ("Ctrl+1-6", "add or remove a group of that system"),
+            ("Ctrl+Tab", "focus the next group"),
+            ("Q/W/E, A/S/D", "step the parameters up and down"),
+            ("R/F", "step a fourth parameter, or reset particles"),
+            ("Alt", "fine parameter steps"),
+            ("Shift+R", "default parameters"),
+            ("Backspace", "restart the particles"),
+            ("Z/X", "time scale"),
+            ("Shift+Z/X", "time step"),
+            ("C/V", "more or fewer particles"),
+            ("Enter", "type a particle count"),
+            ("-/=", "noise"),
+            ("Ctrl+X", "message log"),
+        ],
+    },
+    Page {
+        title: "Analysis",
+        lines: &[
+            ("N", "density colors (Shift+N: speed)"),
+            ("K", "correlation dimension"),
+            ("P", "power spectrum"),
+            ("L", "Lyapunov exponent"),
+            ("Y", "entropy axis"),
+            ("B", "basin map (Shift+B: next plane)"),
+            (";", "FTLE field (Shift+;: next plane)"),
+            ("'", "escape time (Shift+': next plane)"),
+            ("O", "fixed points"),
+            ("U", "nullclines"),
+            ("J", "sensitivity demo"),
+            ("M", "histograms"),
+            ("`", "measure (Shift+`: export)"),
+            ("I", "chaos map (Shift+I: next pair)"),
+            ("F10", "regime finder (F1-F9: load a regime)"),
+            ("Ctrl+F10", "move the plots to a window of their own"),
+            (".", "delay embedding ([/] delay, Shift+[/] dimension)"),
+            ("/", "stroboscopic section (Shift+/: swap with the inset)"),
+            (",", "periodic orbit"),
+            ("\\", "lobe symbols (Shift+\\: print them)"),
+        ],
+    },
+    Page {
+        title: "Particles and looks",
+        lines: &[
+            ("Click", "record a trajectory (Ctrl+Insert: spawn instead)"),
+            ("Shift+click", "pin a particle"),
+            ("Ctrl+N/M", "previous or next leader"),
+            ("Ctrl+P, Ctrl+U", "pin the leader, release all pins"),
+            ("Ctrl+J", "parameter jitter"),
+            ("7, 8", "network, coupling (Shift/Ctrl: strength)"),
+            ("Insert", "seed pattern"),
+            ("PageUp/PageDown", "seed extent"),
+            ("Home", "lifetimes"),
+            ("End, Delete", "emitter shape, clear emitters"),
+            ("T", "trails (Shift+T: width, Ctrl+T: 3D ribbons)"),
+            ("G", "trail mode (Shift+G: gradient)"),
+            ("9", "particle shape"),
+            ("0", "halos (Ctrl+-/=: halo size)"),
+            ("Tab", "parameter drift (Shift+Tab: next parameter)"),
+            ("F11", "integrator"),
+            ("F12", "color by integrator error"),
+            ("Ctrl+R, Ctrl+A", "next theme from themes/, next palette"),
+            ("Ctrl+/", "soft clamp runaway particles at a ring"),
+        ],
+    },
+    Page {
+        title: "Camera, presets and recording",
+        lines: &[
+            ("Wheel, arrows", "zoom, pan"),
+            ("Ctrl+drag", "zoom to box"),
+            ("Ctrl+0, Ctrl+9", "reset the view, fit to particles"),
+            ("Ctrl+G", "follow the leader"),
+            ("Ctrl+F1-F9", "save the camera (Shift+F1-F9: recall)"),
+            ("Ctrl+B", "record a camera path (Ctrl+Shift+B: play)"),
+            ("Ctrl+E", "save the path (Ctrl+Shift+E: load)"),
+            ("Ctrl+K/L", "save or load a preset"),
+            ("Ctrl+C/V", "copy or paste the state"),
+            ("Ctrl+Y", "keyframe (Ctrl+Shift+Y: save the timeline)"),
+            ("Ctrl+O", "play the timeline (Ctrl+Shift+O: clear)"),
+            ("Ctrl+F", "LFO (Ctrl+Shift+F: its parameter)"),
+            ("Ctrl+[/]", "LFO rate (with Shift: depth)"),
+            ("Ctrl+I", "link parameters"),
+            ("Ctrl+H", "parameter history (←/→ or click to scrub)"),
+            ("Ctrl+Z", "rewind the last 30 s (Ctrl+Z again to resume)"),
+            ("Ctrl+S", "export a trajectory (Ctrl+Shift+S: JSON)"),
+            ("Ctrl+F12", "4K poster (Shift: 8K, Alt: gigapixel)"),
+        ],
+    },
+    Page {
+        title: "Comparisons, tours and other modes",
+        lines: &[
+            ("Ctrl+D", "split view against a copy"),
+            ("Shift+F11", "the copy's integrator"),
+            ("Ctrl+Q", "integrator error against RK4"),
+            ("Ctrl+End", "XY pad for two parameters (Ctrl+Shift+End: next pair)"),
+            ("Ctrl+W", "guided tour from tour.txt (Ctrl+Shift+W: next step)"),
+            ("Ctrl+Home", "double pendulums"),
+            ("Ctrl+PageUp", "magnetic pendulum"),
+            ("Ctrl+PageDown", "three bodies"),
+        ],
+    },
+];
+
+pub struct KeyReference {
+    page: usize,
+}
+
+impl KeyReference {
+    pub fn new() -> Self {
+        KeyReference { page: 0 }
+    }
+
+    // Moves `delta` pages, wrapping around at either end.
+    pub fn turn(&mut self, delta: isize) {
+        self.page = (self.page as isize + delta).rem_euclid(PAGES.len() as isize) as usize;
+    }
+
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let page = &PAGES[self.page];
+        let font = graphics::Font::default();
+        let scale = graphics::PxScale::from(16.0);
+        let rows = PAGES.iter().map(|p| p.lines.len()).max().unwrap_or(0) + 3;
+        let area = graphics::Rect::new(40.0, 40.0, SCREEN_WIDTH - 80.0, rows as f32 * LINE_HEIGHT + 2.0 * PADDING);
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), area, theme.panel)?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), area, theme.frame)?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let (x, mut y) = (area.x + PADDING, area.y + PADDING);
+        let title = format!("Keys: {} (page {} of {})", page.title, self.page + 1, PAGES.len());
+        let title = graphics::Text::new(graphics::TextFragment::new(title).font(font).scale(scale));
+        graphics::draw(ctx, &title, graphics::DrawParam::default().dest([x, y]).color(theme.text))?;
+        y += 1.5 * LINE_HEIGHT;
+        for &(keys, action) in page.lines {
+            let keys = graphics::Text::new(graphics::TextFragment::new(keys).font(font).scale(scale));
+            let action = graphics::Text::new(graphics::TextFragment::new(action).font(font).scale(scale));
+            graphics::draw(ctx, &keys, graphics::DrawParam::default().dest([x, y]).color(theme.frame))?;
+            graphics::draw(ctx, &action, graphics::DrawParam::default().dest([x + KEY_WIDTH, y]).color(theme.text))?;
+            y += LINE_HEIGHT;
+        }
+        let footer = graphics::Text::new(
+            graphics::TextFragment::new("←/→: turn the page, Esc or Ctrl+?: close").font(font).scale(scale),
+        );
+        let bottom = area.bottom() - PADDING - LINE_HEIGHT;
+        graphics::draw(ctx, &footer, graphics::DrawParam::default().dest([x, bottom]).color(theme.frame))
+    }
+}
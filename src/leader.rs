@@ -0,0 +1,84 @@
+// Filename: leader.rs
+// Project: EntropicRust
+// Description: Leader particle. One particle is singled out and drawn larger in its own
+//              colour; its state, speed and the share of time it has spent on each side of
+//              x = 0 (the lobes of the butterfly attractors) are kept for a live readout.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::graphics;
+
+use crate::dynamics;
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::symbolic;
+use crate::system_parameters::SystemParameters;
+
+pub const LEADER_COLOR: graphics::Color = graphics::Color::new(1.0, 0.9, 0.2, 1.0);
+pub const LEADER_RADIUS: f32 = 4.5;
+
+pub struct Leader {
+    pub id: ParticleId,
+    pub state: [f32; 3],
+    pub speed: f32,
+    lobe_steps: [u64; 2],
+    lobe: Option<usize>,
+    pub lobe_switches: u64,
+}
+
+impl Leader {
+    pub fn new(id: ParticleId) -> Self {
+        Leader {
+            id,
+            state: [0.0; 3],
+            speed: 0.0,
+            lobe_steps: [0; 2],
+            lobe: None,
+            lobe_switches: 0,
+        }
+    }
+
+    // Steps from the current leader to the next (`forward`) or previous live particle,
+    // wrapping through "no leader" at either end of the pool.
+    pub fn cycle(current: Option<ParticleId>, particles: &ParticlePool, forward: bool) -> Option<ParticleId> {
+        let ids: Vec<ParticleId> = particles.iter_with_ids().map(|(id, _)| id).collect();
+        let position = current.and_then(|id| ids.iter().position(|other| *other == id));
+        match (position, forward) {
+            (None, true) => ids.first().copied(),
+            (None, false) => ids.last().copied(),
+            (Some(i), true) => ids.get(i + 1).copied(),
+            (Some(i), false) => i.checked_sub(1).map(|i| ids[i]),
+        }
+    }
+
+    // Returns false once the leader no longer exists.
+    pub fn record(&mut self, particles: &ParticlePool, system_type: SystemType, params: &SystemParameters) -> bool {
+        let Some(p) = particles.get(self.id) else {
+            return false;
+        };
+        self.state = [p.x, p.y, p.z];
+        let [dx, dy, dz] = dynamics::derivative(system_type, params, self.state);
+        self.speed = (dx * dx + dy * dy + dz * dz).sqrt();
+        if p.x.is_finite() {
+            let lobe = symbolic::lobe(p.x);
+            self.lobe_steps[lobe] += 1;
+            if self.lobe.is_some_and(|last| last != lobe) {
+                self.lobe_switches += 1;
+            }
+            self.lobe = Some(lobe);
+        }
+        true
+    }
+
+    // Fraction of recorded steps spent with x > 0.
+    pub fn positive_lobe_share(&self) -> Option<f32> {
+        let total = self.lobe_steps[0] + self.lobe_steps[1];
+        (total > 0).then(|| self.lobe_steps[1] as f32 / total as f32)
+    }
+}
@@ -0,0 +1,105 @@
+// Filename: lfo.rs
+// Project: EntropicRust
+// Description: Low-frequency oscillators for live visuals. An LFO attached to a parameter
+//              swings it around the value it had when attached, following a sine, a
+//              triangle or smoothed random noise, at a rate in cycles per unit of
+//              simulated time and a depth relative to the centre value.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::system_parameters::{SystemParameters, get_parameter_bounds};
+
+pub const DEFAULT_RATE: f32 = 0.05;
+pub const MIN_RATE: f32 = 0.001;
+pub const MAX_RATE: f32 = 5.0;
+pub const DEFAULT_DEPTH: f32 = 0.2;
+pub const MAX_DEPTH: f32 = 1.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LfoShape {
+    Sine,
+    Triangle,
+    Noise,
+}
+
+impl LfoShape {
+    pub fn name(self) -> &'static str {
+        match self {
+            LfoShape::Sine => "sine",
+            LfoShape::Triangle => "triangle",
+            LfoShape::Noise => "noise",
+        }
+    }
+
+    // None once the cycle has gone through every shape.
+    pub fn next(self) -> Option<LfoShape> {
+        match self {
+            LfoShape::Sine => Some(LfoShape::Triangle),
+            LfoShape::Triangle => Some(LfoShape::Noise),
+            LfoShape::Noise => None,
+        }
+    }
+}
+
+pub struct Lfo {
+    pub name: &'static str,
+    pub shape: LfoShape,
+    pub center: f32,
+    pub rate: f32,
+    pub depth: f32,
+    // Position within the current cycle, in [0, 1).
+    phase: f32,
+    // Noise interpolates from the first to the second random target over each cycle.
+    targets: [f32; 2],
+}
+
+impl Lfo {
+    pub fn new(name: &'static str, params: &SystemParameters, shape: LfoShape) -> Self {
+        Lfo {
+            name,
+            shape,
+            center: params.get(name).unwrap_or(0.0),
+            rate: DEFAULT_RATE,
+            depth: DEFAULT_DEPTH,
+            phase: 0.0,
+            targets: [0.0, 0.0],
+        }
+    }
+
+    // Offset in [-1, 1] at the current phase.
+    fn wave(&self) -> f32 {
+        match self.shape {
+            LfoShape::Sine => (TAU * self.phase).sin(),
+            // Shifted a quarter cycle so it starts at the centre and rises, like the sine.
+            LfoShape::Triangle => 1.0 - 4.0 * ((self.phase + 0.25).fract() - 0.5).abs(),
+            LfoShape::Noise => {
+                let s = self.phase * self.phase * (3.0 - 2.0 * self.phase);
+                self.targets[0] + (self.targets[1] - self.targets[0]) * s
+            }
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        let (min, max) = get_parameter_bounds(self.name);
+        (self.center + self.center.abs().max(0.1) * self.depth * self.wave()).clamp(min, max)
+    }
+
+    pub fn advance<R: Rng>(&mut self, params: &mut SystemParameters, dt: f32, rng: &mut R) {
+        self.phase += self.rate * dt;
+        if self.phase >= 1.0 {
+            self.phase = self.phase.fract();
+            self.targets = [self.targets[1], rng.gen_range(-1.0..=1.0)];
+        }
+        params.set(self.name, self.value());
+    }
+}
@@ -0,0 +1,15 @@
+// Filename: lib.rs
+// Project: EntropicRust
+// Description: Library target holding the parts of EntropicRust that need no window: the
+//              equations of motion of every system with their step functions. The
+//              application links against it like any other user would.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+pub mod systems;
@@ -0,0 +1,77 @@
+// Filename: locale.rs
+// Project: EntropicRust
+// Description: Locale-aware number formatting for the HUD. Parameter values and statistics
+//              are written with the decimal separator of the selected locale, so a class
+//              used to `2,5` does not read `2.5`. Where numbers are listed, locales with a
+//              decimal comma separate the items with semicolons instead, as they do in
+//              print. `locale = system` follows LC_ALL, LC_NUMERIC or LANG.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::log_scale;
+
+// Languages that write a decimal comma, by ISO 639-1 code.
+const DECIMAL_COMMA: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "eu", "fi", "fr", "gl", "hr", "hu", "id", "it", "lt", "lv", "nb",
+    "nl", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Locale {
+    pub decimal_comma: bool,
+}
+
+impl Locale {
+    // A language tag such as `de`, `pt_BR` or `fr-CA.UTF-8`, or `system`.
+    pub fn from_name(name: &str) -> Result<Locale, String> {
+        if name == "system" {
+            return Ok(Locale::system());
+        }
+        let language = name.split(['_', '-', '.', '@']).next().unwrap_or("").to_ascii_lowercase();
+        if language.len() != 2 && !matches!(language.as_str(), "c" | "posix") {
+            return Err(format!("unknown locale `{}` (expected a language code such as en or de, or system)", name));
+        }
+        let decimal_comma = DECIMAL_COMMA.contains(&language.as_str());
+        Ok(Locale { decimal_comma })
+    }
+
+    // The locale of the environment, by the usual POSIX precedence; English when unset.
+    pub fn system() -> Locale {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_name(&value).ok())
+            .unwrap_or_default()
+    }
+
+    // `value` with `decimals` digits after the separator.
+    pub fn fixed(&self, value: f32, decimals: usize) -> String {
+        self.localize(format!("{:.*}", decimals, value))
+    }
+
+    // `value` with three significant digits.
+    pub fn significant(&self, value: f32) -> String {
+        self.localize(log_scale::format_significant(value))
+    }
+
+    // `value` in scientific notation with `decimals` digits after the separator.
+    pub fn scientific(&self, value: f32, decimals: usize) -> String {
+        self.localize(format!("{:.*e}", decimals, value))
+    }
+
+    // Separator between numbers in a list.
+    pub fn list_separator(&self) -> &'static str {
+        if self.decimal_comma { "; " } else { ", " }
+    }
+
+    fn localize(&self, formatted: String) -> String {
+        if self.decimal_comma { formatted.replace('.', ",") } else { formatted }
+    }
+}
@@ -0,0 +1,40 @@
+// Filename: log_scale.rs
+// Project: EntropicRust
+// Description: Logarithmic controls for quantities that span orders of magnitude (time
+//              step, time scale, noise and coupling strengths). Key presses move along a
+//              grid of equal ratios, so each decade takes the same number of presses and
+//              stepping back down retraces the same values; readouts keep three
+//              significant figures so small values stay legible.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+#[derive(Clone, Copy)]
+pub struct LogScale {
+    pub min: f32,
+    pub max: f32,
+    pub steps_per_decade: f32,
+}
+
+impl LogScale {
+    // Moves `steps` grid points up (positive) or down, snapping off-grid values (from the
+    // config or a preset) onto the grid first.
+    pub fn step(&self, value: f32, steps: i32) -> f32 {
+        let index = (value.max(self.min).log10() * self.steps_per_decade).round() + steps as f32;
+        10f32.powf(index / self.steps_per_decade).clamp(self.min, self.max)
+    }
+}
+
+// Three significant figures whatever the magnitude, e.g. "0.000316", "0.0100", "12.6".
+pub fn format_significant(value: f32) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{}", value);
+    }
+    let decimals = (2 - value.abs().log10().floor() as i32).max(0) as usize;
+    format!("{:.*}", decimals, value)
+}
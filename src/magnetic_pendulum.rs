@@ -0,0 +1,235 @@
+// Filename: magnetic_pendulum.rs
+// Project: EntropicRust
+// Description: Magnetic pendulum mode. Every pixel-sized starting point of a bob over
+//              three magnets is run in the background until a magnet holds it, and the
+//              window is painted by the basin-map renderer in that magnet's colour; the
+//              boundaries between the colours are fractal. Bobs released with the mouse
+//              swing over the map with trails, coloured by the magnet they will end on.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+use std::thread::{self, JoinHandle};
+
+use entropicrust::systems::magnetic_pendulum::{self, MAGNETS, Params, State};
+use ggez::{graphics, mint::Point2, Context, GameResult};
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::basin::{self, BasinMap, BASIN_HEIGHT, BASIN_WIDTH, PALETTE};
+use crate::double_pendulum::draw_text;
+use crate::locale::Locale;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::theme::Theme;
+use crate::trail_gradient::{self, TrailGradient, TrailWidth};
+
+const DEFAULT_PARAMS: Params = Params { restoring: 0.5, friction: 0.2, height: 0.25 };
+pub const FRICTION_STEP: f32 = 0.02;
+const MIN_FRICTION: f32 = 0.04;
+const MAX_FRICTION: f32 = 1.0;
+const DT: f32 = 0.02;
+// Runs still swinging after this many steps are left black.
+const MAX_STEPS: usize = 20_000;
+// The window spans x in [-2, 2] and y in [-1.5, 1.5].
+const PIXELS_PER_UNIT: f32 = 200.0;
+// One unit of the application's time step is four here, so a bob settles in seconds.
+const TIME_UNIT: f32 = 4.0;
+const SWARM: usize = 24;
+const TRAIL_LENGTH: usize = 200;
+
+struct Bob {
+    state: State,
+    trail: VecDeque<Point2<f32>>,
+    color: graphics::Color,
+}
+
+pub struct MagneticPendulum {
+    pub params: Params,
+    job: Option<JoinHandle<BasinMap>>,
+    map: Option<BasinMap>,
+    image: Option<graphics::Image>,
+    bobs: Vec<Bob>,
+    pub paused: bool,
+}
+
+impl MagneticPendulum {
+    pub fn new() -> Self {
+        let mut mode = MagneticPendulum {
+            params: DEFAULT_PARAMS,
+            job: None,
+            map: None,
+            image: None,
+            bobs: Vec::new(),
+            paused: false,
+        };
+        mode.start_map();
+        mode.release_swarm();
+        mode
+    }
+
+    // Recomputes the basins for the current parameters; the old picture stays up meanwhile.
+    pub fn start_map(&mut self) {
+        let params = self.params;
+        self.job = Some(thread::spawn(move || compute(params)));
+    }
+
+    pub fn poll_map(&mut self) {
+        if !self.job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.job.take() {
+            self.map = job.join().ok();
+        }
+    }
+
+    pub fn adjust_friction(&mut self, change: f32) {
+        self.params.friction = (self.params.friction + change).clamp(MIN_FRICTION, MAX_FRICTION);
+        self.start_map();
+        self.release_swarm();
+    }
+
+    // Drops a bob at rest over the point under the cursor.
+    pub fn release(&mut self, x: f32, y: f32) {
+        let state = from_screen(x, y);
+        let color = match settle(state, &self.params) {
+            Some(magnet) => magnet_color(magnet),
+            None => graphics::Color::WHITE,
+        };
+        self.bobs.push(Bob { state, trail: VecDeque::with_capacity(TRAIL_LENGTH), color });
+    }
+
+    // Replaces the bobs with a fresh handful scattered over the window.
+    pub fn release_swarm(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.bobs.clear();
+        for _ in 0..SWARM {
+            self.release(rng.gen_range(0.0..SCREEN_WIDTH), rng.gen_range(0.0..SCREEN_HEIGHT));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bobs.clear();
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        let steps = (dt * TIME_UNIT / DT).round().max(1.0) as usize;
+        for bob in &mut self.bobs {
+            if magnetic_pendulum::captured(bob.state).is_some() {
+                continue;
+            }
+            for _ in 0..steps {
+                bob.state = magnetic_pendulum::step(bob.state, &self.params, DT);
+            }
+            if bob.trail.len() == TRAIL_LENGTH {
+                bob.trail.pop_front();
+            }
+            bob.trail.push_back(to_screen(bob.state));
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        ctx: &mut Context,
+        theme: &Theme,
+        gradient: TrailGradient,
+        width: TrailWidth,
+        locale: Locale,
+    ) -> GameResult {
+        if let Some(map) = self.map.take() {
+            let mut image = graphics::Image::from_rgba8(ctx, map.width, map.height, &map.rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            self.image = Some(image);
+        }
+        if let Some(image) = &self.image {
+            graphics::draw(
+                ctx,
+                image,
+                graphics::DrawParam::default()
+                    .scale([SCREEN_WIDTH / image.width() as f32, SCREEN_HEIGHT / image.height() as f32]),
+            )?;
+        }
+
+        let mut builder = graphics::MeshBuilder::new();
+        for (i, &[x, y]) in MAGNETS.iter().enumerate() {
+            let at = to_screen([x, y, 0.0, 0.0]);
+            builder.circle(graphics::DrawMode::fill(), at, 9.0, 0.2, magnet_color(i))?;
+            builder.circle(graphics::DrawMode::stroke(2.0), at, 9.0, 0.2, theme.text)?;
+        }
+        for bob in &self.bobs {
+            let color = theme.palette.particle_color(bob.color);
+            let points: Vec<Point2<f32>> = bob.trail.iter().copied().collect();
+            trail_gradient::append_trail(&mut builder, &points, 1.5, color, gradient, width, theme.trail_alpha)?;
+            let at = to_screen(bob.state);
+            builder.circle(graphics::DrawMode::fill(), at, 5.0, 0.2, color)?;
+            builder.circle(graphics::DrawMode::stroke(1.0), at, 5.0, 0.2, theme.text)?;
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let status = format!(
+            "Magnetic pendulum   friction {}{}{}",
+            locale.fixed(self.params.friction, 2),
+            if self.job.is_some() { "   computing basins..." } else { "" },
+            if self.paused { "   (paused)" } else { "" }
+        );
+        draw_text(ctx, &status, [20.0, 20.0], 16.0, theme.text)?;
+        let hint = "Click: release a bob, R: new bobs, C: clear, Up/Down: friction, Space: pause, \
+                    Esc or Ctrl+PageUp: back";
+        draw_text(ctx, hint, [20.0, SCREEN_HEIGHT - 30.0], 14.0, theme.text)
+    }
+}
+
+fn magnet_color(magnet: usize) -> graphics::Color {
+    let [r, g, b] = PALETTE[magnet % PALETTE.len()];
+    graphics::Color::from_rgb(r, g, b)
+}
+
+fn to_screen(state: State) -> Point2<f32> {
+    Point2 { x: SCREEN_WIDTH / 2.0 + state[0] * PIXELS_PER_UNIT, y: SCREEN_HEIGHT / 2.0 + state[1] * PIXELS_PER_UNIT }
+}
+
+// A bob at rest over the pixel at (x, y).
+fn from_screen(x: f32, y: f32) -> State {
+    [(x - SCREEN_WIDTH / 2.0) / PIXELS_PER_UNIT, (y - SCREEN_HEIGHT / 2.0) / PIXELS_PER_UNIT, 0.0, 0.0]
+}
+
+// The magnet a bob released from `start` comes to rest over.
+fn settle(start: State, params: &Params) -> Option<usize> {
+    let mut state = start;
+    for _ in 0..MAX_STEPS {
+        state = magnetic_pendulum::step(state, params, DT);
+        if let Some(magnet) = magnetic_pendulum::captured(state) {
+            return Some(magnet);
+        }
+    }
+    None
+}
+
+// One bob released at rest from the centre of each cell of the basin grid, which spans
+// the window.
+fn compute(params: Params) -> BasinMap {
+    let labels: Vec<Option<usize>> = (0..BASIN_WIDTH * BASIN_HEIGHT)
+        .into_par_iter()
+        .map(|cell| {
+            let (col, row) = (cell % BASIN_WIDTH, cell / BASIN_WIDTH);
+            let x = (col as f32 + 0.5) / BASIN_WIDTH as f32 * SCREEN_WIDTH;
+            let y = (row as f32 + 0.5) / BASIN_HEIGHT as f32 * SCREEN_HEIGHT;
+            settle(from_screen(x, y), &params)
+        })
+        .collect();
+    BasinMap {
+        width: BASIN_WIDTH as u16,
+        height: BASIN_HEIGHT as u16,
+        rgba: basin::paint(&labels, BASIN_WIDTH, BASIN_HEIGHT),
+        attractors: MAGNETS.len(),
+    }
+}
@@ -11,19 +11,119 @@
 // See LICENSE file for details.
 //
 
+mod accessibility;
+mod basin;
+mod camera;
+mod color_range;
+mod command;
+mod comparison;
+mod config;
+mod correlation_dimension;
+mod coupling;
+mod detached_panels;
+mod double_pendulum;
+mod drift;
+mod dynamics;
+mod embedding;
+mod emitter;
+mod entropy;
+mod escape_time;
+mod events;
+mod fixed_points;
+mod flat_toml;
+mod golden;
+mod ftle;
+mod groups;
+mod halo;
+mod haptics;
+mod histogram;
+mod history;
+mod hot_reload;
+mod integrator_overlay;
+mod key_reference;
+mod palette;
 mod particle;
+mod periodic_orbit;
+mod particle_pool;
+mod plot_panel;
+mod poster;
+mod regime_finder;
+mod rewind;
+mod seeding;
+mod ribbon;
+mod screensaver;
+mod sensitivity;
+mod share;
+mod shapes;
+mod sim_worker;
+mod soft_clamp;
+mod spatial_hash;
+mod spectrum;
+mod start_menu;
+mod stats;
+mod stroboscope;
+mod sweep;
+mod symbolic;
 mod system_parameters;
+mod theme;
+mod three_body;
+mod timeline;
+mod toast;
+mod tour;
+mod tutorial;
+mod leader;
+mod lfo;
+mod locale;
+mod log_scale;
+mod lyapunov;
+mod magnetic_pendulum;
 mod main_state;
+mod measure;
+mod mode;
+mod network;
+mod nullclines;
+mod param_scan;
+mod preset;
+mod trail_feedback;
+mod trail_gradient;
+mod trajectory;
+mod transition;
+mod versioning;
+mod wallpaper;
+mod watchdog;
+mod xy_pad;
 
-use ggez::{conf, event, GameResult};
+use ggez::{conf, event, GameError, GameResult};
+use config::AppConfig;
 use main_state::MainState;
 
 fn main() -> GameResult {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("sweep") {
+        return sweep::run_cli(&args[1..]).map_err(GameError::CustomError);
+    }
+    if args.first().map(String::as_str) == Some("validate") {
+        return golden::run_cli(&args[1..]).map_err(GameError::CustomError);
+    }
+    if args.first().map(String::as_str) == Some("panels") {
+        return detached_panels::run_viewer(&args[1..]);
+    }
+
+    let (config, mut problems) = AppConfig::load();
     let cb = ggez::ContextBuilder::new("entropicrust", "emanuellcs")
-        .window_setup(conf::WindowSetup::default().title("EntropicRust"))
-        .window_mode(conf::WindowMode::default().dimensions(main_state::SCREEN_WIDTH, main_state::SCREEN_HEIGHT));
+        .window_setup(conf::WindowSetup::default().title("EntropicRust").vsync(config.vsync))
+        .window_mode(
+            conf::WindowMode::default()
+                .dimensions(main_state::SCREEN_WIDTH, main_state::SCREEN_HEIGHT)
+                .borderless(config.wallpaper),
+        );
 
-    let (ctx, event_loop) = cb.build()?;
-    let state = MainState::new()?;
+    let (mut ctx, event_loop) = cb.build()?;
+    if config.wallpaper
+        && let Err(e) = wallpaper::attach(&mut ctx)
+    {
+        problems.push(format!("Wallpaper mode: {}", e));
+    }
+    let state = MainState::new(config, problems)?;
     event::run(ctx, event_loop, state)
 }
@@ -12,220 +12,3943 @@
 //
 
 use ggez::{
-    event, graphics, Context, GameResult,
-    input::keyboard::{KeyCode, KeyMods},
+    event::{self, MouseButton}, graphics, timer, Context, GameResult,
+    input::{keyboard::{self, KeyCode, KeyMods}, mouse::{self, CursorIcon}},
     mint::Point2,
 };
 use rand::Rng;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
-use crate::particle::{Particle, SystemType};
-use crate::system_parameters::{SystemParameters, get_scale_factor};
+use crate::accessibility::Announcer;
+use crate::basin::{self, BasinMap, BasinPlane};
+use crate::camera::{self, Camera, CameraPath};
+use crate::config::{self, AppConfig};
+use crate::command::{self, Command, KeyContext};
+use crate::color_range::{ColorRange, ColorScale};
+use crate::comparison::Comparison;
+use crate::coupling::{self, Coupling, CouplingKind};
+use crate::correlation_dimension::{self, CorrelationEstimate};
+use crate::detached_panels::DetachedPanels;
+use crate::double_pendulum::{self, DoublePendulum};
+use crate::drift::ParameterDrift;
+use crate::dynamics::{self, Integrator};
+use crate::embedding::DelayEmbedding;
+use crate::emitter::{EMITTER_EXTENT_PX, Emitter, EmitterShape};
+use crate::entropy::EntropyMonitor;
+use crate::escape_time::{self, EscapeMap};
+use crate::events::EventHooks;
+use crate::fixed_points::{self, FixedPoint, Stability};
+use crate::groups::SystemGroup;
+use crate::ftle::{self, FtleField};
+use crate::hot_reload::{self, FileWatcher};
+use crate::histogram::{CoordinateHistograms, HistogramSource};
+use crate::history::ParameterHistory;
+use crate::integrator_overlay::{self, IntegratorOverlay};
+use crate::key_reference::KeyReference;
+use crate::halo::{self, HaloSprites};
+use crate::haptics::Haptics;
+use crate::leader::{self, Leader};
+use crate::lyapunov::{self, LyapunovSpectrum};
+use crate::magnetic_pendulum::{self, MagneticPendulum};
+use crate::measure::{InvariantMeasure, MEASURE_BINS};
+use crate::mode::{Analysis, Mode};
+use crate::network::{self, Network, Topology};
+use crate::nullclines;
+use crate::param_scan::{self, ScanAxes, ScanImage};
+use crate::palette::Accent;
+use crate::particle::{FLASH_FRAMES, Particle, SystemType};
+use crate::preset::{self, Preset};
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::periodic_orbit::{self, PeriodicOrbit};
+use crate::plot_panel::PlotPanel;
+use crate::poster::{self, Mark, Poster};
+use crate::regime_finder::{self, Regime};
+use crate::rewind::RewindBuffer;
+use crate::screensaver::{self, Screensaver};
+use crate::seeding::{self, SeedPattern};
+use crate::share;
+use crate::shapes::{self, ParticleShape, ParticleSprite};
+use crate::soft_clamp::{self, SoftClamp};
+use crate::ribbon::{self, RibbonView};
+use crate::sensitivity::SensitivityDemo;
+use crate::sim_worker::SimWorker;
+use crate::spatial_hash::SpatialHash;
+use crate::spectrum::SpectrumAnalyzer;
+use crate::start_menu::{self, Choice, StartMenu};
+use crate::stroboscope::StroboscopicSection;
+use crate::symbolic::{self, SymbolTracker};
+use crate::system_parameters::{
+    ParameterInfo, ParameterLink, SystemParameters, find_parameter, get_default_time_step, get_initial_ranges,
+    get_parameter_names, get_key_parameters, get_parameter_info, get_scale_factor, get_view_preset, random_jitter,
+};
+use crate::theme::{self, Theme};
+use crate::three_body::{Problem, ThreeBody};
+use crate::trail_feedback::{FeedbackTrails, TrailMode};
+use crate::trail_gradient::{self, TrailAlpha, TrailGradient, TrailWidth};
+use crate::log_scale::LogScale;
+use crate::lfo::{self, Lfo, LfoShape};
+use crate::timeline::{self, Timeline};
+use crate::toast::Toasts;
+use crate::tour::{self, Tour};
+use crate::tutorial::{self, Tutorial};
+use crate::transition::SystemTransition;
+use crate::trajectory::{self, TrajectoryRecorder};
+use crate::xy_pad::XyPad;
+use crate::watchdog::{self, Recovery, Watchdog};
 
 pub const SCREEN_WIDTH: f32 = 800.0;
 pub const SCREEN_HEIGHT: f32 = 600.0;
+pub const DENSITY_RADIUS_PX: f32 = 25.0;
+const NOISE_SCALE: LogScale = LogScale { min: 0.01, max: 50.0, steps_per_decade: 6.0 };
+const TIME_SCALE: LogScale = LogScale { min: 0.01, max: 10.0, steps_per_decade: 10.0 };
+const TIME_STEP: LogScale = LogScale { min: 1.0e-4, max: 0.05, steps_per_decade: 6.0 };
+const DEFAULT_LIFETIME: f32 = 30.0;
+// Particles fade out over this last fraction of their lifetime.
+const FADE_FRACTION: f32 = 0.2;
+const PICK_RADIUS_PX: f32 = 12.0;
+// Memory guard only; there is no frame-time budget that would lower this automatically.
+pub const MAX_PARTICLES: usize = 100_000;
+const MAX_COUNT_DIGITS: usize = 6;
+const BOUND_HINT_DURATION: Duration = Duration::from_secs(2);
+const KEY_ROWS: [&str; 4] = ["Q/A", "W/S", "E/D", "R/F"];
 
 pub struct MainState {
-    pub particles: Vec<Particle>,
+    pub config: AppConfig,
+    pub particles: ParticlePool,
     pub system_type: SystemType,
     pub parameters: SystemParameters,
     pub dt: f32,
     pub show_ui: bool,
     pub trail_enabled: bool,
+    pub trail_mode: TrailMode,
+    pub trail_gradient: TrailGradient,
+    pub trail_width: TrailWidth,
+    pub theme: Theme,
+    pub ribbon_trails: bool,
+    // Soft-sprite radius in pixels; None draws plain circles.
+    pub halo_radius: Option<f32>,
+    pub halo_sprites: Option<HaloSprites>,
+    pub particle_shape: ParticleShape,
+    pub particle_sprite: Option<ParticleSprite>,
+    pub coupling: Option<Coupling>,
+    pub network: Option<Network>,
+    pub feedback_trails: Option<FeedbackTrails>,
     pub time_scale: f32,
     pub particle_count: usize,
+    // Digits typed so far while entering an exact particle count.
+    pub count_entry: Option<String>,
+    pub paused: bool,
+    pub focused: bool,
+    pub minimized: bool,
+    pub last_frame: Instant,
+    pub sim_worker: SimWorker,
+    pub spatial_hash: SpatialHash,
+    pub density_coloring: bool,
+    pub speed_coloring: bool,
+    pub color_range: ColorRange,
+    pub correlation: Option<CorrelationEstimate>,
+    pub correlation_job: Option<JoinHandle<Option<CorrelationEstimate>>>,
+    pub reference_particle: Option<ParticleId>,
+    pub spectrum: Option<SpectrumAnalyzer>,
+    // Set while the analysis panels are shown in the viewer window instead of here.
+    pub detached_panels: Option<DetachedPanels>,
+    pub entropy: EntropyMonitor,
+    pub lyapunov: Option<LyapunovSpectrum>,
+    pub lyapunov_job: Option<JoinHandle<Option<LyapunovSpectrum>>>,
+    pub basin_plane: BasinPlane,
+    pub basin_job: Option<JoinHandle<BasinMap>>,
+    pub basin_map: Option<BasinMap>,
+    pub basin_image: Option<graphics::Image>,
+    pub basin_attractors: Option<usize>,
+    pub ftle_job: Option<JoinHandle<FtleField>>,
+    pub ftle_field: Option<FtleField>,
+    pub ftle_image: Option<graphics::Image>,
+    pub ftle_max: Option<f32>,
+    pub escape_job: Option<JoinHandle<EscapeMap>>,
+    pub escape_map: Option<EscapeMap>,
+    pub escape_image: Option<graphics::Image>,
+    pub escape_count: Option<usize>,
+    pub show_fixed_points: bool,
+    pub fixed_points: Vec<FixedPoint>,
+    pub fixed_points_for: Option<(SystemType, SystemParameters)>,
+    pub show_nullclines: bool,
+    pub sensitivity: Option<SensitivityDemo>,
+    pub trajectory: Option<TrajectoryRecorder>,
+    pub leader: Option<Leader>,
+    // Counted while drawing and reported once in the UI rather than logged every frame.
+    pub non_finite_particles: usize,
+    pub broken_trails: usize,
+    pub histograms: Option<CoordinateHistograms>,
+    pub scan_pair: usize,
+    pub scan_axes: Option<ScanAxes>,
+    pub scan_job: Option<JoinHandle<ScanImage>>,
+    pub scan_result: Option<ScanImage>,
+    pub scan_image: Option<graphics::Image>,
+    pub show_regimes: bool,
+    pub regime_search: Option<JoinHandle<Vec<Regime>>>,
+    pub regimes: Vec<Regime>,
+    pub regimes_for: SystemType,
+    pub embedding: Option<DelayEmbedding>,
+    pub stroboscope: Option<StroboscopicSection>,
+    pub show_periodic_orbit: bool,
+    pub periodic_job: Option<JoinHandle<Option<PeriodicOrbit>>>,
+    pub periodic_orbit: Option<PeriodicOrbit>,
+    pub symbolic: Option<SymbolTracker>,
+    pub noise_intensity: f32,
+    // Relative per-particle parameter spread; None shares one parameter set.
+    pub parameter_jitter: Option<f32>,
+    // Last parameter an adjustment pushed against its bound, shown briefly in the HUD.
+    pub bound_hit: Option<(&'static ParameterInfo, f32, Instant)>,
+    // Parameter last changed from the keyboard, described briefly under the parameters.
+    pub last_adjusted: Option<(&'static ParameterInfo, Instant)>,
+    // Presets found in the preset directory at startup, plus any saved since.
+    pub presets: Vec<Preset>,
+    pub preset_index: Option<usize>,
+    pub timeline: Option<Timeline>,
+    pub lfos: Vec<Lfo>,
+    pub links: Vec<ParameterLink>,
+    // Parameter of the current system that the LFO and link keys act on.
+    pub selected_parameter: usize,
+    // Parameter values over wall-clock time; its cursor is set while scrubbing.
+    pub history: ParameterHistory,
+    pub rewind: RewindBuffer,
+    pub camera: Camera,
+    // Bookmark slot last saved or recalled, named in the camera HUD line.
+    pub bookmark: Option<usize>,
+    // Camera keeps the leader particle centred.
+    pub follow_leader: bool,
+    // Window pixel where a Ctrl+drag zoom box started.
+    pub zoom_box: Option<Point2<f32>>,
+    // Left clicks place particles instead of picking one to record.
+    pub spawn_mode: bool,
+    pub xy_pad: Option<XyPad>,
+    pub camera_path: Option<CameraPath>,
+    pub transition: Option<SystemTransition>,
+    // Split view with a copy of the cloud on the right.
+    pub comparison: Option<Comparison>,
+    pub integrator_overlay: Option<IntegratorOverlay>,
+    pub soft_clamp: Option<SoftClamp>,
+    pub screensaver: Option<Screensaver>,
+    pub announcer: Option<Announcer>,
+    pub haptics: Option<Haptics>,
+    pub watchdog: Watchdog,
+    pub mode: Mode,
+    pub tutorial: Option<Tutorial>,
+    pub watcher: Option<FileWatcher>,
+    pub toasts: Toasts,
+    pub key_reference: Option<KeyReference>,
+    pub last_input: Instant,
+    pub drift: Option<ParameterDrift>,
+    pub drift_index: usize,
+    pub measure: Option<InvariantMeasure>,
+    pub events: EventHooks,
+    pub integrator: Integrator,
+    pub error_coloring: bool,
+    pub lifetime: Option<f32>,
+    pub emitters: Vec<Emitter>,
+    pub emitter_shape: EmitterShape,
+    pub seed_pattern: SeedPattern,
+    pub seed_extent: f32,
+    pub groups: Vec<SystemGroup>,
 }
 
 impl MainState {
-    pub fn new() -> GameResult<MainState> {
+    // `problems` are messages from before the window opened, such as rejected config lines.
+    pub fn new(config: AppConfig, problems: Vec<String>) -> GameResult<MainState> {
+        let particle_count = config.particles;
+        let noise_intensity = config.noise;
+        let integrator = config.integrator;
+        let lifetime = config.lifetime;
+        let (seed_pattern, seed_extent) = (config.seed_pattern, config.seed_extent);
+        let trail_gradient = config.trail_gradient;
+        let mut toasts = Toasts::default();
+        let (theme, theme_problems) = theme::configured(config.theme.as_deref(), config.palette);
+        for problem in problems.into_iter().chain(theme_problems) {
+            toasts.error(problem);
+        }
+        let (presets, preset_problems) = preset::scan();
+        for problem in preset_problems {
+            toasts.error(problem);
+        }
+        let watcher = config
+            .hot_reload
+            .then(|| FileWatcher::new(&[config::CONFIG_PATH, theme::THEME_DIR, preset::PRESET_DIR]))
+            .and_then(|watcher| match watcher {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    toasts.warn(e);
+                    None
+                }
+            });
+        let trail_width = config.trail_width;
+        let ribbon_trails = config.ribbons;
+        let halo_radius = config.halo;
+        let soft_clamp = config.soft_clamp.map(|radius_px| SoftClamp { radius_px });
+        let particle_shape = config.particle_shape;
+        let coupling = config.coupling.map(|kind| config.coupling(kind));
+        let network = config.network.map(|topology| Network::new(topology, config.network_strength));
+        let timeline = config.timeline.as_deref().and_then(|path| match Timeline::load(path) {
+            Ok(mut timeline) => {
+                timeline.play();
+                Some(timeline)
+            }
+            Err(e) => {
+                toasts.error(format!("{}: {}", path, e));
+                None
+            }
+        });
+        let camera_path = config.camera_path.as_deref().and_then(|path| match CameraPath::load(path) {
+            Ok(mut camera_path) => {
+                camera_path.play();
+                Some(camera_path)
+            }
+            Err(e) => {
+                toasts.error(format!("{}: {}", path, e));
+                None
+            }
+        });
+        let defaults = SystemParameters::new();
+        let links = config
+            .links
+            .iter()
+            .filter_map(|(leader, follower)| {
+                let link = find_parameter(leader)
+                    .zip(find_parameter(follower))
+                    .and_then(|(leader, follower)| ParameterLink::new(leader.name, follower.name, &defaults));
+                if link.is_none() {
+                    toasts.warn(format!("Cannot link `{}` to `{}`", leader, follower));
+                }
+                link
+            })
+            .collect();
+        let events = EventHooks::new(config.events.clone(), config.osc_target.clone());
+        let haptics = config.haptics.then(Haptics::new).and_then(|haptics| match haptics {
+            Ok(haptics) => Some(haptics),
+            Err(e) => {
+                toasts.warn(e);
+                None
+            }
+        });
         let mut s = MainState {
-            particles: Vec::new(),
+            config,
+            particles: ParticlePool::with_capacity(200),
             system_type: SystemType::Lorenz,
             parameters: SystemParameters::new(),
             dt: 0.01,
             show_ui: true,
             trail_enabled: true,
+            trail_mode: TrailMode::Lines,
+            trail_gradient,
+            theme,
+            trail_width,
+            ribbon_trails,
+            halo_radius,
+            halo_sprites: None,
+            particle_shape,
+            particle_sprite: None,
+            coupling,
+            network,
+            feedback_trails: None,
             time_scale: 1.0,
-            particle_count: 50,
+            particle_count,
+            count_entry: None,
+            paused: false,
+            focused: true,
+            minimized: false,
+            last_frame: Instant::now(),
+            sim_worker: SimWorker::new(),
+            spatial_hash: SpatialHash::new(1.0),
+            density_coloring: false,
+            speed_coloring: false,
+            color_range: ColorRange::new(),
+            correlation: None,
+            correlation_job: None,
+            reference_particle: None,
+            spectrum: None,
+            detached_panels: None,
+            entropy: EntropyMonitor::new(),
+            lyapunov: None,
+            lyapunov_job: None,
+            basin_plane: BasinPlane::XY,
+            basin_job: None,
+            basin_map: None,
+            basin_image: None,
+            basin_attractors: None,
+            ftle_job: None,
+            ftle_field: None,
+            ftle_image: None,
+            ftle_max: None,
+            escape_job: None,
+            escape_map: None,
+            escape_image: None,
+            escape_count: None,
+            show_fixed_points: false,
+            fixed_points: Vec::new(),
+            fixed_points_for: None,
+            show_nullclines: false,
+            sensitivity: None,
+            trajectory: None,
+            leader: None,
+            non_finite_particles: 0,
+            broken_trails: 0,
+            histograms: None,
+            scan_pair: 0,
+            scan_axes: None,
+            scan_job: None,
+            scan_result: None,
+            scan_image: None,
+            show_regimes: false,
+            regime_search: None,
+            regimes: Vec::new(),
+            regimes_for: SystemType::Lorenz,
+            embedding: None,
+            stroboscope: None,
+            show_periodic_orbit: false,
+            periodic_job: None,
+            periodic_orbit: None,
+            symbolic: None,
+            noise_intensity,
+            parameter_jitter: None,
+            bound_hit: None,
+            last_adjusted: None,
+            presets,
+            preset_index: None,
+            timeline,
+            lfos: Vec::new(),
+            links,
+            selected_parameter: 0,
+            history: ParameterHistory::new(),
+            rewind: RewindBuffer::new(),
+            camera: Camera::default(),
+            bookmark: None,
+            follow_leader: false,
+            zoom_box: None,
+            spawn_mode: false,
+            xy_pad: None,
+            camera_path,
+            transition: None,
+            comparison: None,
+            integrator_overlay: None,
+            soft_clamp,
+            screensaver: None,
+            announcer: None,
+            haptics,
+            watchdog: Watchdog::new(),
+            mode: Mode::Simulate,
+            tutorial: None,
+            watcher,
+            toasts,
+            key_reference: None,
+            last_input: Instant::now(),
+            drift: None,
+            drift_index: 0,
+            measure: None,
+            events,
+            integrator,
+            error_coloring: false,
+            lifetime,
+            emitters: Vec::new(),
+            emitter_shape: EmitterShape::Point,
+            seed_pattern,
+            seed_extent,
+            groups: Vec::new(),
         };
 
         s.initialize_particles();
+        if let Some(state) = s.config.state.clone() {
+            match share::decode(&state) {
+                Ok(preset) => s.apply_preset(&preset),
+                Err(e) => s.toasts.error(format!("--state: {}", e)),
+            }
+        }
+        if let Some(path) = s.config.tour.clone() {
+            s.start_tour(&path);
+        }
+        if s.config.screensaver {
+            s.start_screensaver(true);
+        }
+        if s.config.accessibility {
+            s.toggle_accessibility();
+        }
+        if s.config.start_menu && s.config.state.is_none() && !s.mode.is_tour() && s.screensaver.is_none() {
+            let session = start_menu::last_session().unwrap_or_else(|e| {
+                s.toasts.error(e);
+                None
+            });
+            s.mode = Mode::Menu(StartMenu::new(session));
+        } else {
+            s.start_tutorial();
+        }
 
         Ok(s)
     }
 
     pub fn initialize_particles(&mut self) {
         self.particles.clear();
+        self.rewind.clear();
+        self.feedback_trails = None;
+        self.lyapunov = None;
+        self.lyapunov_job = None;
+        if self.measure.is_some() {
+            self.measure = Some(InvariantMeasure::new());
+        }
+        if let Some(section) = self.stroboscope.as_mut() {
+            section.clear();
+        }
+        if let Some(analysis) = self.mode.analysis() {
+            self.start_analysis(analysis);
+        }
+        self.sync_particle_count();
+        if self.show_periodic_orbit {
+            self.start_periodic_orbit_search();
+        }
+        if let Some(comparison) = self.comparison.as_mut() {
+            comparison.reseed(&self.particles, self.system_type);
+        }
+        if let Some(overlay) = self.integrator_overlay.as_mut() {
+            overlay.reseed(&mut self.particles);
+        }
+    }
+
+    fn particle_target(&self) -> usize {
+        let demo_particles = self
+            .sensitivity
+            .as_ref()
+            .map_or(0, |demo| demo.pair.iter().filter(|id| self.particles.get(**id).is_some()).count());
+        self.particle_count + demo_particles
+    }
+
+    pub fn set_particle_count(&mut self, count: usize) {
+        self.particle_count = count.clamp(1, MAX_PARTICLES);
+        self.sync_particle_count();
+    }
+
+    // Keys while a count is being typed; digits themselves arrive as text input.
+    pub fn edit_count_entry(&mut self, keycode: KeyCode) {
+        match keycode {
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                if let Some(count) = self.count_entry.take().and_then(|digits| digits.parse().ok()) {
+                    self.set_particle_count(count);
+                }
+            }
+            KeyCode::Escape => self.count_entry = None,
+            KeyCode::Back => {
+                if let Some(digits) = self.count_entry.as_mut() {
+                    digits.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn sync_particle_count(&mut self) {
+        let mut rng = rand::thread_rng();
+
+        let target = self.particle_target();
+        // Laid out for the full target so that added particles extend the same pattern.
+        let starts = seeding::positions(self.seed_pattern, self.system_type, target, self.seed_extent, &mut rng);
+
+        // Emitters feed the cloud themselves; only trim it here.
+        while self.emitters.is_empty() && self.particles.len() < target {
+            let [x, y, z] = starts[self.particles.len()];
+
+            let id = self.particles.spawn(x, y, z);
+            // Stagger ages so the cloud does not expire and respawn all at once.
+            if let Some(lifetime) = self.lifetime
+                && let Some(particle) = self.particles.get_mut(id)
+            {
+                particle.age = rng.gen_range(0.0..lifetime);
+            }
+        }
+
+        let surplus = self.particles.len().saturating_sub(target);
+        let doomed: Vec<ParticleId> = self
+            .particles
+            .iter_with_ids()
+            .map(|(id, _)| id)
+            .filter(|id| !self.sensitivity.as_ref().is_some_and(|demo| demo.contains(*id)))
+            .collect();
+        for id in doomed.into_iter().rev().take(surplus) {
+            self.particles.despawn(id);
+        }
+    }
+
+    // Gives particles without their own parameter multipliers a fresh draw (new spawns,
+    // respawns, or everyone right after jitter is switched on).
+    pub fn assign_jitter(&mut self) {
+        let Some(amplitude) = self.parameter_jitter else {
+            return;
+        };
         let mut rng = rand::thread_rng();
+        for particle in self.particles.iter_mut().filter(|p| p.jitter.is_none()) {
+            particle.jitter = Some(random_jitter(amplitude, &mut rng));
+        }
+    }
+
+    // Nudges the parameter on key row `slot` (Q/A, W/S, E/D, R/F) by one step, or a tenth
+    // of one for fine adjustment.
+    pub fn step_parameter(&mut self, slot: usize, direction: f32, fine: bool) {
+        let Some(&info) = get_key_parameters(self.system_type).get(slot) else {
+            return;
+        };
+        let step = self.config.step(info) * if fine { 0.1 } else { 1.0 };
+        if let Some(value) = self.parameters.get(info.name) {
+            self.parameters.set(info.name, value + direction * step);
+            self.last_adjusted = Some((info, Instant::now()));
+        }
+    }
+
+    pub fn enforce_parameter_bounds(&mut self) {
+        if let Some((info, bound)) = self.parameters.clamp_to_bounds(self.system_type) {
+            self.bound_hit = Some((info, bound, Instant::now()));
+        }
+    }
+
+    pub fn current_preset(&self) -> Preset {
+        Preset {
+            name: preset::next_name(self.system_type),
+            system_type: self.system_type,
+            parameters: get_parameter_names(self.system_type)
+                .iter()
+                .filter_map(|&name| self.parameters.get(name).map(|value| (name, value)))
+                .collect(),
+            particles: Some(self.particle_count),
+            time_scale: Some(self.time_scale),
+            dt: Some(self.dt),
+            seed_pattern: Some(self.seed_pattern),
+            seed_extent: Some(self.seed_extent),
+            trail_gradient: Some(self.trail_gradient),
+            trail_width: Some(self.trail_width),
+            particle_shape: Some(self.particle_shape),
+            halo: Some(self.halo_radius),
+        }
+    }
+
+    pub fn save_preset(&mut self) {
+        let preset = self.current_preset();
+        match preset.save() {
+            Ok(path) => {
+                self.toasts.info(format!("Saved preset to {}", path));
+                // Already applied; no need to reload it.
+                if let Some(watcher) = self.watcher.as_mut() {
+                    watcher.forget_changes();
+                }
+                self.presets.push(preset);
+                self.preset_index = Some(self.presets.len() - 1);
+            }
+            Err(e) => self.toasts.error(format!("Failed to save preset: {}", e)),
+        }
+    }
+
+    // Applies edits picked up by the file watcher. Config settings are applied only where
+    // the file changed, so settings changed with keys since are not reset by unrelated edits.
+    pub fn reload_changed_files(&mut self) {
+        let Some(changed) = self.watcher.as_mut().map(FileWatcher::poll) else {
+            return;
+        };
+        if changed.iter().any(|path| path == Path::new(config::CONFIG_PATH)) {
+            self.reload_config();
+        }
+        let theme_file = |path: &&PathBuf| {
+            path.starts_with(theme::THEME_DIR) && path.file_stem().is_some_and(|stem| *stem == *self.theme.name)
+        };
+        if let Some(path) = changed.iter().find(theme_file) {
+            match theme::load(path) {
+                Ok(mut theme) => {
+                    if let Some(palette) = self.config.palette {
+                        theme.palette = palette;
+                    }
+                    self.theme = theme;
+                    self.toasts.info(format!("Reloaded theme {}", self.theme.name));
+                }
+                Err(e) => self.toasts.error(format!("{}: {}", path.display(), e)),
+            }
+        }
+        if hot_reload::touches(&changed, preset::PRESET_DIR) {
+            self.reload_presets(&changed);
+        }
+    }
+
+    fn reload_config(&mut self) {
+        let (config, problems) = AppConfig::load();
+        let old = std::mem::replace(&mut self.config, config);
+        if self.config.particles != old.particles {
+            self.particle_count = self.config.particles;
+            self.sync_particle_count();
+        }
+        if self.config.noise != old.noise {
+            self.noise_intensity = self.config.noise;
+        }
+        if self.config.integrator != old.integrator {
+            self.integrator = self.config.integrator;
+        }
+        if self.config.lifetime != old.lifetime {
+            self.set_lifetime(self.config.lifetime);
+        }
+        if self.config.trail_gradient != old.trail_gradient {
+            self.trail_gradient = self.config.trail_gradient;
+        }
+        if self.config.trail_width != old.trail_width {
+            self.trail_width = self.config.trail_width;
+        }
+        if self.config.ribbons != old.ribbons {
+            self.ribbon_trails = self.config.ribbons;
+        }
+        if self.config.halo != old.halo {
+            self.halo_radius = self.config.halo;
+        }
+        if self.config.particle_shape != old.particle_shape {
+            self.particle_shape = self.config.particle_shape;
+        }
+        if self.config.theme != old.theme || self.config.palette != old.palette {
+            let (theme, theme_problems) = theme::configured(self.config.theme.as_deref(), self.config.palette);
+            self.theme = theme;
+            for problem in theme_problems {
+                self.toasts.error(problem);
+            }
+        }
+        // Rejected lines keep their defaults, so the reload only counts as clean without any.
+        if problems.is_empty() {
+            self.toasts.info(format!("Reloaded {}", config::CONFIG_PATH));
+        }
+        for problem in problems {
+            self.toasts.error(problem);
+        }
+    }
+
+    // Rescans the presets, keeping the current one selected, and applies it again if its
+    // file is among the changed ones.
+    fn reload_presets(&mut self, changed: &[PathBuf]) {
+        let current = self.preset_index.and_then(|i| self.presets.get(i)).map(|p| p.name.clone());
+        let (presets, problems) = preset::scan();
+        self.presets = presets;
+        for problem in problems {
+            self.toasts.error(problem);
+        }
+        self.preset_index = current.as_ref().and_then(|name| self.presets.iter().position(|p| &p.name == name));
+        if let Some(preset) = self.preset_index.map(|i| self.presets[i].clone())
+            && changed.iter().any(|path| path.file_stem().is_some_and(|stem| *stem == *preset.name))
+        {
+            self.apply_preset(&preset);
+            self.toasts.info(format!("Reloaded preset {}", preset.name));
+        }
+    }
+
+    pub fn load_next_preset(&mut self) {
+        if self.presets.is_empty() {
+            self.toasts.warn(format!("No presets in {}/", preset::PRESET_DIR));
+            return;
+        }
+        let index = self.preset_index.map_or(0, |i| (i + 1) % self.presets.len());
+        let preset = self.presets[index].clone();
+        self.preset_index = Some(index);
+
+        self.apply_preset(&preset);
+    }
+
+    pub fn capture_keyframe(&mut self) {
+        let timeline = self.timeline.get_or_insert_with(Timeline::new);
+        timeline.playing = false;
+        timeline.capture(self.system_type, &self.parameters);
+    }
+
+    pub fn toggle_timeline_playback(&mut self) {
+        if let Some(timeline) = self.timeline.as_mut() {
+            if timeline.playing {
+                timeline.playing = false;
+            } else {
+                timeline.play();
+                self.drift = None;
+            }
+        }
+    }
+
+    // Saves the camera into a bookmark slot (0-based) and into the config file.
+    pub fn save_bookmark(&mut self, slot: usize) {
+        let bookmark = camera::Bookmark { name: format!("view{}", slot + 1), camera: self.camera };
+        let key = format!("bookmark_{}", slot + 1);
+        match config::save_setting(&key, &bookmark.to_setting()) {
+            Ok(()) => self.toasts.info(format!("Camera saved to {} in {}", key, config::CONFIG_PATH)),
+            Err(e) => self.toasts.error(format!("Failed to save camera bookmark: {}", e)),
+        }
+        self.config.bookmarks[slot] = Some(bookmark);
+        self.bookmark = Some(slot);
+    }
+
+    pub fn recall_bookmark(&mut self, slot: usize) {
+        if let Some(bookmark) = &self.config.bookmarks[slot] {
+            self.camera = bookmark.camera;
+            self.bookmark = Some(slot);
+        }
+    }
+
+    // Closing the scrubber keeps whatever moment was restored and resumes recording.
+    pub fn toggle_history_scrubber(&mut self) {
+        if self.history.cursor.take().is_none()
+            && let Some(params) = self.history.step(0)
+        {
+            self.restore_history(params);
+        }
+    }
+
+    // Only the parameters go back in time; the particles carry on from where they are.
+    // Anything that would move the parameters straight away again is stopped.
+    pub fn restore_history(&mut self, params: SystemParameters) {
+        self.parameters = params;
+        self.drift = None;
+        self.lfos.clear();
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.playing = false;
+        }
+    }
+
+    // Ctrl+Z stops the simulation on its latest snapshot, or resumes from the one shown.
+    pub fn toggle_rewind(&mut self) {
+        if self.rewind.cursor.is_some() {
+            self.rewind.resume();
+            return;
+        }
+        // A step still in flight belongs to the present and would undo the rewind when collected.
+        match self.sim_worker.collect() {
+            Ok(Some(job)) => self.sim_worker.recycle(job),
+            Ok(None) => {}
+            Err(e) => self.toasts.error(e),
+        }
+        match self.rewind.step(0, &mut self.particles) {
+            Some(params) => self.restore_rewind(params),
+            None => self.toasts.error("Nothing recorded to rewind to yet"),
+        }
+    }
+
+    // The restored particles are back in the past; RK4 twins start again from there.
+    fn restore_rewind(&mut self, params: SystemParameters) {
+        self.restore_history(params);
+        if let Some(overlay) = self.integrator_overlay.as_mut() {
+            overlay.reseed(&mut self.particles);
+        }
+    }
+
+    // Ctrl+B starts a recording, or ends the one in progress.
+    pub fn toggle_camera_recording(&mut self) {
+        if self.mode.is_recording() {
+            self.set_mode(Mode::Simulate);
+        } else {
+            self.camera_path.get_or_insert_with(CameraPath::new).record();
+            self.follow_leader = false;
+            self.set_mode(Mode::Record);
+        }
+    }
+
+    pub fn toggle_camera_playback(&mut self) {
+        if self.mode.is_recording() {
+            self.set_mode(Mode::Simulate);
+        }
+        if let Some(camera_path) = self.camera_path.as_mut() {
+            if camera_path.playing {
+                camera_path.playing = false;
+            } else {
+                camera_path.play();
+                self.follow_leader = false;
+            }
+        }
+    }
+
+    pub fn save_camera_path(&mut self) {
+        let Some(camera_path) = &self.camera_path else {
+            return;
+        };
+        match camera_path.save(camera::CAMERA_PATH) {
+            Ok(keys) => self.toasts.info(format!("Wrote {} camera keys to {}", keys, camera::CAMERA_PATH)),
+            Err(e) => self.toasts.error(format!("Failed to write {}: {}", camera::CAMERA_PATH, e)),
+        }
+    }
 
-        let (init_x_range, init_y_range, init_z_range) = match self.system_type {
-            SystemType::Lorenz => (-1.0..1.0, -1.0..1.0, 15.0..25.0),
-            SystemType::Rossler => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
-            SystemType::Aizawa => (-0.1..0.1, -0.1..0.1, -0.1..0.1),
-            SystemType::ChenLee => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
+    pub fn load_camera_path(&mut self) {
+        match CameraPath::load(camera::CAMERA_PATH) {
+            Ok(camera_path) => {
+                let keys = camera_path.key_count();
+                self.toasts.info(format!("Loaded {} camera keys from {}", keys, camera::CAMERA_PATH));
+                self.camera_path = Some(camera_path);
+            }
+            Err(e) => self.toasts.error(format!("{}: {}", camera::CAMERA_PATH, e)),
+        }
+    }
+
+    pub fn save_timeline(&mut self) {
+        let Some(timeline) = &self.timeline else {
+            return;
         };
+        match timeline.save(timeline::TIMELINE_PATH) {
+            Ok(keys) => self.toasts.info(format!("Wrote {} keyframes to {}", keys, timeline::TIMELINE_PATH)),
+            Err(e) => self.toasts.error(format!("Failed to write {}: {}", timeline::TIMELINE_PATH, e)),
+        }
+    }
+
+    pub fn selected_parameter_name(&self) -> &'static str {
+        let names = get_parameter_names(self.system_type);
+        names[self.selected_parameter % names.len()]
+    }
+
+    // Off -> sine -> triangle -> noise -> off on the selected parameter. Removing an LFO
+    // puts the parameter back at its centre value.
+    pub fn cycle_lfo(&mut self) {
+        let name = self.selected_parameter_name();
+        match self.lfos.iter().position(|lfo| lfo.name == name) {
+            Some(i) => match self.lfos[i].shape.next() {
+                Some(shape) => self.lfos[i].shape = shape,
+                None => {
+                    let lfo = self.lfos.remove(i);
+                    self.parameters.set(lfo.name, lfo.center);
+                }
+            },
+            None => self.lfos.push(Lfo::new(name, &self.parameters, LfoShape::Sine)),
+        }
+    }
+
+    // Links the selected parameter to each following parameter of the system in turn, then
+    // releases it.
+    pub fn cycle_link(&mut self) {
+        let names = get_parameter_names(self.system_type);
+        let leader = self.selected_parameter_name();
+        let current = self.links.iter().position(|link| link.leader == leader);
+        let start = self.selected_parameter % names.len();
+        let after = current.and_then(|i| names.iter().position(|&n| n == self.links[i].follower)).unwrap_or(start);
+        if let Some(i) = current {
+            self.links.remove(i);
+        }
+        let next = (after + 1) % names.len();
+        if next != start
+            && let Some(link) = ParameterLink::new(leader, names[next], &self.parameters)
+        {
+            self.links.retain(|l| l.follower != link.follower && l.leader != link.follower);
+            self.links.push(link);
+        }
+    }
+
+    pub fn enforce_parameter_links(&mut self, before: &SystemParameters) {
+        for link in &self.links {
+            link.enforce(before, &mut self.parameters);
+        }
+    }
+
+    pub fn adjust_lfo(&mut self, faster_or_deeper: bool, depth: bool) {
+        let name = self.selected_parameter_name();
+        if let Some(lfo) = self.lfos.iter_mut().find(|lfo| lfo.name == name) {
+            let factor = if faster_or_deeper { 1.5 } else { 1.0 / 1.5 };
+            if depth {
+                lfo.depth = (lfo.depth * factor).clamp(0.01, lfo::MAX_DEPTH);
+            } else {
+                lfo.rate = (lfo.rate * factor).clamp(lfo::MIN_RATE, lfo::MAX_RATE);
+            }
+        }
+    }
 
-        for _ in 0..self.particle_count {
-            let x = rng.gen_range(init_x_range.clone());
-            let y = rng.gen_range(init_y_range.clone());
-            let z = rng.gen_range(init_z_range.clone());
+    // Cross-fades from the current cloud to a fresh one of `system_type`.
+    pub fn switch_system(&mut self, system_type: SystemType) {
+        self.transition =
+            Some(SystemTransition::new(&self.particles, self.system_type, system_type, self.theme.palette));
+        self.carry_time_step(system_type);
+        self.system_type = system_type;
+        self.initialize_particles();
+    }
+
+    // Moves to the new system's own time step, unless the step was set by hand away from
+    // the old system's.
+    fn carry_time_step(&mut self, system_type: SystemType) {
+        if self.dt == get_default_time_step(self.system_type) {
+            self.dt = get_default_time_step(system_type);
+        }
+    }
+
+    // Missing settings keep their current values.
+    pub fn apply_preset(&mut self, preset: &Preset) {
+        if preset.system_type != self.system_type {
+            self.transition =
+                Some(SystemTransition::new(&self.particles, self.system_type, preset.system_type, self.theme.palette));
+        }
+        self.carry_time_step(preset.system_type);
+        self.system_type = preset.system_type;
+        self.parameters = preset.apply_parameters(&self.parameters);
+        self.drift = None;
+        if let Some(particles) = preset.particles {
+            self.particle_count = particles.clamp(1, MAX_PARTICLES);
+        }
+        self.time_scale = preset.time_scale.unwrap_or(self.time_scale);
+        if let Some(dt) = preset.dt {
+            self.dt = dt.clamp(TIME_STEP.min, TIME_STEP.max);
+        }
+        self.seed_pattern = preset.seed_pattern.unwrap_or(self.seed_pattern);
+        self.seed_extent = preset.seed_extent.unwrap_or(self.seed_extent);
+        self.trail_gradient = preset.trail_gradient.unwrap_or(self.trail_gradient);
+        self.trail_width = preset.trail_width.unwrap_or(self.trail_width);
+        self.particle_shape = preset.particle_shape.unwrap_or(self.particle_shape);
+        if let Some(halo) = preset.halo {
+            self.halo_radius = halo.map(|radius| radius.clamp(halo::MIN_HALO_RADIUS, halo::MAX_HALO_RADIUS));
+        }
+        self.initialize_particles();
+    }
+
+    // Prints the state string and writes it to `state.txt`; there is no clipboard access.
+    pub fn copy_state(&mut self) {
+        let state = share::encode(&self.current_preset());
+        self.toasts.info(format!("State: {}", state));
+        if let Err(e) = std::fs::write(share::SHARE_PATH, format!("{}\n", state)) {
+            self.toasts.error(format!("Failed to write {}: {}", share::SHARE_PATH, e));
+        }
+    }
+
+    pub fn paste_state(&mut self) {
+        let decoded = std::fs::read_to_string(share::SHARE_PATH)
+            .map_err(|e| e.to_string())
+            .and_then(|text| share::decode(&text));
+        match decoded {
+            Ok(preset) => self.apply_preset(&preset),
+            Err(e) => self.toasts.error(format!("{}: {}", share::SHARE_PATH, e)),
+        }
+    }
 
-            self.particles.push(Particle::new(x, y, z));
+    pub fn toggle_parameter_jitter(&mut self) {
+        self.parameter_jitter = match self.parameter_jitter {
+            Some(_) => None,
+            None => Some(self.config.parameter_jitter),
+        };
+        for particle in self.particles.iter_mut() {
+            particle.jitter = None;
         }
+        self.assign_jitter();
     }
 
     pub fn update_particles(&mut self, _ctx: &mut Context) {
         let dt = self.dt * self.time_scale;
+        let record_trail = self.trail_mode == TrailMode::Lines;
+        let view = get_view_preset(self.system_type);
+        let driven = self.system_type.is_driven();
+        let mut rng = rand::thread_rng();
 
-        for particle in self.particles.iter_mut() {
-            let x = particle.x;
-            let y = particle.y;
-            let z = particle.z;
-
-            let (dx, dy, dz) = match self.system_type {
-                SystemType::Lorenz => {
-                    let dx = self.parameters.sigma * (y - x);
-                    let dy = x * (self.parameters.rho - z) - y;
-                    let dz = x * y - self.parameters.beta * z;
-                    (dx, dy, dz)
-                },
-                SystemType::Rossler => {
-                    let dx = -y - z;
-                    let dy = x + self.parameters.a * y;
-                    let dz = self.parameters.b + z * (x - self.parameters.c);
-                    (dx, dy, dz)
-                },
-                SystemType::Aizawa => {
-                    let dx = (z - self.parameters.gamma) * x - self.parameters.delta * y;
-                    let dy = self.parameters.delta * x + (z - self.parameters.gamma) * y;
-                    let dz = self.parameters.alpha + self.parameters.beta * z - z.powi(3)/3.0 - (x*x + y*y) * (1.0 + self.parameters.epsilon * z) + self.parameters.delta * z * x*x*x;
-                    (dx, dy, dz)
-                },
-                SystemType::ChenLee => {
-                    let dx = self.parameters.p * x - y * z;
-                    let dy = self.parameters.q * y + x * z;
-                    let dz = self.parameters.r * z + x * y / 3.0;
-                    (dx, dy, dz)
-                },
-            };
+        for particle in self.particles.iter_mut().filter(|p| !p.pinned) {
+            let parameters = particle
+                .jitter
+                .map_or(self.parameters, |jitter| self.parameters.jittered(self.system_type, &jitter));
+            let ([new_x, new_y, new_z], error) = dynamics::step_noisy(
+                self.system_type,
+                &parameters,
+                [particle.x, particle.y, particle.z],
+                dt,
+                self.integrator,
+                self.noise_intensity,
+                &mut rng,
+            );
+            particle.error = error;
+            particle.age += dt;
+
+            let mut new_state = [new_x, new_y, new_z];
+            if let Some(clamp) = self.soft_clamp {
+                new_state = clamp.apply(self.system_type, [particle.x, particle.y, particle.z], new_state);
+            }
+            let [new_x, new_y, new_z] = new_state;
+            let trail_point = record_trail.then(|| (view.project(new_state), view.depth(new_state)));
+
+            if driven && let Some(section) = self.stroboscope.as_mut() {
+                section.record(particle.z, [new_x, new_y, new_z]);
+            }
+            let flash = self.events.check([particle.x, particle.y, particle.z], [new_x, new_y, new_z]);
+            particle.update(new_x, new_y, new_z, trail_point);
+            if flash {
+                particle.flash = FLASH_FRAMES;
+            }
+        }
+        self.events.advance(dt);
+    }
 
-            let new_x = x + dx * dt;
-            let new_y = y + dy * dt;
-            let new_z = z + dz * dt;
+    pub fn update_particles_double_buffered(&mut self) {
+        let collected = self.sim_worker.collect().unwrap_or_else(|e| {
+            self.toasts.error(e);
+            None
+        });
+        if let Some(job) = collected {
+            if job.system_type == self.system_type {
+                let record_trail = self.trail_mode == TrailMode::Lines;
+                let view = get_view_preset(self.system_type);
+                let driven = self.system_type.is_driven();
 
-            let scale_factor = get_scale_factor(self.system_type);
-            let display_x = SCREEN_WIDTH / 2.0 + new_x * scale_factor;
-            let display_y = SCREEN_HEIGHT / 2.0 + new_y * scale_factor;
-            let screen_pos = Point2 { x: display_x, y: display_y };
+                for ((id, [new_x, new_y, new_z]), error) in
+                    job.ids.iter().zip(job.states.iter().copied()).zip(job.errors.iter().copied())
+                {
+                    // Pinned after the job was launched.
+                    if let Some(particle) = self.particles.get_mut(*id)
+                        && !particle.pinned
+                    {
+                        particle.error = error;
+                        particle.age += job.dt;
+                        let mut new_state = [new_x, new_y, new_z];
+                        if let Some(clamp) = self.soft_clamp {
+                            new_state = clamp.apply(self.system_type, [particle.x, particle.y, particle.z], new_state);
+                        }
+                        let [new_x, new_y, new_z] = new_state;
+                        let trail_point = record_trail.then(|| (view.project(new_state), view.depth(new_state)));
 
-            particle.update(new_x, new_y, new_z, screen_pos);
+                        if driven && let Some(section) = self.stroboscope.as_mut() {
+                            section.record(particle.z, [new_x, new_y, new_z]);
+                        }
+                        let flash = self.events.check([particle.x, particle.y, particle.z], [new_x, new_y, new_z]);
+                        particle.update(new_x, new_y, new_z, trail_point);
+                        if flash {
+                            particle.flash = FLASH_FRAMES;
+                        }
+                    }
+                }
+                self.events.advance(job.dt);
+            }
+            self.sim_worker.recycle(job);
         }
+
+        let dt = self.dt * self.time_scale;
+        self.sim_worker.launch(
+            &self.particles,
+            self.system_type,
+            self.parameters,
+            dt,
+            self.integrator,
+            self.noise_intensity,
+        );
     }
 
-    pub fn draw_ui(&self, ctx: &mut Context) -> GameResult {
-        let font = graphics::Font::default();
-        let mut y_offset = 20.0;
-        let line_height = 20.0;
+    pub fn run_emitters(&mut self) {
+        if self.emitters.is_empty() {
+            return;
+        }
+        let dt = self.dt * self.time_scale;
+        let mut rng = rand::thread_rng();
+        let mut starts = Vec::new();
+        for emitter in self.emitters.iter_mut() {
+            starts.extend(emitter.emit(dt, self.config.emitter_rate, &mut rng));
+        }
+        for [x, y, z] in starts {
+            if self.particles.len() < self.particle_target() {
+                self.particles.spawn(x, y, z);
+                continue;
+            }
+            // At capacity the oldest particle is recycled into the new one.
+            let oldest = self
+                .particles
+                .iter_with_ids()
+                .filter(|(id, _)| !self.sensitivity.as_ref().is_some_and(|demo| demo.contains(*id)))
+                .max_by(|a, b| a.1.age.total_cmp(&b.1.age))
+                .map(|(id, _)| id);
+            if let Some(particle) = oldest.and_then(|id| self.particles.get_mut(id)) {
+                particle.reset(x, y, z);
+            }
+        }
+    }
 
-        let system_name = match self.system_type {
-            SystemType::Lorenz => "Lorenz",
-            SystemType::Rossler => "Rossler",
-            SystemType::Aizawa => "Aizawa",
-            SystemType::ChenLee => "Chen-Lee",
-        };
+    pub fn add_emitter(&mut self, screen_x: f32, screen_y: f32) {
+        let scale = get_scale_factor(self.system_type);
+        // The click fixes the point on screen; depth is taken from the middle of the cloud.
+        let depth = self.nullcline_slice();
+        let origin = get_view_preset(self.system_type).unproject(Point2 { x: screen_x, y: screen_y }, depth);
+        if self.emitters.is_empty() {
+            self.particles.clear();
+            self.feedback_trails = None;
+        }
+        self.emitters.push(Emitter::new(origin, self.emitter_shape, EMITTER_EXTENT_PX / scale));
+    }
 
-        let system_text = graphics::Text::new(graphics::TextFragment::new(
-            format!("System: {} (Press 1-4 to change)", system_name)
-        ).font(font).scale(graphics::PxScale::from(16.0)));
+    // Point of phase space a click at (`screen_x`, `screen_y`) spawns at, with depth taken
+    // from the middle of the cloud as for emitters.
+    fn spawn_state(&self, screen_x: f32, screen_y: f32) -> [f32; 3] {
+        get_view_preset(self.system_type).unproject(Point2 { x: screen_x, y: screen_y }, self.nullcline_slice())
+    }
+
+    pub fn toggle_spawn_mode(&mut self) {
+        self.spawn_mode = !self.spawn_mode;
+    }
+
+    // A crosshair while clicks spawn particles. Set from update, since spawn mode also ends
+    // in set_mode, which has no context to change the cursor with.
+    fn sync_cursor(&self, ctx: &mut Context) {
+        let cursor = if self.spawn_mode { CursorIcon::Crosshair } else { CursorIcon::Default };
+        if mouse::cursor_type(ctx) != cursor {
+            mouse::set_cursor_type(ctx, cursor);
+        }
+    }
 
+    // The new particle joins the count, so it is not trimmed away again.
+    pub fn spawn_particle(&mut self, screen_x: f32, screen_y: f32) {
+        if self.particle_count >= MAX_PARTICLES {
+            self.toasts.warn(format!("Particle limit of {} reached", MAX_PARTICLES));
+            return;
+        }
+        let [x, y, z] = self.spawn_state(screen_x, screen_y);
+        self.particles.spawn(x, y, z);
+        self.particle_count += 1;
+    }
+
+    // Ghost of the particle a click would place, under the cursor with its state alongside.
+    fn draw_spawn_preview(&self, ctx: &mut Context) -> GameResult {
+        let at = mouse::position(ctx);
+        let view = self.view_camera(1.0);
+        let target = view.unproject(at.x, at.y);
+        let state = self.spawn_state(target.x, target.y);
+        let radius = self.theme.particle_size * 2.0 * view.zoom;
+        let mut ghost = graphics::MeshBuilder::new();
+        ghost.circle(graphics::DrawMode::stroke(1.0), at, radius + 3.0, 0.1, graphics::Color::new(1.0, 1.0, 1.0, 0.6))?;
+        ghost.circle(graphics::DrawMode::fill(), at, radius, 0.1, graphics::Color::new(1.0, 1.0, 1.0, 0.35))?;
+        let ghost = ghost.build(ctx)?;
+        graphics::draw(ctx, &ghost, graphics::DrawParam::default())?;
+        let locale = self.config.locale;
+        let readout = graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "({})",
+                state.map(|v| locale.fixed(v, 2)).join(locale.list_separator())
+            ))
+            .font(graphics::Font::default())
+            .scale(graphics::PxScale::from(14.0)),
+        );
         graphics::draw(
             ctx,
-            &system_text,
+            &readout,
             graphics::DrawParam::default()
-                .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
-        )?;
-        y_offset += line_height;
+                .dest(Point2 { x: at.x + 12.0, y: at.y + 12.0 })
+                .color(self.theme.text),
+        )
+    }
 
-        let param_text_str = match self.system_type {
-            SystemType::Lorenz => {
-                format!(
-                    "Parameters (Q/A: σ={:.2}, W/S: ρ={:.2}, E/D: β={:.2})",
-                    self.parameters.sigma, self.parameters.rho, self.parameters.beta
-                )
-            },
-            SystemType::Rossler => {
-                format!(
-                    "Parameters (Q/A: a={:.2}, W/S: b={:.2}, E/D: c={:.2})",
-                    self.parameters.a, self.parameters.b, self.parameters.c
-                )
-            },
-            SystemType::Aizawa => {
-                format!(
-                    "Parameters (Q/A: α={:.2}, W/S: γ={:.2}, E/D: δ={:.2}, R/F: ε={:.2}) (BETA: {:.2})",
-                    self.parameters.alpha, self.parameters.gamma, self.parameters.delta, self.parameters.epsilon, self.parameters.beta
-                )
-            },
-            SystemType::ChenLee => {
-                format!(
-                    "Parameters (Q/A: p={:.2}, W/S: q={:.2}, E/D: r={:.2})",
-                    self.parameters.p, self.parameters.q, self.parameters.r
-                )
-            },
-        };
+    pub fn draw_emitters(&self, ctx: &mut Context) -> GameResult {
+        let view = get_view_preset(self.system_type);
+        let scale = view.scale;
+        let color = graphics::Color::new(0.5, 1.0, 0.6, 0.9);
+        let mut builder = graphics::MeshBuilder::new();
+        for emitter in &self.emitters {
+            let center = view.project(emitter.origin);
+            let extent_px = emitter.extent * scale;
+            match emitter.shape {
+                EmitterShape::Point => {
+                    builder.circle(graphics::DrawMode::stroke(1.5), center, 5.0, 0.1, color)?;
+                }
+                EmitterShape::Segment => {
+                    builder.line(
+                        &[
+                            Point2 { x: center.x - extent_px, y: center.y },
+                            Point2 { x: center.x + extent_px, y: center.y },
+                        ],
+                        2.0,
+                        color,
+                    )?;
+                }
+                EmitterShape::Shell => {
+                    builder.circle(graphics::DrawMode::stroke(1.5), center, extent_px, 0.1, color)?;
+                }
+            }
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())
+    }
+
+    // Axes of the positive octant a population model lives in, from extinction at the origin
+    // out past the seeding box, each labelled with its species. The axis along the depth of
+    // the view collapses to a point and is left out.
+    fn draw_octant_axes(&self, ctx: &mut Context) -> GameResult {
+        let view = get_view_preset(self.system_type);
+        let origin = view.project([0.0; 3]);
+        let (x, y, z) = get_initial_ranges(self.system_type);
+        let reach = [x.end, y.end, z.end].map(|end| end * 1.2);
+        let color = graphics::Color { a: 0.7, ..self.theme.frame };
+        let mut builder = graphics::MeshBuilder::new();
+        let mut labels = Vec::new();
+        for (axis, name) in self.system_type.axis_names().into_iter().enumerate() {
+            let mut end = [0.0; 3];
+            end[axis] = reach[axis];
+            let tip = view.project(end);
+            if (tip.x - origin.x).hypot(tip.y - origin.y) < 1.0 {
+                continue;
+            }
+            builder.line(&[origin, tip], 1.0, color)?;
+            labels.push((name, tip));
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        for (name, tip) in labels {
+            let label = graphics::Text::new(
+                graphics::TextFragment::new(name).font(graphics::Font::default()).scale(graphics::PxScale::from(14.0)),
+            );
+            graphics::draw(
+                ctx,
+                &label,
+                graphics::DrawParam::default().dest(Point2 { x: tip.x + 4.0, y: tip.y + 4.0 }).color(self.theme.text),
+            )?;
+        }
+        Ok(())
+    }
+
+    // Adds a group running `system_type` next to the main simulation, or removes it if one
+    // is already running.
+    pub fn toggle_group(&mut self, system_type: SystemType) {
+        if system_type == self.system_type {
+            return;
+        }
+        if let Some(index) = self.groups.iter().position(|g| g.system_type == system_type) {
+            self.groups.remove(index);
+            return;
+        }
+        self.groups.push(SystemGroup::new(
+            system_type,
+            self.particle_count,
+            self.seed_pattern,
+            self.seed_extent,
+            self.groups.len(),
+            self.particle_shape,
+        ));
+    }
+
+    // Rotates focus: the first group becomes the main simulation and the main one moves to
+    // the back of the list.
+    pub fn focus_next_group(&mut self) {
+        if self.groups.is_empty() {
+            return;
+        }
+        let mut next = self.groups.remove(0);
+        std::mem::swap(&mut self.system_type, &mut next.system_type);
+        std::mem::swap(&mut self.parameters, &mut next.parameters);
+        std::mem::swap(&mut self.particles, &mut next.particles);
+        std::mem::swap(&mut self.particle_shape, &mut next.shape);
+        self.groups.push(next);
+
+        self.feedback_trails = None;
+        self.lyapunov = None;
+        self.lyapunov_job = None;
+        self.correlation = None;
+        self.correlation_job = None;
+        self.reference_particle = None;
+        self.periodic_orbit = None;
+        self.trajectory = None;
+        self.leader = None;
+        if let Some(analysis) = self.mode.analysis() {
+            self.start_analysis(analysis);
+        }
+    }
+
+    pub fn respawn_expired(&mut self) {
+        let Some(lifetime) = self.lifetime else {
+            return;
+        };
+        let expired: Vec<ParticleId> = self
+            .particles
+            .iter_with_ids()
+            .filter(|(id, particle)| {
+                particle.age >= lifetime && !self.sensitivity.as_ref().is_some_and(|demo| demo.contains(*id))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+        if !self.emitters.is_empty() {
+            for id in expired {
+                self.particles.despawn(id);
+            }
+            return;
+        }
+        let mut rng = rand::thread_rng();
+        let starts = seeding::positions(
+            self.seed_pattern,
+            self.system_type,
+            self.particle_target(),
+            self.seed_extent,
+            &mut rng,
+        );
+        for id in expired {
+            if let Some(particle) = self.particles.get_mut(id) {
+                let [x, y, z] = starts[rng.gen_range(0..starts.len())];
+                particle.reset(x, y, z);
+            }
+        }
+    }
+
+    pub fn set_lifetime(&mut self, lifetime: Option<f32>) {
+        self.lifetime = lifetime;
+        if let Some(lifetime) = lifetime {
+            let mut rng = rand::thread_rng();
+            for particle in self.particles.iter_mut() {
+                particle.age = rng.gen_range(0.0..lifetime);
+            }
+        }
+    }
+
+    pub fn fade(&self, particle: &Particle) -> f32 {
+        let incoming = self.transition.as_ref().map_or(1.0, SystemTransition::progress);
+        match self.lifetime {
+            Some(lifetime) => incoming * ((lifetime - particle.age) / (lifetime * FADE_FRACTION)).clamp(0.0, 1.0),
+            None => incoming,
+        }
+    }
+
+    pub fn apply_coupling(&mut self) {
+        let Some(coupling) = self.coupling else {
+            return;
+        };
+        let dt = self.dt * self.time_scale;
+        self.spatial_hash.rebuild(&self.particles, coupling.radius(self.system_type));
+        coupling.apply(&mut self.particles, &self.spatial_hash, self.system_type, &self.parameters, dt);
+    }
+
+    pub fn cycle_network(&mut self) {
+        let strength = self.network.as_ref().map_or(self.config.network_strength, |n| n.strength);
+        let next = match self.network.as_ref().map(|n| n.topology) {
+            None => Some(Topology::Ring),
+            Some(Topology::Ring) => Some(Topology::SmallWorld),
+            Some(Topology::SmallWorld) => Some(Topology::AllToAll),
+            Some(Topology::AllToAll) => None,
+        };
+        self.network = next.map(|topology| Network::new(topology, strength));
+    }
+
+    pub fn refresh_spatial_hash(&mut self) {
+        let cell_size = DENSITY_RADIUS_PX / get_scale_factor(self.system_type);
+        self.spatial_hash.rebuild(&self.particles, cell_size);
+    }
+
+    // Radius and colour `particle` is drawn with, and its value along `color_scale`.
+    pub fn particle_style(
+        &self,
+        id: ParticleId,
+        particle: &Particle,
+        color_scale: Option<ColorScale>,
+    ) -> (f32, graphics::Color, Option<f32>) {
+        let leader = self.leader.as_ref().is_some_and(|l| l.id == id);
+        let mut value = None;
+        let color = if leader {
+            self.theme.palette.accent(Accent::Leader)
+        } else if self.symbolic.is_some() {
+            symbolic::LOBE_COLORS[symbolic::lobe(particle.x)]
+        } else if let Some(scale) = color_scale {
+            value = self.color_value(scale, particle);
+            self.scale_color(scale, value)
+        } else {
+            self.theme.palette.particle_color(particle.color)
+        };
+        let (radius, mut color) = if particle.flash > 0 {
+            (self.theme.particle_size + 0.5 * particle.flash as f32, graphics::Color::WHITE)
+        } else if leader {
+            (leader::LEADER_RADIUS, color)
+        } else {
+            (self.theme.particle_size, color)
+        };
+        color.a *= self.fade(particle);
+        (radius, color, value)
+    }
+
+    // Re-renders the attractor offscreen at `width` x `height` pixels into a PNG.
+    pub fn export_poster(&mut self, ctx: &mut Context, width: u32, height: u32) {
+        let poster = Poster { width, height, trail_width: self.config.poster_trail_width };
+        let trails = self.trail_enabled && self.trail_mode == TrailMode::Lines;
+        let color_scale = self.color_scale();
+        let marks: Vec<Mark> = self
+            .particles
+            .iter_with_ids()
+            .filter(|(_, particle)| particle.is_finite())
+            .map(|(id, particle)| {
+                let (radius, color, _) = self.particle_style(id, particle, color_scale);
+                let mut trail_color = self.theme.palette.particle_color(particle.color);
+                trail_color.a *= self.fade(particle);
+                Mark {
+                    trail: if trails { particle.trail.iter().copied().collect() } else { Vec::new() },
+                    trail_color,
+                    position: particle.get_screen_pos(self.system_type),
+                    radius,
+                    color,
+                }
+            })
+            .collect();
+        let style = poster::Style {
+            background: self.theme.background,
+            gradient: self.trail_gradient,
+            width: self.trail_width,
+            alpha: self.theme.trail_alpha,
+            shape: self.particle_shape,
+        };
+        match poster.render(ctx, self.view_camera(1.0).view(), &marks, &style) {
+            Ok(()) => self.toasts.info(format!("Saved {}x{} poster to {}", width, height, poster.path())),
+            Err(e) => self.toasts.error(format!("Failed to write {}: {}", poster.path(), e)),
+        }
+    }
+
+    // Scale the particles are coloured along, if any; error colours win over density, and
+    // density over speed.
+    pub fn color_scale(&self) -> Option<ColorScale> {
+        if self.error_coloring {
+            Some(ColorScale::Error)
+        } else if self.density_coloring {
+            Some(ColorScale::Density)
+        } else if self.speed_coloring {
+            Some(ColorScale::Speed)
+        } else {
+            None
+        }
+    }
+
+    // Value of `particle` along `scale`; None where there is nothing to show, such as the
+    // error under an integrator without an estimate.
+    pub fn color_value(&self, scale: ColorScale, particle: &Particle) -> Option<f32> {
+        let scale_factor = get_scale_factor(self.system_type);
+        match scale {
+            ColorScale::Error => particle.error.map(|error| (error * scale_factor).max(1.0e-6).log10()),
+            ColorScale::Density => {
+                let radius = DENSITY_RADIUS_PX / scale_factor;
+                let neighbors = self
+                    .spatial_hash
+                    .count_within([particle.x, particle.y, particle.z], radius)
+                    .saturating_sub(1);
+                Some(neighbors as f32)
+            }
+            ColorScale::Speed => {
+                let parameters = particle
+                    .jitter
+                    .map_or(self.parameters, |jitter| self.parameters.jittered(self.system_type, &jitter));
+                let state = [particle.x, particle.y, particle.z];
+                let [dx, dy, dz] = dynamics::derivative(self.system_type, &parameters, state);
+                Some((dx * dx + dy * dy + dz * dz).sqrt() * scale_factor)
+            }
+        }
+    }
+
+    // Colour of `value` along `scale`, against the running range or the fixed one.
+    pub fn scale_color(&self, scale: ColorScale, value: Option<f32>) -> graphics::Color {
+        let Some(value) = value else {
+            return graphics::Color::new(0.5, 0.5, 0.5, 1.0);
+        };
+        let t = if self.config.auto_color_range {
+            self.color_range.normalize(value)
+        } else {
+            let (low, high) = scale.fixed_range();
+            (value - low) / (high - low)
+        };
+        let (low, high) = scale.colors();
+        self.theme.palette.scale(t, low, high)
+    }
+
+    pub fn start_correlation_dimension(&mut self) {
+        let Some(reference) = self.particles.iter().next() else {
+            return;
+        };
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let start = [reference.x, reference.y, reference.z];
+        let dt = self.dt;
+        self.correlation_job = Some(thread::spawn(move || {
+            let points = correlation_dimension::sample_trajectory(system_type, &parameters, start, dt);
+            correlation_dimension::estimate(&points)
+        }));
+    }
+
+    pub fn poll_correlation_dimension(&mut self) {
+        if !self.correlation_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.correlation_job.take() {
+            match job.join() {
+                Ok(Some(correlation)) => self.correlation = Some(correlation),
+                Ok(None) => self.toasts.warn("Correlation dimension: trajectory too short or degenerate to estimate"),
+                Err(_) => self.toasts.error("Correlation dimension worker panicked"),
+            }
+        }
+    }
+
+    pub fn poll_spectrum(&mut self) {
+        if let Some(spectrum) = self.spectrum.as_mut()
+            && !spectrum.poll()
+        {
+            self.toasts.error("Power spectrum worker panicked");
+        }
+    }
+
+    // Hands the panels to the viewer window, or brings them back here once it is closed.
+    pub fn export_detached_panels(&mut self) {
+        let Some(mut detached) = self.detached_panels.take() else {
+            return;
+        };
+        if detached.viewer_closed() {
+            return;
+        }
+        if let Err(e) = detached.export(&self.analysis_panels()) {
+            self.toasts.error(e);
+            return;
+        }
+        self.detached_panels = Some(detached);
+    }
+
+    pub fn start_lyapunov_spectrum(&mut self) {
+        let Some(reference) = self.particles.iter().next() else {
+            return;
+        };
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let start = [reference.x, reference.y, reference.z];
+        let dt = self.dt;
+        self.lyapunov_job = Some(thread::spawn(move || lyapunov::spectrum(system_type, &parameters, start, dt)));
+    }
+
+    pub fn poll_lyapunov_spectrum(&mut self) {
+        if !self.lyapunov_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.lyapunov_job.take() {
+            match job.join() {
+                Ok(Some(spectrum)) => self.lyapunov = Some(spectrum),
+                Ok(None) => {
+                    self.lyapunov = None;
+                    self.toasts.warn("Lyapunov spectrum: trajectory diverged before the estimate converged");
+                }
+                Err(_) => self.toasts.error("Lyapunov spectrum worker panicked"),
+            }
+        }
+    }
+
+    pub fn start_basin_map(&mut self) {
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let plane = self.basin_plane;
+        let dt = self.dt;
+        self.basin_image = None;
+        self.basin_attractors = None;
+        // A job that is still running is left to finish on its own; its result is dropped.
+        self.basin_job = Some(thread::spawn(move || basin::compute(system_type, parameters, plane, dt)));
+    }
+
+    pub fn poll_basin_map(&mut self) {
+        if !self.basin_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.basin_job.take() {
+            match job.join() {
+                Ok(map) => {
+                    self.basin_attractors = Some(map.attractors);
+                    self.basin_map = Some(map);
+                }
+                Err(_) => self.toasts.error("Basin map worker panicked"),
+            }
+        }
+    }
+
+    pub fn start_ftle_field(&mut self) {
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let plane = self.basin_plane;
+        let dt = self.dt;
+        self.ftle_image = None;
+        self.ftle_max = None;
+        self.ftle_job = Some(thread::spawn(move || ftle::compute(system_type, parameters, plane, dt)));
+    }
+
+    pub fn poll_ftle_field(&mut self) {
+        if !self.ftle_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.ftle_job.take() {
+            match job.join() {
+                Ok(field) => {
+                    self.ftle_max = Some(field.max_exponent);
+                    self.ftle_field = Some(field);
+                }
+                Err(_) => self.toasts.error("FTLE worker panicked"),
+            }
+        }
+    }
+
+    pub fn start_escape_map(&mut self) {
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let plane = self.basin_plane;
+        let dt = self.dt;
+        self.escape_image = None;
+        self.escape_count = None;
+        self.escape_job = Some(thread::spawn(move || escape_time::compute(system_type, parameters, plane, dt)));
+    }
+
+    pub fn poll_escape_map(&mut self) {
+        if !self.escape_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.escape_job.take() {
+            match job.join() {
+                Ok(map) => {
+                    self.escape_count = Some(map.escaped);
+                    self.escape_map = Some(map);
+                }
+                Err(_) => self.toasts.error("Escape-time worker panicked"),
+            }
+        }
+    }
+
+    pub fn start_periodic_orbit_search(&mut self) {
+        let Some(reference) = self.particles.iter().next() else {
+            return;
+        };
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let start = [reference.x, reference.y, reference.z];
+        let dt = self.dt;
+        self.periodic_orbit = None;
+        self.periodic_job = Some(thread::spawn(move || periodic_orbit::find(system_type, &parameters, start, dt)));
+    }
+
+    pub fn poll_periodic_orbit(&mut self) {
+        if !self.periodic_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.periodic_job.take() {
+            match job.join() {
+                Ok(orbit) => self.periodic_orbit = orbit,
+                Err(_) => self.toasts.error("Periodic orbit worker panicked"),
+            }
+        }
+    }
+
+    pub fn draw_periodic_orbit(&self, ctx: &mut Context, orbit: &PeriodicOrbit) -> GameResult {
+        let view = get_view_preset(self.system_type);
+        let points: Vec<Point2<f32>> = orbit.points.iter().map(|&p| view.project(p)).collect();
+        if points.len() < 2 {
+            return Ok(());
+        }
+        // A dark underlay keeps the loop readable on top of bright trails.
+        let underlay = graphics::Mesh::new_polyline(
+            ctx,
+            graphics::DrawMode::stroke(5.0),
+            &points,
+            graphics::Color::new(0.0, 0.0, 0.0, 0.6),
+        )?;
+        graphics::draw(ctx, &underlay, graphics::DrawParam::default())?;
+        let loop_mesh = graphics::Mesh::new_polyline(
+            ctx,
+            graphics::DrawMode::stroke(2.5),
+            &points,
+            graphics::Color::new(1.0, 0.3, 0.9, 1.0),
+        )?;
+        graphics::draw(ctx, &loop_mesh, graphics::DrawParam::default())
+    }
+
+    // Trail points are in screen space, where x > 0 is the right half of the window.
+    pub fn draw_symbolic_trail(&self, ctx: &mut Context, points: &[Point2<f32>]) -> GameResult {
+        let side = |p: &Point2<f32>| symbolic::lobe(p.x - SCREEN_WIDTH / 2.0);
+        let mut start = 0;
+        while start + 1 < points.len() {
+            let lobe = side(&points[start]);
+            let mut end = start + 1;
+            while end + 1 < points.len() && side(&points[end]) == lobe {
+                end += 1;
+            }
+            if let Ok(line) = graphics::Mesh::new_line(ctx, &points[start..=end], 1.0, symbolic::LOBE_COLORS[lobe]) {
+                graphics::draw(ctx, &line, graphics::DrawParam::default())?;
+            }
+            start = end;
+        }
+        Ok(())
+    }
+
+    pub fn draw_drift_progress(&self, ctx: &mut Context, drift: &ParameterDrift) -> GameResult {
+        let bar = graphics::Rect::new(20.0, SCREEN_HEIGHT - 30.0, 290.0, 10.0);
+        let progress = drift.progress();
+        let value = drift.axis.value_at(progress);
+
+        let label = graphics::Text::new(graphics::TextFragment::new(format!(
+            "Drifting {} {} {}  [{} .. {}]",
+            drift.axis.name,
+            if drift.descending() { "↓" } else { "↑" },
+            self.config.locale.fixed(value, 3),
+            self.config.locale.fixed(drift.axis.from, 2),
+            self.config.locale.fixed(drift.axis.to, 2)
+        ))
+        .font(graphics::Font::default())
+        .scale(graphics::PxScale::from(14.0)));
+        graphics::draw(
+            ctx,
+            &label,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: bar.x, y: bar.y - 18.0 })
+                .color(graphics::Color::WHITE),
+        )?;
+
+        let mut filled = bar;
+        filled.w *= progress;
+        let mesh = graphics::MeshBuilder::new()
+            .rectangle(graphics::DrawMode::fill(), filled, graphics::Color::new(0.4, 0.8, 1.0, 0.9))?
+            .rectangle(graphics::DrawMode::stroke(1.0), bar, graphics::Color::WHITE)?
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())
+    }
+
+    pub fn draw_measure_marginals(&self, ctx: &mut Context, measure: &InvariantMeasure) -> GameResult {
+        let size = 100.0;
+        let top = SCREEN_HEIGHT - 220.0;
+        let title = if measure.is_warming_up() {
+            "Invariant measure: sizing box...".to_string()
+        } else {
+            format!("Invariant measure: {} samples (Shift+` to export)", measure.total)
+        };
+        let text = graphics::Text::new(graphics::TextFragment::new(title)
+            .font(graphics::Font::default()).scale(graphics::PxScale::from(14.0)));
+        graphics::draw(
+            ctx,
+            &text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: top - 36.0 })
+                .color(graphics::Color::WHITE),
+        )?;
+
+        for (k, (plane, name)) in [((0, 1), "x-y"), ((0, 2), "x-z"), ((1, 2), "y-z")].into_iter().enumerate() {
+            let left = 20.0 + k as f32 * (size + 5.0);
+            let rgba = measure.marginal_rgba(plane);
+            let mut image = graphics::Image::from_rgba8(ctx, MEASURE_BINS as u16, MEASURE_BINS as u16, &rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            graphics::draw(
+                ctx,
+                &image,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: left, y: top })
+                    .scale([size / MEASURE_BINS as f32, size / MEASURE_BINS as f32]),
+            )?;
+            let label = graphics::Text::new(graphics::TextFragment::new(name)
+                .font(graphics::Font::default()).scale(graphics::PxScale::from(12.0)));
+            graphics::draw(
+                ctx,
+                &label,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: left, y: top - 14.0 })
+                    .color(graphics::Color::WHITE),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn export_measure(&mut self) {
+        let Some(measure) = &self.measure else {
+            return;
+        };
+        match measure.export_csv() {
+            Ok(0) => self.toasts.warn("Invariant measure: nothing to export yet"),
+            Ok(bins) => self.toasts.info(format!("Wrote {} occupied bins to {}", bins, crate::measure::EXPORT_PATH)),
+            Err(e) => self.toasts.error(format!("Failed to write {}: {}", crate::measure::EXPORT_PATH, e)),
+        }
+    }
+
+    pub fn refresh_fixed_points(&mut self) {
+        let key = (self.system_type, self.parameters);
+        if self.fixed_points_for == Some(key) {
+            return;
+        }
+        self.fixed_points = fixed_points::find(self.system_type, &self.parameters);
+        self.fixed_points_for = Some(key);
+    }
+
+    pub fn draw_fixed_points(&self, ctx: &mut Context) -> GameResult {
+        let view = get_view_preset(self.system_type);
+        for fixed_point in &self.fixed_points {
+            let screen_pos = view.project(fixed_point.position);
+            let color = match fixed_point.stability {
+                Stability::StableNode | Stability::StableFocus => self.theme.palette.accent(Accent::Stable),
+                Stability::UnstableNode | Stability::UnstableFocus => self.theme.palette.accent(Accent::Unstable),
+                Stability::Saddle | Stability::SaddleFocus => self.theme.palette.accent(Accent::Saddle),
+                Stability::Degenerate => graphics::Color::new(0.7, 0.7, 0.7, 1.0),
+            };
+            let marker = graphics::Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::stroke(1.5),
+                screen_pos,
+                6.0,
+                0.1,
+                color,
+            )?;
+            graphics::draw(ctx, &marker, graphics::DrawParam::default())?;
+
+            let label = graphics::Text::new(graphics::TextFragment::new(fixed_point.stability.label())
+                .font(graphics::Font::default()).scale(graphics::PxScale::from(12.0)));
+            graphics::draw(
+                ctx,
+                &label,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: screen_pos.x + 9.0, y: screen_pos.y - 6.0 })
+                    .color(color),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn toggle_comparison(&mut self) {
+        self.comparison = match self.comparison {
+            Some(_) => None,
+            None => Some(Comparison::new(&self.particles, self.system_type, self.parameters, self.integrator)),
+        };
+    }
+
+    pub fn toggle_soft_clamp(&mut self) {
+        self.soft_clamp = match self.soft_clamp {
+            Some(_) => None,
+            None => Some(SoftClamp { radius_px: self.config.soft_clamp.unwrap_or(soft_clamp::DEFAULT_RADIUS_PX) }),
+        };
+    }
+
+    // Announcements start from the current state, so turning them on is silent.
+    pub fn toggle_accessibility(&mut self) {
+        self.announcer = match self.announcer {
+            Some(_) => None,
+            None => Some(Announcer::new(self.system_type, self.parameters, self.paused, self.config.speech)),
+        };
+    }
+
+    pub fn toggle_integrator_overlay(&mut self) {
+        self.integrator_overlay = match self.integrator_overlay {
+            Some(_) => {
+                IntegratorOverlay::clear(&mut self.particles);
+                None
+            }
+            None => Some(IntegratorOverlay::new(&mut self.particles)),
+        };
+    }
+
+    // Ctrl+W starts the tour from the configured file, or `tour.txt`, or ends it.
+    pub fn toggle_tour(&mut self) {
+        if self.mode.is_tour() {
+            self.set_mode(Mode::Simulate);
+        } else {
+            let path = self.config.tour.clone().unwrap_or_else(|| tour::TOUR_PATH.to_string());
+            self.start_tour(&path);
+        }
+    }
+
+    pub fn start_tour(&mut self, path: &str) {
+        match Tour::load(path) {
+            Ok(tour) => {
+                self.toasts.info(format!("Loaded tour with {} steps from {}", tour.len(), path));
+                let first = tour.current().scene.clone();
+                self.set_mode(Mode::Tour(tour));
+                self.show_scene(&first);
+            }
+            Err(e) => self.toasts.error(format!("{}: {}", path, e)),
+        }
+    }
+
+    pub fn toggle_double_pendulum(&mut self) {
+        if self.mode.is_pendulum() {
+            self.set_mode(Mode::Simulate);
+        } else {
+            self.set_mode(Mode::Pendulum(DoublePendulum::new(double_pendulum::DEFAULT_COUNT)));
+        }
+    }
+
+    pub fn toggle_magnetic_pendulum(&mut self) {
+        if self.mode.is_magnets() {
+            self.set_mode(Mode::Simulate);
+        } else {
+            self.set_mode(Mode::Magnets(MagneticPendulum::new()));
+        }
+    }
+
+    pub fn toggle_three_body(&mut self) {
+        if self.mode.is_three_body() {
+            self.set_mode(Mode::Simulate);
+        } else {
+            self.set_mode(Mode::ThreeBody(ThreeBody::new(Problem::FigureEight)));
+        }
+    }
+
+    pub fn pause_mechanics(&mut self) {
+        match &mut self.mode {
+            Mode::Pendulum(pendulum) => pendulum.paused = !pendulum.paused,
+            Mode::Magnets(magnets) => magnets.paused = !magnets.paused,
+            Mode::ThreeBody(three_body) => three_body.paused = !three_body.paused,
+            _ => {}
+        }
+    }
+
+    // Releases the pendulums again, drops new bobs or restarts the three-body problem.
+    pub fn restart_mechanics(&mut self) {
+        match &mut self.mode {
+            Mode::Pendulum(pendulum) => *pendulum = DoublePendulum::new(pendulum.count()),
+            Mode::Magnets(magnets) => magnets.release_swarm(),
+            Mode::ThreeBody(three_body) => *three_body = ThreeBody::new(three_body.problem),
+            _ => {}
+        }
+    }
+
+    // More or fewer pendulums, or more or less friction on the magnetic pendulum.
+    pub fn adjust_mechanics(&mut self, delta: i32) {
+        match &mut self.mode {
+            Mode::Pendulum(pendulum) => {
+                *pendulum = DoublePendulum::new((pendulum.count() as i32 + delta).max(1) as usize);
+            }
+            Mode::Magnets(magnets) => magnets.adjust_friction(delta as f32 * magnetic_pendulum::FRICTION_STEP),
+            _ => {}
+        }
+    }
+
+    pub fn skip_tour_step(&mut self) {
+        if let Mode::Tour(tour) = &mut self.mode {
+            let scene = tour.skip().scene.clone();
+            self.show_scene(&scene);
+        }
+    }
+
+    // Within one system only the parameters change, so the cloud visibly reshapes into the
+    // new regime; a different system comes up through the usual cross-fade.
+    pub fn show_scene(&mut self, scene: &Preset) {
+        if scene.system_type == self.system_type {
+            self.parameters = scene.apply_parameters(&self.parameters);
+            self.drift = None;
+        } else {
+            self.apply_preset(scene);
+        }
+    }
+
+    // Starts the attract loop, remembering the scene, camera and pause state it interrupts.
+    pub fn start_screensaver(&mut self, quit_on_input: bool) {
+        let screensaver =
+            Screensaver::new(&self.presets, self.current_preset(), self.camera, self.paused, quit_on_input);
+        let first = screensaver.current().clone();
+        self.screensaver = Some(screensaver);
+        self.camera = Camera::default();
+        self.paused = false;
+        self.apply_preset(&first);
+    }
+
+    // Called on every input. Ends the screensaver if one is running and returns whether the
+    // input was used up doing so.
+    pub fn note_input(&mut self, ctx: &mut Context) -> bool {
+        self.last_input = Instant::now();
+        // A wallpaper is not interacted with; stray focus must not end or steer it.
+        if self.config.wallpaper {
+            return true;
+        }
+        let Some(screensaver) = self.screensaver.take() else {
+            return false;
+        };
+        if screensaver.in_grace_period() {
+            self.screensaver = Some(screensaver);
+        } else if screensaver.quit_on_input {
+            event::quit(ctx);
+        } else {
+            self.apply_preset(&screensaver.interrupted);
+            self.camera = screensaver.camera;
+            self.paused = screensaver.paused;
+        }
+        true
+    }
+
+    // Leaves the start screen with the system or session at `index`.
+    pub fn choose_start(&mut self, index: usize) {
+        let Mode::Menu(menu) = &self.mode else {
+            return;
+        };
+        match menu.choose(index) {
+            Some(Choice::System(system_type)) => {
+                self.carry_time_step(system_type);
+                self.system_type = system_type;
+                self.initialize_particles();
+            }
+            Some(Choice::Resume(preset)) => self.apply_preset(&preset),
+            None => return,
+        }
+        // The cloud set up behind the menu was never on screen, so there is nothing to fade from.
+        self.transition = None;
+        self.set_mode(Mode::Simulate);
+        self.start_tutorial();
+    }
+
+    fn tutorial_snapshot(&self) -> tutorial::Snapshot {
+        tutorial::Snapshot {
+            parameters: self.parameters,
+            trails: self.trail_enabled,
+            system_type: self.system_type,
+        }
+    }
+
+    // Shows the tutorial on a first run, unless a tour or the screensaver has the screen.
+    pub fn start_tutorial(&mut self) {
+        if self.config.tutorial && !self.mode.is_tour() && self.screensaver.is_none() {
+            self.tutorial = Some(Tutorial::new(self.tutorial_snapshot()));
+        }
+    }
+
+    // Closes the tutorial for good, in this session and in the config file.
+    pub fn end_tutorial(&mut self) {
+        self.tutorial = None;
+        self.config.tutorial = false;
+        if let Err(e) = config::save_setting("tutorial", "false") {
+            self.toasts.error(format!("Failed to save tutorial setting: {}", e));
+        }
+    }
+
+    // Enter moves on and Backspace dismisses; other keys go on to their usual actions so
+    // the steps can be followed.
+    pub fn tutorial_key(&mut self, keycode: KeyCode) -> bool {
+        let snapshot = self.tutorial_snapshot();
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return false;
+        };
+        match keycode {
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                if !tutorial.next(snapshot) {
+                    self.end_tutorial();
+                }
+            }
+            KeyCode::Back => self.end_tutorial(),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn start_menu_key(&mut self, ctx: &mut Context, keycode: KeyCode, keymod: KeyMods) {
+        let Mode::Menu(menu) = &mut self.mode else {
+            return;
+        };
+        match keycode {
+            KeyCode::Left => menu.move_selection(-1, 0),
+            KeyCode::Right => menu.move_selection(1, 0),
+            KeyCode::Up => menu.move_selection(0, -1),
+            KeyCode::Down => menu.move_selection(0, 1),
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => {
+                let index = menu.selected;
+                self.choose_start(index);
+            }
+            KeyCode::Key1 | KeyCode::Key2 | KeyCode::Key3 | KeyCode::Key4 | KeyCode::Key5 | KeyCode::Key6 => {
+                if let Some(system_type) = SystemType::from_digit(keycode, keymod.contains(KeyMods::SHIFT)) {
+                    let index = SystemType::ALL.iter().position(|&s| s == system_type).unwrap_or(0);
+                    self.choose_start(index);
+                }
+            }
+            KeyCode::Escape => event::quit(ctx),
+            _ => {}
+        }
+    }
+
+    // Leaves the current mode for `mode`. Entering an analysis starts its map for the
+    // current scene; the mode left behind is simply dropped, along with its menu or tour.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        // Clicks only spawn particles in the plain simulation.
+        if !matches!(self.mode, Mode::Simulate) {
+            self.spawn_mode = false;
+        }
+        if let Some(analysis) = self.mode.analysis() {
+            self.start_analysis(analysis);
+        }
+    }
+
+    pub fn toggle_analysis(&mut self, analysis: Analysis) {
+        if self.mode.analysis() == Some(analysis) {
+            self.set_mode(Mode::Simulate);
+        } else {
+            self.set_mode(Mode::Analyze(analysis));
+        }
+    }
+
+    // The basin, FTLE and escape maps share one plane of initial conditions.
+    pub fn next_analysis_plane(&mut self, analysis: Analysis) {
+        self.basin_plane = self.basin_plane.next();
+        self.set_mode(Mode::Analyze(analysis));
+    }
+
+    pub fn start_analysis(&mut self, analysis: Analysis) {
+        match analysis {
+            Analysis::Scan => self.start_parameter_scan(),
+            Analysis::Basin => self.start_basin_map(),
+            Analysis::Ftle => self.start_ftle_field(),
+            Analysis::Escape => self.start_escape_map(),
+        }
+    }
+
+    pub fn poll_analysis(&mut self, analysis: Analysis) {
+        match analysis {
+            Analysis::Scan => self.poll_parameter_scan(),
+            Analysis::Basin => self.poll_basin_map(),
+            Analysis::Ftle => self.poll_ftle_field(),
+            Analysis::Escape => self.poll_escape_map(),
+        }
+    }
+
+    // Stores the scene for the start screen's resume entry. Nothing is stored before a
+    // scene has been chosen, or for a screensaver launched from the command line.
+    pub fn save_session(&mut self) {
+        if self.mode.is_menu() {
+            return;
+        }
+        let saved = match &self.screensaver {
+            Some(screensaver) if screensaver.quit_on_input => Ok(()),
+            Some(screensaver) => start_menu::save_session(&screensaver.interrupted),
+            None => start_menu::save_session(&self.current_preset()),
+        };
+        if let Err(e) = saved {
+            self.toasts.error(e);
+        }
+    }
+
+    // Carries out one command, from whichever input it came from.
+    pub fn apply(&mut self, ctx: &mut Context, command: Command) {
+        let before = self.parameters;
+        match command {
+            Command::SwitchSystem(system_type) => {
+                if system_type != self.system_type {
+                    self.switch_system(system_type);
+                }
+            }
+            Command::ToggleGroup(system_type) => self.toggle_group(system_type),
+            Command::FocusNextGroup => self.focus_next_group(),
+            Command::StepParameter { slot, direction, fine } => self.step_parameter(slot, direction, fine),
+            Command::ResetParameters => {
+                self.parameters.reset_system(self.system_type);
+                self.drift = None;
+            }
+            Command::ToggleParameterJitter => self.toggle_parameter_jitter(),
+            Command::CycleLink => self.cycle_link(),
+            Command::ToggleHistoryScrubber => self.toggle_history_scrubber(),
+            Command::ScrubHistory(delta) => {
+                if let Some(params) = self.history.step(delta) {
+                    self.restore_history(params);
+                }
+            }
+            Command::SeekHistory(fraction) => {
+                if let Some(params) = self.history.seek(fraction) {
+                    self.restore_history(params);
+                }
+            }
+            Command::ToggleXyPad => {
+                self.xy_pad = match self.xy_pad {
+                    Some(_) => None,
+                    None => Some(XyPad::new(self.system_type, &self.parameters, 0)),
+                };
+            }
+            Command::NextPadPair => {
+                if let Some(pad) = &self.xy_pad {
+                    let pair = (pad.pair + 1) % ScanAxes::pair_count(self.system_type);
+                    self.xy_pad = Some(XyPad::new(self.system_type, &self.parameters, pair));
+                }
+            }
+            Command::SetPadPoint { x, y } => {
+                if let Some(pad) = &self.xy_pad {
+                    pad.apply(&mut self.parameters, x, y);
+                }
+            }
+            Command::ToggleRewind => self.toggle_rewind(),
+            Command::ScrubRewind(delta) => {
+                if let Some(params) = self.rewind.step(delta, &mut self.particles) {
+                    self.restore_rewind(params);
+                }
+            }
+            Command::SeekRewind(fraction) => {
+                if let Some(params) = self.rewind.seek(fraction, &mut self.particles) {
+                    self.restore_rewind(params);
+                }
+            }
+            Command::ToggleDrift => {
+                self.drift = match self.drift {
+                    Some(_) => None,
+                    None => Some(ParameterDrift::new(self.system_type, &self.parameters, self.drift_index)),
+                };
+            }
+            Command::NextDriftParameter => {
+                self.drift_index = (self.drift_index + 1) % get_parameter_names(self.system_type).len();
+                self.drift = Some(ParameterDrift::new(self.system_type, &self.parameters, self.drift_index));
+            }
+            Command::CycleLfo => self.cycle_lfo(),
+            Command::NextLfoParameter => self.selected_parameter += 1,
+            Command::AdjustLfo { up, depth } => self.adjust_lfo(up, depth),
+            Command::StepNoise(direction) if direction > 0 => {
+                self.noise_intensity = if self.noise_intensity > 0.0 {
+                    NOISE_SCALE.step(self.noise_intensity, 1)
+                } else {
+                    NOISE_SCALE.min
+                };
+            }
+            Command::StepNoise(_) => {
+                // Stepping down from the bottom of the range switches noise off.
+                self.noise_intensity = if self.noise_intensity > NOISE_SCALE.min {
+                    NOISE_SCALE.step(self.noise_intensity, -1)
+                } else {
+                    0.0
+                };
+            }
+            Command::StepTimeStep(direction) => self.dt = TIME_STEP.step(self.dt, direction),
+            Command::StepTimeScale(direction) => self.time_scale = TIME_SCALE.step(self.time_scale, direction),
+            Command::NextIntegrator => self.integrator = self.integrator.next(),
+            Command::NextComparisonIntegrator => {
+                if let Some(comparison) = self.comparison.as_mut() {
+                    comparison.integrator = comparison.integrator.next();
+                }
+            }
+            Command::ToggleComparison => self.toggle_comparison(),
+            Command::ToggleIntegratorOverlay => self.toggle_integrator_overlay(),
+            Command::ToggleSoftClamp => self.toggle_soft_clamp(),
+            Command::ToggleAccessibility => self.toggle_accessibility(),
+            Command::ExportPoster { double } => {
+                let (width, height) = self.config.poster_size;
+                let factor = if double { 2 } else { 1 };
+                self.export_poster(ctx, width.saturating_mul(factor), height.saturating_mul(factor));
+            }
+            Command::ExportTiledPoster => {
+                let (width, height) = self.config.tiled_poster_size;
+                self.export_poster(ctx, width, height);
+            }
+            Command::TogglePause => self.paused = !self.paused,
+
+            Command::Restart => self.initialize_particles(),
+            // Steps of 5, or 10% once the cloud is large.
+            Command::MoreParticles => {
+                self.set_particle_count(self.particle_count + (self.particle_count / 10).max(5));
+            }
+            Command::FewerParticles => {
+                let step = (self.particle_count / 10).max(5);
+                self.set_particle_count(self.particle_count.saturating_sub(step).max(5));
+            }
+            Command::EnterParticleCount => self.count_entry = Some(String::new()),
+            Command::NextSeedPattern => {
+                self.seed_pattern = self.seed_pattern.next();
+                self.initialize_particles();
+            }
+            Command::ScaleSeedExtent(factor) => {
+                self.seed_extent = (self.seed_extent * factor).clamp(1.0, SCREEN_WIDTH);
+                if self.seed_pattern != SeedPattern::RandomBox {
+                    self.initialize_particles();
+                }
+            }
+            Command::ToggleLifetime => {
+                let lifetime = match self.lifetime {
+                    Some(_) => None,
+                    None => Some(self.config.lifetime.unwrap_or(DEFAULT_LIFETIME)),
+                };
+                self.set_lifetime(lifetime);
+            }
+            Command::NextEmitterShape => self.emitter_shape = self.emitter_shape.next(),
+            Command::AddEmitter { x, y } => self.add_emitter(x, y),
+            Command::ToggleSpawnMode => self.toggle_spawn_mode(),
+            Command::SpawnParticle { x, y } => self.spawn_particle(x, y),
+            Command::ClearEmitters => {
+                self.emitters.clear();
+                self.sync_particle_count();
+            }
+            Command::CycleNetwork => self.cycle_network(),
+            Command::StepNetworkStrength(direction) => {
+                if let Some(network) = self.network.as_mut() {
+                    network.strength = network::STRENGTH_SCALE.step(network.strength, direction);
+                }
+            }
+            Command::CycleCoupling => {
+                self.coupling = match self.coupling.map(|c| c.kind) {
+                    None => Some(self.config.coupling(CouplingKind::Spring)),
+                    Some(CouplingKind::Spring) => Some(self.config.coupling(CouplingKind::Alignment)),
+                    Some(CouplingKind::Alignment) => None,
+                };
+            }
+            Command::StepCouplingStrength(direction) => {
+                if let Some(coupling) = self.coupling.as_mut() {
+                    coupling.strength = coupling::STRENGTH_SCALE.step(coupling.strength, direction);
+                }
+            }
+
+            Command::CycleLeader { forward } => self.cycle_leader(forward),
+            Command::ToggleFollowLeader => self.toggle_follow_leader(),
+            Command::PinLeader => {
+                if let Some(id) = self.leader.as_ref().map(|l| l.id) {
+                    self.toggle_pin(id);
+                }
+            }
+            Command::TogglePin(id) => self.toggle_pin(id),
+            Command::ReleasePins => self.release_pins(),
+            Command::ToggleTrajectory { x, y } => self.toggle_trajectory(x, y),
+            Command::ExportTrajectory { json } => self.export_trajectory(json),
+
+            Command::Pan { dx, dy } => self.camera.pan(dx, dy),
+            Command::Zoom { factor, x, y } => self.camera.zoom_at(factor, x, y),
+            Command::FrameRegion(region) => {
+                self.camera.frame(region);
+                self.follow_leader = false;
+            }
+            Command::FitCamera => {
+                let system_type = self.system_type;
+                self.camera.fit(self.particles.iter().map(|p| p.get_screen_pos(system_type)));
+                self.follow_leader = false;
+            }
+            Command::ResetCamera => {
+                self.camera = Camera::default();
+                self.bookmark = None;
+                self.follow_leader = false;
+            }
+            Command::SaveBookmark(slot) => self.save_bookmark(slot),
+            Command::RecallBookmark(slot) => self.recall_bookmark(slot),
+            Command::ToggleCameraRecording => self.toggle_camera_recording(),
+            Command::ToggleCameraPlayback => self.toggle_camera_playback(),
+            Command::SaveCameraPath => self.save_camera_path(),
+            Command::LoadCameraPath => self.load_camera_path(),
+
+            Command::SavePreset => self.save_preset(),
+            Command::LoadNextPreset => self.load_next_preset(),
+            Command::CopyState => self.copy_state(),
+            Command::PasteState => self.paste_state(),
+            Command::CaptureKeyframe => self.capture_keyframe(),
+            Command::SaveTimeline => self.save_timeline(),
+            Command::ToggleTimelinePlayback => self.toggle_timeline_playback(),
+            Command::ClearTimeline => self.timeline = None,
+            Command::ToggleTour => self.toggle_tour(),
+            Command::SkipTourStep => self.skip_tour_step(),
+            Command::ToggleDoublePendulum => self.toggle_double_pendulum(),
+            Command::ToggleMagneticPendulum => self.toggle_magnetic_pendulum(),
+            Command::ToggleThreeBody => self.toggle_three_body(),
+            Command::MechanicsPause => self.pause_mechanics(),
+            Command::MechanicsRestart => self.restart_mechanics(),
+            Command::MechanicsNext => {
+                if let Mode::ThreeBody(three_body) = &mut self.mode {
+                    *three_body = ThreeBody::new(three_body.problem.next());
+                }
+            }
+            Command::MechanicsAdjust(delta) => self.adjust_mechanics(delta),
+            Command::MechanicsClear => {
+                if let Mode::Magnets(magnets) = &mut self.mode {
+                    magnets.clear();
+                }
+            }
+
+            Command::ToggleTrails => self.trail_enabled = !self.trail_enabled,
+            Command::ToggleRibbons => self.ribbon_trails = !self.ribbon_trails,
+            Command::NextTrailWidth => self.trail_width = self.trail_width.next(),
+            Command::NextTrailGradient => self.trail_gradient = self.trail_gradient.next(),
+            Command::ToggleTrailMode => {
+                self.trail_mode = match self.trail_mode {
+                    TrailMode::Lines => TrailMode::Feedback,
+                    TrailMode::Feedback => TrailMode::Lines,
+                };
+                self.feedback_trails = None;
+                for particle in self.particles.iter_mut() {
+                    particle.clear_trail();
+                }
+            }
+            Command::NextParticleShape => {
+                self.particle_shape = self.particle_shape.next();
+                if self.particle_shape == ParticleShape::Sprite && self.config.sprite.is_none() {
+                    self.particle_shape = self.particle_shape.next();
+                }
+            }
+            Command::ToggleHalo => {
+                self.halo_radius = match self.halo_radius {
+                    Some(_) => None,
+                    None => Some(self.config.halo.unwrap_or(halo::DEFAULT_HALO_RADIUS)),
+                };
+            }
+            Command::StepHalo(direction) => {
+                if let Some(radius) = self.halo_radius.as_mut() {
+                    *radius = if direction > 0 {
+                        (*radius * 1.25).min(halo::MAX_HALO_RADIUS)
+                    } else {
+                        (*radius / 1.25).max(halo::MIN_HALO_RADIUS)
+                    };
+                }
+            }
+            Command::ToggleDensityColoring => {
+                self.density_coloring = !self.density_coloring;
+                if self.density_coloring {
+                    self.refresh_spatial_hash();
+                }
+            }
+            Command::ToggleErrorColoring => self.error_coloring = !self.error_coloring,
+            Command::ToggleSpeedColoring => self.speed_coloring = !self.speed_coloring,
+            Command::NextPalette => self.theme.palette = self.theme.palette.next(),
+            // Reads the theme directory again, so edited themes come in with the switch.
+            Command::NextTheme => {
+                let (theme, problems) = theme::next(&self.theme.name);
+                self.theme = theme;
+                for problem in problems {
+                    self.toasts.error(problem);
+                }
+            }
+            Command::ToggleHud => self.show_ui = !self.show_ui,
+            Command::ToggleMessageLog => self.toasts.show_log = !self.toasts.show_log,
+            Command::ToggleKeyReference => {
+                self.key_reference = if self.key_reference.is_some() { None } else { Some(KeyReference::new()) };
+            }
+            Command::TurnKeyReference(delta) => {
+                if let Some(reference) = self.key_reference.as_mut() {
+                    reference.turn(delta);
+                }
+            }
+
+            Command::CycleEntropyAxis => self.entropy.cycle_axis(),
+            Command::ComputeLyapunov => self.start_lyapunov_spectrum(),
+            Command::ToggleFixedPoints => self.show_fixed_points = !self.show_fixed_points,
+            Command::ToggleNullclines => self.show_nullclines = !self.show_nullclines,
+            Command::ToggleSensitivityDemo => self.toggle_sensitivity_demo(),
+            Command::ToggleEmbedding => {
+                self.embedding = match self.embedding {
+                    Some(_) => None,
+                    None => Some(DelayEmbedding::new()),
+                };
+            }
+            Command::AdjustEmbeddingDelay(delta) => {
+                if let Some(embedding) = self.embedding.as_mut() {
+                    embedding.adjust_delay(delta);
+                }
+            }
+            Command::AdjustEmbeddingDimension(delta) => {
+                if let Some(embedding) = self.embedding.as_mut() {
+                    embedding.adjust_dimension(delta);
+                }
+            }
+            Command::ToggleSymbolic => {
+                self.symbolic = match self.symbolic {
+                    Some(_) => None,
+                    None => Some(SymbolTracker::new()),
+                };
+            }
+            Command::ToggleSymbolEcho => {
+                let symbolic = self.symbolic.get_or_insert_with(SymbolTracker::new);
+                symbolic.echo = !symbolic.echo;
+            }
+            Command::TogglePeriodicOrbit => {
+                self.show_periodic_orbit = !self.show_periodic_orbit;
+                if self.show_periodic_orbit {
+                    self.start_periodic_orbit_search();
+                }
+            }
+            Command::ToggleStroboscope => {
+                self.stroboscope = match self.stroboscope {
+                    Some(_) => None,
+                    None => Some(StroboscopicSection::new()),
+                };
+            }
+            Command::SwapStroboscopeAxes => {
+                if let Some(section) = self.stroboscope.as_mut() {
+                    section.swapped = !section.swapped;
+                }
+            }
+            Command::ToggleMeasure => {
+                self.measure = match self.measure {
+                    Some(_) => None,
+                    None => Some(InvariantMeasure::new()),
+                };
+            }
+            Command::ExportMeasure => self.export_measure(),
+            Command::ToggleRegimes => {
+                if self.show_regimes && self.regime_search.is_none() && !self.regimes.is_empty() {
+                    self.show_regimes = false;
+                } else {
+                    self.show_regimes = true;
+                    if self.regime_search.is_none() {
+                        self.start_regime_search();
+                    }
+                }
+            }
+            Command::LoadRegime(index) => self.load_regime(index),
+            Command::ToggleScan => self.toggle_analysis(Analysis::Scan),
+            Command::NextScanPair => {
+                self.scan_pair = (self.scan_pair + 1) % ScanAxes::pair_count(self.system_type);
+                self.set_mode(Mode::Analyze(Analysis::Scan));
+            }
+            Command::PickScanPoint { x, y } => {
+                if let Some(axes) = self.scan_axes {
+                    self.parameters.set(axes.x.name, axes.x.value_at(x));
+                    self.parameters.set(axes.y.name, axes.y.value_at(1.0 - y));
+                    self.set_mode(Mode::Simulate);
+                    self.initialize_particles();
+                }
+            }
+            Command::ToggleFtle => self.toggle_analysis(Analysis::Ftle),
+            Command::ToggleEscapeMap => self.toggle_analysis(Analysis::Escape),
+            Command::ToggleBasinMap => self.toggle_analysis(Analysis::Basin),
+            Command::NextFtlePlane => self.next_analysis_plane(Analysis::Ftle),
+            Command::NextEscapePlane => self.next_analysis_plane(Analysis::Escape),
+            Command::NextBasinPlane => self.next_analysis_plane(Analysis::Basin),
+            Command::CycleHistograms => {
+                self.histograms = match self.histograms.as_ref().map(|h| h.source) {
+                    None => Some(CoordinateHistograms::new(HistogramSource::Ensemble)),
+                    Some(HistogramSource::Ensemble) => {
+                        Some(CoordinateHistograms::new(HistogramSource::ReferenceHistory))
+                    }
+                    Some(HistogramSource::ReferenceHistory) => None,
+                };
+            }
+            Command::ToggleSpectrum => {
+                self.spectrum = match self.spectrum {
+                    Some(_) => None,
+                    None => Some(SpectrumAnalyzer::new()),
+                };
+            }
+            Command::ToggleCorrelation => {
+                if self.correlation.is_some() || self.correlation_job.is_some() {
+                    self.correlation = None;
+                    self.correlation_job = None;
+                } else {
+                    self.start_correlation_dimension();
+                }
+            }
+            Command::ToggleDetachedPanels => {
+                self.detached_panels = match self.detached_panels.take() {
+                    Some(_) => None,
+                    None => match DetachedPanels::open() {
+                        Ok(detached) => Some(detached),
+                        Err(e) => {
+                            self.toasts.error(e);
+                            None
+                        }
+                    },
+                };
+            }
+
+            // quit_event only runs when the window is closed, so the session is saved here too.
+            Command::Quit => {
+                self.save_session();
+                self.detached_panels = None;
+                event::quit(ctx);
+            }
+        }
+        self.enforce_parameter_links(&before);
+        self.enforce_parameter_bounds();
+    }
+
+    // Camera the attractor layer is drawn with: the user's camera times `extra_zoom`, moved
+    // so its target sits in the middle of the left half while the split view is open.
+    pub fn view_camera(&self, extra_zoom: f32) -> Camera {
+        let mut camera = self.camera;
+        camera.zoom *= extra_zoom;
+        if self.comparison.is_some() {
+            camera.target.x += SCREEN_WIDTH / 4.0 / camera.zoom;
+        }
+        camera
+    }
+
+    pub fn toggle_sensitivity_demo(&mut self) {
+        if let Some(demo) = self.sensitivity.take() {
+            for id in demo.pair {
+                self.particles.despawn(id);
+            }
+            return;
+        }
+        let start = match self.particles.iter().next() {
+            Some(reference) => [reference.x, reference.y, reference.z],
+            None => {
+                let (x_range, y_range, z_range) = get_initial_ranges(self.system_type);
+                [
+                    (x_range.start + x_range.end) / 2.0,
+                    (y_range.start + y_range.end) / 2.0,
+                    (z_range.start + z_range.end) / 2.0,
+                ]
+            }
+        };
+        self.sensitivity = Some(SensitivityDemo::spawn(&mut self.particles, start));
+    }
+
+    pub fn draw_sensitivity_pair(&self, ctx: &mut Context, demo: &SensitivityDemo) -> GameResult {
+        for (id, color) in demo.pair.iter().zip([
+            graphics::Color::new(1.0, 0.2, 0.8, 1.0),
+            graphics::Color::new(0.2, 1.0, 1.0, 1.0),
+        ]) {
+            if let Some(particle) = self.particles.get(*id) {
+                let ring = graphics::Mesh::new_circle(
+                    ctx,
+                    graphics::DrawMode::stroke(2.0),
+                    particle.get_screen_pos(self.system_type),
+                    6.0,
+                    0.1,
+                    color,
+                )?;
+                graphics::draw(ctx, &ring, graphics::DrawParam::default())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn draw_sensitivity_demo(&self, ctx: &mut Context, demo: &SensitivityDemo) -> GameResult {
+        let attractor_size = SCREEN_HEIGHT / 2.0 / get_scale_factor(self.system_type);
+        let title = match demo.growth_rate(attractor_size) {
+            Some(rate) => format!("log10 separation vs t   slope λ ≈ {}", self.config.locale.fixed(rate, 2)),
+            None => "log10 separation vs t".to_string(),
+        };
+        PlotPanel {
+            area: graphics::Rect::new(20.0, SCREEN_HEIGHT - 160.0, 300.0, 140.0),
+            title,
+            series: Cow::Borrowed(&demo.samples),
+            overlay: None,
+            color: graphics::Color::new(1.0, 0.6, 0.9, 1.0),
+        }
+        .draw(ctx, &self.theme)
+    }
+
+    // Plots of the analyses that are switched on, in their places in the main window. They
+    // are drawn there unless they have been detached into the panel viewer.
+    pub fn analysis_panels(&self) -> Vec<PlotPanel<'_>> {
+        let mut panels = Vec::new();
+        if let Some(network) = &self.network {
+            let top = if self.sensitivity.is_some() { SCREEN_HEIGHT - 310.0 } else { SCREEN_HEIGHT - 160.0 };
+            panels.push(PlotPanel {
+                area: graphics::Rect::new(20.0, top, 300.0, 140.0),
+                title: format!("log10 sync error vs t ({})", network.topology.name()),
+                series: Cow::Borrowed(&network.samples),
+                overlay: None,
+                color: graphics::Color::new(0.6, 0.8, 1.0, 1.0),
+            });
+        }
+
+        if let Some(histograms) = &self.histograms {
+            let source = match histograms.source {
+                HistogramSource::Ensemble => "ensemble",
+                HistogramSource::ReferenceHistory => "over time",
+            };
+            for (axis, curve) in histograms.curves.iter().enumerate() {
+                panels.push(PlotPanel {
+                    area: graphics::Rect::new(330.0 + axis as f32 * 155.0, SCREEN_HEIGHT - 130.0, 150.0, 110.0),
+                    title: format!("{} ({})", self.system_type.axis_names()[axis], source),
+                    series: Cow::Borrowed(curve),
+                    overlay: None,
+                    color: graphics::Color::new(0.9, 0.8, 0.4, 1.0),
+                });
+            }
+        }
+
+        if let Some(embedding) = &self.embedding {
+            panels.push(PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH - 270.0, 210.0, 250.0, 180.0),
+                title: format!(
+                    "Delay embedding of x(t): τ={}·dt, m={}",
+                    embedding.delay, embedding.dimension
+                ),
+                series: Cow::Owned(embedding.projected_points()),
+                overlay: None,
+                color: graphics::Color::new(0.9, 0.7, 1.0, 0.8),
+            });
+        }
+
+        if let Some(correlation) = &self.correlation {
+            panels.push(PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH - 270.0, 20.0, 250.0, 180.0),
+                title: format!("log C(r) vs log r   D2 ≈ {}", self.config.locale.fixed(correlation.slope, 2)),
+                series: Cow::Owned(correlation.curve()),
+                overlay: Some(correlation.fit_line()),
+                color: graphics::Color::new(0.5, 0.8, 1.0, 1.0),
+            });
+        } else if self.correlation_job.is_some() {
+            panels.push(PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH - 270.0, 20.0, 250.0, 180.0),
+                title: "log C(r) vs log r (computing...)".to_string(),
+                series: Cow::Borrowed(&[]),
+                overlay: None,
+                color: graphics::Color::new(0.5, 0.8, 1.0, 1.0),
+            });
+        }
+
+        if let Some(spectrum) = &self.spectrum {
+            panels.push(PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH - 270.0, SCREEN_HEIGHT - 200.0, 250.0, 180.0),
+                title: if spectrum.power.is_empty() {
+                    "Power spectrum of x(t) (collecting...)".to_string()
+                } else {
+                    "Power spectrum of x(t) (log10)".to_string()
+                },
+                series: Cow::Borrowed(&spectrum.power),
+                overlay: None,
+                color: graphics::Color::new(0.6, 1.0, 0.5, 1.0),
+            });
+        }
+        panels
+    }
+
+    // Nearest particle within PICK_RADIUS_PX of a screen position.
+    pub fn particle_at(&self, screen_x: f32, screen_y: f32) -> Option<ParticleId> {
+        self.particles
+            .iter_with_ids()
+            .map(|(id, p)| {
+                let pos = p.get_screen_pos(self.system_type);
+                (id, (pos.x - screen_x).powi(2) + (pos.y - screen_y).powi(2))
+            })
+            .filter(|(_, d2)| *d2 <= PICK_RADIUS_PX * PICK_RADIUS_PX)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    pub fn toggle_trajectory(&mut self, screen_x: f32, screen_y: f32) {
+        let Some(id) = self.particle_at(screen_x, screen_y) else {
+            return;
+        };
+        self.trajectory = match &self.trajectory {
+            Some(recorder) if recorder.id == id => None,
+            _ => Some(TrajectoryRecorder::new(id, self.system_type, &self.particles)),
+        };
+    }
+
+    pub fn export_trajectory(&mut self, json: bool) {
+        let Some(recorder) = &self.trajectory else {
+            return;
+        };
+        let (result, path) = if json {
+            (recorder.export_json(), trajectory::JSON_PATH)
+        } else {
+            (recorder.export_csv(), trajectory::CSV_PATH)
+        };
+        match result {
+            Ok(samples) => self.toasts.info(format!("Wrote {} trajectory samples to {}", samples, path)),
+            Err(e) => self.toasts.error(format!("Failed to write {}: {}", path, e)),
+        }
+    }
+
+    pub fn cycle_leader(&mut self, forward: bool) {
+        let next = Leader::cycle(self.leader.as_ref().map(|l| l.id), &self.particles, forward);
+        self.leader = next.map(Leader::new);
+        if let Some(leader) = self.leader.as_mut() {
+            leader.record(&self.particles, self.system_type, &self.parameters);
+        }
+    }
+
+    // Following needs someone to follow, so switching it on picks a leader if there is none.
+    pub fn toggle_follow_leader(&mut self) {
+        self.follow_leader = !self.follow_leader;
+        if self.follow_leader && self.leader.is_none() {
+            self.cycle_leader(true);
+        }
+    }
+
+    pub fn toggle_pin(&mut self, id: ParticleId) {
+        if let Some(particle) = self.particles.get_mut(id) {
+            particle.pinned = !particle.pinned;
+        }
+    }
+
+    pub fn release_pins(&mut self) {
+        for particle in self.particles.iter_mut() {
+            particle.pinned = false;
+        }
+    }
+
+    pub fn draw_pinned_markers(&self, ctx: &mut Context) -> GameResult {
+        let mut builder = graphics::MeshBuilder::new();
+        let mut any = false;
+        for particle in self.particles.iter().filter(|p| p.pinned) {
+            let pos = particle.get_screen_pos(self.system_type);
+            if !pos.x.is_finite() || !pos.y.is_finite() {
+                continue;
+            }
+            builder.rectangle(
+                graphics::DrawMode::stroke(1.0),
+                graphics::Rect::new(pos.x - 5.0, pos.y - 5.0, 10.0, 10.0),
+                graphics::Color::new(0.9, 0.9, 0.9, 0.9),
+            )?;
+            any = true;
+        }
+        if any {
+            let mesh = builder.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        Ok(())
+    }
+
+    // Loads the configured PNG the first time any system asks for sprites; without a usable
+    // file every sprite shape falls back to circles.
+    pub fn ensure_particle_sprite(&mut self, ctx: &mut Context) {
+        let wanted = self.particle_shape == ParticleShape::Sprite
+            || self.groups.iter().any(|g| g.shape == ParticleShape::Sprite);
+        if !wanted || self.particle_sprite.is_some() {
+            return;
+        }
+        let loaded = match &self.config.sprite {
+            Some(path) => ParticleSprite::load(ctx, path).map_err(|e| e.to_string()),
+            None => Err("no `sprite` file configured".to_string()),
+        };
+        match loaded {
+            Ok(sprite) => self.particle_sprite = Some(sprite),
+            Err(e) => {
+                self.toasts.warn(format!("Particle sprite unavailable: {}", e));
+                if self.particle_shape == ParticleShape::Sprite {
+                    self.particle_shape = ParticleShape::Circle;
+                }
+                for group in self.groups.iter_mut().filter(|g| g.shape == ParticleShape::Sprite) {
+                    group.shape = ParticleShape::Circle;
+                }
+            }
+        }
+    }
+
+    pub fn draw_followed_particle(&self, ctx: &mut Context, recorder: &TrajectoryRecorder) -> GameResult {
+        let Some(particle) = self.particles.get(recorder.id) else {
+            return Ok(());
+        };
+        let ring = graphics::Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::stroke(1.5),
+            particle.get_screen_pos(self.system_type),
+            8.0,
+            0.1,
+            graphics::Color::new(1.0, 0.3, 0.3, 1.0),
+        )?;
+        graphics::draw(ctx, &ring, graphics::DrawParam::default())
+    }
+
+    // Mean depth of the cloud along the view direction.
+    pub fn nullcline_slice(&self) -> f32 {
+        let view = get_view_preset(self.system_type);
+        let (sum, count) = self
+            .particles
+            .iter()
+            .map(|p| view.depth([p.x, p.y, p.z]))
+            .filter(|depth| depth.is_finite())
+            .fold((0.0, 0usize), |(sum, count), depth| (sum + depth, count + 1));
+        if count == 0 { 0.0 } else { sum / count as f32 }
+    }
+
+    pub fn start_parameter_scan(&mut self) {
+        let system_type = self.system_type;
+        let parameters = self.parameters;
+        let axes = ScanAxes::for_pair(system_type, &parameters, self.scan_pair);
+        let dt = self.dt;
+        self.scan_axes = Some(axes);
+        self.scan_image = None;
+        self.scan_job = Some(thread::spawn(move || param_scan::compute(system_type, parameters, axes, dt)));
+    }
+
+    pub fn poll_parameter_scan(&mut self) {
+        if !self.scan_job.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.scan_job.take() {
+            match job.join() {
+                Ok(image) => self.scan_result = Some(image),
+                Err(_) => self.toasts.error("Parameter scan worker panicked"),
+            }
+        }
+    }
+
+    pub fn start_regime_search(&mut self) {
+        let system_type = self.system_type;
+        let dt = self.dt;
+        let seed = rand::thread_rng().gen_range(0..u64::MAX);
+        self.regimes.clear();
+        self.regimes_for = system_type;
+        self.regime_search = Some(thread::spawn(move || regime_finder::search(system_type, dt, seed)));
+    }
+
+    pub fn poll_regime_search(&mut self) {
+        if !self.regime_search.as_ref().is_some_and(|job| job.is_finished()) {
+            return;
+        }
+        if let Some(job) = self.regime_search.take() {
+            match job.join() {
+                Ok(regimes) => self.regimes = regimes,
+                Err(_) => self.toasts.error("Regime search worker panicked"),
+            }
+        }
+    }
+
+    pub fn load_regime(&mut self, index: usize) {
+        if self.regimes_for != self.system_type {
+            return;
+        }
+        if let Some(regime) = self.regimes.get(index) {
+            self.parameters = regime.parameters;
+            self.initialize_particles();
+        }
+    }
+
+    pub fn draw_regime_gallery(&self, ctx: &mut Context) -> GameResult {
+        let locale = self.config.locale;
+        let names = get_parameter_names(self.regimes_for);
+        let mut lines = vec![if self.regime_search.is_some() {
+            "Searching parameter space...".to_string()
+        } else if self.regimes.is_empty() {
+            "No chaotic regimes found (F10 to search again)".to_string()
+        } else {
+            "Discovered regimes (F1-F9 to load, F10 to search again)".to_string()
+        }];
+        for (i, regime) in self.regimes.iter().enumerate() {
+            let values: Vec<String> = names
+                .iter()
+                .map(|name| format!("{}={}", name, locale.fixed(regime.parameters.get(name).unwrap_or(0.0), 2)))
+                .collect();
+            lines.push(format!(
+                "F{}: λ={} speed={}  {}",
+                i + 1,
+                locale.fixed(regime.lyapunov, 3),
+                locale.fixed(regime.mean_speed, 1),
+                values.join(" ")
+            ));
+        }
+
+        let area = graphics::Rect::new(SCREEN_WIDTH - 470.0, 210.0, 450.0, 18.0 * lines.len() as f32 + 10.0);
+        let background = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            area,
+            graphics::Color::new(0.0, 0.0, 0.0, 0.7),
+        )?;
+        graphics::draw(ctx, &background, graphics::DrawParam::default())?;
+
+        let text = graphics::Text::new(graphics::TextFragment::new(lines.join("\n"))
+            .font(graphics::Font::default()).scale(graphics::PxScale::from(14.0)));
+        graphics::draw(
+            ctx,
+            &text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: area.x + 6.0, y: area.y + 5.0 })
+                .color(graphics::Color::WHITE),
+        )
+    }
+
+    pub fn record_reference_sample(&mut self) {
+        let reference = self
+            .reference_particle
+            .and_then(|id| self.particles.get(id).map(|p| (id, [p.x, p.y, p.z])));
+        let (id, state) = match reference {
+            Some(found) => found,
+            None => {
+                let Some((id, particle)) = self.particles.iter_with_ids().next() else {
+                    return;
+                };
+                if let Some(spectrum) = self.spectrum.as_mut() {
+                    spectrum.clear();
+                }
+                self.entropy.clear();
+                if let Some(embedding) = self.embedding.as_mut() {
+                    embedding.clear();
+                }
+                if let Some(symbolic) = self.symbolic.as_mut() {
+                    symbolic.clear();
+                }
+                (id, [particle.x, particle.y, particle.z])
+            }
+        };
+        self.reference_particle = Some(id);
+
+        if let Some(spectrum) = self.spectrum.as_mut() {
+            spectrum.push(state[0]);
+            spectrum.start(self.dt * self.time_scale);
+        }
+        self.entropy.push(state);
+        if let Some(embedding) = self.embedding.as_mut() {
+            embedding.push(state[0]);
+        }
+        if let Some(histograms) = self.histograms.as_mut() {
+            histograms.record(&self.particles, Some(state));
+        }
+        if let Some(symbolic) = self.symbolic.as_mut() {
+            symbolic.push(state);
+        }
+    }
+
+    // Recovers from a diverged cloud or a sustained slow frame rate, and logs what it did.
+    fn run_watchdog(&mut self, frame_secs: f32) {
+        let all_diverged = self.particles.len() > 0 && self.particles.iter().all(|p| !p.is_finite());
+        let Some(recovery) = self.watchdog.check(all_diverged, frame_secs, self.config.max_fps) else {
+            return;
+        };
+        let message = match recovery {
+            Recovery::ResetParticles => {
+                self.initialize_particles();
+                format!("every {} particle diverged; reset the particles", self.system_type.name())
+            }
+            Recovery::ResetParameters => {
+                self.parameters.reset_system(self.system_type);
+                self.drift = None;
+                self.initialize_particles();
+                format!("{} diverged again right after a reset; reset its parameters", self.system_type.name())
+            }
+            Recovery::ShedLoad => {
+                let (before, count) = (self.particle_count, (self.particle_count / 2).max(5));
+                let slow = format!("frames stayed slow ({:.0} ms)", frame_secs * 1e3);
+                if count < before {
+                    self.set_particle_count(count);
+                    format!("{}; reduced particles from {} to {}", slow, before, count)
+                } else {
+                    format!("{} with only {} particles left", slow, count)
+                }
+            }
+        };
+        self.toasts.warn(format!("Watchdog: {}", message));
+        if let Err(e) = watchdog::log(&message) {
+            self.toasts.error(format!("Failed to write {}: {}", watchdog::LOG_PATH, e));
+        }
+    }
+
+    // The mechanics modes have a pause of their own; the simulation's does not stop them.
+    pub fn is_idle(&self) -> bool {
+        let paused = match &self.mode {
+            Mode::Pendulum(pendulum) => pendulum.paused,
+            Mode::Magnets(magnets) => magnets.paused,
+            Mode::ThreeBody(three_body) => three_body.paused,
+            _ => self.paused,
+        };
+        paused || !self.focused || self.minimized
+    }
+
+    // Presents the frame and holds it back to the frame-rate cap.
+    pub fn finish_frame(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::present(ctx)?;
+
+        if let Some(max_fps) = self.config.max_fps {
+            let frame_budget = Duration::from_secs_f64(1.0 / max_fps.max(1) as f64);
+            let elapsed = self.last_frame.elapsed();
+            if elapsed < frame_budget {
+                timer::sleep(frame_budget - elapsed);
+            }
+        }
+        self.last_frame = Instant::now();
+        Ok(())
+    }
+
+    pub fn draw_ui(&self, ctx: &mut Context) -> GameResult {
+        let font = graphics::Font::default();
+        let locale = self.config.locale;
+        let list = locale.list_separator();
+        let mut y_offset = 20.0;
+        let line_height = 20.0;
+
+        let system_text = graphics::Text::new(graphics::TextFragment::new(
+            format!(
+                "System: {} (Press 1-6 or Shift+1-6 to change){}",
+                self.system_type.name(),
+                match self.preset_index.and_then(|i| self.presets.get(i)) {
+                    Some(preset) => format!("  Preset: {} (Ctrl+L next, Ctrl+K save)", preset.name),
+                    None => String::new(),
+                }
+            )
+        ).font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &system_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let info = get_parameter_info(self.system_type);
+        let format_value =
+            |p: &ParameterInfo| format!("{}={}", p.symbol, locale.fixed(self.parameters.get(p.name).unwrap_or(0.0), 2));
+        let keyed: Vec<String> = info
+            .iter()
+            .filter(|p| p.keyed)
+            .zip(KEY_ROWS)
+            .map(|(p, keys)| format!("{}: {}", keys, format_value(p)))
+            .collect();
+        let fixed: Vec<String> = info.iter().filter(|p| !p.keyed).map(format_value).collect();
+        let param_text_str = if fixed.is_empty() {
+            format!("Parameters ({})", keyed.join(list))
+        } else {
+            format!("Parameters ({}) ({})", keyed.join(list), fixed.join(list))
+        };
+
+        let param_text = graphics::Text::new(graphics::TextFragment::new(param_text_str)
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &param_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        if let Some((info, at)) = self.last_adjusted
+            && at.elapsed() < BOUND_HINT_DURATION
+            && self.bound_hit.is_none_or(|(_, _, hit)| hit.elapsed() >= BOUND_HINT_DURATION)
+        {
+            let adjusted_str = format!(
+                "{} ({}): {}, range {} to {}",
+                info.symbol,
+                info.label,
+                info.description,
+                locale.fixed(info.min, 2),
+                locale.fixed(info.max, 2)
+            );
+            let adjusted_text = graphics::Text::new(graphics::TextFragment::new(adjusted_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &adjusted_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(graphics::Color::new(0.75, 0.75, 0.75, 1.0)),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some((info, bound, at)) = self.bound_hit
+            && at.elapsed() < BOUND_HINT_DURATION
+        {
+            let bound_str = format!(
+                "{} held at its {} bound {} (range {} to {})",
+                info.symbol,
+                if bound <= info.min { "lower" } else { "upper" },
+                locale.fixed(bound, 2),
+                locale.fixed(info.min, 2),
+                locale.fixed(info.max, 2)
+            );
+            let bound_text = graphics::Text::new(graphics::TextFragment::new(bound_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &bound_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(graphics::Color::new(1.0, 0.8, 0.2, 1.0)),
+            )?;
+            y_offset += line_height;
+        }
+
+        let time_text = graphics::Text::new(graphics::TextFragment::new(
+            format!(
+                "Time Scale: {}x (Z/X to adjust), dt {} (Shift+Z/X){}  FPS: {:.0}",
+                locale.significant(self.time_scale),
+                locale.significant(self.dt),
+                if self.paused { " [PAUSED]" } else { "" },
+                timer::fps(ctx)
+            )
+        ).font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &time_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let integrator_text = graphics::Text::new(graphics::TextFragment::new(format!(
+            "Integrator: {} (F11 to switch, F12 error colors){}",
+            self.integrator.name(),
+            match (self.error_coloring, self.integrator, self.config.auto_color_range) {
+                (false, _, _) => "",
+                (true, Integrator::Heun, true) => ", colored by local error",
+                (true, Integrator::Heun, false) => ", colored by local error (green <0.01 px, red >1 px)",
+                (true, _, _) => ", error coloring needs Heun-Euler",
+            }
+        ))
+        .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &integrator_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        if self.config.auto_color_range
+            && let Some(scale) = self.color_range.scale.filter(|&scale| self.color_scale() == Some(scale))
+        {
+            let range_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Color range: {} {} to {} (following the cloud)",
+                scale.name(),
+                locale.significant(self.color_range.low),
+                locale.significant(self.color_range.high)
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &range_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        let noise_str = if self.noise_intensity > 0.0 {
+            format!("Noise: σ={} (Euler–Maruyama, -/= to adjust)", locale.significant(self.noise_intensity))
+        } else {
+            "Noise: off (= to add stochastic forcing)".to_string()
+        };
+        let noise_text = graphics::Text::new(graphics::TextFragment::new(noise_str)
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &noise_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let format_entropy = |value: Option<f32>| value.map_or("--".to_string(), |v| locale.fixed(v, 3));
+        let entropy_text = graphics::Text::new(graphics::TextFragment::new(
+            format!(
+                "Entropy of {} (Y to change): permutation={}, sample={}",
+                self.system_type.axis_names()[self.entropy.axis],
+                format_entropy(self.entropy.permutation),
+                format_entropy(self.entropy.sample)
+            )
+        ).font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &entropy_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let lyapunov_str = match &self.lyapunov {
+            _ if self.lyapunov_job.is_some() => "Lyapunov: computing spectrum...".to_string(),
+            Some(spectrum) => format!(
+                "Lyapunov: λ=({}), D_KY={} (L to recompute)",
+                spectrum.exponents.map(|e| locale.fixed(e, 3)).join(list),
+                locale.fixed(spectrum.kaplan_yorke, 3)
+            ),
+            None => "Lyapunov: -- (L to compute spectrum)".to_string(),
+        };
+        let lyapunov_text = graphics::Text::new(graphics::TextFragment::new(lyapunov_str)
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &lyapunov_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let analysis = self.mode.analysis();
+        if analysis == Some(Analysis::Basin) {
+            let basin_str = match self.basin_attractors {
+                Some(count) if self.basin_job.is_none() => format!(
+                    "Basin map ({} plane): {} attractor(s) found (Shift+B to change plane)",
+                    self.basin_plane.name(),
+                    count
+                ),
+                _ => format!("Basin map ({} plane): computing...", self.basin_plane.name()),
+            };
+            let basin_text = graphics::Text::new(graphics::TextFragment::new(basin_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &basin_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if analysis == Some(Analysis::Ftle) {
+            let ftle_str = match self.ftle_max {
+                Some(max) if self.ftle_job.is_none() => format!(
+                    "FTLE field ({} plane, T={}): max {} (Shift+; to change plane)",
+                    self.basin_plane.name(),
+                    locale.fixed(ftle::FTLE_STEPS as f32 * self.dt, 2),
+                    locale.fixed(max, 3)
+                ),
+                _ => format!("FTLE field ({} plane): computing...", self.basin_plane.name()),
+            };
+            let ftle_text = graphics::Text::new(graphics::TextFragment::new(ftle_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &ftle_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if analysis == Some(Analysis::Escape) {
+            let escape_str = match self.escape_count {
+                Some(count) if self.escape_job.is_none() => format!(
+                    "Escape time ({} plane): {}% of initial conditions escape within {} steps (Shift+' to change plane)",
+                    self.basin_plane.name(),
+                    self.config.locale.fixed(
+                        100.0 * count as f32 / (escape_time::ESCAPE_WIDTH * escape_time::ESCAPE_HEIGHT) as f32,
+                        1
+                    ),
+                    escape_time::MAX_ESCAPE_STEPS
+                ),
+                _ => format!("Escape time ({} plane): computing...", self.basin_plane.name()),
+            };
+            let escape_text = graphics::Text::new(graphics::TextFragment::new(escape_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &escape_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if analysis == Some(Analysis::Scan) && let Some(axes) = &self.scan_axes {
+            let scan_str = format!(
+                "Chaos map: {} {}..{} (→), {} {}..{} (↑){} - click to jump, Shift+I for other pair",
+                axes.x.name, locale.fixed(axes.x.from, 2), locale.fixed(axes.x.to, 2),
+                axes.y.name, locale.fixed(axes.y.from, 2), locale.fixed(axes.y.to, 2),
+                if self.scan_job.is_some() { " computing..." } else { "" }
+            );
+            let scan_text = graphics::Text::new(graphics::TextFragment::new(scan_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &scan_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if self.show_periodic_orbit {
+            let orbit_str = match &self.periodic_orbit {
+                _ if self.periodic_job.is_some() => "Periodic orbit: searching for close returns...".to_string(),
+                Some(orbit) => format!(
+                    "Periodic orbit: T={}, max |Floquet multiplier|={} ({})",
+                    locale.fixed(orbit.period, 3),
+                    locale.fixed(orbit.max_multiplier, 2),
+                    if orbit.max_multiplier > 1.01 { "unstable" } else { "stable" }
+                ),
+                None => "Periodic orbit: no closed orbit found (press , twice to retry)".to_string(),
+            };
+            let orbit_text = graphics::Text::new(graphics::TextFragment::new(orbit_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &orbit_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(symbolic) = &self.symbolic {
+            let symbolic_str = format!(
+                "Symbols (1: x>0 lobe, 0: x<0 lobe{}): {}",
+                if symbolic.echo { ", echoing to stdout" } else { "" },
+                symbolic.sequence()
+            );
+            let symbolic_text = graphics::Text::new(graphics::TextFragment::new(symbolic_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &symbolic_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if self.show_nullclines {
+            let nullcline_text = graphics::Text::new(graphics::TextFragment::new(
+                format!(
+                    "Nullclines at {}={}: red dx=0, green dy=0, blue dz=0",
+                    get_view_preset(self.system_type).depth_axis(self.system_type.axis_names()),
+                    locale.fixed(self.nullcline_slice(), 2)
+                )
+            ).font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &nullcline_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(section) = &self.stroboscope {
+            let strobe_str = if self.system_type.is_driven() {
+                let large = if section.swapped { "section" } else { "attractor" };
+                format!(
+                    "Stroboscopic section: {} samples, one per driving period, {} large (Shift+/ to swap)",
+                    section.len(),
+                    large
+                )
+            } else {
+                "Stroboscopic section: needs a driven system (5: Duffing, 6: Van der Pol)".to_string()
+            };
+            let strobe_text = graphics::Text::new(graphics::TextFragment::new(strobe_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &strobe_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if self.events.rule_count() > 0 {
+            let events_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Events: {} rule(s), fired {} times (t={})",
+                self.events.rule_count(),
+                self.events.fired,
+                self.config.locale.fixed(self.events.clock, 1)
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &events_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(comparison) = &self.comparison {
+            let comparison_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Split view: right runs {} with the split-time parameters (Ctrl+D close, Shift+F11 integrator)",
+                comparison.integrator.name()
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &comparison_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(clamp) = &self.soft_clamp {
+            let clamp_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Soft clamp: outward motion damped beyond {:.0} px from the centre (ring; Ctrl+/ off)",
+                clamp.radius_px
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &clamp_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(overlay) = &self.integrator_overlay {
+            let separation = overlay.separation.map_or("-".to_string(), |s| locale.scientific(s, 2));
+            let overlay_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Integrator error: {} particles vs RK4 twins (cyan), mean separation {} (Ctrl+Q close)",
+                self.integrator.name(),
+                separation
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &overlay_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if !self.groups.is_empty() {
+            let others: Vec<&str> = self.groups.iter().map(|g| g.system_type.name()).collect();
+            let groups_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Also running: {} (Ctrl+[Shift+]1-6 add/remove, Ctrl+Tab to switch focus)",
+                others.join(", ")
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &groups_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        let seed_str = match self.seed_pattern {
+            SeedPattern::RandomBox => "Seeding: random box (Insert to change pattern)".to_string(),
+            pattern => format!(
+                "Seeding: {}, extent {:.0} px (Insert: pattern, PgUp/PgDn: extent)",
+                pattern.name(),
+                self.seed_extent
+            ),
+        };
+        let seed_text = graphics::Text::new(graphics::TextFragment::new(seed_str)
+            .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &seed_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        let emitter_text = graphics::Text::new(graphics::TextFragment::new(format!(
+            "Emitters: {} (right-click to place a {}, End: shape, Delete: remove all)",
+            self.emitters.len(),
+            self.emitter_shape.name()
+        ))
+        .font(font).scale(graphics::PxScale::from(16.0)));
+
+        graphics::draw(
+            ctx,
+            &emitter_text,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: 20.0, y: y_offset })
+                .color(self.theme.text),
+        )?;
+        y_offset += line_height;
+
+        if let Some(timeline) = &self.timeline {
+            let timeline_str = format!(
+                "Timeline: {} / {}, {} keyframes{} (Ctrl+Y key, Ctrl+O play, Ctrl+Shift+Y save, +O clear)",
+                locale.fixed(timeline.elapsed, 1),
+                locale.fixed(timeline.duration(), 1),
+                timeline.keyframe_count(),
+                if timeline.playing { " [PLAYING]" } else { "" }
+            );
+            let timeline_text = graphics::Text::new(graphics::TextFragment::new(timeline_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &timeline_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(camera_path) = &self.camera_path {
+            let state = if self.mode.is_recording() {
+                " [RECORDING]"
+            } else if camera_path.playing {
+                " [PLAYING]"
+            } else {
+                ""
+            };
+            let camera_path_str = format!(
+                "Camera path: {} / {} s, {} keys{} (Ctrl+B record, Ctrl+Shift+B play, Ctrl+E save, +E load)",
+                locale.fixed(camera_path.elapsed, 1),
+                locale.fixed(camera_path.duration(), 1),
+                camera_path.key_count(),
+                state
+            );
+            let camera_path_text = graphics::Text::new(graphics::TextFragment::new(camera_path_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &camera_path_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if !self.lfos.is_empty() {
+            let lfo_str = format!(
+                "LFOs: {} (Ctrl+F on {}, Ctrl+Shift+F next, Ctrl+[/]: rate, Ctrl+Shift+[/]: depth)",
+                self.lfos
+                    .iter()
+                    .map(|lfo| {
+                        let (shape, percent) = (lfo.shape.name(), lfo.depth * 100.0);
+                        let (rate, value) = (locale.fixed(lfo.rate, 3), locale.fixed(lfo.value(), 2));
+                        format!("{} {} {}/t ±{:.0}% → {}", lfo.name, shape, rate, percent, value)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(list),
+                self.selected_parameter_name()
+            );
+            let lfo_text = graphics::Text::new(graphics::TextFragment::new(lfo_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &lfo_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if !self.links.is_empty() {
+            let link_str = format!(
+                "Links: {} (Ctrl+I on {})",
+                self.links
+                    .iter()
+                    .map(|link| format!("{} = {} × {}", link.follower, locale.fixed(link.ratio, 3), link.leader))
+                    .collect::<Vec<_>>()
+                    .join(list),
+                self.selected_parameter_name()
+            );
+            let link_text = graphics::Text::new(graphics::TextFragment::new(link_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &link_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(amplitude) = self.parameter_jitter {
+            let jitter_str = format!(
+                "Parameter jitter: each particle's parameters within ±{}% (Ctrl+J to toggle)",
+                locale.fixed(amplitude * 100.0, 1)
+            );
+            let jitter_text = graphics::Text::new(graphics::TextFragment::new(jitter_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &jitter_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if self.camera != Camera::default() || self.bookmark.is_some() {
+            let bookmark = self
+                .bookmark
+                .and_then(|slot| Some((slot, self.config.bookmarks[slot].as_ref()?)))
+                .map(|(slot, b)| format!(", bookmark F{} {}", slot + 1, b.name))
+                .unwrap_or_default();
+            let camera_str = format!(
+                "Camera: {}x at ({:.0}{}{:.0}){} (Ctrl+0 to reset)",
+                locale.fixed(self.camera.zoom, 2), self.camera.target.x, list, self.camera.target.y, bookmark
+            );
+            let camera_text = graphics::Text::new(graphics::TextFragment::new(camera_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &camera_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(coupling) = &self.coupling {
+            let coupling_str = format!(
+                "Coupling: {}, strength {}, radius {:.0} px (8 to cycle, Shift+8/Ctrl+8 to adjust)",
+                coupling.kind.name(),
+                locale.significant(coupling.strength),
+                coupling.radius_px
+            );
+            let coupling_text = graphics::Text::new(graphics::TextFragment::new(coupling_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &coupling_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(network) = &self.network {
+            let network_str = format!(
+                "Network: {}, coupling {}, sync error {} (7 to cycle, Shift+7/Ctrl+7 to adjust)",
+                network.topology.name(),
+                locale.significant(network.strength),
+                locale.scientific(network.sync_error, 3)
+            );
+            let network_text = graphics::Text::new(graphics::TextFragment::new(network_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &network_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(radius) = self.halo_radius {
+            let halo_str = format!(
+                "Halo sprites: radius {:.0} px (0 to toggle, Ctrl+-/Ctrl+= to resize)",
+                radius
+            );
+            let halo_text = graphics::Text::new(graphics::TextFragment::new(halo_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &halo_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(recorder) = &self.trajectory {
+            let trajectory_str = format!(
+                "Recording trajectory: {} samples, t = {}{} (Ctrl+S: CSV, Ctrl+Shift+S: JSON)",
+                recorder.len(),
+                self.config.locale.fixed(recorder.elapsed, 1),
+                if recorder.is_full() { " [full]" } else { "" }
+            );
+            let trajectory_text = graphics::Text::new(graphics::TextFragment::new(trajectory_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &trajectory_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        let pinned = self.particles.iter().filter(|p| p.pinned).count();
+        if pinned > 0 {
+            let pin_str = format!(
+                "Pinned: {} (Shift+click or Ctrl+P on the leader to unpin, Ctrl+U: release all)",
+                pinned
+            );
+            let pin_text = graphics::Text::new(graphics::TextFragment::new(pin_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &pin_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.text),
+            )?;
+            y_offset += line_height;
+        }
+
+        if let Some(leader) = &self.leader {
+            let leader_str = format!(
+                "Leader: ({}), speed {}, x > 0 for {} of steps (Ctrl+N/M){}",
+                leader.state.map(|v| locale.fixed(v, 2)).join(list),
+                locale.fixed(leader.speed, 2),
+                leader.positive_lobe_share().map_or("--".to_string(), |share| format!("{:.0}%", share * 100.0)),
+                if self.follow_leader { ", camera following (Ctrl+G)" } else { "" }
+            );
+            let leader_text = graphics::Text::new(graphics::TextFragment::new(leader_str)
+                .font(font).scale(graphics::PxScale::from(16.0)));
+
+            graphics::draw(
+                ctx,
+                &leader_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(self.theme.palette.accent(Accent::Leader)),
+            )?;
+            y_offset += line_height;
+        }
 
-        let param_text = graphics::Text::new(graphics::TextFragment::new(param_text_str)
+        let particles_str = match &self.count_entry {
+            Some(digits) => format!("Particles: {}_ (type a count, Enter to apply, Esc to cancel)", digits),
+            None => format!(
+                "Particles: {} (C/V, Enter to type), lifetime: {} (Home), shape: {} (9)",
+                self.particle_count,
+                self.lifetime.map_or("unlimited".to_string(), |l| format!("{:.0}", l)),
+                self.particle_shape.name()
+            ),
+        };
+        let particles_text = graphics::Text::new(graphics::TextFragment::new(particles_str)
             .font(font).scale(graphics::PxScale::from(16.0)));
 
         graphics::draw(
             ctx,
-            &param_text,
+            &particles_text,
             graphics::DrawParam::default()
                 .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
+                .color(self.theme.text),
         )?;
         y_offset += line_height;
 
-        let time_text = graphics::Text::new(graphics::TextFragment::new(
-            format!("Time Scale: {:.2}x (Z/X to adjust)", self.time_scale)
+        let trail_mode_name = match self.trail_mode {
+            TrailMode::Lines if self.ribbon_trails => "Ribbons",
+            TrailMode::Lines => "Lines",
+            TrailMode::Feedback => "Feedback",
+        };
+
+        let trail_text = graphics::Text::new(graphics::TextFragment::new(
+            format!(
+                "Trails: {} (T), Mode: {} (G), Gradient: {} (Shift+G), Width: {} (Shift+T)",
+                if self.trail_enabled { "Enabled" } else { "Disabled" },
+                trail_mode_name,
+                self.trail_gradient.name(),
+                self.trail_width.name()
+            )
         ).font(font).scale(graphics::PxScale::from(16.0)));
 
         graphics::draw(
             ctx,
-            &time_text,
+            &trail_text,
             graphics::DrawParam::default()
                 .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
+                .color(self.theme.text),
         )?;
         y_offset += line_height;
 
-        let particles_text = graphics::Text::new(graphics::TextFragment::new(
-            format!("Particles: {} (C/V to adjust)", self.particle_count)
+        let palette_text = graphics::Text::new(graphics::TextFragment::new(
+            format!("Theme: {} (Ctrl+R), palette: {} (Ctrl+A)", self.theme.name, self.theme.palette.name())
         ).font(font).scale(graphics::PxScale::from(16.0)));
 
         graphics::draw(
             ctx,
-            &particles_text,
+            &palette_text,
             graphics::DrawParam::default()
                 .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
+                .color(self.theme.text),
         )?;
         y_offset += line_height;
 
-        let trail_text = graphics::Text::new(graphics::TextFragment::new(
-            format!("Trails: {} (T to toggle)", if self.trail_enabled { "Enabled" } else { "Disabled" })
-        ).font(font).scale(graphics::PxScale::from(16.0)));
+        if self.non_finite_particles > 0 || self.broken_trails > 0 {
+            let warning_text = graphics::Text::new(graphics::TextFragment::new(format!(
+                "Warning: {} particle(s) diverged to NaN/inf, {} trail(s) not drawn (Backspace to reset)",
+                self.non_finite_particles, self.broken_trails
+            ))
+            .font(font).scale(graphics::PxScale::from(16.0)));
 
-        graphics::draw(
-            ctx,
-            &trail_text,
-            graphics::DrawParam::default()
-                .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
-        )?;
-        y_offset += line_height;
+            graphics::draw(
+                ctx,
+                &warning_text,
+                graphics::DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: y_offset })
+                    .color(graphics::Color::new(1.0, 0.4, 0.3, 1.0)),
+            )?;
+            y_offset += line_height;
+        }
 
         let help_text = graphics::Text::new(graphics::TextFragment::new(
-            "Press H to hide UI, R to reset particles, ESC to quit"
+            "H: hide UI, Ctrl+?: all keys, Space: pause, Esc: quit"
         ).font(font).scale(graphics::PxScale::from(16.0)));
 
         graphics::draw(
@@ -233,7 +3956,7 @@ impl MainState {
             &help_text,
             graphics::DrawParam::default()
                 .dest(Point2 { x: 20.0, y: y_offset })
-                .color(graphics::Color::WHITE),
+                .color(self.theme.text),
         )?;
 
         Ok(())
@@ -242,154 +3965,763 @@ impl MainState {
 
 impl event::EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
-        self.update_particles(ctx);
+        self.reload_changed_files();
+        self.sync_cursor(ctx);
+        // Throttled here, ahead of the modes that return early below.
+        let throttled = self.is_idle() && self.config.idle_throttle;
+        if throttled {
+            timer::sleep(Duration::from_secs_f32(1.0 / self.config.idle_fps as f32));
+        }
+        match &mut self.mode {
+            // Nothing runs behind the start screen or the pendulums.
+            Mode::Menu(_) => return Ok(()),
+            Mode::Pendulum(pendulum) => {
+                if !throttled {
+                    pendulum.step(self.dt * self.time_scale);
+                }
+                return Ok(());
+            }
+            Mode::Magnets(magnets) => {
+                magnets.poll_map();
+                if !throttled {
+                    magnets.step(self.dt * self.time_scale);
+                }
+                return Ok(());
+            }
+            Mode::ThreeBody(three_body) => {
+                if !throttled {
+                    three_body.step(self.dt * self.time_scale);
+                }
+                return Ok(());
+            }
+            Mode::Tour(tour) => {
+                if let Some(scene) = tour.advance().map(|step| step.scene.clone()) {
+                    self.show_scene(&scene);
+                }
+            }
+            Mode::Analyze(analysis) => {
+                let analysis = *analysis;
+                self.poll_analysis(analysis);
+            }
+            Mode::Simulate | Mode::Record => {}
+        }
+        self.poll_lyapunov_spectrum();
+        self.poll_correlation_dimension();
+        self.poll_spectrum();
+        self.export_detached_panels();
+        let snapshot = self.tutorial_snapshot();
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            tutorial.observe(snapshot);
+        }
+        if let Some(announcer) = self.announcer.as_mut()
+            && let Err(e) = announcer.observe(self.system_type, &self.parameters, self.paused, self.config.locale)
+        {
+            self.toasts.warn(e);
+        }
+        if let Some(haptics) = self.haptics.as_mut() {
+            let crossings = self.stroboscope.as_ref().map_or(0, |section| section.crossings);
+            haptics.update(self.leader.as_ref().map(|leader| leader.lobe_switches), crossings);
+        }
+        if self.transition.as_ref().is_some_and(SystemTransition::is_finished) {
+            self.transition = None;
+        }
+        if self.screensaver.is_none()
+            && !self.mode.is_tour()
+            && self.tutorial.is_none()
+            && let Some(idle) = self.config.screensaver_idle
+            && self.focused
+            && !self.minimized
+            && self.last_input.elapsed().as_secs_f32() >= idle
+        {
+            self.start_screensaver(false);
+        }
+        let interval = self.config.screensaver_interval;
+        if let Some(scene) = self.screensaver.as_mut().and_then(|s| s.advance(interval)).cloned() {
+            self.apply_preset(&scene);
+        }
+        // Recording pauses while scrubbing so the restored values do not become the latest,
+        // and while the screensaver runs so its scenes do not fill the history.
+        if self.history.cursor.is_none() && self.screensaver.is_none() {
+            self.history.record(&self.parameters);
+        }
+        self.poll_periodic_orbit();
+        self.poll_regime_search();
+        for problem in self.events.take_problems() {
+            self.toasts.warn(problem);
+        }
+        if throttled {
+            return Ok(());
+        }
+        if self.config.watchdog {
+            self.run_watchdog(timer::delta(ctx).as_secs_f32());
+        }
+        // A pad left over from another system starts over on this one's first pair.
+        if let Some(pad) = &self.xy_pad
+            && !get_parameter_names(self.system_type).contains(&pad.axes.x.name)
+        {
+            self.xy_pad = Some(XyPad::new(self.system_type, &self.parameters, 0));
+        }
+        // While rewound the cloud holds still on the snapshot shown.
+        if !self.paused && self.rewind.cursor.is_none() {
+            let before = self.parameters;
+            if let Some(drift) = self.drift.as_mut() {
+                if get_parameter_names(self.system_type).contains(&drift.axis.name) {
+                    drift.advance(&mut self.parameters, self.dt * self.time_scale);
+                } else {
+                    self.drift = None;
+                }
+            }
+            // LFOs on another system's parameters hand them back at their centre values.
+            let names = get_parameter_names(self.system_type);
+            for lfo in self.lfos.iter().filter(|lfo| !names.contains(&lfo.name)) {
+                self.parameters.set(lfo.name, lfo.center);
+            }
+            self.lfos.retain(|lfo| names.contains(&lfo.name));
+            let mut rng = rand::thread_rng();
+            for lfo in self.lfos.iter_mut() {
+                lfo.advance(&mut self.parameters, self.dt * self.time_scale, &mut rng);
+            }
+            if let Some(timeline) = self.timeline.as_mut() {
+                timeline.advance(&mut self.parameters, self.dt * self.time_scale);
+            }
+            self.enforce_parameter_links(&before);
+            self.assign_jitter();
+            // Coupling reads neighbours' current states, which the worker's in-flight step
+            // would overwrite, so coupled ensembles always step on the main thread.
+            if self.config.double_buffer && self.coupling.is_none() && self.network.is_none() {
+                self.update_particles_double_buffered();
+            } else {
+                self.update_particles(ctx);
+            }
+            self.rewind.record(&self.particles, &self.parameters, self.dt * self.time_scale);
+            self.apply_coupling();
+            if let Some(network) = self.network.as_mut() {
+                network.apply(&mut self.particles, self.system_type, self.dt * self.time_scale);
+            }
+            self.respawn_expired();
+            self.run_emitters();
+            let record_trail = self.trail_mode == TrailMode::Lines;
+            for group in self.groups.iter_mut() {
+                group.step(self.dt * self.time_scale, self.integrator, self.noise_intensity, record_trail);
+            }
+            if let Some(comparison) = self.comparison.as_mut() {
+                comparison.step(&self.particles, self.dt * self.time_scale, self.noise_intensity, record_trail);
+            }
+            if let Some(overlay) = self.integrator_overlay.as_mut() {
+                overlay.step(&mut self.particles, self.system_type, &self.parameters, self.dt * self.time_scale);
+            }
+            if self.density_coloring {
+                self.refresh_spatial_hash();
+            }
+            self.record_reference_sample();
+            if let Some(recorder) = self.trajectory.as_mut()
+                && !recorder.record(&self.particles, self.dt * self.time_scale)
+            {
+                self.trajectory = None;
+            }
+            if let Some(leader) = self.leader.as_mut()
+                && !leader.record(&self.particles, self.system_type, &self.parameters)
+            {
+                self.leader = None;
+            }
+            if let Some(measure) = self.measure.as_mut() {
+                measure.record(&self.particles);
+            }
+            if let Some(demo) = self.sensitivity.as_mut()
+                && !demo.record(&self.particles, self.dt * self.time_scale)
+            {
+                self.sensitivity = None;
+            }
+        }
+        if let Some(camera_path) = self.camera_path.as_mut() {
+            camera_path.advance(&mut self.camera, timer::delta(ctx).as_secs_f32(), self.mode.is_recording());
+        }
+        if self.follow_leader
+            && let Some(particle) = self.leader.as_ref().and_then(|l| self.particles.get(l.id))
+        {
+            let at = particle.get_screen_pos(self.system_type);
+            self.camera.follow(at, timer::delta(ctx).as_secs_f32());
+        }
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        graphics::clear(ctx, graphics::Color::new(0.1, 0.1, 0.15, 1.0));
+        graphics::clear(ctx, self.theme.background);
+        if let Mode::Menu(menu) = &self.mode {
+            menu.draw(ctx, &self.theme)?;
+            self.toasts.draw(ctx, &self.theme)?;
+            return self.finish_frame(ctx);
+        }
+        if let Mode::Pendulum(pendulum) = &self.mode {
+            pendulum.draw(ctx, &self.theme, self.trail_gradient, self.trail_width, self.config.locale)?;
+            self.toasts.draw(ctx, &self.theme)?;
+            return self.finish_frame(ctx);
+        }
+        if let Mode::Magnets(magnets) = &mut self.mode {
+            magnets.draw(ctx, &self.theme, self.trail_gradient, self.trail_width, self.config.locale)?;
+            self.toasts.draw(ctx, &self.theme)?;
+            return self.finish_frame(ctx);
+        }
+        if let Mode::ThreeBody(three_body) = &self.mode {
+            three_body.draw(ctx, &self.theme, self.trail_gradient, self.trail_width, self.config.locale)?;
+            self.toasts.draw(ctx, &self.theme)?;
+            return self.finish_frame(ctx);
+        }
         let current_system_type = self.system_type;
 
-        if self.trail_enabled {
-            for particle in &self.particles {
-                if particle.trail.len() < 2 {
-                    continue;
+        if let Some(map) = self.basin_map.take() {
+            let mut image = graphics::Image::from_rgba8(ctx, map.width, map.height, &map.rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            self.basin_image = Some(image);
+        }
+        if let Some(field) = self.ftle_field.take() {
+            let mut image = graphics::Image::from_rgba8(ctx, field.width, field.height, &field.rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            self.ftle_image = Some(image);
+        }
+        if let Some(map) = self.escape_map.take() {
+            let mut image = graphics::Image::from_rgba8(ctx, map.width, map.height, &map.rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            self.escape_image = Some(image);
+        }
+        if let Some(scan) = self.scan_result.take() {
+            let mut image = graphics::Image::from_rgba8(ctx, scan.width, scan.height, &scan.rgba)?;
+            image.set_filter(graphics::FilterMode::Nearest);
+            self.scan_image = Some(image);
+        }
+        let analysis = self.mode.analysis();
+        if analysis == Some(Analysis::Scan) && let Some(image) = &self.scan_image {
+            graphics::draw(
+                ctx,
+                image,
+                graphics::DrawParam::default().scale([
+                    SCREEN_WIDTH / image.width() as f32,
+                    SCREEN_HEIGHT / image.height() as f32,
+                ]),
+            )?;
+        }
+
+        if let Some(screensaver) = self.screensaver.as_mut() {
+            screensaver.begin(ctx, self.theme.background)?;
+        } else if let Some(section) = self.stroboscope.as_mut() {
+            section.begin(ctx, self.theme.background)?;
+        }
+        // Everything from here to the pinned markers lives in the projected view and moves
+        // with the camera; the scan image above is parameter space and stays put.
+        if let Some(transition) = &self.transition {
+            self.view_camera(transition.outgoing_zoom()).apply(ctx)?;
+            transition.draw(ctx)?;
+        }
+        let transition_zoom = self.transition.as_ref().map_or(1.0, SystemTransition::zoom);
+        let view_camera = self.view_camera(transition_zoom);
+        view_camera.apply(ctx)?;
+        if analysis == Some(Analysis::Basin) && let Some(image) = &self.basin_image {
+            graphics::draw(
+                ctx,
+                image,
+                graphics::DrawParam::default().scale([
+                    SCREEN_WIDTH / image.width() as f32,
+                    SCREEN_HEIGHT / image.height() as f32,
+                ]),
+            )?;
+        }
+        if analysis == Some(Analysis::Ftle) && let Some(image) = &self.ftle_image {
+            graphics::draw(
+                ctx,
+                image,
+                graphics::DrawParam::default().scale([
+                    SCREEN_WIDTH / image.width() as f32,
+                    SCREEN_HEIGHT / image.height() as f32,
+                ]),
+            )?;
+        }
+        if analysis == Some(Analysis::Escape) && let Some(image) = &self.escape_image {
+            graphics::draw(
+                ctx,
+                image,
+                graphics::DrawParam::default().scale([
+                    SCREEN_WIDTH / image.width() as f32,
+                    SCREEN_HEIGHT / image.height() as f32,
+                ]),
+            )?;
+        }
+
+        if self.trail_enabled && self.trail_mode == TrailMode::Feedback {
+            if self.feedback_trails.is_none() {
+                self.feedback_trails = Some(FeedbackTrails::new(ctx)?);
+            }
+            if let Some(feedback) = self.feedback_trails.as_mut() {
+                // The canvas holds window pixels, so it is copied without the camera.
+                camera::reset(ctx)?;
+                feedback.accumulate(ctx, &self.particles, current_system_type, view_camera.view(), self.theme.palette)?;
+                feedback.draw(ctx)?;
+                if let Some(screensaver) = self.screensaver.as_mut() {
+                    screensaver.redirect(ctx)?;
+                } else if let Some(section) = self.stroboscope.as_mut() {
+                    section.redirect(ctx)?;
                 }
+                view_camera.apply(ctx)?;
+            }
+        }
+
+        // Problems are announced once when they start, not on every frame they last.
+        let broken_before = self.broken_trails;
+        self.broken_trails = 0;
+        if self.trail_enabled && self.trail_mode == TrailMode::Lines {
+            let mut gradient_trails = graphics::MeshBuilder::new();
+            let ribbon_view = if self.ribbon_trails {
+                RibbonView::from_particles(&self.particles, current_system_type)
+            } else {
+                None
+            };
+            let mut ordered: Vec<&Particle> = self.particles.iter().filter(|p| p.trail.len() >= 2).collect();
+            if ribbon_view.is_some() {
+                // Far to near, so nearer ribbons are drawn over farther ones.
+                let view = get_view_preset(current_system_type);
+                ordered.sort_by(|a, b| view.depth([a.x, a.y, a.z]).total_cmp(&view.depth([b.x, b.y, b.z])));
+            }
+            for particle in ordered {
                 let points: Vec<Point2<f32>> = particle.trail.iter().copied().collect();
 
-                match graphics::Mesh::new_line(ctx, &points, 1.0, particle.color) {
+                if self.symbolic.is_some() {
+                    self.draw_symbolic_trail(ctx, &points)?;
+                    continue;
+                }
+
+                let mut trail_color = self.theme.palette.particle_color(particle.color);
+                trail_color.a *= self.fade(particle);
+                if let Some(view) = &ribbon_view {
+                    let depths: Vec<f32> = particle.trail_depth.iter().copied().collect();
+                    ribbon::append_ribbon(
+                        &mut gradient_trails,
+                        &points,
+                        &depths,
+                        view,
+                        trail_color,
+                        self.trail_gradient,
+                        self.theme.trail_alpha,
+                    )?;
+                    continue;
+                }
+                if self.trail_gradient != TrailGradient::Solid
+                    || self.trail_width != TrailWidth::Constant
+                    || self.theme.trail_alpha != TrailAlpha::FLAT
+                {
+                    trail_gradient::append_trail(
+                        &mut gradient_trails,
+                        &points,
+                        1.0,
+                        trail_color,
+                        self.trail_gradient,
+                        self.trail_width,
+                        self.theme.trail_alpha,
+                    )?;
+                    continue;
+                }
+                match graphics::Mesh::new_line(ctx, &points, 1.0, trail_color) {
                     Ok(line) => {
                         graphics::draw(ctx, &line, graphics::DrawParam::default())?;
                     }
-                    Err(e) => {
-                        eprintln!("Failed to create trail mesh: {:?}. Points: {:?}", e, points.len());
-                    }
+                    Err(_) => self.broken_trails += 1,
                 }
             }
+            if let Ok(mesh) = gradient_trails.build(ctx) {
+                graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+            }
+        }
+        if broken_before == 0 && self.broken_trails > 0 {
+            let broken = self.broken_trails;
+            self.toasts.warn(format!("{} trail(s) could not be built into a mesh and were skipped", broken));
+        }
+
+        if let Some(network) = &self.network {
+            network.draw_edges(ctx, &self.particles, current_system_type)?;
         }
 
-        for particle in &self.particles {
+        if self.halo_radius.is_some() && self.halo_sprites.is_none() {
+            self.halo_sprites = Some(HaloSprites::new(ctx)?);
+        }
+        self.ensure_particle_sprite(ctx);
+        let mut particle_meshes = graphics::MeshBuilder::new();
+        let mut any_particle_mesh = false;
+        let diverged_before = self.non_finite_particles;
+        self.non_finite_particles = 0;
+        let color_scale = self.color_scale();
+        let mut color_values = Vec::new();
+        for (id, particle) in self.particles.iter_with_ids() {
+            if !particle.is_finite() {
+                self.non_finite_particles += 1;
+                continue;
+            }
             let screen_pos = particle.get_screen_pos(current_system_type);
-            let circle = graphics::Mesh::new_circle(
+            let (radius, color, value) = self.particle_style(id, particle, color_scale);
+            color_values.extend(value);
+            if let Some(halo_radius) = self.halo_radius
+                && let Some(sprites) = self.halo_sprites.as_mut()
+            {
+                sprites.add(screen_pos, halo_radius * radius / 2.0, color);
+                continue;
+            }
+            if self.particle_shape == ParticleShape::Sprite
+                && let Some(sprite) = self.particle_sprite.as_mut()
+            {
+                sprite.add(screen_pos, radius, color);
+                continue;
+            }
+            shapes::add_shape(&mut particle_meshes, self.particle_shape, screen_pos, radius, color)?;
+            any_particle_mesh = true;
+        }
+
+        if any_particle_mesh {
+            let mesh = particle_meshes.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        // The range follows what was drawn this frame and applies from the next one.
+        if let Some(scale) = color_scale {
+            self.color_range.observe(scale, &color_values);
+        }
+        if diverged_before == 0 && self.non_finite_particles > 0 {
+            self.toasts.warn(format!(
+                "{} particle(s) diverged to NaN/inf and are not drawn (Backspace to reset)",
+                self.non_finite_particles
+            ));
+        }
+        if self.halo_radius.is_none()
+            && self.particle_shape == ParticleShape::Sprite
+            && let Some(sprite) = self.particle_sprite.as_mut()
+        {
+            sprite.flush(ctx)?;
+        }
+
+        if self.halo_radius.is_some()
+            && let Some(sprites) = self.halo_sprites.as_mut()
+        {
+            sprites.flush(ctx)?;
+        }
+
+        for group in &self.groups {
+            group.draw(
                 ctx,
-                graphics::DrawMode::fill(),
-                screen_pos,
-                2.0,
-                0.1,
-                particle.color,
+                self.trail_enabled && self.trail_mode == TrailMode::Lines,
+                self.trail_gradient,
+                self.trail_width,
+                &self.theme,
+                self.particle_sprite.as_mut(),
+            )?;
+        }
+
+        if !self.emitters.is_empty() {
+            self.draw_emitters(ctx)?;
+        }
+
+        if self.system_type.is_population() && self.screensaver.is_none() {
+            self.draw_octant_axes(ctx)?;
+        }
+
+        if self.show_nullclines {
+            let segments = nullclines::compute(current_system_type, &self.parameters, self.nullcline_slice());
+            nullclines::draw(ctx, &segments)?;
+        }
+
+        if self.show_fixed_points {
+            self.refresh_fixed_points();
+            self.draw_fixed_points(ctx)?;
+        }
+
+        if self.show_periodic_orbit && let Some(orbit) = &self.periodic_orbit {
+            self.draw_periodic_orbit(ctx, orbit)?;
+        }
+
+        if let Some(demo) = &self.sensitivity {
+            self.draw_sensitivity_pair(ctx, demo)?;
+        }
+
+        if let Some(recorder) = &self.trajectory {
+            self.draw_followed_particle(ctx, recorder)?;
+        }
+
+        self.draw_pinned_markers(ctx)?;
+        if let Some(clamp) = &self.soft_clamp {
+            clamp.draw(ctx, &self.theme)?;
+        }
+        if let Some(overlay) = &self.integrator_overlay {
+            overlay.draw(ctx, &self.particles, self.system_type)?;
+        }
+        camera::reset(ctx)?;
+
+        // The screensaver shows the attractor alone: no panels, no HUD.
+        if let Some(screensaver) = &self.screensaver {
+            screensaver.draw(ctx)?;
+            return self.finish_frame(ctx);
+        }
+
+        if let Some(section) = &self.stroboscope {
+            section.draw(ctx, get_scale_factor(current_system_type), view_camera.view(), &self.theme)?;
+        }
+
+        if let Some(comparison) = self.comparison.as_mut() {
+            let view = Camera { target: self.camera.target, zoom: view_camera.zoom }.view();
+            let trails = self.trail_enabled && self.trail_mode == TrailMode::Lines;
+            comparison.draw(ctx, view, trails, self.trail_gradient, self.trail_width, &self.theme)?;
+            let title = match comparison.separation {
+                Some(separation) => {
+                    format!("log10 mean twin separation vs t   now {}", self.config.locale.scientific(separation, 2))
+                }
+                None => "log10 mean twin separation vs t".to_string(),
+            };
+            PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH / 2.0 + 20.0, SCREEN_HEIGHT - 160.0, 300.0, 140.0),
+                title,
+                series: Cow::Borrowed(&comparison.samples),
+                overlay: None,
+                color: graphics::Color::new(1.0, 0.8, 0.4, 1.0),
+            }
+            .draw(ctx, &self.theme)?;
+        }
+
+        if let Some(overlay) = &self.integrator_overlay {
+            PlotPanel {
+                area: graphics::Rect::new(SCREEN_WIDTH - 320.0, SCREEN_HEIGHT - 160.0, 300.0, 140.0),
+                title: format!("log10 mean {}-RK4 separation vs t", self.integrator.name()),
+                series: Cow::Borrowed(&overlay.samples),
+                overlay: None,
+                color: integrator_overlay::TWIN_COLOR,
+            }
+            .draw(ctx, &self.theme)?;
+        }
+
+        if let Some(start) = self.zoom_box {
+            let at = mouse::position(ctx);
+            let (w, h) = ((at.x - start.x).abs(), (at.y - start.y).abs());
+            let area = graphics::Rect::new(start.x.min(at.x), start.y.min(at.y), w, h);
+            let outline = graphics::Mesh::new_rectangle(
+                ctx,
+                graphics::DrawMode::stroke(1.0),
+                area,
+                graphics::Color::new(1.0, 1.0, 1.0, 0.8),
             )?;
-            graphics::draw(ctx, &circle, graphics::DrawParam::default())?;
+            graphics::draw(ctx, &outline, graphics::DrawParam::default())?;
+        }
+
+        if self.spawn_mode {
+            self.draw_spawn_preview(ctx)?;
+        }
+
+        if let Some(pad) = &self.xy_pad {
+            pad.draw(ctx, &self.parameters, &self.theme, self.config.locale)?;
+        }
+
+        if let Some(demo) = &self.sensitivity {
+            self.draw_sensitivity_demo(ctx, demo)?;
+        }
+
+        if self.detached_panels.is_none() {
+            for panel in self.analysis_panels() {
+                panel.draw(ctx, &self.theme)?;
+            }
+        }
+
+        if self.show_regimes {
+            self.draw_regime_gallery(ctx)?;
+        }
+
+        if let Some(drift) = &self.drift {
+            self.draw_drift_progress(ctx, drift)?;
+        }
+
+        if self.rewind.cursor.is_some() {
+            self.rewind.draw(ctx, self.config.locale)?;
+        }
+        if self.history.cursor.is_some() {
+            self.history.draw(ctx)?;
+        }
+
+        if let Some(measure) = &self.measure {
+            self.draw_measure_marginals(ctx, measure)?;
+        }
+
+        if let Mode::Tour(tour) = &self.mode {
+            tour.draw(ctx, &self.parameters, self.config.locale)?;
         }
 
         if self.show_ui {
             self.draw_ui(ctx)?;
         }
+        if let Some(announcer) = &self.announcer {
+            announcer.draw(ctx, self.config.locale)?;
+        }
+        if let Some(tutorial) = &self.tutorial {
+            tutorial.draw(ctx, &self.theme)?;
+        }
+        if let Some(reference) = &self.key_reference {
+            reference.draw(ctx, &self.theme)?;
+        }
+        self.toasts.draw(ctx, &self.theme)?;
 
-        graphics::present(ctx)?;
-        Ok(())
+        self.finish_frame(ctx)
     }
 
     fn key_down_event(
         &mut self,
         ctx: &mut Context,
         keycode: KeyCode,
-        _keymod: KeyMods,
+        keymod: KeyMods,
         _repeat: bool,
     ) {
-        match keycode {
-            KeyCode::Key1 => {
-                if self.system_type != SystemType::Lorenz {
-                    self.system_type = SystemType::Lorenz;
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::Key2 => {
-                if self.system_type != SystemType::Rossler {
-                    self.system_type = SystemType::Rossler;
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::Key3 => {
-                if self.system_type != SystemType::Aizawa {
-                    self.system_type = SystemType::Aizawa;
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::Key4 => {
-                if self.system_type != SystemType::ChenLee {
-                    self.system_type = SystemType::ChenLee;
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::Q => match self.system_type {
-                SystemType::Lorenz => self.parameters.sigma += 0.1,
-                SystemType::Rossler => self.parameters.a += 0.01,
-                SystemType::Aizawa => self.parameters.alpha += 0.01,
-                SystemType::ChenLee => self.parameters.p += 0.1,
-            },
-            KeyCode::A => match self.system_type {
-                SystemType::Lorenz => self.parameters.sigma -= 0.1,
-                SystemType::Rossler => self.parameters.a -= 0.01,
-                SystemType::Aizawa => self.parameters.alpha -= 0.01,
-                SystemType::ChenLee => self.parameters.p -= 0.1,
-            },
-            KeyCode::W => match self.system_type {
-                SystemType::Lorenz => self.parameters.rho += 0.1,
-                SystemType::Rossler => self.parameters.b += 0.01,
-                SystemType::Aizawa => self.parameters.gamma += 0.01,
-                SystemType::ChenLee => self.parameters.q += 0.1,
-            },
-            KeyCode::S => match self.system_type {
-                SystemType::Lorenz => self.parameters.rho -= 0.1,
-                SystemType::Rossler => self.parameters.b -= 0.01,
-                SystemType::Aizawa => self.parameters.gamma -= 0.01,
-                SystemType::ChenLee => self.parameters.q -= 0.1,
-            },
-            KeyCode::E => match self.system_type {
-                SystemType::Lorenz => self.parameters.beta += 0.01,
-                SystemType::Rossler => self.parameters.c += 0.01,
-                SystemType::Aizawa => self.parameters.delta += 0.01,
-                SystemType::ChenLee => self.parameters.r += 0.01,
-            },
-            KeyCode::D => match self.system_type {
-                SystemType::Lorenz => self.parameters.beta -= 0.01,
-                SystemType::Rossler => self.parameters.c -= 0.01,
-                SystemType::Aizawa => self.parameters.delta -= 0.01,
-                SystemType::ChenLee => self.parameters.r -= 0.01,
-            },
-            KeyCode::R => {
-                if self.system_type == SystemType::Aizawa {
-                    self.parameters.epsilon += 0.01;
-                } else {
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::F => {
-                if self.system_type == SystemType::Aizawa {
-                    self.parameters.epsilon -= 0.01;
-                } else {
-                    self.initialize_particles();
-                }
-            }
-            KeyCode::Back => {
-                self.initialize_particles();
+        if self.note_input(ctx) {
+            return;
+        }
+        if self.mode.is_menu() {
+            self.start_menu_key(ctx, keycode, keymod);
+            return;
+        }
+        // The tutorial only ever speaks about the attractor scene.
+        if !self.mode.is_mechanics() && self.tutorial_key(keycode) {
+            return;
+        }
+        if self.count_entry.is_some() {
+            self.edit_count_entry(keycode);
+            return;
+        }
+        let context = KeyContext {
+            system_type: self.system_type,
+            mechanics: self.mode.mechanics(),
+            scrubbing: self.history.cursor.is_some(),
+            rewinding: self.rewind.cursor.is_some(),
+            key_reference: self.key_reference.is_some(),
+            key_parameters: get_key_parameters(self.system_type).len(),
+        };
+        if let Some(command) = command::from_key(keycode, keymod, &context) {
+            self.apply(ctx, command);
+        }
+    }
+
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        if self.note_input(ctx) {
+            return;
+        }
+        if let Mode::Menu(menu) = &self.mode {
+            if button == MouseButton::Left
+                && let Some(index) = menu.entry_at(x, y)
+            {
+                self.choose_start(index);
             }
-            KeyCode::Z => self.time_scale = (self.time_scale + 0.1).min(5.0),
-            KeyCode::X => self.time_scale = (self.time_scale - 0.1).max(0.1),
-            KeyCode::C => {
-                self.particle_count = (self.particle_count + 5).min(200);
-                self.initialize_particles();
+            return;
+        }
+        if let Mode::Magnets(magnets) = &mut self.mode {
+            if button == MouseButton::Left {
+                magnets.release(x, y);
             }
-            KeyCode::V => {
-                self.particle_count = (self.particle_count.saturating_sub(5)).max(5);
-                if self.particle_count > 0 {
-                    self.initialize_particles();
-                }
+            return;
+        }
+        if self.mode.is_mechanics() {
+            return;
+        }
+        let bar = ParameterHistory::bar();
+        let rewind_bar = RewindBuffer::bar();
+        if button == MouseButton::Left
+            && let Some(pad) = self.xy_pad.as_mut()
+            && XyPad::area().contains(Point2 { x, y })
+        {
+            pad.dragging = true;
+            let (x, y) = XyPad::fraction(x, y);
+            self.apply(ctx, Command::SetPadPoint { x, y });
+        } else if button == MouseButton::Left
+            && self.rewind.cursor.is_some()
+            && graphics::Rect::new(rewind_bar.x, rewind_bar.y - 5.0, rewind_bar.w, rewind_bar.h + 10.0)
+                .contains(Point2 { x, y })
+        {
+            self.apply(ctx, Command::SeekRewind((x - rewind_bar.x) / rewind_bar.w));
+        } else if button == MouseButton::Left
+            && self.history.cursor.is_some()
+            && graphics::Rect::new(bar.x, bar.y - 5.0, bar.w, bar.h + 10.0).contains(Point2 { x, y })
+        {
+            self.apply(ctx, Command::SeekHistory((x - bar.x) / bar.w));
+        } else if button == MouseButton::Left && keyboard::is_mod_active(ctx, KeyMods::CTRL) {
+            self.zoom_box = Some(Point2 { x, y });
+        } else if button == MouseButton::Left
+            && self.mode.analysis() == Some(Analysis::Scan)
+            && self.scan_axes.is_some()
+        {
+            self.apply(ctx, Command::PickScanPoint { x: x / SCREEN_WIDTH, y: y / SCREEN_HEIGHT });
+        } else if button == MouseButton::Left && keyboard::is_mod_active(ctx, KeyMods::SHIFT) {
+            let at = self.view_camera(1.0).unproject(x, y);
+            if let Some(id) = self.particle_at(at.x, at.y) {
+                self.apply(ctx, Command::TogglePin(id));
             }
-            KeyCode::T => self.trail_enabled = !self.trail_enabled,
-            KeyCode::H => self.show_ui = !self.show_ui,
-            KeyCode::Escape => event::quit(ctx),
-            _ => (),
+        } else if button == MouseButton::Left && self.spawn_mode {
+            let at = self.view_camera(1.0).unproject(x, y);
+            self.apply(ctx, Command::SpawnParticle { x: at.x, y: at.y });
+        } else if button == MouseButton::Left {
+            let at = self.view_camera(1.0).unproject(x, y);
+            self.apply(ctx, Command::ToggleTrajectory { x: at.x, y: at.y });
+        } else if button == MouseButton::Right {
+            let at = self.view_camera(1.0).unproject(x, y);
+            self.apply(ctx, Command::AddEmitter { x: at.x, y: at.y });
+        }
+    }
+
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        if button != MouseButton::Left {
+            return;
+        }
+        if let Some(pad) = self.xy_pad.as_mut() {
+            pad.dragging = false;
+        }
+        let Some(start) = self.zoom_box.take() else {
+            return;
+        };
+        if (x - start.x).abs() >= camera::MIN_BOX_PX && (y - start.y).abs() >= camera::MIN_BOX_PX {
+            let view = self.view_camera(1.0);
+            let (a, b) = (view.unproject(start.x, start.y), view.unproject(x, y));
+            let region = graphics::Rect::new(a.x.min(b.x), a.y.min(b.y), (a.x - b.x).abs(), (a.y - b.y).abs());
+            self.apply(ctx, Command::FrameRegion(region));
+        }
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) {
+        if dx.abs() + dy.abs() > screensaver::MOTION_THRESHOLD_PX {
+            self.note_input(ctx);
+        }
+        if let Mode::Menu(menu) = &mut self.mode
+            && let Some(index) = menu.entry_at(x, y)
+        {
+            menu.selected = index;
+        }
+        if self.xy_pad.as_ref().is_some_and(|pad| pad.dragging) {
+            let (x, y) = XyPad::fraction(x, y);
+            self.apply(ctx, Command::SetPadPoint { x, y });
+        }
+    }
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> bool {
+        self.save_session();
+        self.detached_panels = None;
+        false
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) {
+        if self.note_input(ctx) || self.mode.is_menu() || self.mode.is_mechanics() {
+            return;
+        }
+        let at = mouse::position(ctx);
+        self.apply(ctx, Command::Zoom { factor: 1.1f32.powf(y), x: at.x, y: at.y });
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) {
+        if let Some(digits) = self.count_entry.as_mut()
+            && character.is_ascii_digit()
+            && digits.len() < MAX_COUNT_DIGITS
+        {
+            digits.push(character);
         }
     }
+
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) {
+        self.focused = gained;
+    }
+
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) {
+        self.minimized = width <= 0.0 || height <= 0.0;
+    }
 }
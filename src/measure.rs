@@ -0,0 +1,154 @@
+// Filename: measure.rs
+// Project: EntropicRust
+// Description: Invariant measure estimate. Every particle's state is binned into a 3D
+//              histogram over long runs; the box is fixed from a short warm-up so bins stay
+//              comparable as counts accumulate. The three 2D marginals are rendered as
+//              density images and the normalised measure can be exported as CSV.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::particle_pool::ParticlePool;
+
+pub const MEASURE_BINS: usize = 32;
+pub const EXPORT_PATH: &str = "invariant_measure.csv";
+const WARMUP_SAMPLES: usize = 5_000;
+// Fraction of the warm-up extent added on each side so later excursions still land inside.
+const BOX_MARGIN: f32 = 0.15;
+
+pub struct InvariantMeasure {
+    warmup: Vec<[f32; 3]>,
+    bounds: Option<[(f32, f32); 3]>,
+    counts: Vec<u32>,
+    pub total: u64,
+    pub outside: u64,
+}
+
+impl InvariantMeasure {
+    pub fn new() -> Self {
+        InvariantMeasure {
+            warmup: Vec::with_capacity(WARMUP_SAMPLES),
+            bounds: None,
+            counts: vec![0; MEASURE_BINS * MEASURE_BINS * MEASURE_BINS],
+            total: 0,
+            outside: 0,
+        }
+    }
+
+    pub fn record(&mut self, particles: &ParticlePool) {
+        for particle in particles.iter() {
+            let state = [particle.x, particle.y, particle.z];
+            if !state.iter().all(|v| v.is_finite()) {
+                continue;
+            }
+            match self.bounds {
+                Some(_) => self.add(state),
+                None => self.warmup.push(state),
+            }
+        }
+        if self.bounds.is_none() && self.warmup.len() >= WARMUP_SAMPLES {
+            let mut bounds = [(f32::MAX, f32::MIN); 3];
+            for state in &self.warmup {
+                for (axis, (lo, hi)) in bounds.iter_mut().enumerate() {
+                    *lo = lo.min(state[axis]);
+                    *hi = hi.max(state[axis]);
+                }
+            }
+            for (lo, hi) in bounds.iter_mut() {
+                let margin = ((*hi - *lo) * BOX_MARGIN).max(1.0e-3);
+                *lo -= margin;
+                *hi += margin;
+            }
+            self.bounds = Some(bounds);
+            for state in std::mem::take(&mut self.warmup) {
+                self.add(state);
+            }
+        }
+    }
+
+    fn add(&mut self, state: [f32; 3]) {
+        self.total += 1;
+        match self.bin_of(state) {
+            Some(index) => self.counts[index] += 1,
+            None => self.outside += 1,
+        }
+    }
+
+    fn bin_of(&self, state: [f32; 3]) -> Option<usize> {
+        let bounds = self.bounds?;
+        let mut index = 0;
+        for (axis, (lo, hi)) in bounds.iter().enumerate() {
+            let t = (state[axis] - lo) / (hi - lo);
+            if !(0.0..1.0).contains(&t) {
+                return None;
+            }
+            index = index * MEASURE_BINS + (t * MEASURE_BINS as f32) as usize;
+        }
+        Some(index)
+    }
+
+    pub fn is_warming_up(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    // Marginal over the axis not in `plane`, as an RGBA image with log-scaled density.
+    pub fn marginal_rgba(&self, plane: (usize, usize)) -> Vec<u8> {
+        let mut marginal = vec![0u64; MEASURE_BINS * MEASURE_BINS];
+        for (index, &count) in self.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let bins = [index / (MEASURE_BINS * MEASURE_BINS), (index / MEASURE_BINS) % MEASURE_BINS, index % MEASURE_BINS];
+            // Rows run top to bottom, matching the screen's y-down convention.
+            marginal[bins[plane.1] * MEASURE_BINS + bins[plane.0]] += count as u64;
+        }
+        let peak = marginal.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let mut rgba = Vec::with_capacity(marginal.len() * 4);
+        for count in marginal {
+            let t = (count as f32).ln_1p() / peak.ln_1p();
+            rgba.extend_from_slice(&[
+                (255.0 * t.sqrt()) as u8,
+                (220.0 * t * t) as u8,
+                (60.0 + 120.0 * t) as u8,
+                if count == 0 { 60 } else { 230 },
+            ]);
+        }
+        rgba
+    }
+
+    pub fn export_csv(&self) -> std::io::Result<usize> {
+        let Some(bounds) = self.bounds else {
+            return Ok(0);
+        };
+        let inside = (self.total - self.outside).max(1) as f64;
+        let widths = bounds.map(|(lo, hi)| (hi - lo) / MEASURE_BINS as f32);
+        let mut out = BufWriter::new(File::create(EXPORT_PATH)?);
+        writeln!(
+            out,
+            "# {}^3 bins; x in [{}, {}], y in [{}, {}], z in [{}, {}]; {} samples, {} outside the box",
+            MEASURE_BINS, bounds[0].0, bounds[0].1, bounds[1].0, bounds[1].1, bounds[2].0, bounds[2].1,
+            self.total, self.outside
+        )?;
+        writeln!(out, "x,y,z,probability")?;
+        let mut written = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let bins = [index / (MEASURE_BINS * MEASURE_BINS), (index / MEASURE_BINS) % MEASURE_BINS, index % MEASURE_BINS];
+            let center = [0, 1, 2].map(|axis| bounds[axis].0 + (bins[axis] as f32 + 0.5) * widths[axis]);
+            writeln!(out, "{},{},{},{}", center[0], center[1], center[2], count as f64 / inside)?;
+            written += 1;
+        }
+        out.flush()?;
+        Ok(written)
+    }
+}
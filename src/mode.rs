@@ -0,0 +1,105 @@
+// Filename: mode.rs
+// Project: EntropicRust
+// Description: What the application is doing as a whole. Exactly one mode is active at a
+//              time and MainState decides how to update, draw and handle input from it, so
+//              the start screen, the full-window analysis maps, camera recording, the
+//              guided tour and the mechanics demos can no longer be switched on together
+//              by accident. Overlays that sit on top of any mode (plots, the HUD, the
+//              screensaver) stay separate.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::double_pendulum::DoublePendulum;
+use crate::magnetic_pendulum::MagneticPendulum;
+use crate::start_menu::StartMenu;
+use crate::three_body::ThreeBody;
+use crate::tour::Tour;
+
+// The maps computed in the background over a plane of initial conditions or parameters.
+// They all fill the window, so only one is shown at a time.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Analysis {
+    // Chaos map over two parameters.
+    Scan,
+    Basin,
+    Ftle,
+    Escape,
+}
+
+// The mechanical systems that take the window in place of the attractor.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mechanics {
+    Pendulum,
+    Magnets,
+    ThreeBody,
+}
+
+pub enum Mode {
+    // The start screen; the simulation waits behind it.
+    Menu(StartMenu),
+    Simulate,
+    Analyze(Analysis),
+    // The camera is sampled into the camera path as it moves.
+    Record,
+    // The scene is driven by the steps of a tour file.
+    Tour(Tour),
+    // The attractor simulation waits while the pendulums take the window.
+    Pendulum(DoublePendulum),
+    // Likewise, with the magnetic pendulum's basins for a background.
+    Magnets(MagneticPendulum),
+    ThreeBody(ThreeBody),
+}
+
+impl Mode {
+    pub fn analysis(&self) -> Option<Analysis> {
+        match self {
+            Mode::Analyze(analysis) => Some(*analysis),
+            _ => None,
+        }
+    }
+
+    pub fn mechanics(&self) -> Option<Mechanics> {
+        match self {
+            Mode::Pendulum(_) => Some(Mechanics::Pendulum),
+            Mode::Magnets(_) => Some(Mechanics::Magnets),
+            Mode::ThreeBody(_) => Some(Mechanics::ThreeBody),
+            _ => None,
+        }
+    }
+
+    pub fn is_menu(&self) -> bool {
+        matches!(self, Mode::Menu(_))
+    }
+
+    pub fn is_tour(&self) -> bool {
+        matches!(self, Mode::Tour(_))
+    }
+
+    pub fn is_pendulum(&self) -> bool {
+        matches!(self, Mode::Pendulum(_))
+    }
+
+    pub fn is_magnets(&self) -> bool {
+        matches!(self, Mode::Magnets(_))
+    }
+
+    pub fn is_three_body(&self) -> bool {
+        matches!(self, Mode::ThreeBody(_))
+    }
+
+    // Modes with a mechanical system of their own in place of the attractor, which ignore
+    // the camera and the particle controls.
+    pub fn is_mechanics(&self) -> bool {
+        self.mechanics().is_some()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self, Mode::Record)
+    }
+}
@@ -0,0 +1,213 @@
+// Filename: network.rs
+// Project: EntropicRust
+// Description: Coupled-oscillator network mode. Each particle is a node running the same
+//              ODE, with diffusive coupling eps/k * sum_j (x_j - x_i) to its neighbours in a
+//              ring, small-world or all-to-all graph. The spread of the nodes around their
+//              mean is tracked as a synchronisation error.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+use rand::Rng;
+
+use crate::log_scale::LogScale;
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+
+pub const DEFAULT_STRENGTH: f32 = 2.0;
+pub const STRENGTH_SCALE: LogScale = LogScale { min: 0.01, max: 50.0, steps_per_decade: 6.0 };
+// Neighbours on each side in the ring and the lattice underlying the small world.
+const RING_REACH: usize = 2;
+const REWIRE_PROBABILITY: f64 = 0.1;
+const MAX_SAMPLES: usize = 2_000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Topology {
+    Ring,
+    SmallWorld,
+    AllToAll,
+}
+
+impl Topology {
+    pub fn from_name(name: &str) -> Option<Topology> {
+        match name.to_ascii_lowercase().as_str() {
+            "ring" => Some(Topology::Ring),
+            "small-world" | "smallworld" => Some(Topology::SmallWorld),
+            "all" | "all-to-all" | "global" => Some(Topology::AllToAll),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Topology::Ring => "ring",
+            Topology::SmallWorld => "small world",
+            Topology::AllToAll => "all-to-all",
+        }
+    }
+}
+
+pub struct Network {
+    pub topology: Topology,
+    pub strength: f32,
+    nodes: Vec<ParticleId>,
+    // Adjacency lists into `nodes`; unused for all-to-all, which couples to the mean field.
+    neighbors: Vec<Vec<usize>>,
+    pub elapsed: f32,
+    pub sync_error: f32,
+    pub samples: Vec<[f32; 2]>,
+}
+
+impl Network {
+    pub fn new(topology: Topology, strength: f32) -> Self {
+        Network {
+            topology,
+            strength,
+            nodes: Vec::new(),
+            neighbors: Vec::new(),
+            elapsed: 0.0,
+            sync_error: 0.0,
+            samples: Vec::with_capacity(MAX_SAMPLES),
+        }
+    }
+
+    // Rebuilds the graph over the current particles when nodes have come or gone.
+    fn sync_nodes(&mut self, particles: &ParticlePool) {
+        let stale = self.nodes.len() != particles.len() || self.nodes.iter().any(|id| particles.get(*id).is_none());
+        if !stale {
+            return;
+        }
+        self.nodes = particles.iter_with_ids().map(|(id, _)| id).collect();
+        let n = self.nodes.len();
+        self.neighbors = match self.topology {
+            Topology::AllToAll => Vec::new(),
+            Topology::Ring | Topology::SmallWorld => {
+                let reach = RING_REACH.min(n.saturating_sub(1) / 2);
+                let mut lists: Vec<Vec<usize>> = (0..n)
+                    .map(|i| (1..=reach).flat_map(|k| [(i + k) % n, (i + n - k) % n]).collect())
+                    .collect();
+                if self.topology == Topology::SmallWorld && n > 2 * reach + 1 {
+                    rewire(&mut lists, &mut rand::thread_rng());
+                }
+                lists
+            }
+        };
+        self.samples.clear();
+        self.elapsed = 0.0;
+    }
+
+    pub fn apply(&mut self, particles: &mut ParticlePool, system_type: SystemType, dt: f32) {
+        self.sync_nodes(particles);
+        let n = self.nodes.len();
+        if n < 2 {
+            return;
+        }
+        // The driving phase of forced systems is shared time, not a coordinate to couple.
+        let axes = if system_type.is_driven() { 2 } else { 3 };
+        let states: Vec<[f32; 3]> = self
+            .nodes
+            .iter()
+            .map(|id| particles.get(*id).map_or([f32::NAN; 3], |p| [p.x, p.y, p.z]))
+            .collect();
+        let total = states.iter().fold([0.0f32; 3], |acc, s| [acc[0] + s[0], acc[1] + s[1], acc[2] + s[2]]);
+
+        for (i, id) in self.nodes.iter().enumerate() {
+            let own = states[i];
+            let target: [f32; 3] = match self.topology {
+                Topology::AllToAll => std::array::from_fn(|axis| (total[axis] - own[axis]) / (n - 1) as f32),
+                Topology::Ring | Topology::SmallWorld => {
+                    let list = &self.neighbors[i];
+                    if list.is_empty() {
+                        continue;
+                    }
+                    let sum = list.iter().fold([0.0f32; 3], |acc, &j| {
+                        [acc[0] + states[j][0], acc[1] + states[j][1], acc[2] + states[j][2]]
+                    });
+                    sum.map(|v| v / list.len() as f32)
+                }
+            };
+            if let Some(p) = particles.get_mut(*id).filter(|p| !p.pinned) {
+                let nudge: [f32; 3] = std::array::from_fn(|axis| {
+                    if axis < axes { self.strength * dt * (target[axis] - own[axis]) } else { 0.0 }
+                });
+                p.x += nudge[0];
+                p.y += nudge[1];
+                p.z += nudge[2];
+            }
+        }
+
+        let mean = total.map(|v| v / n as f32);
+        let spread: f32 = states
+            .iter()
+            .map(|s| (0..axes).map(|axis| (s[axis] - mean[axis]).powi(2)).sum::<f32>().sqrt())
+            .sum::<f32>()
+            / n as f32;
+        self.sync_error = spread;
+        self.elapsed += dt;
+        if spread.is_finite() && spread > 0.0 && self.samples.len() < MAX_SAMPLES {
+            self.samples.push([self.elapsed, spread.log10()]);
+        }
+    }
+
+    pub fn draw_edges(&self, ctx: &mut Context, particles: &ParticlePool, system_type: SystemType) -> GameResult {
+        if self.topology == Topology::AllToAll {
+            return Ok(());
+        }
+        let positions: Vec<Option<Point2<f32>>> = self
+            .nodes
+            .iter()
+            .map(|id| {
+                particles
+                    .get(*id)
+                    .map(|p| p.get_screen_pos(system_type))
+                    .filter(|pos| pos.x.is_finite() && pos.y.is_finite())
+            })
+            .collect();
+        let color = graphics::Color::new(0.6, 0.8, 1.0, 0.15);
+        let mut builder = graphics::MeshBuilder::new();
+        let mut any = false;
+        for (i, list) in self.neighbors.iter().enumerate() {
+            for &j in list.iter().filter(|&&j| j > i) {
+                if let (Some(a), Some(b)) = (positions[i], positions[j])
+                    && (a.x - b.x).abs() + (a.y - b.y).abs() > 0.5
+                {
+                    builder.line(&[a, b], 1.0, color)?;
+                    any = true;
+                }
+            }
+        }
+        if any {
+            let mesh = builder.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        Ok(())
+    }
+}
+
+// Watts–Strogatz rewiring: each forward lattice edge is moved to a random new endpoint with
+// probability REWIRE_PROBABILITY, keeping the graph undirected.
+fn rewire<R: Rng>(lists: &mut [Vec<usize>], rng: &mut R) {
+    let n = lists.len();
+    for i in 0..n {
+        let forward: Vec<usize> = lists[i].iter().copied().filter(|&j| (j + n - i) % n <= RING_REACH).collect();
+        for j in forward {
+            if !rng.gen_bool(REWIRE_PROBABILITY) {
+                continue;
+            }
+            let k = rng.gen_range(0..n);
+            if k == i || lists[i].contains(&k) {
+                continue;
+            }
+            lists[i].retain(|&other| other != j);
+            lists[j].retain(|&other| other != i);
+            lists[i].push(k);
+            lists[k].push(i);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// Filename: nullclines.rs
+// Project: EntropicRust
+// Description: Nullcline overlay. The vector field is sampled on a screen-aligned grid in
+//              the system's view plane at a fixed depth, and marching squares extracts the
+//              curves where dx, dy or dz vanish.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::dynamics;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_view_preset};
+
+const GRID_COLUMNS: usize = 160;
+const GRID_ROWS: usize = 120;
+
+pub const NULLCLINE_COLORS: [(f32, f32, f32); 3] = [(1.0, 0.45, 0.45), (0.45, 1.0, 0.45), (0.45, 0.6, 1.0)];
+
+pub type Segment = [Point2<f32>; 2];
+
+pub fn compute(system_type: SystemType, params: &SystemParameters, depth: f32) -> [Vec<Segment>; 3] {
+    let view = get_view_preset(system_type);
+    let cell_w = SCREEN_WIDTH / GRID_COLUMNS as f32;
+    let cell_h = SCREEN_HEIGHT / GRID_ROWS as f32;
+
+    let mut field = vec![[0.0f32; 3]; (GRID_COLUMNS + 1) * (GRID_ROWS + 1)];
+    for row in 0..=GRID_ROWS {
+        for col in 0..=GRID_COLUMNS {
+            let at = view.unproject(Point2 { x: col as f32 * cell_w, y: row as f32 * cell_h }, depth);
+            field[row * (GRID_COLUMNS + 1) + col] = dynamics::derivative(system_type, params, at);
+        }
+    }
+
+    let mut segments: [Vec<Segment>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+    for (component, out) in segments.iter_mut().enumerate() {
+        for row in 0..GRID_ROWS {
+            for col in 0..GRID_COLUMNS {
+                let at = |c: usize, r: usize| field[r * (GRID_COLUMNS + 1) + c][component];
+                let corners = [
+                    (col as f32 * cell_w, row as f32 * cell_h, at(col, row)),
+                    ((col + 1) as f32 * cell_w, row as f32 * cell_h, at(col + 1, row)),
+                    ((col + 1) as f32 * cell_w, (row + 1) as f32 * cell_h, at(col + 1, row + 1)),
+                    (col as f32 * cell_w, (row + 1) as f32 * cell_h, at(col, row + 1)),
+                ];
+
+                let mut crossings: Vec<Point2<f32>> = Vec::with_capacity(4);
+                for edge in 0..4 {
+                    let (ax, ay, av) = corners[edge];
+                    let (bx, by, bv) = corners[(edge + 1) % 4];
+                    if !av.is_finite() || !bv.is_finite() || (av < 0.0) == (bv < 0.0) {
+                        continue;
+                    }
+                    let t = av / (av - bv);
+                    crossings.push(Point2 { x: ax + (bx - ax) * t, y: ay + (by - ay) * t });
+                }
+                for pair in crossings.chunks_exact(2) {
+                    out.push([pair[0], pair[1]]);
+                }
+            }
+        }
+    }
+    segments
+}
+
+pub fn draw(ctx: &mut Context, segments: &[Vec<Segment>; 3]) -> GameResult {
+    let mut builder = graphics::MeshBuilder::new();
+    let mut any = false;
+    for (component, list) in segments.iter().enumerate() {
+        let (r, g, b) = NULLCLINE_COLORS[component];
+        let color = graphics::Color::new(r, g, b, 0.8);
+        for segment in list {
+            let dx = segment[1].x - segment[0].x;
+            let dy = segment[1].y - segment[0].y;
+            if dx * dx + dy * dy < 1.0e-4 {
+                continue;
+            }
+            builder.line(segment, 1.0, color)?;
+            any = true;
+        }
+    }
+    if any {
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+    }
+    Ok(())
+}
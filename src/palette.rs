@@ -0,0 +1,159 @@
+// Filename: palette.rs
+// Project: EntropicRust
+// Description: Colour palettes for particles and UI accents. The classic palette keeps
+//              the original pastel particles and red/green accents; the others stay
+//              distinguishable with deuteranopia and protanopia. Okabe–Ito uses the
+//              colour set from Okabe and Ito's "Color Universal Design", Cividis
+//              the blue-to-yellow colormap designed to read the same with and without
+//              red-green colour blindness. Particles keep their own colours internally;
+//              a palette maps each one by hue at draw time, so groups that differ in
+//              hue keep differing after the mapping.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::graphics;
+
+use crate::leader;
+
+// Okabe–Ito colours without black, with the hue each one stands in for.
+const OKABE_ITO: [(f32, (f32, f32, f32)); 7] = [
+    (25.0, (0.835, 0.369, 0.0)),    // vermillion
+    (45.0, (0.902, 0.624, 0.0)),    // orange
+    (60.0, (0.941, 0.894, 0.259)),  // yellow
+    (160.0, (0.0, 0.62, 0.451)),    // bluish green
+    (200.0, (0.337, 0.706, 0.914)), // sky blue
+    (235.0, (0.0, 0.447, 0.698)),   // blue
+    (325.0, (0.8, 0.475, 0.655)),   // reddish purple
+];
+// Okabe–Ito blue through yellow to vermillion, for scales.
+const OKABE_ITO_SCALE: [(f32, f32, f32); 5] =
+    [(0.0, 0.447, 0.698), (0.337, 0.706, 0.914), (0.941, 0.894, 0.259), (0.902, 0.624, 0.0), (0.835, 0.369, 0.0)];
+const CIVIDIS_STOPS: [(f32, f32, f32); 5] =
+    [(0.0, 0.135, 0.305), (0.26, 0.3, 0.42), (0.49, 0.49, 0.47), (0.75, 0.7, 0.45), (1.0, 0.92, 0.27)];
+// Particles darker than this are brightened to it, so they stay visible on the background.
+const MIN_VALUE: f32 = 0.6;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Palette {
+    Classic,
+    OkabeIto,
+    Cividis,
+}
+
+// UI colours that carry meaning.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Accent {
+    Leader,
+    Stable,
+    Unstable,
+    Saddle,
+}
+
+impl Palette {
+    pub fn from_name(name: &str) -> Option<Palette> {
+        match name.to_ascii_lowercase().as_str() {
+            "classic" | "default" => Some(Palette::Classic),
+            "okabe-ito" | "okabeito" | "colorblind" => Some(Palette::OkabeIto),
+            "cividis" => Some(Palette::Cividis),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Classic => "classic",
+            Palette::OkabeIto => "Okabe–Ito",
+            Palette::Cividis => "cividis",
+        }
+    }
+
+    pub fn next(self) -> Palette {
+        match self {
+            Palette::Classic => Palette::OkabeIto,
+            Palette::OkabeIto => Palette::Cividis,
+            Palette::Cividis => Palette::Classic,
+        }
+    }
+
+    // Display colour of a particle whose own colour is `base`.
+    pub fn particle_color(self, base: graphics::Color) -> graphics::Color {
+        if self == Palette::Classic {
+            return base;
+        }
+        let (hue, value) = hue_value(base);
+        let (r, g, b) = match self {
+            // The dark end of the map is left out; it would vanish against the background.
+            Palette::Cividis => colormap(&CIVIDIS_STOPS, 0.25 + 0.75 * hue / 360.0),
+            _ => {
+                let distance = |nominal: f32| (hue - nominal).abs().min(360.0 - (hue - nominal).abs());
+                OKABE_ITO
+                    .iter()
+                    .min_by(|a, b| distance(a.0).total_cmp(&distance(b.0)))
+                    .map_or((1.0, 1.0, 1.0), |&(_, rgb)| rgb)
+            }
+        };
+        let shade = value.max(MIN_VALUE);
+        graphics::Color::new(r * shade, g * shade, b * shade, base.a)
+    }
+
+    // Colour at `t` in [0, 1] along a scale that runs from `low` to `high` in the classic
+    // palette.
+    pub fn scale(self, t: f32, low: (f32, f32, f32), high: (f32, f32, f32)) -> graphics::Color {
+        let t = t.clamp(0.0, 1.0);
+        let (r, g, b) = match self {
+            Palette::Classic => {
+                (low.0 + (high.0 - low.0) * t, low.1 + (high.1 - low.1) * t, low.2 + (high.2 - low.2) * t)
+            }
+            Palette::OkabeIto => colormap(&OKABE_ITO_SCALE, t),
+            Palette::Cividis => colormap(&CIVIDIS_STOPS, t),
+        };
+        graphics::Color::new(r, g, b, 1.0)
+    }
+
+    pub fn accent(self, accent: Accent) -> graphics::Color {
+        let (r, g, b) = match (self, accent) {
+            (Palette::Classic, Accent::Leader) => return leader::LEADER_COLOR,
+            (Palette::Classic, Accent::Stable) => (0.3, 1.0, 0.4),
+            (Palette::Classic, Accent::Unstable) => (1.0, 0.3, 0.3),
+            (Palette::Classic, Accent::Saddle) => (1.0, 0.85, 0.2),
+            // Blue against vermillion reads apart for every common colour vision.
+            (_, Accent::Leader) => (0.8, 0.475, 0.655),
+            (_, Accent::Stable) => (0.337, 0.706, 0.914),
+            (_, Accent::Unstable) => (0.835, 0.369, 0.0),
+            (_, Accent::Saddle) => (0.941, 0.894, 0.259),
+        };
+        graphics::Color::new(r, g, b, 1.0)
+    }
+}
+
+// Hue in degrees and the largest component.
+fn hue_value(color: graphics::Color) -> (f32, f32) {
+    let (r, g, b) = (color.r, color.g, color.b);
+    let max = r.max(g).max(b);
+    let spread = max - r.min(g).min(b);
+    if spread <= f32::EPSILON {
+        return (0.0, max);
+    }
+    let hue = if max == r {
+        60.0 * ((g - b) / spread)
+    } else if max == g {
+        60.0 * ((b - r) / spread + 2.0)
+    } else {
+        60.0 * ((r - g) / spread + 4.0)
+    };
+    (hue.rem_euclid(360.0), max)
+}
+
+fn colormap(stops: &[(f32, f32, f32)], t: f32) -> (f32, f32, f32) {
+    let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (position as usize).min(stops.len() - 2);
+    let f = position - index as f32;
+    let ((r0, g0, b0), (r1, g1, b1)) = (stops[index], stops[index + 1]);
+    (r0 + (r1 - r0) * f, g0 + (g1 - g0) * f, b0 + (b1 - b0) * f)
+}
@@ -0,0 +1,114 @@
+// Filename: param_scan.rs
+// Project: EntropicRust
+// Description: Parameter-plane "chaos map". Two parameters of the current system are
+//              scanned over a grid around their current values; every cell's largest
+//              Lyapunov exponent is computed on the rayon pool and coloured by regime
+//              (chaotic, periodic, fixed point or divergent).
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rayon::prelude::*;
+
+use crate::particle::SystemType;
+use crate::sweep;
+use crate::system_parameters::{SystemParameters, get_parameter_names};
+
+pub const SCAN_WIDTH: usize = 100;
+pub const SCAN_HEIGHT: usize = 75;
+const SCAN_STEPS: usize = 3_000;
+const SCAN_TRANSIENT: usize = 500;
+const NEUTRAL_BAND: f32 = 0.01;
+
+#[derive(Clone, Copy)]
+pub struct ScanAxis {
+    pub name: &'static str,
+    pub from: f32,
+    pub to: f32,
+}
+
+impl ScanAxis {
+    pub fn around(name: &'static str, params: &SystemParameters) -> ScanAxis {
+        let value = params.get(name).unwrap_or(1.0);
+        let half_span = (value.abs() * 0.5).max(0.1);
+        ScanAxis { name, from: value - half_span, to: value + half_span }
+    }
+
+    pub fn value_at(&self, t: f32) -> f32 {
+        self.from + (self.to - self.from) * t.clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ScanAxes {
+    pub x: ScanAxis,
+    pub y: ScanAxis,
+}
+
+impl ScanAxes {
+    pub fn for_pair(system_type: SystemType, params: &SystemParameters, pair: usize) -> ScanAxes {
+        let names = get_parameter_names(system_type);
+        let pairs: Vec<(usize, usize)> = (0..names.len())
+            .flat_map(|i| (i + 1..names.len()).map(move |j| (i, j)))
+            .collect();
+        let (i, j) = pairs[pair % pairs.len()];
+        ScanAxes {
+            x: ScanAxis::around(names[i], params),
+            y: ScanAxis::around(names[j], params),
+        }
+    }
+
+    pub fn pair_count(system_type: SystemType) -> usize {
+        let n = get_parameter_names(system_type).len();
+        n * (n - 1) / 2
+    }
+}
+
+pub struct ScanImage {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+}
+
+pub fn compute(system_type: SystemType, base: SystemParameters, axes: ScanAxes, dt: f32) -> ScanImage {
+    let exponents: Vec<Option<f32>> = (0..SCAN_WIDTH * SCAN_HEIGHT)
+        .into_par_iter()
+        .map(|cell| {
+            let (col, row) = (cell % SCAN_WIDTH, cell / SCAN_WIDTH);
+            let mut params = base;
+            params.set(axes.x.name, axes.x.value_at((col as f32 + 0.5) / SCAN_WIDTH as f32));
+            // Rows run top to bottom on screen, so flip to put larger y values at the top.
+            params.set(axes.y.name, axes.y.value_at(1.0 - (row as f32 + 0.5) / SCAN_HEIGHT as f32));
+            let run = sweep::run_single(system_type, &params, cell as u64, SCAN_STEPS, SCAN_TRANSIENT, dt);
+            run.bounded.then_some(run.lyapunov)
+        })
+        .collect();
+
+    let mut rgba = Vec::with_capacity(SCAN_WIDTH * SCAN_HEIGHT * 4);
+    for exponent in exponents {
+        let color = match exponent {
+            None => [0, 0, 0],
+            Some(l) if l > NEUTRAL_BAND => {
+                let t = (l / 2.0).min(1.0);
+                [255, (200.0 - 150.0 * t) as u8, (60.0 - 60.0 * t) as u8]
+            }
+            Some(l) if l < -NEUTRAL_BAND => {
+                let t = (-l / 2.0).min(1.0);
+                [(40.0 - 40.0 * t) as u8, (80.0 - 40.0 * t) as u8, (160.0 + 95.0 * t) as u8]
+            }
+            Some(_) => [120, 220, 140],
+        };
+        rgba.extend_from_slice(&[color[0], color[1], color[2], 230]);
+    }
+
+    ScanImage {
+        width: SCAN_WIDTH as u16,
+        height: SCAN_HEIGHT as u16,
+        rgba,
+    }
+}
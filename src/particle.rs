@@ -12,18 +12,128 @@
 //
 
 use ggez::graphics;
+use ggez::input::keyboard::KeyCode;
 use ggez::mint::Point2;
 use rand::Rng;
 use std::collections::VecDeque;
 
+use crate::system_parameters::Jitter;
+
 pub const MAX_TRAIL_LENGTH: usize = 100;
+pub const FLASH_FRAMES: u8 = 12;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SystemType {
     Lorenz,
     Rossler,
     Aizawa,
     ChenLee,
+    Duffing,
+    VanDerPol,
+    RabinovichFabrikant,
+    NewtonLeipnik,
+    FourWing,
+    Sakarya,
+    FoodChain,
+}
+
+impl SystemType {
+    pub const ALL: [SystemType; 11] = [
+        SystemType::Lorenz,
+        SystemType::Rossler,
+        SystemType::Aizawa,
+        SystemType::ChenLee,
+        SystemType::Duffing,
+        SystemType::VanDerPol,
+        SystemType::RabinovichFabrikant,
+        SystemType::NewtonLeipnik,
+        SystemType::FourWing,
+        SystemType::Sakarya,
+        SystemType::FoodChain,
+    ];
+
+    // The system on number key `key`, 1 to 6, or with Shift the one six further along.
+    pub fn from_digit(key: KeyCode, shift: bool) -> Option<SystemType> {
+        const DIGITS: [KeyCode; 6] =
+            [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5, KeyCode::Key6];
+        let index = DIGITS.iter().position(|&k| k == key)? + if shift { DIGITS.len() } else { 0 };
+        SystemType::ALL.get(index).copied()
+    }
+
+    // Key that selects the system, as written in hints and captions.
+    pub fn key_label(self) -> String {
+        let index = SystemType::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        if index < 6 { format!("{}", index + 1) } else { format!("Shift+{}", index - 5) }
+    }
+
+    pub fn from_name(name: &str) -> Option<SystemType> {
+        match name.to_ascii_lowercase().as_str() {
+            "lorenz" => Some(SystemType::Lorenz),
+            "rossler" => Some(SystemType::Rossler),
+            "aizawa" => Some(SystemType::Aizawa),
+            "chen-lee" | "chenlee" => Some(SystemType::ChenLee),
+            "duffing" => Some(SystemType::Duffing),
+            "van-der-pol" | "vanderpol" => Some(SystemType::VanDerPol),
+            "rabinovich-fabrikant" | "rabinovichfabrikant" => Some(SystemType::RabinovichFabrikant),
+            "newton-leipnik" | "newtonleipnik" => Some(SystemType::NewtonLeipnik),
+            "four-wing" | "fourwing" => Some(SystemType::FourWing),
+            "sakarya" => Some(SystemType::Sakarya),
+            "food-chain" | "foodchain" | "hastings-powell" => Some(SystemType::FoodChain),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SystemType::Lorenz => "Lorenz",
+            SystemType::Rossler => "Rossler",
+            SystemType::Aizawa => "Aizawa",
+            SystemType::ChenLee => "Chen-Lee",
+            SystemType::Duffing => "Duffing",
+            SystemType::VanDerPol => "Van der Pol",
+            SystemType::RabinovichFabrikant => "Rabinovich-Fabrikant",
+            SystemType::NewtonLeipnik => "Newton-Leipnik",
+            SystemType::FourWing => "Four-wing",
+            SystemType::Sakarya => "Sakarya",
+            SystemType::FoodChain => "Food chain",
+        }
+    }
+
+    // Spelling accepted by `from_name`, for config and preset files.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            SystemType::Lorenz => "lorenz",
+            SystemType::Rossler => "rossler",
+            SystemType::Aizawa => "aizawa",
+            SystemType::ChenLee => "chen-lee",
+            SystemType::Duffing => "duffing",
+            SystemType::VanDerPol => "van-der-pol",
+            SystemType::RabinovichFabrikant => "rabinovich-fabrikant",
+            SystemType::NewtonLeipnik => "newton-leipnik",
+            SystemType::FourWing => "four-wing",
+            SystemType::Sakarya => "sakarya",
+            SystemType::FoodChain => "food-chain",
+        }
+    }
+
+    // Periodically forced systems carry the driving phase in `z` instead of a third
+    // spatial coordinate.
+    pub fn is_driven(self) -> bool {
+        matches!(self, SystemType::Duffing | SystemType::VanDerPol)
+    }
+
+    // Population models track abundances, which live in the positive octant.
+    pub fn is_population(self) -> bool {
+        matches!(self, SystemType::FoodChain)
+    }
+
+    // Names of the three state coordinates, for axis labels and readouts.
+    pub fn axis_names(self) -> [&'static str; 3] {
+        match self {
+            SystemType::FoodChain => ["prey", "predator", "top predator"],
+            _ => ["x", "y", "z"],
+        }
+    }
 }
 
 pub struct Particle {
@@ -31,7 +141,21 @@ pub struct Particle {
     pub y: f32,
     pub z: f32,
     pub trail: VecDeque<Point2<f32>>,
+    // z of each trail point, kept in step with `trail` for depth-shaded ribbons.
+    pub trail_depth: VecDeque<f32>,
     pub color: graphics::Color,
+    // Frames left in an event-triggered highlight.
+    pub flash: u8,
+    // Latest local error estimate from an embedded integrator, if one is in use.
+    pub error: Option<f32>,
+    // Simulated time since the particle was (re)spawned.
+    pub age: f32,
+    // Pinned particles hold their state while the rest of the cloud evolves.
+    pub pinned: bool,
+    // Own parameter multipliers for parameter-uncertainty ensembles; None uses the shared set.
+    pub jitter: Option<Jitter>,
+    // The same start integrated with RK4, carried while the integrator overlay is on.
+    pub twin: Option<[f32; 3]>,
 }
 
 impl Particle {
@@ -42,35 +166,82 @@ impl Particle {
             y,
             z,
             trail: VecDeque::with_capacity(MAX_TRAIL_LENGTH),
+            trail_depth: VecDeque::with_capacity(MAX_TRAIL_LENGTH),
             color: graphics::Color::new(
                 rng.gen_range(0.5..1.0),
                 rng.gen_range(0.5..1.0),
                 rng.gen_range(0.5..1.0),
                 1.0,
             ),
+            flash: 0,
+            error: None,
+            age: 0.0,
+            pinned: false,
+            jitter: None,
+            twin: None,
         }
     }
 
-    pub fn update(&mut self, new_x: f32, new_y: f32, new_z: f32, screen_pos: Point2<f32>) {
-        if self.trail.len() >= MAX_TRAIL_LENGTH && MAX_TRAIL_LENGTH > 0 {
-            self.trail.pop_front();
-        }
-        if MAX_TRAIL_LENGTH > 0 {
-            if self.trail.is_empty() {
+    pub fn reset(&mut self, x: f32, y: f32, z: f32) {
+        let mut rng = rand::thread_rng();
+        self.x = x;
+        self.y = y;
+        self.z = z;
+        self.clear_trail();
+        self.flash = 0;
+        self.error = None;
+        self.age = 0.0;
+        self.pinned = false;
+        self.jitter = None;
+        self.twin = None;
+        self.color = graphics::Color::new(
+            rng.gen_range(0.5..1.0),
+            rng.gen_range(0.5..1.0),
+            rng.gen_range(0.5..1.0),
+            1.0,
+        );
+    }
+
+    pub fn clear_trail(&mut self) {
+        self.trail.clear();
+        self.trail_depth.clear();
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    // `trail_point` is the new screen position and view depth, when trails are recorded.
+    pub fn update(&mut self, new_x: f32, new_y: f32, new_z: f32, trail_point: Option<(Point2<f32>, f32)>) {
+        // A diverged particle would poison its trail mesh; drop the broken history instead.
+        let trail_point = match trail_point {
+            Some((pos, _)) if !pos.x.is_finite() || !pos.y.is_finite() => {
+                self.clear_trail();
+                None
+            }
+            other => other,
+        };
+        if let Some((screen_pos, depth)) = trail_point {
+            if self.trail.len() >= MAX_TRAIL_LENGTH && MAX_TRAIL_LENGTH > 0 {
+                self.trail.pop_front();
+                self.trail_depth.pop_front();
+            }
+            if MAX_TRAIL_LENGTH > 0 {
+                if self.trail.is_empty() {
+                    self.trail.push_back(screen_pos);
+                    self.trail_depth.push_back(depth);
+                }
                 self.trail.push_back(screen_pos);
+                self.trail_depth.push_back(depth);
             }
-            self.trail.push_back(screen_pos);
         }
         self.x = new_x;
         self.y = new_y;
         self.z = new_z;
+        self.flash = self.flash.saturating_sub(1);
     }
 
     pub fn get_screen_pos(&self, system_type: SystemType) -> Point2<f32> {
-        let scale_factor = crate::system_parameters::get_scale_factor(system_type);
-        Point2 {
-            x: crate::main_state::SCREEN_WIDTH / 2.0 + self.x * scale_factor,
-            y: crate::main_state::SCREEN_HEIGHT / 2.0 + self.y * scale_factor,
-        }
+        crate::system_parameters::get_view_preset(system_type).project([self.x, self.y, self.z])
     }
 }
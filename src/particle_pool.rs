@@ -0,0 +1,142 @@
+// Filename: particle_pool.rs
+// Project: EntropicRust
+// Description: Pooled particle storage with generation-indexed handles. Despawned slots
+//              are recycled (including their trail buffers) so spawning never reallocates
+//              and stale handles are detected instead of silently aliasing a new particle.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::particle::Particle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ParticleId {
+    index: u32,
+    generation: u32,
+}
+
+struct Slot {
+    generation: u32,
+    alive: bool,
+    particle: Particle,
+}
+
+pub struct ParticlePool {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    live: usize,
+}
+
+impl ParticlePool {
+    pub fn with_capacity(capacity: usize) -> Self {
+        ParticlePool {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::with_capacity(capacity),
+            live: 0,
+        }
+    }
+
+    pub fn spawn(&mut self, x: f32, y: f32, z: f32) -> ParticleId {
+        self.live += 1;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.alive = true;
+            slot.particle.reset(x, y, z);
+            return ParticleId { index, generation: slot.generation };
+        }
+
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot {
+            generation: 0,
+            alive: true,
+            particle: Particle::new(x, y, z),
+        });
+        ParticleId { index, generation: 0 }
+    }
+
+    pub fn despawn(&mut self, id: ParticleId) -> bool {
+        match self.slots.get_mut(id.index as usize) {
+            Some(slot) if slot.alive && slot.generation == id.generation => {
+                slot.alive = false;
+                slot.generation = slot.generation.wrapping_add(1);
+                slot.particle.clear_trail();
+                self.free.push(id.index);
+                self.live -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for index in 0..self.slots.len() {
+            let slot = &mut self.slots[index];
+            if slot.alive {
+                slot.alive = false;
+                slot.generation = slot.generation.wrapping_add(1);
+                slot.particle.clear_trail();
+                self.free.push(index as u32);
+            }
+        }
+        // Hand slots back out in ascending order so a respawned field keeps a stable layout.
+        self.free.sort_unstable_by(|a, b| b.cmp(a));
+        self.live = 0;
+    }
+
+    pub fn get(&self, id: ParticleId) -> Option<&Particle> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.alive && slot.generation == id.generation)
+            .map(|slot| &slot.particle)
+    }
+
+    pub fn get_mut(&mut self, id: ParticleId) -> Option<&mut Particle> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.alive && slot.generation == id.generation)
+            .map(|slot| &mut slot.particle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.slots.iter().filter(|slot| slot.alive).map(|slot| &slot.particle)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Particle> {
+        self.slots.iter_mut().filter(|slot| slot.alive).map(|slot| &mut slot.particle)
+    }
+
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (ParticleId, &Particle)> {
+        self.slots.iter().enumerate().filter(|(_, slot)| slot.alive).map(|(index, slot)| {
+            (ParticleId { index: index as u32, generation: slot.generation }, &slot.particle)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_ids_do_not_reach_a_respawned_slot() {
+        let mut pool = ParticlePool::with_capacity(1);
+        let stale = pool.spawn(1.0, 2.0, 3.0);
+        assert!(pool.despawn(stale));
+        let fresh = pool.spawn(4.0, 5.0, 6.0);
+
+        assert_ne!(stale, fresh);
+        assert!(pool.get(stale).is_none());
+        assert!(pool.get_mut(stale).is_none());
+        assert!(!pool.despawn(stale));
+        assert_eq!(pool.get(fresh).map(|p| p.x), Some(4.0));
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -0,0 +1,217 @@
+// Filename: periodic_orbit.rs
+// Project: EntropicRust
+// Description: Unstable periodic orbit detection. A long trajectory is scanned for close
+//              returns to an earlier state; each candidate (x0, T) is refined by Newton
+//              shooting on φ_T(x0) - x0 = 0 with a phase condition, and the first one that
+//              converges is returned as a closed loop together with its Floquet multipliers.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+use crate::dynamics;
+use crate::fixed_points;
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_scale_factor};
+
+const TRANSIENT_STEPS: usize = 1_000;
+const SEARCH_STEPS: usize = 20_000;
+const MIN_RETURN_STEPS: usize = 50;
+const MAX_RETURN_STEPS: usize = 2_000;
+// A close return must come back within this many screen pixels of where it started.
+const RETURN_TOLERANCE_PX: f32 = 10.0;
+const EXCURSION_FACTOR: f32 = 3.0;
+const MAX_CANDIDATES: usize = 40;
+const NEWTON_ITERATIONS: usize = 12;
+const CONVERGED_RESIDUAL_PX: f64 = 0.05;
+// Loops smaller than this are the linearised spiral around a fixed point, not a real orbit.
+const MIN_EXTENT_PX: f32 = 10.0;
+
+pub struct PeriodicOrbit {
+    pub period: f32,
+    pub points: Vec<[f32; 3]>,
+    pub max_multiplier: f32,
+}
+
+pub fn find(system_type: SystemType, params: &SystemParameters, start: [f32; 3], dt: f32) -> Option<PeriodicOrbit> {
+    let mut state = start;
+    for _ in 0..TRANSIENT_STEPS {
+        state = dynamics::step(system_type, params, state, dt);
+    }
+    let mut trajectory = Vec::with_capacity(SEARCH_STEPS);
+    for _ in 0..SEARCH_STEPS {
+        state = dynamics::step(system_type, params, state, dt);
+        if !state.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+        trajectory.push(state);
+    }
+
+    let scale = get_scale_factor(system_type);
+    let tolerance = RETURN_TOLERANCE_PX / scale;
+    let mut tried: Vec<usize> = Vec::with_capacity(MAX_CANDIDATES);
+    // Shorter periods first: they are the easiest to converge and the most recognisable.
+    for lag in MIN_RETURN_STEPS..MAX_RETURN_STEPS {
+        let Some(t) = (0..SEARCH_STEPS - lag).step_by(5).find(|&t| {
+            !tried.iter().any(|&s| s.abs_diff(t) < MIN_RETURN_STEPS)
+                && distance(system_type, &trajectory[t], &trajectory[t + lag]) < tolerance
+                // The orbit has to leave the neighbourhood in between, or this is just slow drift.
+                && trajectory[t..t + lag]
+                    .iter()
+                    .any(|p| distance(system_type, p, &trajectory[t]) > EXCURSION_FACTOR * tolerance)
+        }) else {
+            continue;
+        };
+        if let Some(orbit) = refine(system_type, params, trajectory[t], lag, dt, scale) {
+            return Some(orbit);
+        }
+        tried.push(t);
+        if tried.len() >= MAX_CANDIDATES {
+            break;
+        }
+    }
+    None
+}
+
+fn refine(
+    system_type: SystemType,
+    params: &SystemParameters,
+    seed: [f32; 3],
+    steps: usize,
+    dt: f32,
+    scale: f32,
+) -> Option<PeriodicOrbit> {
+    let mut x0 = seed.map(f64::from);
+    let mut period = steps as f64 * dt as f64;
+
+    for _ in 0..NEWTON_ITERATIONS {
+        let h = (period / steps as f64) as f32;
+        let (end, monodromy) = flow_with_monodromy(system_type, params, x0.map(|v| v as f32), steps, h)?;
+        let residual = difference(system_type, &end, &x0);
+        let norm = residual.iter().map(|r| r * r).sum::<f64>().sqrt();
+        if norm * scale as f64 <= CONVERGED_RESIDUAL_PX {
+            let multipliers = fixed_points::eigenvalues(&monodromy);
+            // One multiplier sits at 1 along the flow; the others decide stability.
+            let max_multiplier = multipliers
+                .iter()
+                .map(|(re, im)| (re * re + im * im).sqrt())
+                .fold(0.0f64, f64::max) as f32;
+            let points = trace(system_type, params, x0.map(|v| v as f32), steps, h);
+            let extent = points.iter().map(|p| distance(system_type, p, &points[0])).fold(0.0f32, f32::max);
+            if extent * scale < MIN_EXTENT_PX {
+                return None;
+            }
+            return Some(PeriodicOrbit { period: period as f32, points, max_multiplier });
+        }
+
+        let f_end = dynamics::derivative(system_type, params, end.map(|v| v as f32)).map(f64::from);
+        let f_start = dynamics::derivative(system_type, params, x0.map(|v| v as f32)).map(f64::from);
+        // [M - I   f(φ_T)] [δx]   [-r]
+        // [f(x0)ᵀ  0     ] [δT] = [ 0]
+        let mut system = [[0.0f64; 5]; 4];
+        for i in 0..3 {
+            for k in 0..3 {
+                system[i][k] = monodromy[i][k] - if i == k { 1.0 } else { 0.0 };
+            }
+            system[i][3] = f_end[i];
+            system[i][4] = -residual[i];
+        }
+        system[3][..3].copy_from_slice(&f_start);
+        let delta = solve4(system)?;
+        for (x, d) in x0.iter_mut().zip(&delta[..3]) {
+            *x += d;
+        }
+        period += delta[3];
+        if !period.is_finite() || period <= 0.0 {
+            return None;
+        }
+    }
+    None
+}
+
+fn flow_with_monodromy(
+    system_type: SystemType,
+    params: &SystemParameters,
+    start: [f32; 3],
+    steps: usize,
+    h: f32,
+) -> Option<([f64; 3], [[f64; 3]; 3])> {
+    let mut state = start;
+    let mut monodromy = [[1.0f64, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..steps {
+        let j = dynamics::jacobian(system_type, params, state).map(|row| row.map(f64::from));
+        let mut jm = [[0.0f64; 3]; 3];
+        for (i, row) in jm.iter_mut().enumerate() {
+            for (k, v) in row.iter_mut().enumerate() {
+                *v = (0..3).map(|m| j[i][m] * monodromy[m][k]).sum();
+            }
+        }
+        for (row, jrow) in monodromy.iter_mut().zip(jm) {
+            for (v, jv) in row.iter_mut().zip(jrow) {
+                *v += h as f64 * jv;
+            }
+        }
+        state = dynamics::step(system_type, params, state, h);
+        if !state.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+    }
+    Some((state.map(f64::from), monodromy))
+}
+
+fn trace(system_type: SystemType, params: &SystemParameters, start: [f32; 3], steps: usize, h: f32) -> Vec<[f32; 3]> {
+    let mut state = start;
+    let mut points = Vec::with_capacity(steps + 1);
+    points.push(state);
+    for _ in 0..steps {
+        state = dynamics::step(system_type, params, state, h);
+        points.push(state);
+    }
+    points
+}
+
+// Driven systems keep their phase in z, which wraps at 2π and must be compared modulo it.
+fn difference(system_type: SystemType, a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    let mut d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    if system_type.is_driven() {
+        let tau = TAU as f64;
+        d[2] = (d[2] + tau / 2.0).rem_euclid(tau) - tau / 2.0;
+    }
+    d
+}
+
+fn distance(system_type: SystemType, a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    let d = difference(system_type, &a.map(f64::from), &b.map(f64::from));
+    (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() as f32
+}
+
+// Gaussian elimination with partial pivoting on an augmented 4x5 matrix.
+fn solve4(mut m: [[f64; 5]; 4]) -> Option<[f64; 4]> {
+    for col in 0..4 {
+        let pivot = (col..4).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() < 1.0e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        for row in col + 1..4 {
+            let factor = m[row][col] / m[col][col];
+            let pivot_row = m[col];
+            for (v, p) in m[row].iter_mut().zip(pivot_row).skip(col) {
+                *v -= factor * p;
+            }
+        }
+    }
+    let mut x = [0.0f64; 4];
+    for row in (0..4).rev() {
+        let tail: f64 = (row + 1..4).map(|k| m[row][k] * x[k]).sum();
+        x[row] = (m[row][4] - tail) / m[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
+
@@ -0,0 +1,95 @@
+// Filename: plot_panel.rs
+// Project: EntropicRust
+// Description: Small overlay panels for analysis output: a framed area with a title,
+//              a data series auto-fitted to the frame and an optional overlay line.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::borrow::Cow;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::theme::Theme;
+
+pub struct PlotPanel<'a> {
+    pub area: graphics::Rect,
+    pub title: String,
+    pub series: Cow<'a, [[f32; 2]]>,
+    pub overlay: Option<([f32; 2], [f32; 2])>,
+    pub color: graphics::Color,
+}
+
+impl PlotPanel<'_> {
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let background = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            self.area,
+            theme.panel,
+        )?;
+        graphics::draw(ctx, &background, graphics::DrawParam::default())?;
+        let frame = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::stroke(1.0),
+            self.area,
+            theme.frame,
+        )?;
+        graphics::draw(ctx, &frame, graphics::DrawParam::default())?;
+
+        let title = graphics::Text::new(graphics::TextFragment::new(self.title.as_str())
+            .font(graphics::Font::default()).scale(graphics::PxScale::from(14.0)));
+        graphics::draw(
+            ctx,
+            &title,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: self.area.x + 6.0, y: self.area.y + 4.0 })
+                .color(theme.text),
+        )?;
+
+        let finite: Vec<[f32; 2]> = self
+            .series
+            .iter()
+            .copied()
+            .filter(|p| p[0].is_finite() && p[1].is_finite())
+            .collect();
+        if finite.len() < 2 {
+            return Ok(());
+        }
+
+        let (mut min_x, mut max_x) = (f32::MAX, f32::MIN);
+        let (mut min_y, mut max_y) = (f32::MAX, f32::MIN);
+        for p in &finite {
+            min_x = min_x.min(p[0]);
+            max_x = max_x.max(p[0]);
+            min_y = min_y.min(p[1]);
+            max_y = max_y.max(p[1]);
+        }
+        let span_x = (max_x - min_x).max(f32::EPSILON);
+        let span_y = (max_y - min_y).max(f32::EPSILON);
+
+        let plot_top = self.area.y + 22.0;
+        let plot_height = self.area.h - 28.0;
+        let to_screen = |p: [f32; 2]| Point2 {
+            x: self.area.x + 6.0 + (p[0] - min_x) / span_x * (self.area.w - 12.0),
+            y: plot_top + plot_height - (p[1] - min_y) / span_y * plot_height,
+        };
+
+        let points: Vec<Point2<f32>> = finite.iter().map(|p| to_screen(*p)).collect();
+        let line = graphics::Mesh::new_line(ctx, &points, 1.0, self.color)?;
+        graphics::draw(ctx, &line, graphics::DrawParam::default())?;
+
+        if let Some((from, to)) = self.overlay {
+            let fit = [to_screen(from), to_screen(to)];
+            let fit_line = graphics::Mesh::new_line(ctx, &fit, 1.0, graphics::Color::new(1.0, 0.4, 0.3, 1.0))?;
+            graphics::draw(ctx, &fit_line, graphics::DrawParam::default())?;
+        }
+
+        Ok(())
+    }
+}
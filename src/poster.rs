@@ -0,0 +1,188 @@
+// Filename: poster.rs
+// Project: EntropicRust
+// Description: High-resolution poster export. The attractor layer of the current scene
+//              (trails and particles, as seen through the camera) is drawn again offscreen
+//              with trails thicker than on screen, so they keep their weight in print, and
+//              saved as a PNG. The view is widened or heightened to the poster's aspect
+//              ratio so nothing on screen is cropped.
+//
+//              Any size is rendered tile by tile: a multisampled canvas of TILE pixels is
+//              moved across the poster, each tile drawing the same meshes, built once from
+//              a single snapshot of the scene, so every tile replays exactly the same
+//              picture. A row of tiles is stitched and streamed into the PNG before the
+//              next is drawn, which keeps gigapixel prints within a few hundred megabytes.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use ggez::conf::NumSamples;
+use ggez::{graphics, mint::Point2, Context, GameError, GameResult};
+
+use crate::camera;
+use crate::shapes::{self, ParticleShape};
+use crate::trail_gradient::{self, TrailAlpha, TrailGradient, TrailWidth};
+
+// 4K UHD; Ctrl+Shift+F12 doubles it to 8K.
+pub const DEFAULT_SIZE: (u32, u32) = (3840, 2160);
+// About a gigapixel, for Ctrl+Alt+F12.
+pub const DEFAULT_TILED_SIZE: (u32, u32) = (40960, 24576);
+// In window pixels, which the poster scales up with everything else.
+pub const DEFAULT_TRAIL_WIDTH: f32 = 2.0;
+// Edge of the offscreen canvas in pixels; small enough for any graphics card.
+const TILE: u32 = 1024;
+
+// One particle as it appears in the scene, in projected-view coordinates.
+pub struct Mark {
+    // Oldest first; empty when trails are off.
+    pub trail: Vec<Point2<f32>>,
+    pub trail_color: graphics::Color,
+    pub position: Point2<f32>,
+    pub radius: f32,
+    pub color: graphics::Color,
+}
+
+pub struct Style {
+    pub background: graphics::Color,
+    pub gradient: TrailGradient,
+    pub width: TrailWidth,
+    pub alpha: TrailAlpha,
+    pub shape: ParticleShape,
+}
+
+pub struct Poster {
+    pub width: u32,
+    pub height: u32,
+    pub trail_width: f32,
+}
+
+// `3840x2160`.
+pub fn parse_size(value: &str) -> Result<(u32, u32), String> {
+    let error = || format!("bad poster size `{}` (expected WIDTHxHEIGHT, e.g. 3840x2160)", value);
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(error)?;
+    let width: u32 = width.trim().parse().map_err(|_| error())?;
+    let height: u32 = height.trim().parse().map_err(|_| error())?;
+    // PNG allows up to 2^31 - 1 pixels a side.
+    if width == 0 || height == 0 || width > i32::MAX as u32 || height > i32::MAX as u32 {
+        return Err(error());
+    }
+    Ok((width, height))
+}
+
+fn png_error(e: png::EncodingError) -> GameError {
+    GameError::CustomError(format!("PNG encoding failed: {}", e))
+}
+
+impl Poster {
+    pub fn path(&self) -> String {
+        format!("poster-{}x{}.png", self.width, self.height)
+    }
+
+    // `view` widened or heightened about its centre to the poster's aspect ratio.
+    fn fit(&self, view: graphics::Rect) -> graphics::Rect {
+        let aspect = self.width as f32 / self.height as f32;
+        let (w, h) = if aspect > view.w / view.h { (view.h * aspect, view.h) } else { (view.w, view.w / aspect) };
+        graphics::Rect::new(view.x + (view.w - w) / 2.0, view.y + (view.h - h) / 2.0, w, h)
+    }
+
+    // Renders `marks` through `view` into the poster file, blocking until it is written.
+    pub fn render(&self, ctx: &mut Context, view: graphics::Rect, marks: &[Mark], style: &Style) -> GameResult {
+        let meshes = self.build_meshes(ctx, marks, style)?;
+        let format = graphics::get_window_color_format(ctx);
+        // Multisampling is unavailable on GLES; a plain canvas still gets the resolution.
+        let canvas = graphics::Canvas::new(ctx, TILE as u16, TILE as u16, NumSamples::Eight, format)
+            .or_else(|_| graphics::Canvas::new(ctx, TILE as u16, TILE as u16, NumSamples::One, format))?;
+        let written = self.render_tiles(ctx, &canvas, self.fit(view), &meshes, style.background);
+        graphics::set_canvas(ctx, None);
+        camera::reset(ctx)?;
+        written
+    }
+
+    fn build_meshes(&self, ctx: &mut Context, marks: &[Mark], style: &Style) -> GameResult<Vec<graphics::Mesh>> {
+        let mut trails = graphics::MeshBuilder::new();
+        let mut particles = graphics::MeshBuilder::new();
+        let (mut any_trail, mut any_particle) = (false, false);
+        // Sprites would need the window's sprite batch; circles stand in for them.
+        let shape = if style.shape == ParticleShape::Sprite { ParticleShape::Circle } else { style.shape };
+        for mark in marks {
+            if mark.trail.len() >= 2 && mark.trail.iter().all(|p| p.x.is_finite() && p.y.is_finite()) {
+                trail_gradient::append_trail(
+                    &mut trails,
+                    &mark.trail,
+                    self.trail_width,
+                    mark.trail_color,
+                    style.gradient,
+                    style.width,
+                    style.alpha,
+                )?;
+                any_trail = true;
+            }
+            shapes::add_shape(&mut particles, shape, mark.position, mark.radius, mark.color)?;
+            any_particle = true;
+        }
+        let mut meshes = Vec::new();
+        for (builder, any) in [(&trails, any_trail), (&particles, any_particle)] {
+            if any {
+                meshes.push(builder.build(ctx)?);
+            }
+        }
+        Ok(meshes)
+    }
+
+    fn render_tiles(
+        &self,
+        ctx: &mut Context,
+        canvas: &graphics::Canvas,
+        area: graphics::Rect,
+        meshes: &[graphics::Mesh],
+        background: graphics::Color,
+    ) -> GameResult {
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(self.path())?), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(png::Compression::Fast);
+        let mut rows = encoder.write_header().map_err(png_error)?.into_stream_writer().map_err(png_error)?;
+
+        // Projected-view units per poster pixel.
+        let (unit_x, unit_y) = (area.w / self.width as f32, area.h / self.height as f32);
+        let tile_area = |left: u32, top: u32| {
+            let (w, h) = (TILE as f32 * unit_x, TILE as f32 * unit_y);
+            graphics::Rect::new(area.x + left as f32 * unit_x, area.y + top as f32 * unit_y, w, h)
+        };
+        let stride = self.width as usize * 4;
+        for top in (0..self.height).step_by(TILE as usize) {
+            let strip_height = TILE.min(self.height - top) as usize;
+            let mut strip = vec![0u8; stride * strip_height];
+            for left in (0..self.width).step_by(TILE as usize) {
+                graphics::set_canvas(ctx, Some(canvas));
+                graphics::set_screen_coordinates(ctx, tile_area(left, top))?;
+                graphics::clear(ctx, background);
+                for mesh in meshes {
+                    graphics::draw(ctx, mesh, graphics::DrawParam::default())?;
+                }
+                // Resolving the multisampled canvas draws it at its own pixel size.
+                graphics::set_screen_coordinates(ctx, graphics::Rect::new(0.0, 0.0, TILE as f32, TILE as f32))?;
+                let pixels = canvas.to_rgba8(ctx)?;
+                let tile_width = TILE.min(self.width - left) as usize * 4;
+                for row in 0..strip_height {
+                    let from = row * TILE as usize * 4;
+                    let to = row * stride + left as usize * 4;
+                    strip[to..to + tile_width].copy_from_slice(&pixels[from..from + tile_width]);
+                }
+            }
+            // Blending leaves translucent alpha behind soft edges; prints are opaque.
+            for alpha in strip.iter_mut().skip(3).step_by(4) {
+                *alpha = u8::MAX;
+            }
+            rows.write_all(&strip)?;
+        }
+        rows.finish().map_err(png_error)
+    }
+}
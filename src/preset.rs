@@ -0,0 +1,256 @@
+// Filename: preset.rs
+// Project: EntropicRust
+// Description: Presets bundle a system, its parameters and the display settings that make
+//              a scene look the way it does. They are stored as small TOML files in the
+//              `presets` directory, which is scanned at startup:
+//
+//                  # entropicrust preset 1
+//                  system = "lorenz"
+//                  particles = 500
+//                  time_scale = 1.0
+//                  dt = 0.01
+//                  seed_pattern = "random"
+//                  seed_extent = 200.0
+//
+//                  [parameters]
+//                  sigma = 10.0
+//                  rho = 28.0
+//                  beta = 2.6667
+//
+//                  [display]
+//                  trail_gradient = "fire"
+//                  trail_width = "constant"
+//                  particle_shape = "circle"
+//                  halo = "off"
+//
+//              Only this flat subset of TOML is understood; missing keys keep the
+//              current setting when the preset is applied. The first line gives the
+//              format version, see versioning.rs.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+use std::path::Path;
+
+use crate::flat_toml::{self, Entry};
+use crate::particle::SystemType;
+use crate::seeding::SeedPattern;
+use crate::shapes::ParticleShape;
+use crate::system_parameters::{SystemParameters, get_parameter_info, get_parameter_names};
+use crate::trail_gradient::{TrailGradient, TrailWidth};
+use crate::versioning::{self, Migration};
+
+pub const PRESET_DIR: &str = "presets";
+pub const EXTENSION: &str = "toml";
+// Version 1 added the header; the keys are as before.
+const MIGRATIONS: &[Migration<String>] = &[versioning::unchanged];
+
+#[derive(Clone)]
+pub struct Preset {
+    pub name: String,
+    pub system_type: SystemType,
+    // Values for the system's own parameters, in the order of `get_parameter_names`.
+    pub parameters: Vec<(&'static str, f32)>,
+    pub particles: Option<usize>,
+    pub time_scale: Option<f32>,
+    pub dt: Option<f32>,
+    pub seed_pattern: Option<SeedPattern>,
+    pub seed_extent: Option<f32>,
+    pub trail_gradient: Option<TrailGradient>,
+    pub trail_width: Option<TrailWidth>,
+    pub particle_shape: Option<ParticleShape>,
+    // Some(None) switches halos off; None leaves them as they are.
+    pub halo: Option<Option<f32>>,
+}
+
+impl Preset {
+    // Just a system and its parameters: defaults, except for those listed in `values`.
+    // Display settings are left as they are when it is applied.
+    pub fn scene(system_type: SystemType, values: &[(&str, f32)]) -> Preset {
+        Preset {
+            name: system_type.config_name().to_string(),
+            system_type,
+            parameters: get_parameter_info(system_type)
+                .iter()
+                .map(|info| {
+                    let value = values.iter().find(|(name, _)| *name == info.name).map_or(info.default, |&(_, v)| v);
+                    (info.name, value)
+                })
+                .collect(),
+            particles: None,
+            time_scale: None,
+            dt: None,
+            seed_pattern: None,
+            seed_extent: None,
+            trail_gradient: None,
+            trail_width: None,
+            particle_shape: None,
+            halo: None,
+        }
+    }
+
+    pub fn parse(name: &str, text: &str) -> Result<Preset, String> {
+        let mut system_type = None;
+        let mut values: Vec<(String, f32)> = Vec::new();
+        let mut preset = Preset {
+            name: name.to_string(),
+            system_type: SystemType::Lorenz,
+            parameters: Vec::new(),
+            particles: None,
+            time_scale: None,
+            dt: None,
+            seed_pattern: None,
+            seed_extent: None,
+            trail_gradient: None,
+            trail_width: None,
+            particle_shape: None,
+            halo: None,
+        };
+
+        let text = versioning::upgrade_text(text, "preset", MIGRATIONS)?;
+        for Entry { line, section, key, value } in flat_toml::parse(&text)? {
+            let error = |what: &str| format!("line {}: {} `{}`", line, what, value);
+            let number = || value.parse::<f32>().map_err(|_| error("expected a number, got"));
+
+            match (section, key) {
+                ("", "system") => system_type = Some(SystemType::from_name(value).ok_or_else(|| error("unknown system"))?),
+                ("", "particles") => preset.particles = Some(value.parse().map_err(|_| error("expected a count, got"))?),
+                ("", "time_scale") => preset.time_scale = Some(number()?),
+                ("", "dt") => preset.dt = Some(number()?),
+                ("", "seed_pattern") => {
+                    preset.seed_pattern = Some(SeedPattern::from_name(value).ok_or_else(|| error("unknown seed pattern"))?)
+                }
+                ("", "seed_extent") => preset.seed_extent = Some(number()?.max(1.0)),
+                ("parameters", _) => values.push((key.to_string(), number()?)),
+                ("display", "trail_gradient") => {
+                    preset.trail_gradient = Some(TrailGradient::from_name(value).ok_or_else(|| error("unknown gradient"))?)
+                }
+                ("display", "trail_width") => {
+                    preset.trail_width = Some(TrailWidth::from_name(value).ok_or_else(|| error("unknown trail width"))?)
+                }
+                ("display", "particle_shape") => {
+                    preset.particle_shape = Some(ParticleShape::from_name(value).ok_or_else(|| error("unknown shape"))?)
+                }
+                ("display", "halo") => {
+                    preset.halo = Some(match value {
+                        "off" | "none" => None,
+                        _ => Some(number()?),
+                    })
+                }
+                _ => return Err(format!("line {}: unknown key `{}`", line, key)),
+            }
+        }
+
+        preset.system_type = system_type.ok_or("missing `system`")?;
+        for info in get_parameter_info(preset.system_type) {
+            if let Some((_, value)) = values.iter().find(|(key, _)| key == info.name) {
+                preset.parameters.push((info.name, value.clamp(info.min, info.max)));
+            }
+        }
+        let names = get_parameter_names(preset.system_type);
+        if let Some((key, _)) = values.iter().find(|(key, _)| !names.contains(&key.as_str())) {
+            return Err(format!("`{}` is not a parameter of {}", key, preset.system_type.name()));
+        }
+        Ok(preset)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = versioning::header("preset", MIGRATIONS);
+        text += &format!("# {}\n", self.name);
+        text += &format!("system = \"{}\"\n", self.system_type.config_name());
+        if let Some(particles) = self.particles {
+            text += &format!("particles = {}\n", particles);
+        }
+        if let Some(time_scale) = self.time_scale {
+            text += &format!("time_scale = {}\n", time_scale);
+        }
+        if let Some(dt) = self.dt {
+            text += &format!("dt = {}\n", dt);
+        }
+        if let Some(pattern) = self.seed_pattern {
+            text += &format!("seed_pattern = \"{}\"\n", pattern.config_name());
+        }
+        if let Some(extent) = self.seed_extent {
+            text += &format!("seed_extent = {}\n", extent);
+        }
+        text += "\n[parameters]\n";
+        for (name, value) in &self.parameters {
+            text += &format!("{} = {}\n", name, value);
+        }
+        text += "\n[display]\n";
+        if let Some(gradient) = self.trail_gradient {
+            text += &format!("trail_gradient = \"{}\"\n", gradient.name());
+        }
+        if let Some(width) = self.trail_width {
+            text += &format!("trail_width = \"{}\"\n", width.config_name());
+        }
+        if let Some(shape) = self.particle_shape {
+            text += &format!("particle_shape = \"{}\"\n", shape.name());
+        }
+        match self.halo {
+            Some(Some(radius)) => text += &format!("halo = {}\n", radius),
+            Some(None) => text += "halo = \"off\"\n",
+            None => {}
+        }
+        text
+    }
+
+    // Parameter values for the preset's system, starting from `base` for anything omitted.
+    pub fn apply_parameters(&self, base: &SystemParameters) -> SystemParameters {
+        let mut parameters = *base;
+        for (name, value) in &self.parameters {
+            parameters.set(name, *value);
+        }
+        parameters
+    }
+
+    // Writes the preset to `presets/<name>.toml`, creating the directory if needed.
+    pub fn save(&self) -> std::io::Result<String> {
+        fs::create_dir_all(PRESET_DIR)?;
+        let path = Path::new(PRESET_DIR).join(format!("{}.{}", self.name, EXTENSION));
+        fs::write(&path, self.to_toml())?;
+        Ok(path.display().to_string())
+    }
+}
+
+// Loads every `*.toml` in the preset directory, sorted by name. Files that fail to parse are
+// skipped, with a message for each.
+pub fn scan() -> (Vec<Preset>, Vec<String>) {
+    let (mut presets, mut problems) = (Vec::new(), Vec::new());
+    let Ok(entries) = fs::read_dir(PRESET_DIR) else {
+        return (presets, problems);
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+        .collect();
+    paths.sort();
+    for path in &paths {
+        match load(path) {
+            Ok(preset) => presets.push(preset),
+            Err(e) => problems.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    (presets, problems)
+}
+
+// Preset from one file, named after it.
+pub fn load(path: &Path) -> Result<Preset, String> {
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Preset::parse(&name, &text)
+}
+
+// First `<system>-NN` name not already taken in the preset directory.
+pub fn next_name(system_type: SystemType) -> String {
+    (1..)
+        .map(|n| format!("{}-{:02}", system_type.config_name(), n))
+        .find(|name| !Path::new(PRESET_DIR).join(format!("{}.{}", name, EXTENSION)).exists())
+        .unwrap_or_default()
+}
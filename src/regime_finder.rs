@@ -0,0 +1,63 @@
+// Filename: regime_finder.rs
+// Project: EntropicRust
+// Description: Automatic search for interesting regimes. Parameter sets are sampled at
+//              random around the system defaults, runs that diverge or settle onto a
+//              fixed point are discarded, and the survivors are ranked by their largest
+//              Lyapunov exponent to form a small gallery.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rayon::prelude::*;
+
+use crate::particle::SystemType;
+use crate::sweep;
+use crate::system_parameters::{SystemParameters, get_parameter_names};
+
+pub const GALLERY_SIZE: usize = 9;
+const CANDIDATES: u64 = 400;
+const RUN_STEPS: usize = 4_000;
+const RUN_TRANSIENT: usize = 1_000;
+// Runs slower than this on average have come to rest on an equilibrium.
+const MIN_MEAN_SPEED: f32 = 1.0e-3;
+const MIN_EXPONENT: f32 = -0.01;
+
+#[derive(Clone, Copy)]
+pub struct Regime {
+    pub parameters: SystemParameters,
+    pub lyapunov: f32,
+    pub mean_speed: f32,
+}
+
+pub fn search(system_type: SystemType, dt: f32, seed: u64) -> Vec<Regime> {
+    let defaults = SystemParameters::new();
+    let names = get_parameter_names(system_type);
+
+    let mut regimes: Vec<Regime> = (0..CANDIDATES)
+        .into_par_iter()
+        .filter_map(|candidate| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_mul(0x9E37_79B9).wrapping_add(candidate));
+            let mut parameters = defaults;
+            for &name in names.iter() {
+                let value = defaults.get(name).unwrap_or(0.0);
+                parameters.set(name, value * rng.gen_range(0.25..1.75));
+            }
+            let run = sweep::run_single(system_type, &parameters, candidate, RUN_STEPS, RUN_TRANSIENT, dt);
+            (run.bounded && run.mean_speed > MIN_MEAN_SPEED && run.lyapunov > MIN_EXPONENT).then_some(Regime {
+                parameters,
+                lyapunov: run.lyapunov,
+                mean_speed: run.mean_speed,
+            })
+        })
+        .collect();
+
+    regimes.sort_by(|a, b| b.lyapunov.total_cmp(&a.lyapunov));
+    regimes.truncate(GALLERY_SIZE);
+    regimes
+}
@@ -0,0 +1,174 @@
+// Filename: rewind.rs
+// Project: EntropicRust
+// Description: Rewind buffer. While the simulation runs, the state of every particle and
+//              the parameters are sampled ten times a second into a rolling buffer covering
+//              the last half minute. Scrubbing pauses the simulation and puts the cloud
+//              back at any of those moments; resuming carries on from the one shown and
+//              drops the later samples, which the simulation is about to replace.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::locale::Locale;
+use crate::main_state::SCREEN_HEIGHT;
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::system_parameters::SystemParameters;
+
+const SNAPSHOT_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_SNAPSHOTS: usize = 300;
+// Particle states kept over all snapshots, about 30 MB; large clouds get a shorter buffer.
+const MAX_STORED_STATES: usize = 2_000_000;
+
+struct Snapshot {
+    // Simulated seconds since the buffer was started.
+    time: f32,
+    parameters: SystemParameters,
+    // Position and age of each particle alive at the time.
+    particles: Vec<(ParticleId, [f32; 3], f32)>,
+}
+
+pub struct RewindBuffer {
+    // Oldest first.
+    snapshots: VecDeque<Snapshot>,
+    stored_states: usize,
+    time: f32,
+    last_snapshot: Option<Instant>,
+    // Snapshot currently shown while scrubbing.
+    pub cursor: Option<usize>,
+}
+
+impl RewindBuffer {
+    pub fn new() -> Self {
+        RewindBuffer { snapshots: VecDeque::new(), stored_states: 0, time: 0.0, last_snapshot: None, cursor: None }
+    }
+
+    // Forgets everything, for a fresh cloud the old states no longer belong to.
+    pub fn clear(&mut self) {
+        *self = RewindBuffer::new();
+    }
+
+    // Called once per simulation step of `dt` simulated seconds.
+    pub fn record(&mut self, particles: &ParticlePool, parameters: &SystemParameters, dt: f32) {
+        self.time += dt;
+        if self.last_snapshot.is_some_and(|at| at.elapsed() < SNAPSHOT_INTERVAL) {
+            return;
+        }
+        self.last_snapshot = Some(Instant::now());
+        let states: Vec<_> = particles.iter_with_ids().map(|(id, p)| (id, [p.x, p.y, p.z], p.age)).collect();
+        self.stored_states += states.len();
+        self.snapshots.push_back(Snapshot { time: self.time, parameters: *parameters, particles: states });
+        let over_budget = |buffer: &Self| buffer.stored_states > MAX_STORED_STATES && buffer.snapshots.len() > 1;
+        while self.snapshots.len() > MAX_SNAPSHOTS || over_budget(self) {
+            if let Some(oldest) = self.snapshots.pop_front() {
+                self.stored_states -= oldest.particles.len();
+            }
+        }
+    }
+
+    // Moves the cursor by `delta` snapshots (starting from the newest), puts the particles
+    // back as they were there and returns the parameters of that moment.
+    pub fn step(&mut self, delta: isize, particles: &mut ParticlePool) -> Option<SystemParameters> {
+        let last = self.snapshots.len().checked_sub(1)?;
+        let index = self.cursor.unwrap_or(last).saturating_add_signed(delta).min(last);
+        self.show(index, particles)
+    }
+
+    // Jumps to the snapshot nearest to `fraction` of the buffered span.
+    pub fn seek(&mut self, fraction: f32, particles: &mut ParticlePool) -> Option<SystemParameters> {
+        let (first, last) = (self.snapshots.front()?.time, self.snapshots.back()?.time);
+        let target = first + (last - first) * fraction.clamp(0.0, 1.0);
+        let index = self
+            .snapshots
+            .iter()
+            .enumerate()
+            .min_by(|a, b| (a.1.time - target).abs().total_cmp(&(b.1.time - target).abs()))
+            .map(|(i, _)| i)?;
+        self.show(index, particles)
+    }
+
+    fn show(&mut self, index: usize, particles: &mut ParticlePool) -> Option<SystemParameters> {
+        let snapshot = self.snapshots.get(index)?;
+        for &(id, [x, y, z], age) in &snapshot.particles {
+            // Particles despawned since then stay gone.
+            if let Some(particle) = particles.get_mut(id) {
+                (particle.x, particle.y, particle.z, particle.age) = (x, y, z, age);
+                particle.clear_trail();
+            }
+        }
+        self.cursor = Some(index);
+        Some(snapshot.parameters)
+    }
+
+    // Leaves scrubbing, dropping the snapshots after the one shown.
+    pub fn resume(&mut self) {
+        let Some(index) = self.cursor.take() else {
+            return;
+        };
+        while self.snapshots.len() > index + 1 {
+            if let Some(newest) = self.snapshots.pop_back() {
+                self.stored_states -= newest.particles.len();
+            }
+        }
+        if let Some(snapshot) = self.snapshots.back() {
+            self.time = snapshot.time;
+        }
+    }
+
+    // Above the parameter history bar so both can show at once.
+    pub fn bar() -> graphics::Rect {
+        graphics::Rect::new(20.0, SCREEN_HEIGHT - 110.0, 400.0, 10.0)
+    }
+
+    pub fn draw(&self, ctx: &mut Context, locale: Locale) -> GameResult {
+        let (Some(index), Some(first), Some(last)) = (self.cursor, self.snapshots.front(), self.snapshots.back())
+        else {
+            return Ok(());
+        };
+        let bar = Self::bar();
+        let span = (last.time - first.time).max(f32::EPSILON);
+        let at = self.snapshots[index].time;
+        let x = bar.x + bar.w * (at - first.time) / span;
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), bar, graphics::Color::new(0.0, 0.0, 0.0, 0.6))?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), bar, graphics::Color::WHITE)?;
+        builder.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(bar.x, bar.y, x - bar.x, bar.h),
+            graphics::Color::new(0.4, 0.8, 1.0, 0.5),
+        )?;
+        builder.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(x - 2.0, bar.y - 3.0, 4.0, bar.h + 6.0),
+            graphics::Color::new(1.0, 0.8, 0.2, 1.0),
+        )?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let label = graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "Rewind: {} s back of {} s (←/→ step, Shift by 1 s, click to jump, Ctrl+Z to resume)",
+                locale.fixed(last.time - at, 1),
+                locale.fixed(span, 1)
+            ))
+            .font(graphics::Font::default())
+            .scale(graphics::PxScale::from(14.0)),
+        );
+        graphics::draw(
+            ctx,
+            &label,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: bar.x, y: bar.y - 18.0 })
+                .color(graphics::Color::WHITE),
+        )
+    }
+}
@@ -0,0 +1,122 @@
+// Filename: ribbon.rs
+// Project: EntropicRust
+// Description: Depth-shaded ribbon trails. Depth is taken along the system's view
+//              direction, and each trail is drawn as a camera-facing strip whose width
+//              and brightness grow towards the viewer, lit with the Kajiya–Kay diffuse
+//              term for thin fibres; trails are emitted far-to-near so closer ribbons
+//              cover farther ones.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, GameResult};
+
+use crate::particle::SystemType;
+use crate::particle_pool::ParticlePool;
+use crate::system_parameters::get_view_preset;
+use crate::trail_gradient::{TrailAlpha, TrailGradient};
+
+pub const RIBBON_WIDTH: f32 = 3.0;
+// Light from the upper left, tilted towards the viewer.
+const LIGHT: [f32; 3] = [-0.4, -0.6, 0.69];
+const AMBIENT: f32 = 0.3;
+
+pub struct RibbonView {
+    pub scale: f32,
+    pub far: f32,
+    pub near: f32,
+}
+
+impl RibbonView {
+    // Depth range of the current cloud. Driven systems carry a phase in z, which has no
+    // depth to show, so they get no ribbon view.
+    pub fn from_particles(particles: &ParticlePool, system_type: SystemType) -> Option<RibbonView> {
+        if system_type.is_driven() {
+            return None;
+        }
+        let view = get_view_preset(system_type);
+        let (far, near) = particles
+            .iter()
+            .map(|p| view.depth([p.x, p.y, p.z]))
+            .filter(|z| z.is_finite())
+            .fold((f32::MAX, f32::MIN), |(lo, hi), z| (lo.min(z), hi.max(z)));
+        (far <= near).then_some(RibbonView {
+            scale: view.scale,
+            far,
+            near,
+        })
+    }
+
+    // 0 at the back of the cloud, 1 at the front.
+    pub fn nearness(&self, z: f32) -> f32 {
+        let span = self.near - self.far;
+        if span <= f32::EPSILON { 0.5 } else { ((z - self.far) / span).clamp(0.0, 1.0) }
+    }
+}
+
+// Appends one trail; `depths` holds the z of each point in `points`.
+pub fn append_ribbon(
+    builder: &mut graphics::MeshBuilder,
+    points: &[Point2<f32>],
+    depths: &[f32],
+    view: &RibbonView,
+    head: graphics::Color,
+    gradient: TrailGradient,
+    alpha: TrailAlpha,
+) -> GameResult {
+    let n = points.len().min(depths.len());
+    if n < 2 {
+        return Ok(());
+    }
+    // Align on the newest point should the two histories ever differ in length.
+    let points = &points[points.len() - n..];
+    let depths = &depths[depths.len() - n..];
+
+    let mut vertices = Vec::with_capacity(n * 2);
+    for i in 0..n {
+        let (before, after) = (i.saturating_sub(1), (i + 1).min(n - 1));
+        let tangent = [
+            points[after].x - points[before].x,
+            points[after].y - points[before].y,
+            (depths[after] - depths[before]) * view.scale,
+        ];
+        let length = tangent.iter().map(|t| t * t).sum::<f32>().sqrt();
+        let screen_length = (tangent[0] * tangent[0] + tangent[1] * tangent[1]).sqrt();
+
+        let near = view.nearness(depths[i]);
+        let half = 0.5 * RIBBON_WIDTH * (0.5 + near);
+        let (nx, ny) = if screen_length > f32::EPSILON {
+            (-tangent[1] / screen_length * half, tangent[0] / screen_length * half)
+        } else {
+            (0.0, 0.0)
+        };
+
+        // Kajiya–Kay: a fibre's diffuse term depends only on the angle to its tangent.
+        let diffuse = if length > f32::EPSILON {
+            let cos = tangent.iter().zip(LIGHT).map(|(t, l)| t * l).sum::<f32>() / length;
+            (1.0 - cos * cos).max(0.0).sqrt()
+        } else {
+            1.0
+        };
+        let shade = (AMBIENT + (1.0 - AMBIENT) * diffuse) * (0.55 + 0.45 * near);
+
+        let age = 1.0 - i as f32 / (n - 1) as f32;
+        let base = gradient.color(head, age);
+        let color = [base.r * shade, base.g * shade, base.b * shade, base.a * alpha.at(age)];
+        let point = points[i];
+        vertices.push(graphics::Vertex { pos: [point.x + nx, point.y + ny], uv: [0.0, 0.0], color });
+        vertices.push(graphics::Vertex { pos: [point.x - nx, point.y - ny], uv: [0.0, 0.0], color });
+    }
+    let mut indices = Vec::with_capacity((n - 1) * 6);
+    for i in 0..(n as u32 - 1) {
+        let (a, b, c, d) = (2 * i, 2 * i + 1, 2 * i + 2, 2 * i + 3);
+        indices.extend_from_slice(&[a, b, c, b, d, c]);
+    }
+    builder.raw(&vertices, &indices, None)?;
+    Ok(())
+}
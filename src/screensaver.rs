@@ -0,0 +1,142 @@
+// Filename: screensaver.rs
+// Project: EntropicRust
+// Description: Screensaver (attract) mode. After a stretch without input, or from launch
+//              with `--screensaver`, the view cycles through a curated list of systems and
+//              parameter sets followed by the user's presets, slowly turning the picture
+//              about the window centre with the UI hidden. Any input leaves the mode and
+//              restores the scene it interrupted; a screensaver started from the command
+//              line quits instead.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::time::Instant;
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::camera::Camera;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::preset::Preset;
+
+pub const DEFAULT_INTERVAL_SECS: f32 = 20.0;
+// Mouse moves smaller than this, in pixels per event, do not count as input.
+pub const MOTION_THRESHOLD_PX: f32 = 3.0;
+const ROTATION_DEG_PER_SEC: f32 = 4.0;
+// Input right after the mode starts is ignored, so the mouse settling after a launch or
+// the tail of a key press does not end it at once.
+const GRACE_SECS: f32 = 1.0;
+
+// Each system's classic regime, with a few well-known alternatives in between. Parameters
+// not listed take their defaults.
+const SCENES: [(SystemType, &[(&str, f32)]); 13] = [
+    (SystemType::Lorenz, &[]),
+    (SystemType::Rossler, &[]),
+    (SystemType::Aizawa, &[]),
+    // Periodic window: a single closed orbit knotted around both wings.
+    (SystemType::Lorenz, &[("rho", 99.96)]),
+    (SystemType::ChenLee, &[]),
+    (SystemType::Rossler, &[("c", 9.0)]),
+    (SystemType::RabinovichFabrikant, &[]),
+    (SystemType::NewtonLeipnik, &[]),
+    (SystemType::FourWing, &[]),
+    (SystemType::Sakarya, &[]),
+    (SystemType::FoodChain, &[]),
+    (SystemType::Duffing, &[]),
+    (SystemType::VanDerPol, &[]),
+];
+
+pub struct Screensaver {
+    playlist: Vec<Preset>,
+    index: usize,
+    started: Instant,
+    // When the current scene came up.
+    shown: Instant,
+    // Launched with `--screensaver`: input quits rather than returning to the scene.
+    pub quit_on_input: bool,
+    // Scene, camera and pause state to go back to.
+    pub interrupted: Preset,
+    pub camera: Camera,
+    pub paused: bool,
+    // The picture is drawn offscreen and turned as a whole, trails included.
+    canvas: Option<graphics::Canvas>,
+}
+
+impl Screensaver {
+    pub fn new(presets: &[Preset], interrupted: Preset, camera: Camera, paused: bool, quit_on_input: bool) -> Self {
+        let mut playlist: Vec<Preset> =
+            SCENES.iter().map(|&(system_type, values)| Preset::scene(system_type, values)).collect();
+        playlist.extend_from_slice(presets);
+        Screensaver {
+            playlist,
+            index: 0,
+            started: Instant::now(),
+            shown: Instant::now(),
+            quit_on_input,
+            interrupted,
+            camera,
+            paused,
+            canvas: None,
+        }
+    }
+
+    pub fn current(&self) -> &Preset {
+        &self.playlist[self.index]
+    }
+
+    pub fn in_grace_period(&self) -> bool {
+        self.started.elapsed().as_secs_f32() < GRACE_SECS
+    }
+
+    // Moves to the next scene once the current one has been up for `interval` seconds.
+    pub fn advance(&mut self, interval: f32) -> Option<&Preset> {
+        if self.shown.elapsed().as_secs_f32() < interval {
+            return None;
+        }
+        self.index = (self.index + 1) % self.playlist.len();
+        self.shown = Instant::now();
+        Some(self.current())
+    }
+
+    // Sends the attractor layer to the offscreen canvas. Called again after anything in
+    // that layer that switches canvases itself.
+    pub fn redirect(&mut self, ctx: &mut Context) -> GameResult {
+        if self.canvas.is_none() {
+            self.canvas = Some(graphics::Canvas::with_window_size(ctx)?);
+        }
+        graphics::set_canvas(ctx, self.canvas.as_ref());
+        Ok(())
+    }
+
+    // Clears the offscreen canvas; call once per frame before the attractor layer.
+    pub fn begin(&mut self, ctx: &mut Context, background: graphics::Color) -> GameResult {
+        self.redirect(ctx)?;
+        graphics::clear(ctx, background);
+        Ok(())
+    }
+
+    // Draws the canvas to the window, turned about its centre, in window coordinates.
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        graphics::set_canvas(ctx, None);
+        let Some(canvas) = &self.canvas else {
+            return Ok(());
+        };
+        let (w, h) = (canvas.width() as f32, canvas.height() as f32);
+        let angle = (self.started.elapsed().as_secs_f32() * ROTATION_DEG_PER_SEC).to_radians();
+        // The offset is in canvas pixels, before the scale to window coordinates.
+        graphics::draw(
+            ctx,
+            canvas,
+            graphics::DrawParam::default()
+                .dest([SCREEN_WIDTH / 2.0, SCREEN_HEIGHT / 2.0])
+                .offset([w / 2.0, h / 2.0])
+                .scale([SCREEN_WIDTH / w, SCREEN_HEIGHT / h])
+                .rotation(angle),
+        )
+    }
+}
@@ -0,0 +1,131 @@
+// Filename: seeding.rs
+// Project: EntropicRust
+// Description: Initial-condition patterns. Besides the random box each system defines,
+//              particles can be seeded on a line, a square grid, a circle or a small sphere
+//              shell around the centre of that box; structured ensembles make the
+//              stretching and folding of the flow easy to follow.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::particle::SystemType;
+use crate::system_parameters::{get_initial_ranges, get_scale_factor};
+
+pub const DEFAULT_EXTENT_PX: f32 = 40.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SeedPattern {
+    RandomBox,
+    Line,
+    Grid,
+    Circle,
+    Shell,
+}
+
+impl SeedPattern {
+    pub fn from_name(name: &str) -> Option<SeedPattern> {
+        match name.to_ascii_lowercase().as_str() {
+            "random" | "box" => Some(SeedPattern::RandomBox),
+            "line" => Some(SeedPattern::Line),
+            "grid" => Some(SeedPattern::Grid),
+            "circle" => Some(SeedPattern::Circle),
+            "shell" | "sphere" => Some(SeedPattern::Shell),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SeedPattern::RandomBox => "random box",
+            SeedPattern::Line => "line",
+            SeedPattern::Grid => "grid",
+            SeedPattern::Circle => "circle",
+            SeedPattern::Shell => "sphere shell",
+        }
+    }
+
+    // Spelling accepted by `from_name`, for config and preset files.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            SeedPattern::RandomBox => "random",
+            SeedPattern::Line => "line",
+            SeedPattern::Grid => "grid",
+            SeedPattern::Circle => "circle",
+            SeedPattern::Shell => "shell",
+        }
+    }
+
+    pub fn next(self) -> SeedPattern {
+        match self {
+            SeedPattern::RandomBox => SeedPattern::Line,
+            SeedPattern::Line => SeedPattern::Grid,
+            SeedPattern::Grid => SeedPattern::Circle,
+            SeedPattern::Circle => SeedPattern::Shell,
+            SeedPattern::Shell => SeedPattern::RandomBox,
+        }
+    }
+}
+
+// `count` start states; structured patterns span `extent_px` screen pixels from the centre.
+pub fn positions<R: Rng>(
+    pattern: SeedPattern,
+    system_type: SystemType,
+    count: usize,
+    extent_px: f32,
+    rng: &mut R,
+) -> Vec<[f32; 3]> {
+    let (x_range, y_range, z_range) = get_initial_ranges(system_type);
+    if pattern == SeedPattern::RandomBox {
+        return (0..count)
+            .map(|_| [rng.gen_range(x_range.clone()), rng.gen_range(y_range.clone()), rng.gen_range(z_range.clone())])
+            .collect();
+    }
+
+    let center = [
+        (x_range.start + x_range.end) / 2.0,
+        (y_range.start + y_range.end) / 2.0,
+        (z_range.start + z_range.end) / 2.0,
+    ];
+    let extent = extent_px / get_scale_factor(system_type);
+    // Position of the i-th of n evenly spaced points across [-1, 1].
+    let spread = |i: usize, n: usize| if n <= 1 { 0.0 } else { 2.0 * i as f32 / (n - 1) as f32 - 1.0 };
+
+    (0..count)
+        .map(|i| match pattern {
+            SeedPattern::Line => [center[0] + extent * spread(i, count), center[1], center[2]],
+            SeedPattern::Grid => {
+                let side = (count as f32).sqrt().ceil() as usize;
+                [
+                    center[0] + extent * spread(i % side, side),
+                    center[1] + extent * spread(i / side, side),
+                    center[2],
+                ]
+            }
+            SeedPattern::Circle => {
+                let angle = TAU * i as f32 / count as f32;
+                [center[0] + extent * angle.cos(), center[1] + extent * angle.sin(), center[2]]
+            }
+            SeedPattern::Shell => {
+                // Fibonacci lattice: near-uniform coverage for any count.
+                let h = spread(i, count);
+                let ring = (1.0 - h * h).sqrt();
+                let angle = i as f32 * TAU * 0.618_034;
+                [
+                    center[0] + extent * ring * angle.cos(),
+                    center[1] + extent * ring * angle.sin(),
+                    center[2] + extent * h,
+                ]
+            }
+            SeedPattern::RandomBox => center,
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+// Filename: sensitivity.rs
+// Project: EntropicRust
+// Description: Sensitivity-to-initial-conditions demo. Two highlighted particles start
+//              1e-6 apart; their separation is recorded on a log scale and the slope of
+//              the growth phase gives a rough estimate of the largest Lyapunov exponent.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::stats::linear_fit;
+
+pub const INITIAL_SEPARATION: f32 = 1.0e-6;
+const MAX_SAMPLES: usize = 2_000;
+// Separations past this fraction of the attractor size are saturated and excluded from
+// the slope fit.
+const SATURATION_FRACTION: f32 = 0.1;
+
+pub struct SensitivityDemo {
+    pub pair: [ParticleId; 2],
+    pub elapsed: f32,
+    pub samples: Vec<[f32; 2]>,
+}
+
+impl SensitivityDemo {
+    pub fn spawn(particles: &mut ParticlePool, start: [f32; 3]) -> Self {
+        let a = particles.spawn(start[0], start[1], start[2]);
+        let b = particles.spawn(start[0] + INITIAL_SEPARATION, start[1], start[2]);
+        SensitivityDemo {
+            pair: [a, b],
+            elapsed: 0.0,
+            samples: Vec::with_capacity(MAX_SAMPLES),
+        }
+    }
+
+    pub fn contains(&self, id: ParticleId) -> bool {
+        self.pair.contains(&id)
+    }
+
+    pub fn record(&mut self, particles: &ParticlePool, dt: f32) -> bool {
+        let (Some(a), Some(b)) = (particles.get(self.pair[0]), particles.get(self.pair[1])) else {
+            return false;
+        };
+        self.elapsed += dt;
+        let dx = a.x - b.x;
+        let dy = a.y - b.y;
+        let dz = a.z - b.z;
+        let separation = (dx * dx + dy * dy + dz * dz).sqrt();
+        if separation.is_finite() && separation > 0.0 && self.samples.len() < MAX_SAMPLES {
+            self.samples.push([self.elapsed, separation.log10()]);
+        }
+        true
+    }
+
+    pub fn growth_rate(&self, attractor_size: f32) -> Option<f32> {
+        let ceiling = (attractor_size * SATURATION_FRACTION).log10();
+        let end = self
+            .samples
+            .iter()
+            .position(|s| s[1] > ceiling)
+            .unwrap_or(self.samples.len());
+        let growth = &self.samples[..end];
+        let times: Vec<f32> = growth.iter().map(|s| s[0]).collect();
+        let logs: Vec<f32> = growth.iter().map(|s| s[1]).collect();
+        // Slope is in decades per unit time; convert to natural-log units.
+        linear_fit(&times, &logs).map(|(slope, _)| slope * std::f32::consts::LN_10)
+    }
+}
@@ -0,0 +1,126 @@
+// Filename: shapes.rs
+// Project: EntropicRust
+// Description: Particle shapes. Particles can be drawn as circles, squares, triangles or
+//              copies of a user-supplied PNG sprite; the choice is kept per system group so
+//              overlaid systems stay distinguishable in recordings.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{
+    graphics::{self, spritebatch::SpriteBatch},
+    mint::Point2,
+    Context, GameResult,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ParticleShape {
+    Circle,
+    Square,
+    Triangle,
+    Sprite,
+}
+
+impl ParticleShape {
+    pub fn from_name(name: &str) -> Option<ParticleShape> {
+        match name.to_ascii_lowercase().as_str() {
+            "circle" => Some(ParticleShape::Circle),
+            "square" => Some(ParticleShape::Square),
+            "triangle" => Some(ParticleShape::Triangle),
+            "sprite" | "png" => Some(ParticleShape::Sprite),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ParticleShape::Circle => "circle",
+            ParticleShape::Square => "square",
+            ParticleShape::Triangle => "triangle",
+            ParticleShape::Sprite => "sprite",
+        }
+    }
+
+    pub fn next(self) -> ParticleShape {
+        match self {
+            ParticleShape::Circle => ParticleShape::Square,
+            ParticleShape::Square => ParticleShape::Triangle,
+            ParticleShape::Triangle => ParticleShape::Sprite,
+            ParticleShape::Sprite => ParticleShape::Circle,
+        }
+    }
+}
+
+// Adds one vector-drawn particle of the given radius. Sprites go through `ParticleSprite`
+// instead and are drawn here as circles.
+pub fn add_shape(
+    builder: &mut graphics::MeshBuilder,
+    shape: ParticleShape,
+    center: Point2<f32>,
+    radius: f32,
+    color: graphics::Color,
+) -> GameResult {
+    match shape {
+        ParticleShape::Circle | ParticleShape::Sprite => {
+            builder.circle(graphics::DrawMode::fill(), center, radius, 0.1, color)?;
+        }
+        ParticleShape::Square => {
+            builder.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(center.x - radius, center.y - radius, 2.0 * radius, 2.0 * radius),
+                color,
+            )?;
+        }
+        ParticleShape::Triangle => {
+            let r = radius * 1.3;
+            builder.polygon(
+                graphics::DrawMode::fill(),
+                &[
+                    Point2 { x: center.x, y: center.y - r },
+                    Point2 { x: center.x + 0.866 * r, y: center.y + 0.5 * r },
+                    Point2 { x: center.x - 0.866 * r, y: center.y + 0.5 * r },
+                ],
+                color,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub struct ParticleSprite {
+    batch: SpriteBatch,
+    size: f32,
+}
+
+impl ParticleSprite {
+    // Reads the PNG from a plain filesystem path rather than ggez's resource directory.
+    pub fn load(ctx: &mut Context, path: &str) -> GameResult<Self> {
+        let bytes = std::fs::read(path).map_err(|e| ggez::GameError::ResourceLoadError(format!("{}: {}", path, e)))?;
+        let image = graphics::Image::from_bytes(ctx, &bytes)?;
+        let size = image.width().max(image.height()).max(1) as f32;
+        Ok(ParticleSprite { batch: SpriteBatch::new(image), size })
+    }
+
+    // Queues one copy scaled so its longer side spans the particle diameter.
+    pub fn add(&mut self, center: Point2<f32>, radius: f32, color: graphics::Color) {
+        let scale = 2.0 * radius / self.size;
+        self.batch.add(
+            graphics::DrawParam::default()
+                .dest(center)
+                .offset([0.5, 0.5])
+                .scale([scale, scale])
+                .color(color),
+        );
+    }
+
+    pub fn flush(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::draw(ctx, &self.batch, graphics::DrawParam::default())?;
+        self.batch.clear();
+        Ok(())
+    }
+}
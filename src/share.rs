@@ -0,0 +1,167 @@
+// Filename: share.rs
+// Project: EntropicRust
+// Description: Shareable state strings. A setup (system, parameters, seeding, time step and
+//              display settings, i.e. everything a preset holds) is packed into a few dozen
+//              bytes and written as URL-safe base64, short enough to paste into a chat
+//              message or an issue and decode back into exactly the same scene.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use crate::particle::SystemType;
+use crate::preset::Preset;
+use crate::seeding::SeedPattern;
+use crate::shapes::ParticleShape;
+use crate::system_parameters::get_parameter_info;
+use crate::trail_gradient::{TrailGradient, TrailWidth};
+use crate::versioning::{self, Migration};
+
+pub const SHARE_PATH: &str = "state.txt";
+// Rewrite the bytes after the version of one version into the next. State strings began
+// at version 1, so the first entry never runs.
+const MIGRATIONS: &[Migration<Vec<u8>>] = &[versioning::unchanged];
+const VERSION: u8 = MIGRATIONS.len() as u8;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Position of `value` in the cycle that `next` walks from `first`; unset values encode as
+// the first entry.
+fn cycle_index<T: Copy + PartialEq>(first: T, value: Option<T>, next: fn(T) -> T) -> u8 {
+    let value = value.unwrap_or(first);
+    let mut current = first;
+    for index in 0..u8::MAX {
+        if current == value {
+            return index;
+        }
+        current = next(current);
+    }
+    0
+}
+
+fn cycle_value<T: Copy>(first: T, index: u8, next: fn(T) -> T) -> T {
+    (0..index).fold(first, |value, _| next(value))
+}
+
+pub fn encode(preset: &Preset) -> String {
+    let mut bytes = vec![VERSION];
+    let system = SystemType::ALL.iter().position(|&s| s == preset.system_type).unwrap_or(0);
+    bytes.push(system as u8);
+    let info = get_parameter_info(preset.system_type);
+    bytes.push(info.len() as u8);
+    for parameter in info {
+        let value = preset
+            .parameters
+            .iter()
+            .find(|(name, _)| *name == parameter.name)
+            .map_or(parameter.default, |&(_, value)| value);
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(preset.particles.unwrap_or(0) as u32).to_le_bytes());
+    for value in [preset.time_scale, preset.dt, preset.seed_extent] {
+        bytes.extend_from_slice(&value.unwrap_or(0.0).to_le_bytes());
+    }
+    bytes.extend([
+        cycle_index(SeedPattern::RandomBox, preset.seed_pattern, SeedPattern::next),
+        cycle_index(TrailGradient::Solid, preset.trail_gradient, TrailGradient::next),
+        cycle_index(TrailWidth::Constant, preset.trail_width, TrailWidth::next),
+        cycle_index(ParticleShape::Circle, preset.particle_shape, ParticleShape::next),
+    ]);
+    bytes.extend_from_slice(&preset.halo.flatten().unwrap_or(0.0).to_le_bytes());
+    to_base64(&bytes)
+}
+
+struct Reader<'a> {
+    bytes: std::slice::Iter<'a, u8>,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut out = [0u8; N];
+        for slot in out.iter_mut() {
+            *slot = *self.bytes.next().ok_or("state string is truncated")?;
+        }
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+}
+
+pub fn decode(text: &str) -> Result<Preset, String> {
+    let bytes = from_base64(text.trim())?;
+    let (&version, payload) = bytes.split_first().ok_or("state string is empty")?;
+    if version == 0 {
+        return Err("state string has no valid version".to_string());
+    }
+    let bytes = versioning::upgrade(version as usize, payload.to_vec(), MIGRATIONS)?;
+    let mut reader = Reader { bytes: bytes.iter() };
+    let system_type = *SystemType::ALL.get(reader.u8()? as usize).ok_or("unknown system in state string")?;
+    let info = get_parameter_info(system_type);
+    if reader.u8()? as usize != info.len() {
+        return Err("parameter count does not match the system".to_string());
+    }
+    let mut parameters = Vec::with_capacity(info.len());
+    for parameter in info {
+        parameters.push((parameter.name, reader.f32()?.clamp(parameter.min, parameter.max)));
+    }
+    let particles = reader.u32()? as usize;
+    let (time_scale, dt, seed_extent) = (reader.f32()?, reader.f32()?, reader.f32()?);
+    let indices = [reader.u8()?, reader.u8()?, reader.u8()?, reader.u8()?];
+    let halo = reader.f32()?;
+    // Zero marks a value the encoder did not have.
+    let positive = |value: f32| (value > 0.0 && value.is_finite()).then_some(value);
+
+    Ok(Preset {
+        name: "shared".to_string(),
+        system_type,
+        parameters,
+        particles: (particles > 0).then_some(particles),
+        time_scale: positive(time_scale),
+        dt: positive(dt),
+        seed_pattern: Some(cycle_value(SeedPattern::RandomBox, indices[0], SeedPattern::next)),
+        seed_extent: positive(seed_extent),
+        trail_gradient: Some(cycle_value(TrailGradient::Solid, indices[1], TrailGradient::next)),
+        trail_width: Some(cycle_value(TrailWidth::Constant, indices[2], TrailWidth::next)),
+        particle_shape: Some(cycle_value(ParticleShape::Circle, indices[3], ParticleShape::next)),
+        halo: Some(positive(halo)),
+    })
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let word = chunk.iter().enumerate().fold(0u32, |word, (i, &b)| word | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(word >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    text
+}
+
+fn from_base64(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut word, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = ALPHABET.iter().position(|&a| a == c).ok_or_else(|| format!("invalid character `{}`", c as char))?;
+        word = word << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((word >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
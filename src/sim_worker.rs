@@ -0,0 +1,140 @@
+// Filename: sim_worker.rs
+// Project: EntropicRust
+// Description: Double-buffered background stepping. A snapshot of particle state is
+//              integrated on a long-lived worker thread while the previous frame renders;
+//              results are matched back to particles by handle, so particles respawned in
+//              the meantime are simply skipped.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::dynamics::{self, Integrator};
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+use crate::system_parameters::{Jitter, SystemParameters};
+
+pub struct StepJob {
+    pub system_type: SystemType,
+    pub dt: f32,
+    pub ids: Vec<ParticleId>,
+    pub states: Vec<[f32; 3]>,
+    pub jitters: Vec<Option<Jitter>>,
+    pub errors: Vec<Option<f32>>,
+}
+
+// A job and the settings it is stepped with.
+struct Request {
+    job: StepJob,
+    parameters: SystemParameters,
+    integrator: Integrator,
+    noise: f32,
+}
+
+// One thread lives as long as the worker and steps each job it is sent; it ends when the
+// job channel is dropped along with the worker.
+pub struct SimWorker {
+    jobs: Sender<Request>,
+    results: Receiver<StepJob>,
+    in_flight: bool,
+    spare: Option<StepJob>,
+}
+
+impl SimWorker {
+    pub fn new() -> Self {
+        let (jobs, results) = spawn_thread();
+        SimWorker {
+            jobs,
+            results,
+            in_flight: false,
+            spare: None,
+        }
+    }
+
+    pub fn launch(
+        &mut self,
+        particles: &ParticlePool,
+        system_type: SystemType,
+        parameters: SystemParameters,
+        dt: f32,
+        integrator: Integrator,
+        noise: f32,
+    ) {
+        if self.in_flight {
+            return;
+        }
+
+        let mut job = self.spare.take().unwrap_or(StepJob {
+            system_type,
+            dt,
+            ids: Vec::new(),
+            states: Vec::new(),
+            jitters: Vec::new(),
+            errors: Vec::new(),
+        });
+        job.system_type = system_type;
+        job.dt = dt;
+        job.ids.clear();
+        job.states.clear();
+        job.jitters.clear();
+        job.errors.clear();
+        for (id, particle) in particles.iter_with_ids().filter(|(_, p)| !p.pinned) {
+            job.ids.push(id);
+            job.states.push([particle.x, particle.y, particle.z]);
+            job.jitters.push(particle.jitter);
+        }
+
+        let request = Request { job, parameters, integrator, noise };
+        // The thread is only gone if a step panicked; start a new one and send it there.
+        if let Err(mpsc::SendError(request)) = self.jobs.send(request) {
+            (self.jobs, self.results) = spawn_thread();
+            if self.jobs.send(request).is_err() {
+                return;
+            }
+        }
+        self.in_flight = true;
+    }
+
+    pub fn collect(&mut self) -> Result<Option<StepJob>, String> {
+        if !self.in_flight {
+            return Ok(None);
+        }
+        self.in_flight = false;
+        match self.results.recv() {
+            Ok(job) => Ok(Some(job)),
+            Err(_) => Err("Simulation worker panicked; dropping its step".to_string()),
+        }
+    }
+
+    pub fn recycle(&mut self, job: StepJob) {
+        self.spare = Some(job);
+    }
+}
+
+fn spawn_thread() -> (Sender<Request>, Receiver<StepJob>) {
+    let (jobs, incoming) = mpsc::channel::<Request>();
+    let (outgoing, results) = mpsc::channel();
+    thread::spawn(move || {
+        let mut rng = rand::thread_rng();
+        for Request { mut job, parameters, integrator, noise } in incoming {
+            for (state, jitter) in job.states.iter_mut().zip(&job.jitters) {
+                let params = jitter.map_or(parameters, |j| parameters.jittered(job.system_type, &j));
+                let (next, error) =
+                    dynamics::step_noisy(job.system_type, &params, *state, job.dt, integrator, noise, &mut rng);
+                *state = next;
+                job.errors.push(error);
+            }
+            if outgoing.send(job).is_err() {
+                break;
+            }
+        }
+    });
+    (jobs, results)
+}
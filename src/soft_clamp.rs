@@ -0,0 +1,73 @@
+// Filename: soft_clamp.rs
+// Project: EntropicRust
+// Description: Soft clamping of runaway trajectories. Beyond a radius around the centre of
+//              the system's view, the outward part of each particle's step is damped more
+//              and more strongly the further out it is, while inward and tangential motion
+//              is left alone. Particles thrown out by unstable parameter combinations then
+//              pile up near a ring drawn at the radius instead of leaving the screen, and
+//              fall back in as soon as the dynamics carry them inwards.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::system_parameters::get_view_preset;
+use crate::theme::Theme;
+
+pub const DEFAULT_RADIUS_PX: f32 = 280.0;
+pub const MIN_RADIUS_PX: f32 = 20.0;
+// Distance past the radius, as a fraction of it, at which outward speed is halved.
+const SOFTNESS: f32 = 0.25;
+
+#[derive(Clone, Copy)]
+pub struct SoftClamp {
+    pub radius_px: f32,
+}
+
+impl SoftClamp {
+    // `next` with the outward part of the step from `previous` damped. Forced oscillators
+    // are clamped in (x, x') only; their phase is a clock.
+    pub fn apply(&self, system_type: SystemType, previous: [f32; 3], next: [f32; 3]) -> [f32; 3] {
+        let view = get_view_preset(system_type);
+        let axes = if system_type.is_driven() { 2 } else { 3 };
+        let offset: Vec<f32> = (0..axes).map(|i| next[i] - view.center[i]).collect();
+        let distance = offset.iter().map(|d| d * d).sum::<f32>().sqrt();
+        let radius = self.radius_px / view.scale;
+        if !distance.is_finite() || distance <= radius {
+            return next;
+        }
+        let outward = (0..axes).map(|i| (next[i] - previous[i]) * offset[i] / distance).sum::<f32>();
+        if outward <= 0.0 {
+            return next;
+        }
+        // Smooth at the radius, and falling like the inverse square of the overshoot past it.
+        let excess = (distance - radius) / (radius * SOFTNESS);
+        let removed = outward * (1.0 - 1.0 / (1.0 + excess * excess));
+        let mut damped = next;
+        for i in 0..axes {
+            damped[i] -= removed * offset[i] / distance;
+        }
+        damped
+    }
+
+    // Ring at the radius; drawn with the attractor camera applied.
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let ring = graphics::Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::stroke(1.0),
+            Point2 { x: SCREEN_WIDTH / 2.0, y: SCREEN_HEIGHT / 2.0 },
+            self.radius_px,
+            0.5,
+            graphics::Color::new(theme.frame.r, theme.frame.g, theme.frame.b, 0.5),
+        )?;
+        graphics::draw(ctx, &ring, graphics::DrawParam::default())
+    }
+}
@@ -0,0 +1,93 @@
+// Filename: spatial_hash.rs
+// Project: EntropicRust
+// Description: Uniform-grid spatial hash over particle positions in system space. It is
+//              rebuilt each frame and answers radius queries by only visiting the cells
+//              that overlap the query sphere.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::HashMap;
+
+use crate::particle_pool::{ParticleId, ParticlePool};
+
+type CellKey = (i32, i32, i32);
+
+// Cell coordinates are clamped to this range, so far-away particles share the edge cells
+// and the query ranges below stay well clear of i32 overflow.
+const MAX_CELL: i32 = 1 << 24;
+
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<CellKey, Vec<(ParticleId, [f32; 3])>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialHash {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn key(&self, pos: [f32; 3]) -> CellKey {
+        let cell = |v: f32| ((v / self.cell_size).floor() as i32).clamp(-MAX_CELL, MAX_CELL);
+        (cell(pos[0]), cell(pos[1]), cell(pos[2]))
+    }
+
+    pub fn rebuild(&mut self, particles: &ParticlePool, cell_size: f32) {
+        self.cell_size = cell_size.max(f32::EPSILON);
+        // Keep the per-cell buffers around; only drop cells that went unused.
+        self.cells.retain(|_, entries| !entries.is_empty());
+        for entries in self.cells.values_mut() {
+            entries.clear();
+        }
+        for (id, particle) in particles.iter_with_ids() {
+            let pos = [particle.x, particle.y, particle.z];
+            if !pos.iter().all(|v| v.is_finite()) {
+                continue;
+            }
+            let key = self.key(pos);
+            self.cells.entry(key).or_default().push((id, pos));
+        }
+    }
+
+    pub fn for_each_neighbor<F: FnMut(ParticleId, [f32; 3], f32)>(&self, pos: [f32; 3], radius: f32, mut f: F) {
+        if !pos.iter().all(|v| v.is_finite()) {
+            return;
+        }
+        let reach = (radius / self.cell_size).ceil() as i32;
+        let (cx, cy, cz) = self.key(pos);
+        let radius_sq = radius * radius;
+
+        for ix in cx.saturating_sub(reach)..=cx.saturating_add(reach) {
+            for iy in cy.saturating_sub(reach)..=cy.saturating_add(reach) {
+                for iz in cz.saturating_sub(reach)..=cz.saturating_add(reach) {
+                    let Some(entries) = self.cells.get(&(ix, iy, iz)) else {
+                        continue;
+                    };
+                    for &(id, other) in entries {
+                        let dx = other[0] - pos[0];
+                        let dy = other[1] - pos[1];
+                        let dz = other[2] - pos[2];
+                        let dist_sq = dx * dx + dy * dy + dz * dz;
+                        if dist_sq <= radius_sq {
+                            f(id, other, dist_sq.sqrt());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn count_within(&self, pos: [f32; 3], radius: f32) -> usize {
+        let mut count = 0;
+        self.for_each_neighbor(pos, radius, |_, _, _| count += 1);
+        count
+    }
+}
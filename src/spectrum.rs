@@ -0,0 +1,107 @@
+// Filename: spectrum.rs
+// Project: EntropicRust
+// Description: Live power spectrum of one coordinate of a reference particle. A sliding
+//              window of samples is Hann-windowed, transformed with rustfft on a worker
+//              thread and converted to log10 power per frequency bin.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+pub const SPECTRUM_WINDOW: usize = 1024;
+
+pub struct SpectrumAnalyzer {
+    samples: VecDeque<f32>,
+    fft: Arc<dyn Fft<f32>>,
+    // The transform of the latest full window, running on a worker thread.
+    job: Option<JoinHandle<Vec<[f32; 2]>>>,
+    pub power: Vec<[f32; 2]>,
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        SpectrumAnalyzer {
+            samples: VecDeque::with_capacity(SPECTRUM_WINDOW),
+            fft: FftPlanner::new().plan_fft_forward(SPECTRUM_WINDOW),
+            job: None,
+            power: Vec::with_capacity(SPECTRUM_WINDOW / 2),
+        }
+    }
+
+    // A job still running is left to finish on its own; its result is dropped.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.power.clear();
+        self.job = None;
+    }
+
+    pub fn push(&mut self, value: f32) {
+        if !value.is_finite() {
+            return;
+        }
+        if self.samples.len() >= SPECTRUM_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.samples.len() >= SPECTRUM_WINDOW
+    }
+
+    // Hands the current window to a worker, unless the previous one is still being
+    // transformed; the spectrum on screen is kept until the new one arrives.
+    pub fn start(&mut self, sample_dt: f32) {
+        if self.job.is_some() || !self.is_full() || sample_dt <= 0.0 {
+            return;
+        }
+        let fft = Arc::clone(&self.fft);
+        let samples: Vec<f32> = self.samples.iter().copied().collect();
+        self.job = Some(thread::spawn(move || power_spectrum(fft.as_ref(), &samples, sample_dt)));
+    }
+
+    // Returns false if the worker panicked.
+    pub fn poll(&mut self) -> bool {
+        if !self.job.as_ref().is_some_and(|job| job.is_finished()) {
+            return true;
+        }
+        match self.job.take().map(JoinHandle::join) {
+            Some(Ok(power)) => self.power = power,
+            Some(Err(_)) => return false,
+            None => {}
+        }
+        true
+    }
+}
+
+fn power_spectrum(fft: &dyn Fft<f32>, samples: &[f32], sample_dt: f32) -> Vec<[f32; 2]> {
+    let mean = samples.iter().sum::<f32>() / SPECTRUM_WINDOW as f32;
+    let n = SPECTRUM_WINDOW as f32;
+    let mut buffer: Vec<Complex<f32>> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            let hann = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (n - 1.0)).cos();
+            Complex::new((value - mean) * hann, 0.0)
+        })
+        .collect();
+    fft.process(&mut buffer);
+
+    let frequency_step = 1.0 / (n * sample_dt);
+    buffer
+        .iter()
+        .enumerate()
+        .take(SPECTRUM_WINDOW / 2)
+        .skip(1)
+        .map(|(k, bin)| [k as f32 * frequency_step, (bin.norm_sqr() / n).max(1.0e-12).log10()])
+        .collect()
+}
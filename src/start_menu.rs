@@ -0,0 +1,235 @@
+// Filename: start_menu.rs
+// Project: EntropicRust
+// Description: Start screen. Lists the systems as tiles with a small picture of each
+//              attractor, traced once at startup from the default parameters, and offers
+//              to resume the scene the last session ended on. The session is stored as a
+//              state string in `session.txt` when the program quits.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::dynamics;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::Accent;
+use crate::particle::SystemType;
+use crate::preset::Preset;
+use crate::share;
+use crate::system_parameters::{SystemParameters, get_default_time_step, get_view_preset};
+use crate::theme::Theme;
+
+pub const SESSION_PATH: &str = "session.txt";
+// Room for three rows of systems and the resume entry above the hint.
+const COLUMNS: usize = 4;
+const TILE_WIDTH: f32 = 170.0;
+const TILE_HEIGHT: f32 = 120.0;
+const GAP: f32 = 20.0;
+const TOP: f32 = 80.0;
+const RESUME_HEIGHT: f32 = 50.0;
+// Steps traced for each picture, after the transient is dropped.
+const TRANSIENT_STEPS: usize = 1_000;
+const THUMBNAIL_STEPS: usize = 4_000;
+const THUMBNAIL_DT: f32 = 0.01;
+
+pub enum Choice {
+    System(SystemType),
+    Resume(Preset),
+}
+
+pub struct StartMenu {
+    // Each system with its orbit, centred on 0 and scaled to fit a unit square.
+    thumbnails: Vec<(SystemType, Vec<Point2<f32>>)>,
+    session: Option<Preset>,
+    pub selected: usize,
+}
+
+impl StartMenu {
+    pub fn new(session: Option<Preset>) -> Self {
+        StartMenu {
+            thumbnails: SystemType::ALL.iter().map(|&system_type| (system_type, thumbnail(system_type))).collect(),
+            // With a session to resume, Enter picks up where it left off.
+            selected: if session.is_some() { SystemType::ALL.len() } else { 0 },
+            session,
+        }
+    }
+
+    fn entries(&self) -> usize {
+        self.thumbnails.len() + usize::from(self.session.is_some())
+    }
+
+    fn tile(index: usize) -> graphics::Rect {
+        let left = (SCREEN_WIDTH - COLUMNS as f32 * TILE_WIDTH - (COLUMNS - 1) as f32 * GAP) / 2.0;
+        let (column, row) = (index % COLUMNS, index / COLUMNS);
+        graphics::Rect::new(
+            left + column as f32 * (TILE_WIDTH + GAP),
+            TOP + row as f32 * (TILE_HEIGHT + GAP),
+            TILE_WIDTH,
+            TILE_HEIGHT,
+        )
+    }
+
+    fn resume_area(&self) -> graphics::Rect {
+        let first = Self::tile(0);
+        let last = Self::tile(self.thumbnails.len() - 1);
+        graphics::Rect::new(first.x, last.y + last.h + GAP, Self::tile(COLUMNS - 1).right() - first.x, RESUME_HEIGHT)
+    }
+
+    fn area(&self, index: usize) -> graphics::Rect {
+        if index < self.thumbnails.len() { Self::tile(index) } else { self.resume_area() }
+    }
+
+    pub fn entry_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.entries()).find(|&index| self.area(index).contains(Point2 { x, y }))
+    }
+
+    // Moves the selection by `columns` across and `rows` down, stopping at the edges.
+    pub fn move_selection(&mut self, columns: isize, rows: isize) {
+        let target = self.selected as isize + columns + rows * COLUMNS as isize;
+        let last = self.entries() as isize - 1;
+        // Down from the bottom row lands on the resume entry below it.
+        self.selected = if rows > 0 && target > last { last } else { target.clamp(0, last) } as usize;
+    }
+
+    pub fn choose(&self, index: usize) -> Option<Choice> {
+        match self.thumbnails.get(index) {
+            Some(&(system_type, _)) => Some(Choice::System(system_type)),
+            None => self.session.clone().filter(|_| index == self.thumbnails.len()).map(Choice::Resume),
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let font = graphics::Font::default();
+        let highlight = theme.palette.accent(Accent::Leader);
+        let title = graphics::Text::new(
+            graphics::TextFragment::new("EntropicRust").font(font).scale(graphics::PxScale::from(32.0)),
+        );
+        let width = title.width(ctx);
+        graphics::draw(
+            ctx,
+            &title,
+            graphics::DrawParam::default().dest([(SCREEN_WIDTH - width) / 2.0, 40.0]).color(theme.text),
+        )?;
+
+        let mut builder = graphics::MeshBuilder::new();
+        for index in 0..self.entries() {
+            let area = self.area(index);
+            let (frame, thickness) = if index == self.selected { (highlight, 2.0) } else { (theme.frame, 1.0) };
+            builder.rectangle(graphics::DrawMode::fill(), area, theme.panel)?;
+            builder.rectangle(graphics::DrawMode::stroke(thickness), area, frame)?;
+        }
+        let line = graphics::Color { a: 0.8, ..theme.palette.particle_color(graphics::Color::new(0.6, 0.8, 1.0, 1.0)) };
+        for (index, (_, points)) in self.thumbnails.iter().enumerate() {
+            let area = Self::tile(index);
+            // Below the caption, with a margin.
+            let size = (area.w - 20.0).min(area.h - 40.0);
+            let centre = Point2 { x: area.x + area.w / 2.0, y: area.y + 30.0 + (area.h - 30.0) / 2.0 };
+            let points: Vec<Point2<f32>> =
+                points.iter().map(|p| Point2 { x: centre.x + p.x * size, y: centre.y + p.y * size }).collect();
+            if points.len() >= 2 {
+                builder.line(&points, 1.0, line)?;
+            }
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        for (index, (system_type, _)) in self.thumbnails.iter().enumerate() {
+            let area = Self::tile(index);
+            let mut caption = graphics::Text::new(
+                graphics::TextFragment::new(format!("{}  {}", system_type.key_label(), system_type.name()))
+                    .font(font)
+                    .scale(graphics::PxScale::from(16.0)),
+            );
+            // Longer names wrap rather than run out of the tile.
+            caption.set_bounds(Point2 { x: area.w - 16.0, y: f32::INFINITY }, graphics::Align::Left);
+            graphics::draw(
+                ctx,
+                &caption,
+                graphics::DrawParam::default().dest([area.x + 8.0, area.y + 8.0]).color(theme.text),
+            )?;
+        }
+        if let Some(session) = &self.session {
+            let area = self.resume_area();
+            let label = graphics::Text::new(
+                graphics::TextFragment::new(format!(
+                    "Resume last session: {}, {} particles",
+                    session.system_type.name(),
+                    session.particles.unwrap_or_default()
+                ))
+                .font(font)
+                .scale(graphics::PxScale::from(18.0)),
+            );
+            let (w, h) = (label.width(ctx), label.height(ctx));
+            graphics::draw(
+                ctx,
+                &label,
+                graphics::DrawParam::default()
+                    .dest([area.x + (area.w - w) / 2.0, area.y + (area.h - h) / 2.0])
+                    .color(theme.text),
+            )?;
+        }
+
+        let hint = "Click or press Enter to start, arrows to choose, 1-6 or Shift+1-6 for a system, Esc to quit";
+        let hint =
+            graphics::Text::new(graphics::TextFragment::new(hint).font(font).scale(graphics::PxScale::from(14.0)));
+        let width = hint.width(ctx);
+        graphics::draw(
+            ctx,
+            &hint,
+            graphics::DrawParam::default()
+                .dest([(SCREEN_WIDTH - width) / 2.0, SCREEN_HEIGHT - 40.0])
+                .color(theme.frame),
+        )
+    }
+}
+
+// Orbit of one point from the default parameters, projected like the main view and fitted
+// into a unit square around 0.
+fn thumbnail(system_type: SystemType) -> Vec<Point2<f32>> {
+    let params = SystemParameters::new();
+    let view = get_view_preset(system_type);
+    let mut state = [0.1, 0.1, 0.1];
+    let mut points = Vec::with_capacity(THUMBNAIL_STEPS);
+    // Systems run at a finer step take several per point, to cover the same stretch of time.
+    let dt = get_default_time_step(system_type);
+    let substeps = (THUMBNAIL_DT / dt).round().max(1.0) as usize;
+    for step in 0..TRANSIENT_STEPS + THUMBNAIL_STEPS {
+        for _ in 0..substeps {
+            state = dynamics::step(system_type, &params, state, dt);
+        }
+        if !state.iter().all(|v| v.is_finite()) {
+            break;
+        }
+        if step >= TRANSIENT_STEPS {
+            points.push(view.project(state));
+        }
+    }
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+    for p in &points {
+        (min_x, max_x) = (min_x.min(p.x), max_x.max(p.x));
+        (min_y, max_y) = (min_y.min(p.y), max_y.max(p.y));
+    }
+    let span = (max_x - min_x).max(max_y - min_y).max(f32::EPSILON);
+    let centre = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    points.iter().map(|p| Point2 { x: (p.x - centre.0) / span, y: (p.y - centre.1) / span }).collect()
+}
+
+// No file means no session yet; a file that does not decode is an error.
+pub fn last_session() -> Result<Option<Preset>, String> {
+    let Ok(text) = fs::read_to_string(SESSION_PATH) else {
+        return Ok(None);
+    };
+    share::decode(&text).map(Some).map_err(|e| format!("{}: {}", SESSION_PATH, e))
+}
+
+pub fn save_session(preset: &Preset) -> Result<(), String> {
+    fs::write(SESSION_PATH, format!("{}\n", share::encode(preset)))
+        .map_err(|e| format!("Failed to write {}: {}", SESSION_PATH, e))
+}
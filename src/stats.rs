@@ -0,0 +1,31 @@
+// Filename: stats.rs
+// Project: EntropicRust
+// Description: Small numeric helpers shared by the analysis tools.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+pub fn linear_fit(xs: &[f32], ys: &[f32]) -> Option<(f32, f32)> {
+    let n = xs.len() as f32;
+    if xs.len() < 2 {
+        return None;
+    }
+    let mean_x = xs.iter().sum::<f32>() / n;
+    let mean_y = ys.iter().sum::<f32>() / n;
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+    }
+    if sxx <= 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    Some((slope, mean_y - slope * mean_x))
+}
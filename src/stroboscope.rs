@@ -0,0 +1,181 @@
+// Filename: stroboscope.rs
+// Project: EntropicRust
+// Description: Stroboscopic section for periodically forced systems. Each particle's
+//              (x, x') is sampled whenever its driving phase wraps past 2π, i.e. once per
+//              forcing period, and the samples accumulate into a Poincaré-like section.
+//              The section is shown in a corner inset over the attractor; swapping puts
+//              it in the window and the attractor, rendered offscreen, in the inset.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::theme::Theme;
+
+pub const MAX_SECTION_POINTS: usize = 20_000;
+
+pub struct StroboscopicSection {
+    points: VecDeque<[f32; 2]>,
+    // Section large and attractor in the inset.
+    pub swapped: bool,
+    // Attractor layer while swapped.
+    canvas: Option<graphics::Canvas>,
+    // Section points recorded since the last clear, including those since dropped.
+    pub crossings: u64,
+}
+
+impl StroboscopicSection {
+    pub fn new() -> Self {
+        StroboscopicSection {
+            points: VecDeque::with_capacity(MAX_SECTION_POINTS),
+            swapped: false,
+            canvas: None,
+            crossings: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.crossings = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    // The phase is kept in [0, 2π), so a drop from one step to the next marks a period.
+    pub fn record(&mut self, old_phase: f32, new_state: [f32; 3]) {
+        let [x, v, phase] = new_state;
+        if phase >= old_phase || !x.is_finite() || !v.is_finite() {
+            return;
+        }
+        if self.points.len() >= MAX_SECTION_POINTS {
+            self.points.pop_front();
+        }
+        self.points.push_back([x, v]);
+        self.crossings += 1;
+    }
+
+    // Same aspect as the window so both views show the same part of the plane.
+    pub fn inset() -> graphics::Rect {
+        graphics::Rect::new(SCREEN_WIDTH - 260.0, 20.0, 240.0, 180.0)
+    }
+
+    // While swapped, sends the attractor layer to the offscreen canvas. Called again after
+    // anything in that layer that switches canvases itself.
+    pub fn redirect(&mut self, ctx: &mut Context) -> GameResult {
+        if !self.swapped {
+            return Ok(());
+        }
+        if self.canvas.is_none() {
+            self.canvas = Some(graphics::Canvas::with_window_size(ctx)?);
+        }
+        graphics::set_canvas(ctx, self.canvas.as_ref());
+        Ok(())
+    }
+
+    // Clears the offscreen canvas; call once per frame before the attractor layer.
+    pub fn begin(&mut self, ctx: &mut Context, background: graphics::Color) -> GameResult {
+        if self.swapped {
+            self.redirect(ctx)?;
+            graphics::clear(ctx, background);
+        }
+        Ok(())
+    }
+
+    // Draws the large view and the inset, in window coordinates. `view` is the part of the
+    // projected view the camera shows; both views show the same part.
+    pub fn draw(&self, ctx: &mut Context, scale_factor: f32, view: graphics::Rect, theme: &Theme) -> GameResult {
+        let window = graphics::Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT);
+        let inset = Self::inset();
+        if self.swapped {
+            graphics::set_canvas(ctx, None);
+            // Covers whatever was drawn before the attractor layer.
+            let background = graphics::Mesh::new_rectangle(
+                ctx,
+                graphics::DrawMode::fill(),
+                window,
+                theme.background,
+            )?;
+            graphics::draw(ctx, &background, graphics::DrawParam::default())?;
+            self.draw_points(ctx, scale_factor, view, window, 2.0)?;
+        }
+
+        let background = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            inset,
+            theme.panel,
+        )?;
+        graphics::draw(ctx, &background, graphics::DrawParam::default())?;
+        match (&self.canvas, self.swapped) {
+            (Some(canvas), true) => graphics::draw(
+                ctx,
+                canvas,
+                graphics::DrawParam::default()
+                    .dest([inset.x, inset.y])
+                    .scale([inset.w / SCREEN_WIDTH, inset.h / SCREEN_HEIGHT]),
+            )?,
+            _ => self.draw_points(ctx, scale_factor, view, inset, 1.0)?,
+        }
+        let frame = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::stroke(1.0),
+            inset,
+            theme.frame,
+        )?;
+        graphics::draw(ctx, &frame, graphics::DrawParam::default())?;
+        let title = if self.swapped { "Attractor" } else { "Stroboscopic section" };
+        let title = graphics::Text::new(graphics::TextFragment::new(title)
+            .font(graphics::Font::default()).scale(graphics::PxScale::from(14.0)));
+        graphics::draw(
+            ctx,
+            &title,
+            graphics::DrawParam::default()
+                .dest([inset.x + 6.0, inset.y + 4.0])
+                .color(theme.text),
+        )
+    }
+
+    // Samples falling in `view` of the projected view, mapped onto `area` of the window.
+    fn draw_points(
+        &self,
+        ctx: &mut Context,
+        scale_factor: f32,
+        view: graphics::Rect,
+        area: graphics::Rect,
+        size: f32,
+    ) -> GameResult {
+        let color = graphics::Color::new(1.0, 0.85, 0.3, 0.9);
+        let mut builder = graphics::MeshBuilder::new();
+        let mut any = false;
+        for [x, v] in &self.points {
+            let projected = [SCREEN_WIDTH / 2.0 + x * scale_factor, SCREEN_HEIGHT / 2.0 + v * scale_factor];
+            if !view.contains(projected) {
+                continue;
+            }
+            let screen_x = area.x + (projected[0] - view.x) / view.w * area.w;
+            let screen_y = area.y + (projected[1] - view.y) / view.h * area.h;
+            builder.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(screen_x - size / 2.0, screen_y - size / 2.0, size, size),
+                color,
+            )?;
+            any = true;
+        }
+        if !any {
+            return Ok(());
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())
+    }
+}
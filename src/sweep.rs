@@ -0,0 +1,259 @@
+// Filename: sweep.rs
+// Project: EntropicRust
+// Description: Headless parameter sweeps. A one- or two-parameter grid of
+//              (parameter, seed) runs is distributed across the rayon thread pool and
+//              each cell is summarised (bounded fraction, largest Lyapunov exponent,
+//              mean speed) into a CSV file.
+//
+//              Usage: entropicrust sweep --system lorenz --x rho:0:50:64 [--y sigma:5:15:32]
+//                                        [--seeds 4] [--steps 20000] [--dt 0.01] [--out sweep.csv]
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::dynamics;
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, get_initial_ranges};
+
+const DIVERGENCE_LIMIT: f32 = 1.0e4;
+const LYAPUNOV_SEPARATION: f32 = 1.0e-4;
+
+#[derive(Clone)]
+pub struct SweepAxis {
+    pub name: String,
+    pub from: f32,
+    pub to: f32,
+    pub count: usize,
+}
+
+impl SweepAxis {
+    fn parse(spec: &str) -> Result<SweepAxis, String> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts.len() != 4 {
+            return Err(format!("expected name:from:to:count, got `{}`", spec));
+        }
+        let number = |s: &str| s.parse::<f32>().map_err(|_| format!("`{}` is not a number", s));
+        Ok(SweepAxis {
+            name: parts[0].to_string(),
+            from: number(parts[1])?,
+            to: number(parts[2])?,
+            count: parts[3].parse::<usize>().map_err(|_| format!("`{}` is not a count", parts[3]))?.max(1),
+        })
+    }
+
+    pub fn value(&self, i: usize) -> f32 {
+        if self.count <= 1 {
+            self.from
+        } else {
+            self.from + (self.to - self.from) * i as f32 / (self.count - 1) as f32
+        }
+    }
+}
+
+pub struct SweepSpec {
+    pub system_type: SystemType,
+    pub base: SystemParameters,
+    pub x: SweepAxis,
+    pub y: Option<SweepAxis>,
+    pub seeds: u64,
+    pub steps: usize,
+    pub transient: usize,
+    pub dt: f32,
+    pub out: String,
+}
+
+pub struct RunSummary {
+    pub bounded: bool,
+    pub lyapunov: f32,
+    pub mean_speed: f32,
+}
+
+pub struct CellSummary {
+    pub x: f32,
+    pub y: Option<f32>,
+    pub bounded_fraction: f32,
+    pub lyapunov: f32,
+    pub mean_speed: f32,
+}
+
+impl SweepSpec {
+    pub fn from_args(args: &[String]) -> Result<SweepSpec, String> {
+        let mut spec = SweepSpec {
+            system_type: SystemType::Lorenz,
+            base: SystemParameters::new(),
+            x: SweepAxis { name: "rho".to_string(), from: 0.0, to: 50.0, count: 64 },
+            y: None,
+            seeds: 4,
+            steps: 20_000,
+            transient: 1_000,
+            dt: 0.01,
+            out: "sweep.csv".to_string(),
+        };
+
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            let value = iter.next().ok_or_else(|| format!("{} needs a value", flag))?;
+            match flag.as_str() {
+                "--system" => {
+                    spec.system_type = SystemType::from_name(value)
+                        .ok_or_else(|| format!("unknown system `{}`", value))?
+                }
+                "--x" => spec.x = SweepAxis::parse(value)?,
+                "--y" => spec.y = Some(SweepAxis::parse(value)?),
+                "--seeds" => spec.seeds = value.parse().map_err(|_| "--seeds expects a count".to_string())?,
+                "--steps" => spec.steps = value.parse().map_err(|_| "--steps expects a count".to_string())?,
+                "--transient" => spec.transient = value.parse().map_err(|_| "--transient expects a count".to_string())?,
+                "--dt" => spec.dt = value.parse().map_err(|_| "--dt expects a number".to_string())?,
+                "--out" => spec.out = value.clone(),
+                _ => return Err(format!("unknown sweep option `{}`", flag)),
+            }
+        }
+
+        for axis in std::iter::once(&spec.x).chain(spec.y.iter()) {
+            if spec.base.get(&axis.name).is_none() {
+                return Err(format!("unknown parameter `{}`", axis.name));
+            }
+        }
+        Ok(spec)
+    }
+
+    pub fn run(&self) -> Vec<CellSummary> {
+        let rows = self.y.as_ref().map_or(1, |axis| axis.count);
+        let cells: Vec<(usize, usize)> = (0..rows)
+            .flat_map(|j| (0..self.x.count).map(move |i| (i, j)))
+            .collect();
+
+        cells
+            .into_par_iter()
+            .map(|(i, j)| {
+                let mut params = self.base;
+                let x = self.x.value(i);
+                params.set(&self.x.name, x);
+                let y = self.y.as_ref().map(|axis| {
+                    let y = axis.value(j);
+                    params.set(&axis.name, y);
+                    y
+                });
+
+                let runs: Vec<RunSummary> = (0..self.seeds)
+                    .map(|seed| run_single(self.system_type, &params, seed, self.steps, self.transient, self.dt))
+                    .collect();
+                summarise(x, y, &runs)
+            })
+            .collect()
+    }
+
+    pub fn write_csv(&self, cells: &[CellSummary]) -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(&self.out)?);
+        match &self.y {
+            Some(y_axis) => writeln!(out, "{},{},bounded_fraction,lyapunov,mean_speed", self.x.name, y_axis.name)?,
+            None => writeln!(out, "{},bounded_fraction,lyapunov,mean_speed", self.x.name)?,
+        }
+        for cell in cells {
+            match cell.y {
+                Some(y) => writeln!(out, "{},{},{},{},{}", cell.x, y, cell.bounded_fraction, cell.lyapunov, cell.mean_speed)?,
+                None => writeln!(out, "{},{},{},{}", cell.x, cell.bounded_fraction, cell.lyapunov, cell.mean_speed)?,
+            }
+        }
+        out.flush()
+    }
+}
+
+pub fn run_single(
+    system_type: SystemType,
+    params: &SystemParameters,
+    seed: u64,
+    steps: usize,
+    transient: usize,
+    dt: f32,
+) -> RunSummary {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (x_range, y_range, z_range) = get_initial_ranges(system_type);
+    let mut state = [rng.gen_range(x_range), rng.gen_range(y_range), rng.gen_range(z_range)];
+
+    for _ in 0..transient {
+        state = dynamics::step(system_type, params, state, dt);
+        if !is_bounded(state) {
+            return RunSummary { bounded: false, lyapunov: f32::NAN, mean_speed: f32::NAN };
+        }
+    }
+
+    let mut shadow = [state[0] + LYAPUNOV_SEPARATION, state[1], state[2]];
+    let mut log_growth = 0.0f64;
+    let mut speed_sum = 0.0f64;
+
+    for _ in 0..steps {
+        let [dx, dy, dz] = dynamics::derivative(system_type, params, state);
+        speed_sum += ((dx * dx + dy * dy + dz * dz) as f64).sqrt();
+
+        state = dynamics::step(system_type, params, state, dt);
+        shadow = dynamics::step(system_type, params, shadow, dt);
+        if !is_bounded(state) {
+            return RunSummary { bounded: false, lyapunov: f32::NAN, mean_speed: f32::NAN };
+        }
+
+        let separation = distance(state, shadow);
+        if separation > 0.0 && separation.is_finite() {
+            log_growth += ((separation / LYAPUNOV_SEPARATION) as f64).ln();
+            let k = LYAPUNOV_SEPARATION / separation;
+            shadow = [
+                state[0] + (shadow[0] - state[0]) * k,
+                state[1] + (shadow[1] - state[1]) * k,
+                state[2] + (shadow[2] - state[2]) * k,
+            ];
+        } else {
+            shadow = [state[0] + LYAPUNOV_SEPARATION, state[1], state[2]];
+        }
+    }
+
+    let steps = steps.max(1) as f64;
+    RunSummary {
+        bounded: true,
+        lyapunov: (log_growth / (steps * dt as f64)) as f32,
+        mean_speed: (speed_sum / steps) as f32,
+    }
+}
+
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let spec = SweepSpec::from_args(args)?;
+    let cells = spec.run();
+    spec.write_csv(&cells).map_err(|e| format!("failed to write {}: {}", spec.out, e))?;
+    println!("Wrote {} sweep cells to {}", cells.len(), spec.out);
+    Ok(())
+}
+
+fn summarise(x: f32, y: Option<f32>, runs: &[RunSummary]) -> CellSummary {
+    let bounded: Vec<&RunSummary> = runs.iter().filter(|run| run.bounded).collect();
+    let mean = |values: &mut dyn Iterator<Item = f32>| {
+        let (sum, n) = values.fold((0.0, 0usize), |(sum, n), v| (sum + v, n + 1));
+        if n == 0 { f32::NAN } else { sum / n as f32 }
+    };
+    CellSummary {
+        x,
+        y,
+        bounded_fraction: bounded.len() as f32 / runs.len().max(1) as f32,
+        lyapunov: mean(&mut bounded.iter().map(|run| run.lyapunov)),
+        mean_speed: mean(&mut bounded.iter().map(|run| run.mean_speed)),
+    }
+}
+
+fn is_bounded(state: [f32; 3]) -> bool {
+    state.iter().all(|v| v.is_finite() && v.abs() < DIVERGENCE_LIMIT)
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let dx = a[0] - b[0];
+    let dy = a[1] - b[1];
+    let dz = a[2] - b[2];
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
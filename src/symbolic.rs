@@ -0,0 +1,75 @@
+// Filename: symbolic.rs
+// Project: EntropicRust
+// Description: Symbolic dynamics on the two lobes of the Lorenz attractor. Each loop of
+//              the reference trajectory, marked by a local maximum of z, is labelled 1 if
+//              it winds around the x > 0 lobe and 0 otherwise, giving the binary itinerary
+//              behind the Lorenz map.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+use ggez::graphics;
+
+pub const SEQUENCE_LENGTH: usize = 64;
+
+pub const LOBE_COLORS: [graphics::Color; 2] = [
+    graphics::Color::new(0.3, 0.7, 1.0, 1.0),
+    graphics::Color::new(1.0, 0.55, 0.2, 1.0),
+];
+
+pub fn lobe(x: f32) -> usize {
+    usize::from(x > 0.0)
+}
+
+pub struct SymbolTracker {
+    pub echo: bool,
+    previous: Option<[f32; 3]>,
+    rising: bool,
+    sequence: VecDeque<u8>,
+}
+
+impl SymbolTracker {
+    pub fn new() -> Self {
+        SymbolTracker {
+            echo: false,
+            previous: None,
+            rising: false,
+            sequence: VecDeque::with_capacity(SEQUENCE_LENGTH),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.previous = None;
+        self.rising = false;
+        self.sequence.clear();
+    }
+
+    pub fn push(&mut self, state: [f32; 3]) {
+        let Some(previous) = self.previous.replace(state) else {
+            return;
+        };
+        let rising = state[2] > previous[2];
+        if self.rising && !rising {
+            let symbol = lobe(previous[0]) as u8;
+            if self.sequence.len() >= SEQUENCE_LENGTH {
+                self.sequence.pop_front();
+            }
+            self.sequence.push_back(symbol);
+            if self.echo {
+                println!("symbol {}", symbol);
+            }
+        }
+        self.rising = rising;
+    }
+
+    pub fn sequence(&self) -> String {
+        self.sequence.iter().map(|s| if *s == 1 { '1' } else { '0' }).collect()
+    }
+}
@@ -1,7 +1,7 @@
 // Filename: system_parameters.rs
 // Project: EntropicRust
 // Description: Defines the SystemParameters struct holding the constants for various
-//              chaotic systems (Lorenz, Rossler, etc.) and related helper functions like scaling.
+//              chaotic systems (Lorenz, Rossler, Duffing, etc.) and related helper functions like scaling.
 //
 // Author: Emanuel Lázaro
 // Contact: emanuellzr01@outlook.com
@@ -11,9 +11,30 @@
 // See LICENSE file for details.
 //
 
+use std::ops::Range;
+
+use entropicrust::systems::{
+    aizawa, chen_lee, duffing, food_chain, four_wing, lorenz, newton_leipnik, rabinovich_fabrikant, rossler, sakarya,
+    van_der_pol,
+};
+use ggez::mint::Point2;
+use rand::Rng;
+
+use crate::basin::BasinPlane;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
 use crate::particle::SystemType;
 
-#[derive(Clone, Copy)]
+// Longest parameter list of any system; sizes per-particle parameter jitter.
+pub const MAX_PARAMETERS: usize = 6;
+
+// Per-parameter multipliers, in the order of `get_parameter_names`.
+pub type Jitter = [f32; MAX_PARAMETERS];
+
+pub fn random_jitter<R: Rng>(amplitude: f32, rng: &mut R) -> Jitter {
+    std::array::from_fn(|_| 1.0 + rng.gen_range(-amplitude..=amplitude))
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
 pub struct SystemParameters {
     // Lorenz
     pub sigma: f32,
@@ -32,33 +53,513 @@ pub struct SystemParameters {
     pub p: f32,
     pub q: f32,
     pub r: f32,
+    // Duffing
+    pub duffing_delta: f32,
+    pub duffing_alpha: f32,
+    pub duffing_beta: f32,
+    pub duffing_gamma: f32,
+    pub duffing_omega: f32,
+    // Van der Pol
+    pub vdp_mu: f32,
+    pub vdp_amplitude: f32,
+    pub vdp_omega: f32,
+    // Rabinovich-Fabrikant
+    pub rf_alpha: f32,
+    pub rf_gamma: f32,
+    // Newton-Leipnik
+    pub nl_a: f32,
+    pub nl_b: f32,
+    // Four-wing
+    pub fw_a: f32,
+    pub fw_b: f32,
+    pub fw_c: f32,
+    // Sakarya
+    pub sk_a: f32,
+    pub sk_b: f32,
+    // Food chain
+    pub fc_a1: f32,
+    pub fc_b1: f32,
+    pub fc_a2: f32,
+    pub fc_b2: f32,
+    pub fc_d1: f32,
+    pub fc_d2: f32,
 }
 
 impl SystemParameters {
+    // Every system's registry defaults.
     pub fn new() -> Self {
-        SystemParameters {
-            sigma: 10.0,
-            rho: 28.0,
-            beta: 8.0 / 3.0,
-            a: 0.2,
-            b: 0.2,
-            c: 5.7,
-            alpha: 0.95,
-            gamma: 0.6,
-            delta: 3.5,
-            epsilon: 0.25,
-            p: 5.0,
-            q: -10.0,
-            r: -0.38,
+        let mut params = SystemParameters::default();
+        for info in SystemType::ALL.into_iter().flat_map(get_parameter_info) {
+            params.set(info.name, info.default);
         }
+        params
     }
 }
 
-pub fn get_scale_factor(system_type: SystemType) -> f32 {
+impl SystemParameters {
+    pub fn get(&self, name: &str) -> Option<f32> {
+        match name {
+            "sigma" => Some(self.sigma),
+            "rho" => Some(self.rho),
+            "beta" => Some(self.beta),
+            "a" => Some(self.a),
+            "b" => Some(self.b),
+            "c" => Some(self.c),
+            "alpha" => Some(self.alpha),
+            "gamma" => Some(self.gamma),
+            "delta" => Some(self.delta),
+            "epsilon" => Some(self.epsilon),
+            "p" => Some(self.p),
+            "q" => Some(self.q),
+            "r" => Some(self.r),
+            "duffing_delta" => Some(self.duffing_delta),
+            "duffing_alpha" => Some(self.duffing_alpha),
+            "duffing_beta" => Some(self.duffing_beta),
+            "duffing_gamma" => Some(self.duffing_gamma),
+            "duffing_omega" => Some(self.duffing_omega),
+            "vdp_mu" => Some(self.vdp_mu),
+            "vdp_amplitude" => Some(self.vdp_amplitude),
+            "vdp_omega" => Some(self.vdp_omega),
+            "rf_alpha" => Some(self.rf_alpha),
+            "rf_gamma" => Some(self.rf_gamma),
+            "nl_a" => Some(self.nl_a),
+            "nl_b" => Some(self.nl_b),
+            "fw_a" => Some(self.fw_a),
+            "fw_b" => Some(self.fw_b),
+            "fw_c" => Some(self.fw_c),
+            "sk_a" => Some(self.sk_a),
+            "sk_b" => Some(self.sk_b),
+            "fc_a1" => Some(self.fc_a1),
+            "fc_b1" => Some(self.fc_b1),
+            "fc_a2" => Some(self.fc_a2),
+            "fc_b2" => Some(self.fc_b2),
+            "fc_d1" => Some(self.fc_d1),
+            "fc_d2" => Some(self.fc_d2),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: f32) -> bool {
+        let slot = match name {
+            "sigma" => &mut self.sigma,
+            "rho" => &mut self.rho,
+            "beta" => &mut self.beta,
+            "a" => &mut self.a,
+            "b" => &mut self.b,
+            "c" => &mut self.c,
+            "alpha" => &mut self.alpha,
+            "gamma" => &mut self.gamma,
+            "delta" => &mut self.delta,
+            "epsilon" => &mut self.epsilon,
+            "p" => &mut self.p,
+            "q" => &mut self.q,
+            "r" => &mut self.r,
+            "duffing_delta" => &mut self.duffing_delta,
+            "duffing_alpha" => &mut self.duffing_alpha,
+            "duffing_beta" => &mut self.duffing_beta,
+            "duffing_gamma" => &mut self.duffing_gamma,
+            "duffing_omega" => &mut self.duffing_omega,
+            "vdp_mu" => &mut self.vdp_mu,
+            "vdp_amplitude" => &mut self.vdp_amplitude,
+            "vdp_omega" => &mut self.vdp_omega,
+            "rf_alpha" => &mut self.rf_alpha,
+            "rf_gamma" => &mut self.rf_gamma,
+            "nl_a" => &mut self.nl_a,
+            "nl_b" => &mut self.nl_b,
+            "fw_a" => &mut self.fw_a,
+            "fw_b" => &mut self.fw_b,
+            "fw_c" => &mut self.fw_c,
+            "sk_a" => &mut self.sk_a,
+            "sk_b" => &mut self.sk_b,
+            "fc_a1" => &mut self.fc_a1,
+            "fc_b1" => &mut self.fc_b1,
+            "fc_a2" => &mut self.fc_a2,
+            "fc_b2" => &mut self.fc_b2,
+            "fc_d1" => &mut self.fc_d1,
+            "fc_d2" => &mut self.fc_d2,
+            _ => return false,
+        };
+        *slot = value;
+        true
+    }
+
+    pub fn lorenz(&self) -> lorenz::Params {
+        lorenz::Params { sigma: self.sigma, rho: self.rho, beta: self.beta }
+    }
+
+    pub fn rossler(&self) -> rossler::Params {
+        rossler::Params { a: self.a, b: self.b, c: self.c }
+    }
+
+    pub fn aizawa(&self) -> aizawa::Params {
+        aizawa::Params {
+            alpha: self.alpha,
+            beta: self.beta,
+            gamma: self.gamma,
+            delta: self.delta,
+            epsilon: self.epsilon,
+        }
+    }
+
+    pub fn chen_lee(&self) -> chen_lee::Params {
+        chen_lee::Params { p: self.p, q: self.q, r: self.r }
+    }
+
+    pub fn duffing(&self) -> duffing::Params {
+        duffing::Params {
+            delta: self.duffing_delta,
+            alpha: self.duffing_alpha,
+            beta: self.duffing_beta,
+            gamma: self.duffing_gamma,
+            omega: self.duffing_omega,
+        }
+    }
+
+    pub fn van_der_pol(&self) -> van_der_pol::Params {
+        van_der_pol::Params { mu: self.vdp_mu, amplitude: self.vdp_amplitude, omega: self.vdp_omega }
+    }
+
+    pub fn rabinovich_fabrikant(&self) -> rabinovich_fabrikant::Params {
+        rabinovich_fabrikant::Params { alpha: self.rf_alpha, gamma: self.rf_gamma }
+    }
+
+    pub fn newton_leipnik(&self) -> newton_leipnik::Params {
+        newton_leipnik::Params { a: self.nl_a, b: self.nl_b }
+    }
+
+    pub fn four_wing(&self) -> four_wing::Params {
+        four_wing::Params { a: self.fw_a, b: self.fw_b, c: self.fw_c }
+    }
+
+    pub fn sakarya(&self) -> sakarya::Params {
+        sakarya::Params { a: self.sk_a, b: self.sk_b }
+    }
+
+    pub fn food_chain(&self) -> food_chain::Params {
+        food_chain::Params {
+            a1: self.fc_a1,
+            b1: self.fc_b1,
+            a2: self.fc_a2,
+            b2: self.fc_b2,
+            d1: self.fc_d1,
+            d2: self.fc_d2,
+        }
+    }
+
+    // Restores one system's parameters to their registry defaults, leaving the others alone.
+    pub fn reset_system(&mut self, system_type: SystemType) {
+        for info in get_parameter_info(system_type) {
+            self.set(info.name, info.default);
+        }
+    }
+
+    // Clamps the system's parameters into their registry ranges, returning the first one
+    // that had left its range together with the bound it was held at.
+    pub fn clamp_to_bounds(&mut self, system_type: SystemType) -> Option<(&'static ParameterInfo, f32)> {
+        let mut hit = None;
+        for info in get_parameter_info(system_type) {
+            if let Some(value) = self.get(info.name)
+                && !(info.min..=info.max).contains(&value)
+            {
+                let bound = value.clamp(info.min, info.max);
+                self.set(info.name, bound);
+                hit = hit.or(Some((info, bound)));
+            }
+        }
+        hit
+    }
+
+    pub fn jittered(&self, system_type: SystemType, jitter: &Jitter) -> SystemParameters {
+        let mut params = *self;
+        for (info, factor) in get_parameter_info(system_type).iter().zip(jitter) {
+            if let Some(value) = self.get(info.name) {
+                params.set(info.name, value * factor);
+            }
+        }
+        params
+    }
+}
+
+// Ratio lock between two parameters: `follower` is kept at `ratio` times `leader`, and
+// moving either one moves the other.
+#[derive(Clone, Copy)]
+pub struct ParameterLink {
+    pub leader: &'static str,
+    pub follower: &'static str,
+    pub ratio: f32,
+}
+
+impl ParameterLink {
+    // Locks the two parameters at the ratio they currently have.
+    pub fn new(leader: &'static str, follower: &'static str, params: &SystemParameters) -> Option<ParameterLink> {
+        let (lead, follow) = (params.get(leader)?, params.get(follower)?);
+        (lead.abs() > f32::EPSILON && leader != follower)
+            .then(|| ParameterLink { leader, follower, ratio: follow / lead })
+    }
+
+    // Re-establishes the lock after `params` has been changed from `before`, propagating
+    // from whichever side moved.
+    pub fn enforce(&self, before: &SystemParameters, params: &mut SystemParameters) {
+        let (Some(lead), Some(follow)) = (params.get(self.leader), params.get(self.follower)) else {
+            return;
+        };
+        if before.get(self.leader) != Some(lead) {
+            params.set(self.follower, lead * self.ratio);
+        } else if before.get(self.follower) != Some(follow) && self.ratio.abs() > f32::EPSILON {
+            params.set(self.leader, follow / self.ratio);
+        }
+    }
+}
+
+// Everything the UI, key handling, presets and scripts need to know about one parameter.
+pub struct ParameterInfo {
+    // Field and config name.
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub label: &'static str,
+    pub default: f32,
+    // Adjustments are clamped to this range, which keeps the system away from values that
+    // blow up numerically while leaving room around the classic regimes.
+    pub min: f32,
+    pub max: f32,
+    // Change per key press; `step_<name>` in the config overrides it.
+    pub step: f32,
+    // Whether the parameter has a key row (Q/A, W/S, E/D, R/F, in registry order).
+    pub keyed: bool,
+    pub description: &'static str,
+}
+
+const fn info(
+    name: &'static str,
+    symbol: &'static str,
+    label: &'static str,
+    (default, min, max, step): (f32, f32, f32, f32),
+    keyed: bool,
+    description: &'static str,
+) -> ParameterInfo {
+    ParameterInfo { name, symbol, label, default, min, max, step, keyed, description }
+}
+
+const LORENZ: [ParameterInfo; 3] = [
+    info("sigma", "σ", "Prandtl number", (10.0, 0.1, 100.0, 0.1), true, "Rate at which x relaxes towards y"),
+    info("rho", "ρ", "Rayleigh number", (28.0, 0.0, 300.0, 0.1), true, "Drive; chaos sets in near 24.74"),
+    info("beta", "β", "Geometric factor", (8.0 / 3.0, 0.01, 20.0, 0.01), true, "Damping of z"),
+];
+
+const ROSSLER: [ParameterInfo; 3] = [
+    info("a", "a", "Spiral growth", (0.2, 0.0, 2.0, 0.01), true, "Outward spiral rate in the x-y plane"),
+    info("b", "b", "Fold offset", (0.2, 0.0, 5.0, 0.01), true, "Constant injection into z"),
+    info("c", "c", "Fold threshold", (5.7, 0.1, 50.0, 0.01), true, "x beyond which z grows and folds the band"),
+];
+
+const AIZAWA: [ParameterInfo; 5] = [
+    info("alpha", "α", "Axial drive", (0.95, 0.0, 2.0, 0.01), true, "Constant push along z"),
+    info("gamma", "γ", "Radial offset", (0.6, 0.0, 2.0, 0.01), true, "Height at which orbits stop spiralling out"),
+    info("delta", "δ", "Rotation rate", (3.5, 0.0, 10.0, 0.01), true, "Angular speed around the z axis"),
+    info("epsilon", "ε", "Radial coupling", (0.25, 0.0, 1.0, 0.01), true, "How radius feeds back on z"),
+    // Aizawa reads the Lorenz β field, so the two systems share it.
+    info("beta", "β", "Axial damping", (8.0 / 3.0, 0.01, 20.0, 0.01), false, "Linear z term, shared with Lorenz"),
+];
+
+const CHEN_LEE: [ParameterInfo; 3] = [
+    info("p", "p", "x gain", (5.0, 0.1, 20.0, 0.1), true, "Self-excitation of x"),
+    info("q", "q", "y damping", (-10.0, -30.0, -0.1, 0.1), true, "Decay of y"),
+    info("r", "r", "z damping", (-0.38, -5.0, -0.01, 0.01), true, "Decay of z"),
+];
+
+const DUFFING: [ParameterInfo; 5] = [
+    info("duffing_delta", "δ", "Damping", (0.3, 0.0, 2.0, 0.01), true, "Velocity damping"),
+    info("duffing_gamma", "γ", "Forcing amplitude", (0.5, 0.0, 5.0, 0.01), true, "Strength of the periodic drive"),
+    info("duffing_omega", "ω", "Forcing frequency", (1.2, 0.05, 5.0, 0.01), true, "Angular frequency of the drive"),
+    info("duffing_alpha", "α", "Linear stiffness", (-1.0, -5.0, 5.0, 0.01), false, "Negative for a double well"),
+    info("duffing_beta", "β", "Cubic stiffness", (1.0, 0.0, 5.0, 0.01), false, "Hardening of the spring"),
+];
+
+const VAN_DER_POL: [ParameterInfo; 3] = [
+    info("vdp_mu", "μ", "Nonlinearity", (8.53, 0.0, 20.0, 0.1), true, "Strength of the nonlinear damping"),
+    info("vdp_amplitude", "A", "Forcing amplitude", (1.2, 0.0, 10.0, 0.01), true, "Strength of the periodic drive"),
+    info("vdp_omega", "ω", "Forcing frequency", (0.63, 0.05, 5.0, 0.01), true, "Angular frequency of the drive"),
+];
+
+// The multi-lobed regime; γ much above 0.2 sends most orbits off to infinity, and the
+// classic α = 1.1, γ = 0.87 attractor has a narrow basin.
+const RABINOVICH_FABRIKANT: [ParameterInfo; 2] = [
+    info("rf_alpha", "α", "Damping", (0.14, 0.05, 2.0, 0.01), true, "Decay of z, offset by the x-y product"),
+    info("rf_gamma", "γ", "Excitation", (0.1, 0.0, 1.0, 0.01), true, "Self-excitation of x and y"),
+];
+
+// Two strange attractors coexist at the defaults, one above the other; past b ≈ 0.5 orbits
+// escape.
+const NEWTON_LEIPNIK: [ParameterInfo; 2] = [
+    info("nl_a", "a", "x damping", (0.4, 0.05, 2.0, 0.01), true, "Decay of x"),
+    info("nl_b", "b", "z growth", (0.175, 0.0, 0.6, 0.005), true, "Self-excitation of z"),
+];
+
+// Symmetric under (x, y, z) -> (-x, -y, z); past a ≈ 0.6 orbits begin to escape.
+const FOUR_WING: [ParameterInfo; 3] = [
+    info("fw_a", "a", "x gain", (0.2, 0.0, 0.6, 0.01), true, "Self-excitation of x; widens the wings"),
+    info("fw_b", "b", "Cross feed", (0.01, -0.5, 0.5, 0.005), true, "How strongly x drives y"),
+    info("fw_c", "c", "y damping", (-0.4, -2.0, 0.0, 0.01), true, "Decay of y"),
+];
+
+const SAKARYA: [ParameterInfo; 2] = [
+    info("sk_a", "a", "x-z coupling", (0.4, 0.05, 2.0, 0.01), true, "How strongly x and z together drive y"),
+    info("sk_b", "b", "x-y coupling", (0.3, 0.05, 2.0, 0.01), true, "How strongly x and y together hold z back"),
+];
+
+// Hastings and Powell's values; b1 is the classic bifurcation parameter, chaotic (the
+// "teacup") from about 2.2 to 6.2.
+const FOOD_CHAIN: [ParameterInfo; 6] = [
+    info("fc_b1", "b1", "Prey handling", (3.0, 2.0, 6.5, 0.05), true, "How soon the predator's appetite saturates"),
+    info("fc_d1", "d1", "Predator death", (0.4, 0.05, 1.0, 0.01), true, "Death rate of the predator"),
+    info("fc_d2", "d2", "Top predator death", (0.01, 0.001, 0.1, 0.001), true, "Death rate of the top predator"),
+    info("fc_a1", "a1", "Prey attack", (5.0, 0.5, 10.0, 0.05), true, "Predator's attack rate on the prey"),
+    info("fc_a2", "a2", "Predator attack", (0.1, 0.01, 0.5, 0.005), false, "Top predator's attack rate"),
+    info("fc_b2", "b2", "Predator handling", (2.0, 0.5, 5.0, 0.05), false, "How soon the top predator saturates"),
+];
+
+pub fn get_parameter_info(system_type: SystemType) -> &'static [ParameterInfo] {
+    match system_type {
+        SystemType::Lorenz => &LORENZ,
+        SystemType::Rossler => &ROSSLER,
+        SystemType::Aizawa => &AIZAWA,
+        SystemType::ChenLee => &CHEN_LEE,
+        SystemType::Duffing => &DUFFING,
+        SystemType::VanDerPol => &VAN_DER_POL,
+        SystemType::RabinovichFabrikant => &RABINOVICH_FABRIKANT,
+        SystemType::FoodChain => &FOOD_CHAIN,
+        SystemType::Sakarya => &SAKARYA,
+        SystemType::FourWing => &FOUR_WING,
+        SystemType::NewtonLeipnik => &NEWTON_LEIPNIK,
+    }
+}
+
+pub fn get_parameter_names(system_type: SystemType) -> Vec<&'static str> {
+    get_parameter_info(system_type).iter().map(|info| info.name).collect()
+}
+
+// Parameters adjusted by the key rows Q/A, W/S, E/D and R/F, in that order.
+pub fn get_key_parameters(system_type: SystemType) -> Vec<&'static ParameterInfo> {
+    get_parameter_info(system_type).iter().filter(|info| info.keyed).collect()
+}
+
+// Looks a parameter up by name across all systems, e.g. for a name read from a file.
+pub fn find_parameter(name: &str) -> Option<&'static ParameterInfo> {
+    SystemType::ALL.into_iter().flat_map(get_parameter_info).find(|info| info.name == name)
+}
+
+pub fn get_parameter_bounds(name: &str) -> (f32, f32) {
+    find_parameter(name).map_or((f32::MIN, f32::MAX), |info| (info.min, info.max))
+}
+
+// Time step each system is shown at. Euler keeps almost every system on its attractor at
+// 0.01; Sakarya's orbits swing out to where they turn fast enough to be thrown off unless
+// the step is ten times smaller.
+pub fn get_default_time_step(system_type: SystemType) -> f32 {
     match system_type {
-        SystemType::Lorenz => 10.0,
-        SystemType::Rossler => 30.0,
-        SystemType::Aizawa => 100.0,
-        SystemType::ChenLee => 30.0,
+        SystemType::Sakarya => 0.001,
+        _ => 0.01,
     }
 }
+
+pub fn get_initial_ranges(system_type: SystemType) -> (Range<f32>, Range<f32>, Range<f32>) {
+    match system_type {
+        SystemType::Lorenz => (-1.0..1.0, -1.0..1.0, 15.0..25.0),
+        SystemType::Rossler => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
+        SystemType::Aizawa => (-0.1..0.1, -0.1..0.1, -0.1..0.1),
+        SystemType::ChenLee => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
+        // Above the invariant plane z = 0, which orbits cannot cross.
+        SystemType::RabinovichFabrikant => (-0.5..0.5, -0.5..0.5, 0.1..0.6),
+        // Straddles the basins of both attractors.
+        SystemType::NewtonLeipnik => (-0.5..0.5, -0.5..0.5, -0.5..0.5),
+        SystemType::FourWing => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
+        SystemType::Sakarya => (-1.0..1.0, -1.0..1.0, -1.0..1.0),
+        SystemType::FoodChain => (0.2..1.0, 0.05..0.4, 8.0..10.0),
+        // Driven systems start in phase with the forcing so their strobe samples line up.
+        SystemType::Duffing | SystemType::VanDerPol => (-1.0..1.0, -1.0..1.0, 0.0..1.0e-6),
+    }
+}
+
+// How a system is shown: the coordinate plane projected onto the screen, the point of
+// phase space at the centre of the view, pixels per unit and an in-plane rotation.
+#[derive(Clone, Copy)]
+pub struct ViewPreset {
+    pub plane: BasinPlane,
+    pub center: [f32; 3],
+    pub scale: f32,
+    // Degrees, clockwise on screen.
+    pub angle: f32,
+}
+
+impl ViewPreset {
+    // Screen-right, screen-down and towards-the-viewer components of `p` relative to the
+    // centre; the three form a right-handed frame, so depth is z for the x-y plane.
+    fn axes(&self, p: [f32; 3]) -> [f32; 3] {
+        let d = [p[0] - self.center[0], p[1] - self.center[1], p[2] - self.center[2]];
+        match self.plane {
+            BasinPlane::XY => [d[0], d[1], d[2]],
+            BasinPlane::XZ => [d[0], -d[2], d[1]],
+            BasinPlane::YZ => [d[1], -d[2], -d[0]],
+        }
+    }
+
+    pub fn project(&self, p: [f32; 3]) -> Point2<f32> {
+        let [u, v, _] = self.axes(p);
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        Point2 {
+            x: SCREEN_WIDTH / 2.0 + (u * cos - v * sin) * self.scale,
+            y: SCREEN_HEIGHT / 2.0 + (u * sin + v * cos) * self.scale,
+        }
+    }
+
+    pub fn depth(&self, p: [f32; 3]) -> f32 {
+        self.axes(p)[2]
+    }
+
+    // Coordinate measured by `depth`, for labels, given the names of the axes.
+    pub fn depth_axis(&self, names: [&str; 3]) -> String {
+        match self.plane {
+            BasinPlane::XY => names[2].to_string(),
+            BasinPlane::XZ => names[1].to_string(),
+            BasinPlane::YZ => format!("-{}", names[0]),
+        }
+    }
+
+    // Point of phase space under `screen` at the given depth.
+    pub fn unproject(&self, screen: Point2<f32>, depth: f32) -> [f32; 3] {
+        let (x, y) = ((screen.x - SCREEN_WIDTH / 2.0) / self.scale, (screen.y - SCREEN_HEIGHT / 2.0) / self.scale);
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (u, v) = (x * cos + y * sin, y * cos - x * sin);
+        let d = match self.plane {
+            BasinPlane::XY => [u, v, depth],
+            BasinPlane::XZ => [u, depth, -v],
+            BasinPlane::YZ => [-depth, u, -v],
+        };
+        [d[0] + self.center[0], d[1] + self.center[1], d[2] + self.center[2]]
+    }
+}
+
+// Lorenz shows its butterfly side-on in x-z, standing on z = 0. Aizawa is seen from above,
+// down its axis of rotation. Newton-Leipnik is side-on too, its two attractors stacked.
+// Driven systems must stay in x-y, the phase plane, because their z is the forcing phase.
+pub fn get_view_preset(system_type: SystemType) -> ViewPreset {
+    let view = |plane, center, scale| ViewPreset { plane, center, scale, angle: 0.0 };
+    match system_type {
+        SystemType::Lorenz => view(BasinPlane::XZ, [0.0, 0.0, 25.0], 10.0),
+        SystemType::Rossler => view(BasinPlane::XY, [0.0, 0.0, 0.0], 30.0),
+        SystemType::Aizawa => view(BasinPlane::XY, [0.0, 0.0, 0.0], 100.0),
+        SystemType::ChenLee => view(BasinPlane::XY, [0.0, 0.0, 0.0], 30.0),
+        SystemType::Duffing => view(BasinPlane::XY, [0.0, 0.0, 0.0], 150.0),
+        SystemType::VanDerPol => view(BasinPlane::XY, [0.0, 0.0, 0.0], 20.0),
+        SystemType::RabinovichFabrikant => view(BasinPlane::XY, [0.0, 0.0, 0.0], 100.0),
+        SystemType::NewtonLeipnik => view(BasinPlane::XZ, [0.0, 0.0, 0.1], 350.0),
+        SystemType::FourWing => view(BasinPlane::XY, [0.0, 0.0, 0.0], 110.0),
+        SystemType::Sakarya => view(BasinPlane::XY, [0.0, 0.0, 0.0], 14.0),
+        SystemType::FoodChain => view(BasinPlane::XY, [0.55, 0.22, 9.0], 600.0),
+    }
+}
+
+// Pixels per unit of phase space in the system's view.
+pub fn get_scale_factor(system_type: SystemType) -> f32 {
+    get_view_preset(system_type).scale
+}
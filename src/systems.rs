@@ -0,0 +1,518 @@
+// Filename: systems.rs
+// Project: EntropicRust
+// Description: The equations of each supported system as plain functions of a state, a
+//              parameter struct and a time step, with no window, particle or screen types
+//              involved. Part of the library target, so the maths can be tested, timed and
+//              run headless; the application's dynamics module dispatches into it.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::f32::consts::TAU;
+
+// (x, y, z); for the forced oscillators (x, x', θ) with θ the driving phase.
+pub type State = [f32; 3];
+
+// Explicit Euler step along `derivative`.
+fn euler(state: State, derivative: State, dt: f32) -> State {
+    [state[0] + derivative[0] * dt, state[1] + derivative[1] * dt, state[2] + derivative[2] * dt]
+}
+
+// Euler step for a forced oscillator, keeping the driving phase in [0, 2π).
+fn driven_euler(state: State, derivative: State, dt: f32) -> State {
+    let [x, y, phase] = euler(state, derivative, dt);
+    [x, y, phase.rem_euclid(TAU)]
+}
+
+// Classical fourth-order Runge-Kutta step along `f`, for systems Euler cannot hold.
+fn rk4<const N: usize>(state: [f32; N], f: impl Fn([f32; N]) -> [f32; N], dt: f32) -> [f32; N] {
+    let along = |k: [f32; N], h: f32| -> [f32; N] { std::array::from_fn(|i| state[i] + k[i] * h) };
+    let k1 = f(state);
+    let k2 = f(along(k1, dt / 2.0));
+    let k3 = f(along(k2, dt / 2.0));
+    let k4 = f(along(k3, dt));
+    std::array::from_fn(|i| state[i] + dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]))
+}
+
+pub mod lorenz {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub sigma: f32,
+        pub rho: f32,
+        pub beta: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [params.sigma * (y - x), x * (params.rho - z) - y, x * y - params.beta * z]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod rossler {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub a: f32,
+        pub b: f32,
+        pub c: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [-y - z, x + params.a * y, params.b + z * (x - params.c)]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod aizawa {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub alpha: f32,
+        pub beta: f32,
+        pub gamma: f32,
+        pub delta: f32,
+        pub epsilon: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        let dx = (z - params.gamma) * x - params.delta * y;
+        let dy = params.delta * x + (z - params.gamma) * y;
+        let dz = params.alpha + params.beta * z - z.powi(3) / 3.0 - (x * x + y * y) * (1.0 + params.epsilon * z)
+            + params.delta * z * x * x * x;
+        [dx, dy, dz]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod chen_lee {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub p: f32,
+        pub q: f32,
+        pub r: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [params.p * x - y * z, params.q * y + x * z, params.r * z + x * y / 3.0]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod rabinovich_fabrikant {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub alpha: f32,
+        pub gamma: f32,
+    }
+
+    // z = 0 is invariant, so orbits started above the plane stay above it.
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [
+            y * (z - 1.0 + x * x) + params.gamma * x,
+            x * (3.0 * z + 1.0 - x * x) + params.gamma * y,
+            -2.0 * z * (params.alpha + x * y),
+        ]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod newton_leipnik {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub a: f32,
+        pub b: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [-params.a * x + y + 10.0 * y * z, -x - 0.4 * y + 5.0 * x * z, params.b * z - 5.0 * x * y]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod four_wing {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub a: f32,
+        pub b: f32,
+        pub c: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [params.a * x + y * z, params.b * x + params.c * y - x * z, -z - x * y]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod sakarya {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub a: f32,
+        pub b: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        [-x + y + y * z, -x - y + params.a * x * z, z - params.b * x * y]
+    }
+
+    // Euler gains energy on the wide, fast outer arcs; the application runs this system at a
+    // tenth of the usual step (see `get_default_time_step`).
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt)
+    }
+}
+
+// Hastings and Powell's three-species food chain: prey x eaten by predator y, which is eaten
+// by top predator z, each with a saturating (Holling type II) appetite. Abundances are
+// scaled by the prey's carrying capacity and cannot go negative.
+pub mod food_chain {
+    use super::State;
+
+    // One unit of time here is ten in the original scaling, so a predator cycle takes a few
+    // seconds at the default step rather than a minute.
+    pub const TIME_UNIT: f32 = 10.0;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub a1: f32,
+        pub b1: f32,
+        pub a2: f32,
+        pub b2: f32,
+        pub d1: f32,
+        pub d2: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, z] = state;
+        let eaten = params.a1 * x / (1.0 + params.b1 * x) * y;
+        let hunted = params.a2 * y / (1.0 + params.b2 * y) * z;
+        [
+            TIME_UNIT * (x * (1.0 - x) - eaten),
+            TIME_UNIT * (eaten - hunted - params.d1 * y),
+            TIME_UNIT * (hunted - params.d2 * z),
+        ]
+    }
+
+    // Held in the positive octant, which a step can overshoot near extinction.
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::euler(state, derivative(state, params), dt).map(|v| v.max(0.0))
+    }
+}
+
+pub mod duffing {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub delta: f32,
+        pub alpha: f32,
+        pub beta: f32,
+        pub gamma: f32,
+        pub omega: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, phase] = state;
+        let dy = -params.delta * y - params.alpha * x - params.beta * x * x * x + params.gamma * phase.cos();
+        [y, dy, params.omega]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::driven_euler(state, derivative(state, params), dt)
+    }
+}
+
+pub mod van_der_pol {
+    use super::State;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub mu: f32,
+        pub amplitude: f32,
+        pub omega: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, phase] = state;
+        [y, params.mu * (1.0 - x * x) * y - x + params.amplitude * phase.sin(), params.omega]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::driven_euler(state, derivative(state, params), dt)
+    }
+}
+
+// Two rigid pendulums, the second hung from the bob of the first, with point masses on
+// massless rods and no friction. Not one of the attractor systems: the state is four
+// dimensional, (θ1, θ2, ω1, ω2), the angles from the downward vertical and their rates.
+pub mod double_pendulum {
+    pub type State = [f32; 4];
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        pub gravity: f32,
+        pub length1: f32,
+        pub length2: f32,
+        pub mass1: f32,
+        pub mass2: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [t1, t2, w1, w2] = state;
+        let Params { gravity: g, length1: l1, length2: l2, mass1: m1, mass2: m2 } = *params;
+        let delta = t1 - t2;
+        let denominator = 2.0 * m1 + m2 - m2 * (2.0 * delta).cos();
+        let a1 = (-g * (2.0 * m1 + m2) * t1.sin()
+            - m2 * g * (t1 - 2.0 * t2).sin()
+            - 2.0 * delta.sin() * m2 * (w2 * w2 * l2 + w1 * w1 * l1 * delta.cos()))
+            / (l1 * denominator);
+        let a2 = 2.0
+            * delta.sin()
+            * (w1 * w1 * l1 * (m1 + m2) + g * (m1 + m2) * t1.cos() + w2 * w2 * l2 * m2 * delta.cos())
+            / (l2 * denominator);
+        [w1, w2, a1, a2]
+    }
+
+    // Kinetic plus potential energy, zero with both rods horizontal and at rest; conserved
+    // by the equations, so its drift measures the integration error.
+    pub fn energy(state: State, params: &Params) -> f32 {
+        let [t1, t2, w1, w2] = state;
+        let Params { gravity: g, length1: l1, length2: l2, mass1: m1, mass2: m2 } = *params;
+        let kinetic = 0.5 * m1 * (l1 * w1).powi(2)
+            + 0.5 * m2 * ((l1 * w1).powi(2) + (l2 * w2).powi(2) + 2.0 * l1 * l2 * w1 * w2 * (t1 - t2).cos());
+        let potential = -(m1 + m2) * g * l1 * t1.cos() - m2 * g * l2 * t2.cos();
+        kinetic + potential
+    }
+
+    // Positions of the two bobs relative to the pivot, with y pointing down.
+    pub fn bobs(state: State, params: &Params) -> [[f32; 2]; 2] {
+        let [t1, t2, _, _] = state;
+        let first = [params.length1 * t1.sin(), params.length1 * t1.cos()];
+        [first, [first[0] + params.length2 * t2.sin(), first[1] + params.length2 * t2.cos()]]
+    }
+
+    // A Runge-Kutta step; Euler would feed the pendulums energy.
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::rk4(state, |s| derivative(s, params), dt)
+    }
+}
+
+// A bob swinging over three magnets set in the plane below it, pulled back towards the
+// middle and slowed by friction until one magnet holds it. The state is the bob's
+// position over the plane and its velocity, (x, y, vx, vy), with y pointing down.
+pub mod magnetic_pendulum {
+    pub type State = [f32; 4];
+
+    // On the unit circle, a third of a turn apart, the first straight up.
+    pub const MAGNETS: [[f32; 2]; 3] = [[0.0, -1.0], [-0.866_025_4, 0.5], [0.866_025_4, 0.5]];
+    // A bob this close to a magnet and this slow is not going anywhere else.
+    const CAPTURE_RADIUS: f32 = 0.2;
+    const CAPTURE_SPEED: f32 = 0.01;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Params {
+        // Strength of the pull back towards the point above the middle.
+        pub restoring: f32,
+        pub friction: f32,
+        // Gap between the bob and the plane of magnets; smaller makes sharper pulls.
+        pub height: f32,
+    }
+
+    pub fn derivative(state: State, params: &Params) -> State {
+        let [x, y, vx, vy] = state;
+        let mut ax = -params.restoring * x - params.friction * vx;
+        let mut ay = -params.restoring * y - params.friction * vy;
+        for [mx, my] in MAGNETS {
+            let (dx, dy) = (mx - x, my - y);
+            let distance = (dx * dx + dy * dy + params.height * params.height).powf(1.5);
+            ax += dx / distance;
+            ay += dy / distance;
+        }
+        [vx, vy, ax, ay]
+    }
+
+    pub fn step(state: State, params: &Params, dt: f32) -> State {
+        super::rk4(state, |s| derivative(s, params), dt)
+    }
+
+    // The magnet the bob has come to rest over, if any yet.
+    pub fn captured(state: State) -> Option<usize> {
+        let [x, y, vx, vy] = state;
+        if vx * vx + vy * vy > CAPTURE_SPEED * CAPTURE_SPEED {
+            return None;
+        }
+        MAGNETS.iter().position(|[mx, my]| (mx - x).powi(2) + (my - y).powi(2) < CAPTURE_RADIUS * CAPTURE_RADIUS)
+    }
+}
+
+// Point masses in the plane pulling on each other by Newton's law of gravity, with the
+// gravitational constant taken as 1.
+pub mod n_body {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Body {
+        pub mass: f32,
+        pub position: [f32; 2],
+        pub velocity: [f32; 2],
+    }
+
+    // Distances are taken as sqrt(r² + softening²), which caps the pull in a close pass
+    // that a fixed step would otherwise cross with a huge error. Zero is exact gravity.
+    fn accelerations(bodies: &[Body], softening: f32) -> Vec<[f32; 2]> {
+        let mut accelerations = vec![[0.0; 2]; bodies.len()];
+        for i in 0..bodies.len() {
+            for j in i + 1..bodies.len() {
+                let (a, b) = (&bodies[i], &bodies[j]);
+                let (dx, dy) = (b.position[0] - a.position[0], b.position[1] - a.position[1]);
+                let r2 = dx * dx + dy * dy + softening * softening;
+                let pull = 1.0 / (r2 * r2.sqrt());
+                accelerations[i][0] += b.mass * pull * dx;
+                accelerations[i][1] += b.mass * pull * dy;
+                accelerations[j][0] -= a.mass * pull * dx;
+                accelerations[j][1] -= a.mass * pull * dy;
+            }
+        }
+        accelerations
+    }
+
+    fn kick(bodies: &mut [Body], softening: f32, dt: f32) {
+        let accelerations = accelerations(bodies, softening);
+        for (body, a) in bodies.iter_mut().zip(accelerations) {
+            body.velocity[0] += a[0] * dt;
+            body.velocity[1] += a[1] * dt;
+        }
+    }
+
+    // Kick-drift-kick leapfrog. It is symplectic, so the energy error stays bounded over
+    // any number of orbits instead of creeping away as it does with Runge-Kutta.
+    pub fn step(bodies: &mut [Body], softening: f32, dt: f32) {
+        kick(bodies, softening, dt / 2.0);
+        for body in bodies.iter_mut() {
+            body.position[0] += body.velocity[0] * dt;
+            body.position[1] += body.velocity[1] * dt;
+        }
+        kick(bodies, softening, dt / 2.0);
+    }
+
+    pub fn energy(bodies: &[Body], softening: f32) -> f32 {
+        let mut energy = 0.0;
+        for (i, a) in bodies.iter().enumerate() {
+            energy += 0.5 * a.mass * (a.velocity[0].powi(2) + a.velocity[1].powi(2));
+            for b in &bodies[i + 1..] {
+                let (dx, dy) = (b.position[0] - a.position[0], b.position[1] - a.position[1]);
+                energy -= a.mass * b.mass / (dx * dx + dy * dy + softening * softening).sqrt();
+            }
+        }
+        energy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LORENZ: lorenz::Params = lorenz::Params { sigma: 10.0, rho: 28.0, beta: 8.0 / 3.0 };
+
+    fn assert_close(actual: &[f32], expected: &[f32], tolerance: f32) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= tolerance, "{:?} is not within {} of {:?}", actual, tolerance, expected);
+        }
+    }
+
+    fn relative_drift(energy: f32, initial: f32) -> f32 {
+        ((energy - initial) / initial).abs()
+    }
+
+    #[test]
+    fn lorenz_derivative_at_a_known_point() {
+        assert_close(&lorenz::derivative([1.0, 2.0, 3.0], &LORENZ), &[10.0, 23.0, -6.0], 1e-5);
+    }
+
+    #[test]
+    fn lorenz_fixed_points_are_stationary() {
+        let c = (LORENZ.beta * (LORENZ.rho - 1.0)).sqrt();
+        for point in [[0.0, 0.0, 0.0], [c, c, LORENZ.rho - 1.0], [-c, -c, LORENZ.rho - 1.0]] {
+            assert_close(&lorenz::derivative(point, &LORENZ), &[0.0; 3], 1e-4);
+            assert_close(&lorenz::step(point, &LORENZ, 0.01), &point, 1e-5);
+        }
+    }
+
+    #[test]
+    fn leapfrog_keeps_the_figure_eight_energy_bounded() {
+        let position = [0.970_004_4, -0.243_087_53];
+        let velocity = [-0.932_407_4, -0.864_731_46];
+        let half = [-velocity[0] / 2.0, -velocity[1] / 2.0];
+        let mut bodies = [
+            n_body::Body { mass: 1.0, position, velocity: half },
+            n_body::Body { mass: 1.0, position: [-position[0], -position[1]], velocity: half },
+            n_body::Body { mass: 1.0, position: [0.0, 0.0], velocity },
+        ];
+        let initial = n_body::energy(&bodies, 0.0);
+        // Ten periods of the orbit, each about 6.33 time units.
+        let mut worst: f32 = 0.0;
+        for _ in 0..63_300 {
+            n_body::step(&mut bodies, 0.0, 1e-3);
+            worst = worst.max(relative_drift(n_body::energy(&bodies, 0.0), initial));
+        }
+        assert!(worst < 1e-4, "energy drifted by {}", worst);
+    }
+
+    #[test]
+    fn double_pendulum_energy_barely_drifts_under_rk4() {
+        let params = double_pendulum::Params { gravity: 9.81, length1: 1.0, length2: 1.0, mass1: 1.0, mass2: 1.0 };
+        let mut state = [2.1, 2.1, 0.0, 0.0];
+        let initial = double_pendulum::energy(state, &params);
+        // Twenty seconds of chaotic swinging at the mode's substep.
+        for _ in 0..8_000 {
+            state = double_pendulum::step(state, &params, 0.0025);
+        }
+        let drift = relative_drift(double_pendulum::energy(state, &params), initial);
+        assert!(drift < 1e-3, "energy drifted by {}", drift);
+    }
+}
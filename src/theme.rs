@@ -0,0 +1,162 @@
+// Filename: theme.rs
+// Project: EntropicRust
+// Description: Visual themes. A theme sets the background, the palette, how trails fade
+//              along their length, the UI colours and the particle size. Themes are small
+//              TOML files in the `themes` directory, in the same flat subset as presets:
+//
+//                  background = "#f4f1ea"
+//                  palette = "okabe-ito"
+//                  particle_size = 2.5
+//
+//                  [trails]
+//                  alpha_head = 1.0
+//                  alpha_tail = 0.1
+//                  alpha_exponent = 1.5
+//
+//                  [ui]
+//                  text = "#202020"
+//                  panel = "#ffffffcc"
+//                  frame = "#808080"
+//
+//              Colours are `#rrggbb` or `#rrggbbaa`; missing keys keep the classic look.
+//              The directory is read again each time the theme changes, and the theme in
+//              use is reloaded when its file is saved, so themes can be edited live.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+use std::path::Path;
+
+use ggez::graphics;
+
+use crate::flat_toml::{self, Entry};
+use crate::palette::Palette;
+use crate::trail_gradient::TrailAlpha;
+
+pub const THEME_DIR: &str = "themes";
+const EXTENSION: &str = "toml";
+const CLASSIC: &str = "classic";
+
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+    pub background: graphics::Color,
+    pub palette: Palette,
+    pub trail_alpha: TrailAlpha,
+    pub text: graphics::Color,
+    // Fill and outline of the plot panels.
+    pub panel: graphics::Color,
+    pub frame: graphics::Color,
+    pub particle_size: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: CLASSIC.to_string(),
+            background: graphics::Color::new(0.1, 0.1, 0.15, 1.0),
+            palette: Palette::Classic,
+            trail_alpha: TrailAlpha::FLAT,
+            text: graphics::Color::WHITE,
+            panel: graphics::Color::new(0.0, 0.0, 0.0, 0.7),
+            frame: graphics::Color::new(0.6, 0.6, 0.7, 1.0),
+            particle_size: 2.0,
+        }
+    }
+}
+
+impl Theme {
+    pub fn parse(name: &str, text: &str) -> Result<Theme, String> {
+        let mut theme = Theme { name: name.to_string(), ..Theme::default() };
+        for Entry { line, section, key, value } in flat_toml::parse(text)? {
+            let error = |what: &str| format!("line {}: {} `{}`", line, what, value);
+            let number = || value.parse::<f32>().map_err(|_| error("expected a number, got"));
+            let color = || parse_color(value).ok_or_else(|| error("expected `#rrggbb` or `#rrggbbaa`, got"));
+
+            match (section, key) {
+                ("", "background") => theme.background = color()?,
+                ("", "palette") => theme.palette = Palette::from_name(value).ok_or_else(|| error("unknown palette"))?,
+                ("", "particle_size") => theme.particle_size = number()?.clamp(0.5, 10.0),
+                ("trails", "alpha_head") => theme.trail_alpha.head = number()?.clamp(0.0, 1.0),
+                ("trails", "alpha_tail") => theme.trail_alpha.tail = number()?.clamp(0.0, 1.0),
+                ("trails", "alpha_exponent") => theme.trail_alpha.exponent = number()?.clamp(0.1, 10.0),
+                ("ui", "text") => theme.text = color()?,
+                ("ui", "panel") => theme.panel = color()?,
+                ("ui", "frame") => theme.frame = color()?,
+                _ => return Err(format!("line {}: unknown key `{}`", line, key)),
+            }
+        }
+        Ok(theme)
+    }
+}
+
+fn parse_color(value: &str) -> Option<graphics::Color> {
+    let hex = value.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(graphics::Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+}
+
+// The classic theme followed by the theme files, by file name. Files that fail to parse
+// are left out, with a message for each.
+pub fn scan() -> (Vec<Theme>, Vec<String>) {
+    let mut themes = vec![Theme::default()];
+    let mut problems = Vec::new();
+    let Ok(entries) = fs::read_dir(THEME_DIR) else {
+        return (themes, problems);
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+        .collect();
+    paths.sort();
+    for path in &paths {
+        match load(path) {
+            Ok(theme) => themes.push(theme),
+            Err(e) => problems.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    (themes, problems)
+}
+
+// Theme from one file, named after it.
+pub fn load(path: &Path) -> Result<Theme, String> {
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Theme::parse(&name, &text)
+}
+
+// Theme the config asks for, read fresh from its file, with its palette override. The
+// classic theme stands in for one that is missing.
+pub fn configured(name: Option<&str>, palette: Option<Palette>) -> (Theme, Vec<String>) {
+    let mut theme = Theme::default();
+    let mut problems = Vec::new();
+    if let Some(name) = name {
+        let (themes, scan_problems) = scan();
+        problems = scan_problems;
+        match themes.into_iter().find(|theme| theme.name.eq_ignore_ascii_case(name)) {
+            Some(found) => theme = found,
+            None => problems.push(format!("no theme `{}` in {}/", name, THEME_DIR)),
+        }
+    }
+    if let Some(palette) = palette {
+        theme.palette = palette;
+    }
+    (theme, problems)
+}
+
+// The theme after `current` in the directory as it is now, wrapping to the classic one.
+pub fn next(current: &str) -> (Theme, Vec<String>) {
+    let (themes, problems) = scan();
+    let index = themes.iter().position(|theme| theme.name == current).map_or(0, |i| i + 1);
+    (themes.into_iter().nth(index).unwrap_or_default(), problems)
+}
@@ -0,0 +1,181 @@
+// Filename: three_body.rs
+// Project: EntropicRust
+// Description: Three-body mode. Three stars under their mutual gravity, set up as one of
+//              two classic problems: the figure-eight orbit, where equal masses chase each
+//              other along one closed curve, and Burrau's Pythagorean problem, where three
+//              stars released at rest swing through close passes until one is thrown out.
+//              Stepped with the leapfrog integrator and drawn with the usual trails.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+
+use entropicrust::systems::n_body::{self, Body};
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::double_pendulum::draw_text;
+use crate::groups::GROUP_PALETTE;
+use crate::locale::Locale;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::theme::Theme;
+use crate::trail_gradient::{self, TrailGradient, TrailWidth};
+
+// Leapfrog steps are at most this long, however fast the application's clock runs.
+const MAX_DT: f32 = 2.0e-4;
+const TRAIL_LENGTH: usize = 700;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Problem {
+    FigureEight,
+    Pythagorean,
+}
+
+impl Problem {
+    pub fn next(self) -> Problem {
+        match self {
+            Problem::FigureEight => Problem::Pythagorean,
+            Problem::Pythagorean => Problem::FigureEight,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Problem::FigureEight => "Figure-eight orbit",
+            Problem::Pythagorean => "Pythagorean problem",
+        }
+    }
+
+    pub fn caption(self) -> &'static str {
+        match self {
+            Problem::FigureEight => "Equal masses on one shared curve (Chenciner and Montgomery, 2000)",
+            Problem::Pythagorean => "Masses 3, 4 and 5 at rest on a 3-4-5 triangle (Burrau, 1913)",
+        }
+    }
+
+    fn bodies(self) -> Vec<Body> {
+        match self {
+            Problem::FigureEight => {
+                let position = [0.970_004_4, -0.243_087_53];
+                let velocity = [-0.932_407_4, -0.864_731_46];
+                let half = [-velocity[0] / 2.0, -velocity[1] / 2.0];
+                vec![
+                    Body { mass: 1.0, position, velocity: half },
+                    Body { mass: 1.0, position: [-position[0], -position[1]], velocity: half },
+                    Body { mass: 1.0, position: [0.0, 0.0], velocity },
+                ]
+            }
+            // Each star sits opposite the side of the triangle its mass is named for.
+            Problem::Pythagorean => vec![
+                Body { mass: 3.0, position: [1.0, 3.0], velocity: [0.0, 0.0] },
+                Body { mass: 4.0, position: [-2.0, -1.0], velocity: [0.0, 0.0] },
+                Body { mass: 5.0, position: [1.0, -1.0], velocity: [0.0, 0.0] },
+            ],
+        }
+    }
+
+    // The figure-eight never brings two stars close; the Pythagorean stars pass close
+    // enough that exact gravity would need a far shorter step than f32 can keep.
+    fn softening(self) -> f32 {
+        match self {
+            Problem::FigureEight => 0.0,
+            Problem::Pythagorean => 0.02,
+        }
+    }
+
+    fn pixels_per_unit(self) -> f32 {
+        match self {
+            Problem::FigureEight => 250.0,
+            Problem::Pythagorean => 60.0,
+        }
+    }
+}
+
+pub struct ThreeBody {
+    pub problem: Problem,
+    bodies: Vec<Body>,
+    // Recent positions of each star, in screen pixels.
+    trails: Vec<VecDeque<Point2<f32>>>,
+    pub paused: bool,
+    time: f32,
+    initial_energy: f32,
+}
+
+impl ThreeBody {
+    pub fn new(problem: Problem) -> Self {
+        let bodies = problem.bodies();
+        ThreeBody {
+            problem,
+            trails: vec![VecDeque::with_capacity(TRAIL_LENGTH); bodies.len()],
+            initial_energy: n_body::energy(&bodies, problem.softening()),
+            bodies,
+            paused: false,
+            time: 0.0,
+        }
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        let steps = (dt / MAX_DT).ceil().max(1.0) as usize;
+        for _ in 0..steps {
+            n_body::step(&mut self.bodies, self.problem.softening(), dt / steps as f32);
+        }
+        let scale = self.problem.pixels_per_unit();
+        for (trail, body) in self.trails.iter_mut().zip(&self.bodies) {
+            if trail.len() == TRAIL_LENGTH {
+                trail.pop_front();
+            }
+            trail.push_back(to_screen(body.position, scale));
+        }
+        self.time += dt;
+    }
+
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        theme: &Theme,
+        gradient: TrailGradient,
+        width: TrailWidth,
+        locale: Locale,
+    ) -> GameResult {
+        let scale = self.problem.pixels_per_unit();
+        let mut builder = graphics::MeshBuilder::new();
+        for (i, (trail, body)) in self.trails.iter().zip(&self.bodies).enumerate() {
+            let (r, g, b) = GROUP_PALETTE[i % GROUP_PALETTE.len()];
+            let color = theme.palette.particle_color(graphics::Color::new(r, g, b, 1.0));
+            let points: Vec<Point2<f32>> = trail.iter().copied().collect();
+            trail_gradient::append_trail(&mut builder, &points, 1.5, color, gradient, width, theme.trail_alpha)?;
+            // Heavier stars are drawn bigger, by volume.
+            let radius = 2.0 * theme.particle_size * body.mass.cbrt();
+            builder.circle(graphics::DrawMode::fill(), to_screen(body.position, scale), radius, 0.2, color)?;
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let energy = n_body::energy(&self.bodies, self.problem.softening());
+        let drift = (energy - self.initial_energy) / self.initial_energy.abs();
+        let status = format!(
+            "t = {}   energy drift {}{}",
+            locale.fixed(self.time, 2),
+            locale.scientific(drift, 1),
+            if self.paused { "   (paused)" } else { "" }
+        );
+        draw_text(ctx, self.problem.name(), [20.0, 20.0], 22.0, theme.text)?;
+        draw_text(ctx, self.problem.caption(), [20.0, 50.0], 14.0, theme.text)?;
+        draw_text(ctx, &status, [20.0, 70.0], 14.0, theme.text)?;
+        let hint = "Tab: other problem, R: start over, Space: pause, Esc or Ctrl+PageDown: back";
+        draw_text(ctx, hint, [20.0, SCREEN_HEIGHT - 40.0], 14.0, theme.frame)
+    }
+}
+
+// The centre of mass stays at the origin in both problems; y is drawn upwards.
+fn to_screen(position: [f32; 2], scale: f32) -> Point2<f32> {
+    Point2 { x: SCREEN_WIDTH / 2.0 + position[0] * scale, y: SCREEN_HEIGHT / 2.0 - position[1] * scale }
+}
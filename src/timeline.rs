@@ -0,0 +1,163 @@
+// Filename: timeline.rs
+// Project: EntropicRust
+// Description: Keyframed parameter timelines for scripted sequences. Each parameter has
+//              its own track of (time, value) keys; during playback the value between two
+//              keys is eased with a smoothstep so sweeps start and stop gently on video.
+//              Keys are captured from the live parameters or read from a text file with
+//              one `time parameter value` triple per line:
+//
+//                  # time  parameter  value
+//                  0       rho        20
+//                  30      rho        28
+//                  30      sigma      10
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+
+use crate::particle::SystemType;
+use crate::system_parameters::{SystemParameters, find_parameter, get_parameter_info};
+use crate::versioning::{self, Migration};
+
+pub const TIMELINE_PATH: &str = "timeline.txt";
+// Version 1 added the header; the lines are as before.
+const MIGRATIONS: &[Migration<String>] = &[versioning::unchanged];
+
+struct Track {
+    name: &'static str,
+    // Sorted by time; at most one key per timestamp.
+    keys: Vec<(f32, f32)>,
+}
+
+impl Track {
+    fn insert(&mut self, time: f32, value: f32) {
+        match self.keys.iter().position(|&(t, _)| t >= time) {
+            Some(i) if (self.keys[i].0 - time).abs() < f32::EPSILON => self.keys[i].1 = value,
+            Some(i) => self.keys.insert(i, (time, value)),
+            None => self.keys.push((time, value)),
+        }
+    }
+
+    // Holds the first and last values outside the keyed span.
+    fn value_at(&self, time: f32) -> Option<f32> {
+        let (first, last) = (self.keys.first()?, self.keys.last()?);
+        if time <= first.0 {
+            return Some(first.1);
+        }
+        if time >= last.0 {
+            return Some(last.1);
+        }
+        let i = self.keys.iter().position(|&(t, _)| t > time)?;
+        let ((t0, v0), (t1, v1)) = (self.keys[i - 1], self.keys[i]);
+        let s = (time - t0) / (t1 - t0);
+        Some(v0 + (v1 - v0) * s * s * (3.0 - 2.0 * s))
+    }
+}
+
+pub struct Timeline {
+    tracks: Vec<Track>,
+    // Simulated time since capture or playback started.
+    pub elapsed: f32,
+    pub playing: bool,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Timeline { tracks: Vec::new(), elapsed: 0.0, playing: false }
+    }
+
+    pub fn keyframe_count(&self) -> usize {
+        self.tracks.iter().map(|t| t.keys.len()).sum()
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.tracks.iter().filter_map(|t| t.keys.last()).map(|&(time, _)| time).fold(0.0, f32::max)
+    }
+
+    pub fn insert(&mut self, name: &'static str, time: f32, value: f32) {
+        match self.tracks.iter_mut().find(|t| t.name == name) {
+            Some(track) => track.insert(time, value),
+            None => self.tracks.push(Track { name, keys: vec![(time, value)] }),
+        }
+    }
+
+    // Keys every parameter of the system at its current value. The first capture starts
+    // the clock at zero.
+    pub fn capture(&mut self, system_type: SystemType, params: &SystemParameters) {
+        if self.tracks.is_empty() {
+            self.elapsed = 0.0;
+        }
+        for info in get_parameter_info(system_type) {
+            if let Some(value) = params.get(info.name) {
+                self.insert(info.name, self.elapsed, value);
+            }
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.elapsed = 0.0;
+        self.playing = !self.tracks.is_empty();
+    }
+
+    // Moves the clock on and, while playing, writes the interpolated values into `params`.
+    // Playback stops once the last key has been reached.
+    pub fn advance(&mut self, params: &mut SystemParameters, dt: f32) {
+        self.elapsed += dt;
+        if !self.playing {
+            return;
+        }
+        for track in &self.tracks {
+            if let Some(value) = track.value_at(self.elapsed) {
+                params.set(track.name, value);
+            }
+        }
+        if self.elapsed >= self.duration() {
+            self.playing = false;
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Timeline, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let text = versioning::upgrade_text(&text, "timeline", MIGRATIONS)?;
+        let mut timeline = Timeline::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [time, name, value] = fields[..] else {
+                return Err(format!("line {}: expected `time parameter value`", line_no + 1));
+            };
+            let time: f32 = time.parse().map_err(|_| format!("line {}: bad time `{}`", line_no + 1, time))?;
+            let value: f32 = value.parse().map_err(|_| format!("line {}: bad value `{}`", line_no + 1, value))?;
+            let Some(info) = find_parameter(name) else {
+                return Err(format!("line {}: unknown parameter `{}`", line_no + 1, name));
+            };
+            timeline.insert(info.name, time.max(0.0), value.clamp(info.min, info.max));
+        }
+        Ok(timeline)
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<usize> {
+        let mut keys: Vec<(f32, &str, f32)> = self
+            .tracks
+            .iter()
+            .flat_map(|track| track.keys.iter().map(move |&(time, value)| (time, track.name, value)))
+            .collect();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut text = versioning::header("timeline", MIGRATIONS);
+        text += "# time  parameter  value\n";
+        for (time, name, value) in &keys {
+            text += &format!("{:.3} {} {}\n", time, name, value);
+        }
+        fs::write(path, text)?;
+        Ok(keys.len())
+    }
+}
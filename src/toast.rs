@@ -0,0 +1,158 @@
+// Filename: toast.rs
+// Project: EntropicRust
+// Description: On-screen messages. Results, warnings and errors that would otherwise only
+//              reach the terminal show up as toasts in the bottom right corner for a few
+//              seconds, and stay in a log that can be brought up to read them later. Each
+//              message is still printed as well, so terminal output stays as it was.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::theme::Theme;
+
+const TOAST_SECS: f32 = 4.0;
+const FADE_SECS: f32 = 0.5;
+const MAX_TOASTS: usize = 4;
+const TOAST_WIDTH: f32 = 340.0;
+const LOG_LIMIT: usize = 200;
+const LOG_LINES: usize = 24;
+const PADDING: f32 = 8.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+
+    fn color(self, theme: &Theme) -> graphics::Color {
+        match self {
+            Level::Info => theme.frame,
+            Level::Warning => graphics::Color::new(1.0, 0.8, 0.3, 1.0),
+            Level::Error => graphics::Color::new(1.0, 0.4, 0.3, 1.0),
+        }
+    }
+}
+
+struct Message {
+    level: Level,
+    text: String,
+    at: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    // Oldest first.
+    log: VecDeque<Message>,
+    pub show_log: bool,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Level::Info, text.into());
+    }
+
+    pub fn warn(&mut self, text: impl Into<String>) {
+        self.push(Level::Warning, text.into());
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Level::Error, text.into());
+    }
+
+    fn push(&mut self, level: Level, text: String) {
+        match level {
+            Level::Info => println!("{}", text),
+            _ => eprintln!("{}", text),
+        }
+        if self.log.len() == LOG_LIMIT {
+            self.log.pop_front();
+        }
+        self.log.push_back(Message { level, text, at: Instant::now() });
+    }
+
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        if self.show_log {
+            self.draw_log(ctx, theme)?;
+        }
+        let font = graphics::Font::default();
+        let mut bottom = SCREEN_HEIGHT - 20.0;
+        let recent = self.log.iter().rev().take_while(|m| m.at.elapsed().as_secs_f32() < TOAST_SECS).take(MAX_TOASTS);
+        for message in recent {
+            let alpha = ((TOAST_SECS - message.at.elapsed().as_secs_f32()) / FADE_SECS).min(1.0);
+            let mut text = graphics::Text::new(
+                graphics::TextFragment::new(message.text.as_str()).font(font).scale(graphics::PxScale::from(14.0)),
+            );
+            text.set_bounds([TOAST_WIDTH - 2.0 * PADDING - 4.0, f32::INFINITY], graphics::Align::Left);
+            let height = text.height(ctx) + 2.0 * PADDING;
+            let area = graphics::Rect::new(SCREEN_WIDTH - TOAST_WIDTH - 20.0, bottom - height, TOAST_WIDTH, height);
+            let fade = |color: graphics::Color| graphics::Color { a: color.a * alpha, ..color };
+            let mut builder = graphics::MeshBuilder::new();
+            builder.rectangle(graphics::DrawMode::fill(), area, fade(theme.panel))?;
+            // A bar in the level's colour along the left edge.
+            builder.rectangle(
+                graphics::DrawMode::fill(),
+                graphics::Rect::new(area.x, area.y, 4.0, area.h),
+                fade(message.level.color(theme)),
+            )?;
+            let mesh = builder.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+            graphics::draw(
+                ctx,
+                &text,
+                graphics::DrawParam::default().dest([area.x + PADDING + 4.0, area.y + PADDING]).color(fade(theme.text)),
+            )?;
+            bottom -= height + 6.0;
+        }
+        Ok(())
+    }
+
+    // The latest messages, newest last, in a panel across the middle of the window.
+    fn draw_log(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let font = graphics::Font::default();
+        let mut text = graphics::Text::default();
+        text.add(graphics::TextFragment::new(format!("Messages ({})\n", self.log.len())).font(font).color(theme.text));
+        if self.log.is_empty() {
+            text.add(graphics::TextFragment::new("Nothing yet.").font(font).color(theme.frame));
+        }
+        for message in self.log.iter().skip(self.log.len().saturating_sub(LOG_LINES)) {
+            let age = message.at.elapsed().as_secs();
+            text.add(
+                graphics::TextFragment::new(format!("{:>5}s ago  {:<8}", age, message.level.name()))
+                    .font(font)
+                    .color(message.level.color(theme)),
+            );
+            text.add(graphics::TextFragment::new(format!("{}\n", message.text)).font(font).color(theme.text));
+        }
+        text.set_font(font, graphics::PxScale::from(14.0));
+        let width = SCREEN_WIDTH - 80.0;
+        text.set_bounds([width - 2.0 * PADDING, f32::INFINITY], graphics::Align::Left);
+        let area = graphics::Rect::new(40.0, 60.0, width, text.height(ctx) + 2.0 * PADDING);
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), area, theme.panel)?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), area, theme.frame)?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        graphics::draw(ctx, &text, graphics::DrawParam::default().dest([area.x + PADDING, area.y + PADDING]))
+    }
+}
@@ -0,0 +1,177 @@
+// Filename: tour.rs
+// Project: EntropicRust
+// Description: Guided tour for demos and outreach events. A text file lists the steps,
+//              one per line: how many seconds the step lasts, the system, any parameters
+//              that differ from its defaults, and after a `|` the caption to show:
+//
+//                  # seconds  system  [parameter=value ...] | caption
+//                  30  lorenz | Lorenz's 1963 model of a convecting fluid layer.
+//                  20  lorenz rho=99.96 | Far above the onset of chaos, a periodic window.
+//
+//              Each step brings up its scene with the caption and the live values of the
+//              system's parameters underneath, and the tour loops so it can run unattended.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs;
+use std::time::Instant;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::locale::Locale;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::particle::SystemType;
+use crate::preset::Preset;
+use crate::system_parameters::{SystemParameters, get_parameter_info};
+
+pub const TOUR_PATH: &str = "tour.txt";
+const PANEL_WIDTH: f32 = 560.0;
+const PADDING: f32 = 10.0;
+
+pub struct TourStep {
+    pub seconds: f32,
+    pub scene: Preset,
+    pub caption: String,
+}
+
+impl TourStep {
+    fn parse(line: &str) -> Result<TourStep, String> {
+        let (head, caption) = line.split_once('|').unwrap_or((line, ""));
+        let mut fields = head.split_whitespace();
+        let seconds = fields
+            .next()
+            .and_then(|s| s.parse::<f32>().ok())
+            .filter(|s| *s > 0.0)
+            .ok_or("expected a positive number of seconds first")?;
+        let system = fields.next().ok_or("expected a system after the seconds")?;
+        let system_type = SystemType::from_name(system).ok_or_else(|| format!("unknown system `{}`", system))?;
+        let mut values = Vec::new();
+        for field in fields {
+            let (name, value) =
+                field.split_once('=').ok_or_else(|| format!("expected `parameter=value`, got `{}`", field))?;
+            let info = get_parameter_info(system_type)
+                .iter()
+                .find(|info| info.name == name)
+                .ok_or_else(|| format!("{} has no parameter `{}`", system_type.name(), name))?;
+            let value = value.parse::<f32>().map_err(|_| format!("expected a number, got `{}`", value))?;
+            values.push((info.name, value.clamp(info.min, info.max)));
+        }
+        Ok(TourStep { seconds, scene: Preset::scene(system_type, &values), caption: caption.trim().to_string() })
+    }
+}
+
+pub struct Tour {
+    steps: Vec<TourStep>,
+    pub index: usize,
+    // When the current step came up.
+    shown: Instant,
+}
+
+impl Tour {
+    pub fn load(path: &str) -> Result<Tour, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut steps = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            // Only whole-line comments, so captions may contain `#`.
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            steps.push(TourStep::parse(line).map_err(|e| format!("line {}: {}", line_no + 1, e))?);
+        }
+        if steps.is_empty() {
+            return Err("no steps".to_string());
+        }
+        Ok(Tour { steps, index: 0, shown: Instant::now() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn current(&self) -> &TourStep {
+        &self.steps[self.index]
+    }
+
+    // Moves to the next step, after the last one back to the first.
+    pub fn skip(&mut self) -> &TourStep {
+        self.index = (self.index + 1) % self.steps.len();
+        self.shown = Instant::now();
+        self.current()
+    }
+
+    // Moves on once the current step's time is up.
+    pub fn advance(&mut self) -> Option<&TourStep> {
+        if self.shown.elapsed().as_secs_f32() < self.current().seconds {
+            return None;
+        }
+        Some(self.skip())
+    }
+
+    // Caption panel along the bottom of the window, with the live parameter values.
+    pub fn draw(&self, ctx: &mut Context, params: &SystemParameters, locale: Locale) -> GameResult {
+        let step = self.current();
+        let font = graphics::Font::default();
+        let text_width = PANEL_WIDTH - 2.0 * PADDING;
+        let title = graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "{}   (step {} of {})",
+                step.scene.system_type.name(),
+                self.index + 1,
+                self.steps.len()
+            ))
+            .font(font)
+            .scale(graphics::PxScale::from(18.0)),
+        );
+        let mut caption = graphics::Text::new(
+            graphics::TextFragment::new(step.caption.as_str()).font(font).scale(graphics::PxScale::from(16.0)),
+        );
+        caption.set_bounds([text_width, f32::INFINITY], graphics::Align::Left);
+        let lines: Vec<String> = get_parameter_info(step.scene.system_type)
+            .iter()
+            .filter_map(|info| {
+                let value = params.get(info.name)?;
+                Some(format!("{} = {}   {}: {}", info.symbol, locale.fixed(value, 3), info.label, info.description))
+            })
+            .collect();
+        let mut parameters = graphics::Text::new(
+            graphics::TextFragment::new(lines.join("\n")).font(font).scale(graphics::PxScale::from(14.0)),
+        );
+        parameters.set_bounds([text_width, f32::INFINITY], graphics::Align::Left);
+
+        let (title_h, caption_h, parameters_h) = (title.height(ctx), caption.height(ctx), parameters.height(ctx));
+        let height = title_h + caption_h + parameters_h + 4.0 * PADDING;
+        let area =
+            graphics::Rect::new((SCREEN_WIDTH - PANEL_WIDTH) / 2.0, SCREEN_HEIGHT - height - 20.0, PANEL_WIDTH, height);
+        let elapsed = (self.shown.elapsed().as_secs_f32() / step.seconds).min(1.0);
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), area, graphics::Color::new(0.0, 0.0, 0.0, 0.75))?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), area, graphics::Color::new(0.6, 0.6, 0.7, 1.0))?;
+        // Time left in the step, as a bar along the bottom edge.
+        builder.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(area.x, area.y + area.h - 3.0, area.w * elapsed, 3.0),
+            graphics::Color::new(0.4, 0.8, 1.0, 0.9),
+        )?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let x = area.x + PADDING;
+        let mut y = area.y + PADDING;
+        for (text, height, color) in [
+            (&title, title_h, graphics::Color::new(1.0, 0.85, 0.4, 1.0)),
+            (&caption, caption_h, graphics::Color::WHITE),
+            (&parameters, parameters_h, graphics::Color::new(0.75, 0.75, 0.8, 1.0)),
+        ] {
+            graphics::draw(ctx, text, graphics::DrawParam::default().dest(Point2 { x, y }).color(color))?;
+            y += height + PADDING;
+        }
+        Ok(())
+    }
+}
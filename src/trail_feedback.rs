@@ -0,0 +1,97 @@
+// Filename: trail_feedback.rs
+// Project: EntropicRust
+// Description: GPU feedback-texture trails. Particle heads are stamped into an offscreen
+//              canvas which is faded and re-blitted into a second canvas every frame
+//              (ping-pong), so trail cost no longer depends on history length.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::palette::Palette;
+use crate::particle::SystemType;
+use crate::particle_pool::ParticlePool;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrailMode {
+    Lines,
+    Feedback,
+}
+
+pub struct FeedbackTrails {
+    front: graphics::Canvas,
+    back: graphics::Canvas,
+    pub fade: f32,
+}
+
+impl FeedbackTrails {
+    pub fn new(ctx: &mut Context) -> GameResult<Self> {
+        let mut trails = FeedbackTrails {
+            front: graphics::Canvas::with_window_size(ctx)?,
+            back: graphics::Canvas::with_window_size(ctx)?,
+            fade: 0.96,
+        };
+        trails.clear(ctx)?;
+        Ok(trails)
+    }
+
+    pub fn clear(&mut self, ctx: &mut Context) -> GameResult {
+        for canvas in [&self.front, &self.back] {
+            graphics::set_canvas(ctx, Some(canvas));
+            graphics::clear(ctx, graphics::Color::new(0.0, 0.0, 0.0, 0.0));
+        }
+        graphics::set_canvas(ctx, None);
+        Ok(())
+    }
+
+    pub fn accumulate(
+        &mut self,
+        ctx: &mut Context,
+        particles: &ParticlePool,
+        system_type: SystemType,
+        view: graphics::Rect,
+        palette: Palette,
+    ) -> GameResult {
+        graphics::set_canvas(ctx, Some(&self.back));
+        graphics::clear(ctx, graphics::Color::new(0.0, 0.0, 0.0, 0.0));
+        graphics::draw(
+            ctx,
+            &self.front,
+            graphics::DrawParam::default().color(graphics::Color::new(1.0, 1.0, 1.0, self.fade)),
+        )?;
+
+        // Heads are placed through the camera view; the faded copy above is not, so trails
+        // left behind before a camera move stay where they were drawn.
+        let window = graphics::screen_coordinates(ctx);
+        graphics::set_screen_coordinates(ctx, view)?;
+        if particles.len() > 0 {
+            let mut heads = graphics::MeshBuilder::new();
+            for particle in particles.iter() {
+                heads.circle(
+                    graphics::DrawMode::fill(),
+                    particle.get_screen_pos(system_type),
+                    1.5,
+                    0.1,
+                    palette.particle_color(particle.color),
+                )?;
+            }
+            let mesh = heads.build(ctx)?;
+            graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+        }
+        graphics::set_screen_coordinates(ctx, window)?;
+
+        graphics::set_canvas(ctx, None);
+        std::mem::swap(&mut self.front, &mut self.back);
+        Ok(())
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        graphics::draw(ctx, &self.front, graphics::DrawParam::default())
+    }
+}
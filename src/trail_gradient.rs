@@ -0,0 +1,239 @@
+// Filename: trail_gradient.rs
+// Project: EntropicRust
+// Description: Trail colouring by point age. Each trail is tessellated into a strip whose
+//              vertex colours run from the head to the tail of a palette, either blending
+//              the particle's own colour towards a tail colour or walking a fixed colormap,
+//              so the direction of motion is readable from a still frame. The strip width
+//              can also follow the local speed, so slow folds and fast jumps read apart.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, GameResult};
+
+const FIRE_STOPS: [(f32, f32, f32); 4] = [(1.0, 1.0, 0.7), (1.0, 0.75, 0.1), (0.85, 0.2, 0.05), (0.25, 0.02, 0.02)];
+const ICE_STOPS: [(f32, f32, f32); 4] = [(1.0, 1.0, 1.0), (0.5, 0.9, 1.0), (0.15, 0.45, 0.9), (0.05, 0.05, 0.3)];
+const VIRIDIS_STOPS: [(f32, f32, f32); 5] = [
+    (0.99, 0.91, 0.14),
+    (0.37, 0.79, 0.38),
+    (0.13, 0.57, 0.55),
+    (0.23, 0.32, 0.55),
+    (0.27, 0.0, 0.33),
+];
+const DUSK_TAIL: (f32, f32, f32) = (0.25, 0.1, 0.45);
+// Speed-scaled widths stay within this factor of the base width either way.
+const MAX_WIDTH_RATIO: f32 = 4.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrailWidth {
+    Constant,
+    // Thick where the particle moves slowly, thin on fast stretches.
+    Inverse,
+    Direct,
+}
+
+impl TrailWidth {
+    pub fn from_name(name: &str) -> Option<TrailWidth> {
+        match name.to_ascii_lowercase().as_str() {
+            "constant" | "off" => Some(TrailWidth::Constant),
+            "inverse" | "slow" => Some(TrailWidth::Inverse),
+            "direct" | "fast" => Some(TrailWidth::Direct),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TrailWidth::Constant => "constant",
+            TrailWidth::Inverse => "thick when slow",
+            TrailWidth::Direct => "thick when fast",
+        }
+    }
+
+    // Spelling accepted by `from_name`, for config and preset files.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            TrailWidth::Constant => "constant",
+            TrailWidth::Inverse => "inverse",
+            TrailWidth::Direct => "direct",
+        }
+    }
+
+    pub fn next(self) -> TrailWidth {
+        match self {
+            TrailWidth::Constant => TrailWidth::Inverse,
+            TrailWidth::Inverse => TrailWidth::Direct,
+            TrailWidth::Direct => TrailWidth::Constant,
+        }
+    }
+
+    // Per-point widths. Speed is the screen distance covered per step around each point,
+    // taken relative to the trail's own mean so every trail uses its full width range.
+    fn widths(self, points: &[Point2<f32>], base: f32) -> Vec<f32> {
+        let n = points.len();
+        if self == TrailWidth::Constant {
+            return vec![base; n];
+        }
+        let speeds: Vec<f32> = (0..n)
+            .map(|i| {
+                let (a, b) = (points[i.saturating_sub(1)], points[(i + 1).min(n - 1)]);
+                let steps = ((i + 1).min(n - 1) - i.saturating_sub(1)).max(1) as f32;
+                ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt() / steps
+            })
+            .collect();
+        let mean = speeds.iter().sum::<f32>() / n as f32;
+        if mean <= f32::EPSILON {
+            return vec![base; n];
+        }
+        speeds
+            .iter()
+            .map(|speed| {
+                let ratio = (speed / mean).max(f32::EPSILON);
+                let factor = match self {
+                    TrailWidth::Inverse => 1.0 / ratio,
+                    _ => ratio,
+                };
+                base * factor.clamp(1.0 / MAX_WIDTH_RATIO, MAX_WIDTH_RATIO)
+            })
+            .collect()
+    }
+}
+
+// Opacity along a trail on top of its gradient, from `head` at the newest point to `tail`
+// at the oldest, with `exponent` shaping the curve in between.
+#[derive(Clone, Copy, PartialEq)]
+pub struct TrailAlpha {
+    pub head: f32,
+    pub tail: f32,
+    pub exponent: f32,
+}
+
+impl TrailAlpha {
+    pub const FLAT: TrailAlpha = TrailAlpha { head: 1.0, tail: 1.0, exponent: 1.0 };
+
+    pub fn at(self, age: f32) -> f32 {
+        self.head + (self.tail - self.head) * age.clamp(0.0, 1.0).powf(self.exponent)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrailGradient {
+    Solid,
+    Fade,
+    Dusk,
+    Fire,
+    Ice,
+    Viridis,
+}
+
+impl TrailGradient {
+    pub fn from_name(name: &str) -> Option<TrailGradient> {
+        match name.to_ascii_lowercase().as_str() {
+            "solid" | "off" | "none" => Some(TrailGradient::Solid),
+            "fade" => Some(TrailGradient::Fade),
+            "dusk" => Some(TrailGradient::Dusk),
+            "fire" => Some(TrailGradient::Fire),
+            "ice" => Some(TrailGradient::Ice),
+            "viridis" => Some(TrailGradient::Viridis),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TrailGradient::Solid => "solid",
+            TrailGradient::Fade => "fade",
+            TrailGradient::Dusk => "dusk",
+            TrailGradient::Fire => "fire",
+            TrailGradient::Ice => "ice",
+            TrailGradient::Viridis => "viridis",
+        }
+    }
+
+    pub fn next(self) -> TrailGradient {
+        match self {
+            TrailGradient::Solid => TrailGradient::Fade,
+            TrailGradient::Fade => TrailGradient::Dusk,
+            TrailGradient::Dusk => TrailGradient::Fire,
+            TrailGradient::Fire => TrailGradient::Ice,
+            TrailGradient::Ice => TrailGradient::Viridis,
+            TrailGradient::Viridis => TrailGradient::Solid,
+        }
+    }
+
+    // `age` runs from 0 at the head (newest point) to 1 at the tail (oldest point).
+    pub fn color(self, head: graphics::Color, age: f32) -> graphics::Color {
+        let age = age.clamp(0.0, 1.0);
+        match self {
+            TrailGradient::Solid => head,
+            TrailGradient::Fade => graphics::Color::new(head.r, head.g, head.b, head.a * (1.0 - age)),
+            TrailGradient::Dusk => {
+                let (r, g, b) = DUSK_TAIL;
+                graphics::Color::new(
+                    head.r + (r - head.r) * age,
+                    head.g + (g - head.g) * age,
+                    head.b + (b - head.b) * age,
+                    head.a * (1.0 - 0.6 * age),
+                )
+            }
+            TrailGradient::Fire => colormap(&FIRE_STOPS, age, head.a),
+            TrailGradient::Ice => colormap(&ICE_STOPS, age, head.a),
+            TrailGradient::Viridis => colormap(&VIRIDIS_STOPS, age, head.a),
+        }
+    }
+}
+
+fn colormap(stops: &[(f32, f32, f32)], t: f32, alpha: f32) -> graphics::Color {
+    let position = t * (stops.len() - 1) as f32;
+    let index = (position as usize).min(stops.len() - 2);
+    let f = position - index as f32;
+    let (r0, g0, b0) = stops[index];
+    let (r1, g1, b1) = stops[index + 1];
+    graphics::Color::new(r0 + (r1 - r0) * f, g0 + (g1 - g0) * f, b0 + (b1 - b0) * f, alpha)
+}
+
+// Appends `points` (oldest first, as stored in a particle trail) as a triangle strip around
+// the given base width whose vertex colours follow `gradient`, faded along `alpha`.
+pub fn append_trail(
+    builder: &mut graphics::MeshBuilder,
+    points: &[Point2<f32>],
+    width: f32,
+    head: graphics::Color,
+    gradient: TrailGradient,
+    width_mode: TrailWidth,
+    alpha: TrailAlpha,
+) -> GameResult {
+    let n = points.len();
+    if n < 2 {
+        return Ok(());
+    }
+    let widths = width_mode.widths(points, width);
+    let mut vertices = Vec::with_capacity(n * 2);
+    for (i, point) in points.iter().enumerate() {
+        // Average the directions of the two adjacent segments for a mitred joint.
+        let before = points[i.saturating_sub(1)];
+        let after = points[(i + 1).min(n - 1)];
+        let (dx, dy) = (after.x - before.x, after.y - before.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        let half = widths[i] * 0.5;
+        let (nx, ny) = if length > f32::EPSILON { (-dy / length * half, dx / length * half) } else { (0.0, 0.0) };
+        let age = 1.0 - i as f32 / (n - 1) as f32;
+        let mut color = gradient.color(head, age);
+        color.a *= alpha.at(age);
+        let color: [f32; 4] = color.into();
+        vertices.push(graphics::Vertex { pos: [point.x + nx, point.y + ny], uv: [0.0, 0.0], color });
+        vertices.push(graphics::Vertex { pos: [point.x - nx, point.y - ny], uv: [0.0, 0.0], color });
+    }
+    let mut indices = Vec::with_capacity((n - 1) * 6);
+    for i in 0..(n as u32 - 1) {
+        let (a, b, c, d) = (2 * i, 2 * i + 1, 2 * i + 2, 2 * i + 3);
+        indices.extend_from_slice(&[a, b, c, b, d, c]);
+    }
+    builder.raw(&vertices, &indices, None)?;
+    Ok(())
+}
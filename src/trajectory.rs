@@ -0,0 +1,94 @@
+// Filename: trajectory.rs
+// Project: EntropicRust
+// Description: Full-history trajectory recording for a single followed particle. Every
+//              simulation step appends (t, x, y, z) to memory, independent of the capped
+//              visual trail, and the record can be written out as CSV or JSON on request.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::particle::SystemType;
+use crate::particle_pool::{ParticleId, ParticlePool};
+
+pub const CSV_PATH: &str = "trajectory.csv";
+pub const JSON_PATH: &str = "trajectory.json";
+// About 80 MB of samples; recording stops rather than growing without bound.
+const MAX_SAMPLES: usize = 5_000_000;
+
+pub struct TrajectoryRecorder {
+    pub id: ParticleId,
+    pub system_type: SystemType,
+    pub elapsed: f32,
+    samples: Vec<[f32; 4]>,
+}
+
+impl TrajectoryRecorder {
+    pub fn new(id: ParticleId, system_type: SystemType, particles: &ParticlePool) -> Self {
+        let mut recorder = TrajectoryRecorder {
+            id,
+            system_type,
+            elapsed: 0.0,
+            samples: Vec::new(),
+        };
+        if let Some(p) = particles.get(id) {
+            recorder.samples.push([0.0, p.x, p.y, p.z]);
+        }
+        recorder
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.samples.len() >= MAX_SAMPLES
+    }
+
+    // Returns false once the followed particle no longer exists.
+    pub fn record(&mut self, particles: &ParticlePool, dt: f32) -> bool {
+        let Some(p) = particles.get(self.id) else {
+            return false;
+        };
+        self.elapsed += dt;
+        if !self.is_full() {
+            self.samples.push([self.elapsed, p.x, p.y, p.z]);
+        }
+        true
+    }
+
+    pub fn export_csv(&self) -> std::io::Result<usize> {
+        let mut out = BufWriter::new(File::create(CSV_PATH)?);
+        writeln!(out, "# {} trajectory, {} samples", self.system_type.name(), self.samples.len())?;
+        writeln!(out, "t,x,y,z")?;
+        for [t, x, y, z] in &self.samples {
+            writeln!(out, "{},{},{},{}", t, x, y, z)?;
+        }
+        out.flush()?;
+        Ok(self.samples.len())
+    }
+
+    pub fn export_json(&self) -> std::io::Result<usize> {
+        let mut out = BufWriter::new(File::create(JSON_PATH)?);
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"system\": \"{}\",", self.system_type.name())?;
+        writeln!(out, "  \"samples\": [")?;
+        for (i, [t, x, y, z]) in self.samples.iter().enumerate() {
+            let separator = if i + 1 < self.samples.len() { "," } else { "" };
+            // JSON has no NaN or infinity; diverged samples become null.
+            let number = |v: f32| if v.is_finite() { v.to_string() } else { "null".to_string() };
+            writeln!(out, "    [{}, {}, {}, {}]{}", number(*t), number(*x), number(*y), number(*z), separator)?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")?;
+        out.flush()?;
+        Ok(self.samples.len())
+    }
+}
@@ -0,0 +1,94 @@
+// Filename: transition.rs
+// Project: EntropicRust
+// Description: Cross-fade between systems. When the system changes, the outgoing cloud
+//              and its trails are kept as a still picture that fades out while the new
+//              particles fade in, and the view scale eases from the old system's to the
+//              new one's so the picture does not jump.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::time::Instant;
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::palette::Palette;
+use crate::particle::SystemType;
+use crate::particle_pool::ParticlePool;
+use crate::system_parameters::get_scale_factor;
+
+const DURATION_SECS: f32 = 0.8;
+
+pub struct SystemTransition {
+    started: Instant,
+    // Outgoing particles in the old system's screen positions.
+    heads: Vec<(Point2<f32>, graphics::Color)>,
+    trails: Vec<(Vec<Point2<f32>>, graphics::Color)>,
+    // Old scale over new scale.
+    scale_ratio: f32,
+}
+
+impl SystemTransition {
+    pub fn new(particles: &ParticlePool, from: SystemType, to: SystemType, palette: Palette) -> Self {
+        SystemTransition {
+            started: Instant::now(),
+            heads: particles
+                .iter()
+                .filter(|p| p.is_finite())
+                .map(|p| (p.get_screen_pos(from), palette.particle_color(p.color)))
+                .collect(),
+            trails: particles
+                .iter()
+                .filter(|p| p.trail.len() >= 2)
+                .map(|p| (p.trail.iter().copied().collect(), palette.particle_color(p.color)))
+                .collect(),
+            scale_ratio: get_scale_factor(from) / get_scale_factor(to),
+        }
+    }
+
+    // Eased from 0 at the switch to 1 when the transition is over.
+    pub fn progress(&self) -> f32 {
+        let t = (self.started.elapsed().as_secs_f32() / DURATION_SECS).min(1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.started.elapsed().as_secs_f32() >= DURATION_SECS
+    }
+
+    // Extra zoom on the new system's view: starts at the old scale, ends at its own. Eased
+    // on a log scale since the scales of two systems can differ tenfold.
+    pub fn zoom(&self) -> f32 {
+        self.scale_ratio.powf(1.0 - self.progress())
+    }
+
+    // Extra zoom that keeps the outgoing picture lined up with the new view.
+    pub fn outgoing_zoom(&self) -> f32 {
+        self.zoom() / self.scale_ratio
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let alpha = 1.0 - self.progress();
+        for (points, color) in &self.trails {
+            let color = graphics::Color { a: color.a * alpha, ..*color };
+            if let Ok(line) = graphics::Mesh::new_line(ctx, points, 1.0, color) {
+                graphics::draw(ctx, &line, graphics::DrawParam::default())?;
+            }
+        }
+        if self.heads.is_empty() {
+            return Ok(());
+        }
+        let mut builder = graphics::MeshBuilder::new();
+        for &(position, color) in &self.heads {
+            let color = graphics::Color { a: color.a * alpha, ..color };
+            builder.circle(graphics::DrawMode::fill(), position, 2.0, 0.1, color)?;
+        }
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())
+    }
+}
@@ -0,0 +1,173 @@
+// Filename: tutorial.rs
+// Project: EntropicRust
+// Description: First-run tutorial. A short sequence of steps points at the HUD and has the
+//              user change a parameter, toggle the trails and switch systems; the steps
+//              that ask for an action move on by themselves once the action shows up in
+//              the simulation state. Finishing or dismissing the tutorial turns it off in
+//              the config file so it does not come back on the next start.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::time::Instant;
+
+use ggez::{graphics, Context, GameResult};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::Accent;
+use crate::particle::SystemType;
+use crate::system_parameters::SystemParameters;
+use crate::theme::Theme;
+
+const PANEL_WIDTH: f32 = 380.0;
+const PADDING: f32 = 10.0;
+// HUD rows as draw_ui lays them out: 20 px apart from y = 20.
+const HUD_ROW: f32 = 20.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Step {
+    Hud,
+    Parameter,
+    Trails,
+    System,
+    Done,
+}
+
+impl Step {
+    const ALL: [Step; 5] = [Step::Hud, Step::Parameter, Step::Trails, Step::System, Step::Done];
+
+    fn text(self) -> &'static str {
+        match self {
+            Step::Hud => {
+                "The panel at the top left shows the current system and its parameters. Each \
+                 particle follows the system's equations from its own starting point."
+            }
+            Step::Parameter => {
+                "Change a parameter: Q raises the first one and A lowers it; W/S and E/D do the \
+                 same for the next ones. Hold Alt for fine steps."
+            }
+            Step::Trails => "Press T to toggle the trails, the lines each particle leaves behind.",
+            Step::System => "Press a number from 1 to 6 to switch to another system.",
+            Step::Done => {
+                "That's the basics. The help text under the HUD lists every key, and H hides \
+                 the HUD for a clear view."
+            }
+        }
+    }
+
+    // Part of the HUD the step is about.
+    fn highlight(self) -> Option<graphics::Rect> {
+        let row = |index: f32| graphics::Rect::new(14.0, HUD_ROW * (index + 1.0) - 3.0, 520.0, HUD_ROW);
+        match self {
+            Step::Hud => Some(graphics::Rect::new(14.0, HUD_ROW - 3.0, 520.0, 2.0 * HUD_ROW)),
+            Step::Parameter => Some(row(1.0)),
+            Step::System => Some(row(0.0)),
+            Step::Trails | Step::Done => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        Step::ALL.iter().position(|&step| step == self).unwrap_or(0)
+    }
+}
+
+// The parts of the simulation state the steps watch.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    pub parameters: SystemParameters,
+    pub trails: bool,
+    pub system_type: SystemType,
+}
+
+pub struct Tutorial {
+    pub step: Step,
+    // State when the current step came up, to tell when its action has been done.
+    baseline: Snapshot,
+    started: Instant,
+}
+
+impl Tutorial {
+    pub fn new(state: Snapshot) -> Self {
+        Tutorial { step: Step::Hud, baseline: state, started: Instant::now() }
+    }
+
+    // Moves to the next step; false once the last one is passed.
+    pub fn next(&mut self, state: Snapshot) -> bool {
+        let Some(&step) = Step::ALL.get(self.step.index() + 1) else {
+            return false;
+        };
+        self.step = step;
+        self.baseline = state;
+        true
+    }
+
+    // Moves on when the current step's action is seen in `state`.
+    pub fn observe(&mut self, state: Snapshot) {
+        let done = match self.step {
+            Step::Parameter => state.system_type == self.baseline.system_type
+                && state.parameters != self.baseline.parameters,
+            Step::Trails => state.trails != self.baseline.trails,
+            Step::System => state.system_type != self.baseline.system_type,
+            Step::Hud | Step::Done => false,
+        };
+        if done {
+            self.next(state);
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, theme: &Theme) -> GameResult {
+        let accent = theme.palette.accent(Accent::Leader);
+        let font = graphics::Font::default();
+        let text_width = PANEL_WIDTH - 2.0 * PADDING;
+        let title = graphics::Text::new(
+            graphics::TextFragment::new(format!("Tutorial {} of {}", self.step.index() + 1, Step::ALL.len()))
+                .font(font)
+                .scale(graphics::PxScale::from(16.0)),
+        );
+        let mut body = graphics::Text::new(
+            graphics::TextFragment::new(self.step.text()).font(font).scale(graphics::PxScale::from(15.0)),
+        );
+        body.set_bounds([text_width, f32::INFINITY], graphics::Align::Left);
+        let keys = match self.step {
+            Step::Parameter | Step::Trails | Step::System => "Enter: skip step   Backspace: don't show again",
+            Step::Done => "Enter: close",
+            Step::Hud => "Enter: next   Backspace: don't show again",
+        };
+        let keys =
+            graphics::Text::new(graphics::TextFragment::new(keys).font(font).scale(graphics::PxScale::from(13.0)));
+        let (title_h, body_h, keys_h) = (title.height(ctx), body.height(ctx), keys.height(ctx));
+        let height = title_h + body_h + keys_h + 4.0 * PADDING;
+        let area = graphics::Rect::new(
+            SCREEN_WIDTH - PANEL_WIDTH - 20.0,
+            SCREEN_HEIGHT - height - 60.0,
+            PANEL_WIDTH,
+            height,
+        );
+
+        let mut builder = graphics::MeshBuilder::new();
+        if let Some(highlight) = self.step.highlight() {
+            // A slow pulse draws the eye without flickering.
+            let pulse = 0.6 + 0.4 * (self.started.elapsed().as_secs_f32() * 3.0).sin().abs();
+            builder.rectangle(graphics::DrawMode::stroke(2.0), highlight, graphics::Color { a: pulse, ..accent })?;
+        }
+        builder.rectangle(graphics::DrawMode::fill(), area, theme.panel)?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), area, accent)?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let x = area.x + PADDING;
+        let mut y = area.y + PADDING;
+        for (text, height, color) in
+            [(&title, title_h, accent), (&body, body_h, theme.text), (&keys, keys_h, theme.frame)]
+        {
+            graphics::draw(ctx, text, graphics::DrawParam::default().dest([x, y]).color(color))?;
+            y += height + PADDING;
+        }
+        Ok(())
+    }
+}
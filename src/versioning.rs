@@ -0,0 +1,115 @@
+// Filename: versioning.rs
+// Project: EntropicRust
+// Description: Format versions for the files and strings the program saves. Text files
+//              start with a header comment naming their kind and version,
+//
+//                  # entropicrust timeline 1
+//
+//              and each format keeps a list of migrations, the n-th of which rewrites
+//              version n into version n + 1; the current version is the length of the list.
+//              Anything older is brought forward step by step before it is parsed, so files
+//              saved by earlier releases keep loading. Files from before headers existed
+//              read as version 0. Being a comment, the header is skipped by readers that
+//              predate it.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+const HEADER_PREFIX: &str = "# entropicrust ";
+
+pub type Migration<T> = fn(T) -> Result<T, String>;
+
+// For a version whose layout did not change, such as the first one with a header.
+pub fn unchanged<T>(data: T) -> Result<T, String> {
+    Ok(data)
+}
+
+// Header line for a text file of `kind` in its current format.
+pub fn header<T>(kind: &str, migrations: &[Migration<T>]) -> String {
+    format!("{}{} {}\n", HEADER_PREFIX, kind, migrations.len())
+}
+
+// Runs the migrations from `version` up to the current one.
+pub fn upgrade<T>(version: usize, data: T, migrations: &[Migration<T>]) -> Result<T, String> {
+    let Some(pending) = migrations.get(version..) else {
+        return Err(format!(
+            "format version {} is from a newer release; this one reads up to version {}",
+            version,
+            migrations.len()
+        ));
+    };
+    pending.iter().try_fold(data, |data, migrate| migrate(data))
+}
+
+// Text of a file of `kind` in the current format, whatever version it was saved in.
+pub fn upgrade_text(text: &str, kind: &str, migrations: &[Migration<String>]) -> Result<String, String> {
+    let header = text.lines().next().and_then(|line| line.strip_prefix(HEADER_PREFIX));
+    let version = match header.map(|rest| rest.split_whitespace().collect::<Vec<_>>()) {
+        None => 0,
+        Some(fields) => match fields[..] {
+            [found, version] if found == kind => {
+                version.parse().map_err(|_| format!("bad format version `{}`", version))?
+            }
+            [found, _] => return Err(format!("this is a {} file, not a {} file", found, kind)),
+            _ => return Err("malformed format header".to_string()),
+        },
+    };
+    upgrade(version, text.to_string(), migrations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::particle::SystemType;
+    use crate::preset::Preset;
+    use crate::share;
+
+    const TWO_STEPS: &[Migration<String>] = &[unchanged, |text| Ok(text.replace("old_key", "new_key"))];
+
+    #[test]
+    fn text_without_a_header_is_version_zero() {
+        let text = upgrade_text("old_key = 1\n", "preset", TWO_STEPS).unwrap();
+        assert_eq!(text, "new_key = 1\n");
+    }
+
+    #[test]
+    fn current_text_is_left_as_it_is() {
+        let text = header("preset", TWO_STEPS) + "old_key = 1\n";
+        assert_eq!(upgrade_text(&text, "preset", TWO_STEPS).unwrap(), text);
+    }
+
+    #[test]
+    fn another_kind_of_file_is_rejected() {
+        let text = header("timeline", TWO_STEPS);
+        assert_eq!(upgrade_text(&text, "preset", TWO_STEPS).unwrap_err(), "this is a timeline file, not a preset file");
+    }
+
+    #[test]
+    fn a_newer_version_is_rejected() {
+        assert!(upgrade_text("# entropicrust preset 3\n", "preset", TWO_STEPS).is_err());
+        assert!(upgrade(3, Vec::<u8>::new(), &[unchanged, unchanged]).is_err());
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        for text in ["# entropicrust preset\n", "# entropicrust preset 1 2\n", "# entropicrust preset one\n"] {
+            assert!(upgrade_text(text, "preset", TWO_STEPS).is_err(), "accepted {:?}", text);
+        }
+    }
+
+    #[test]
+    fn presets_and_state_strings_round_trip() {
+        let preset = Preset::scene(SystemType::Lorenz, &[("rho", 99.5)]);
+        let text = preset.to_toml();
+        assert_eq!(Preset::parse(&preset.name, &text).unwrap().to_toml(), text);
+
+        let decoded = share::decode(&share::encode(&preset)).unwrap();
+        assert!(decoded.system_type == preset.system_type);
+        assert_eq!(decoded.parameters, preset.parameters);
+    }
+}
@@ -0,0 +1,170 @@
+// Filename: wallpaper.rs
+// Project: EntropicRust
+// Description: Desktop live-wallpaper mode. With `--wallpaper` the borderless window is
+//              stretched to cover the monitor it opened on, keeping the scene's aspect
+//              ratio and cropping the overflow, then handed to the desktop: on X11 it
+//              becomes a sticky desktop-type window kept below all others, on Windows it
+//              is parented to the WorkerW window that sits behind the desktop icons. In
+//              both cases it takes no mouse input, so clicks reach the desktop beneath.
+//              The scene itself is the screensaver's attract loop at a low particle count
+//              and a capped frame rate.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::winit::dpi::{PhysicalPosition, PhysicalSize};
+use ggez::{graphics, Context};
+
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+pub const DEFAULT_PARTICLES: usize = 30;
+pub const DEFAULT_FPS: u32 = 30;
+
+// Turns the window into the desktop background. On failure the window is left as an
+// ordinary borderless one covering the monitor.
+pub fn attach(ctx: &mut Context) -> Result<(), String> {
+    cover_monitor(ctx);
+    platform::attach(ctx)
+}
+
+fn cover_monitor(ctx: &mut Context) {
+    let window = graphics::window(ctx);
+    let Some(monitor) = window.current_monitor() else {
+        return;
+    };
+    let (size, origin) = (monitor.size(), monitor.position());
+    let aspect = SCREEN_WIDTH / SCREEN_HEIGHT;
+    let width = (size.width as f32).max(size.height as f32 * aspect);
+    let height = width / aspect;
+    window.set_outer_position(PhysicalPosition::new(
+        origin.x + ((size.width as f32 - width) / 2.0) as i32,
+        origin.y + ((size.height as f32 - height) / 2.0) as i32,
+    ));
+    window.set_inner_size(PhysicalSize::new(width as u32, height as u32));
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use std::ffi::CString;
+    use std::ptr;
+
+    use ggez::winit::platform::unix::WindowExtUnix;
+    use ggez::{graphics, Context};
+    use x11_dl::{xfixes, xlib};
+
+    // From the X Shape extension; the region of a window that receives pointer input.
+    const SHAPE_INPUT: i32 = 2;
+
+    pub fn attach(ctx: &mut Context) -> Result<(), String> {
+        let window = graphics::window(ctx);
+        let (Some(display), Some(id)) = (window.xlib_display(), window.xlib_window()) else {
+            return Err("wallpaper mode needs an X11 session".to_string());
+        };
+        let xlib = xlib::Xlib::open().map_err(|e| format!("cannot load Xlib: {}", e))?;
+        let xfixes = xfixes::Xlib::open().map_err(|e| format!("cannot load XFixes: {}", e))?;
+        let display = display as *mut xlib::Display;
+        let atom = |name: &str| {
+            let name = CString::new(name).expect("atom names have no NUL");
+            unsafe { (xlib.XInternAtom)(display, name.as_ptr(), xlib::False) }
+        };
+        let set_atoms = |property: &str, values: &[xlib::Atom]| unsafe {
+            (xlib.XChangeProperty)(
+                display,
+                id,
+                atom(property),
+                xlib::XA_ATOM,
+                32,
+                xlib::PropModeReplace,
+                values.as_ptr() as *const u8,
+                values.len() as i32,
+            );
+        };
+        // Window managers read the type and state when a window is mapped, so it is mapped
+        // again once they are set.
+        unsafe { (xlib.XUnmapWindow)(display, id) };
+        set_atoms("_NET_WM_WINDOW_TYPE", &[atom("_NET_WM_WINDOW_TYPE_DESKTOP")]);
+        let states = [
+            atom("_NET_WM_STATE_BELOW"),
+            atom("_NET_WM_STATE_STICKY"),
+            atom("_NET_WM_STATE_SKIP_TASKBAR"),
+            atom("_NET_WM_STATE_SKIP_PAGER"),
+        ];
+        set_atoms("_NET_WM_STATE", &states);
+        unsafe {
+            // An empty input region lets the pointer through to whatever is underneath.
+            let region = (xfixes.XFixesCreateRegion)(display, ptr::null_mut(), 0);
+            (xfixes.XFixesSetWindowShapeRegion)(display, id, SHAPE_INPUT, 0, 0, region);
+            (xfixes.XFixesDestroyRegion)(display, region);
+            (xlib.XMapWindow)(display, id);
+            (xlib.XLowerWindow)(display, id);
+            (xlib.XFlush)(display);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::ptr;
+
+    use ggez::winit::platform::windows::WindowExtWindows;
+    use ggez::{graphics, Context};
+    use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+    use winapi::shared::windef::HWND;
+    use winapi::um::winuser::{
+        EnumWindows, FindWindowA, FindWindowExA, GetWindowLongPtrA, SendMessageTimeoutA, SetLayeredWindowAttributes,
+        SetParent, SetWindowLongPtrA, GWL_EXSTYLE, LWA_ALPHA, SMTO_NORMAL, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
+        WS_EX_TRANSPARENT,
+    };
+
+    // Undocumented Progman message that spawns a WorkerW window between the desktop icons
+    // and the wallpaper.
+    const SPAWN_WORKERW: u32 = 0x052C;
+
+    // Finds the WorkerW that follows the one holding the desktop icons.
+    unsafe extern "system" fn find_workerw(window: HWND, found: LPARAM) -> BOOL {
+        unsafe {
+            let icons = FindWindowExA(window, ptr::null_mut(), c"SHELLDLL_DefView".as_ptr(), ptr::null());
+            if !icons.is_null() {
+                *(found as *mut HWND) = FindWindowExA(ptr::null_mut(), window, c"WorkerW".as_ptr(), ptr::null());
+            }
+        }
+        TRUE
+    }
+
+    pub fn attach(ctx: &mut Context) -> Result<(), String> {
+        let window = graphics::window(ctx).hwnd() as HWND;
+        unsafe {
+            let progman = FindWindowA(c"Progman".as_ptr(), ptr::null());
+            if progman.is_null() {
+                return Err("no desktop window (Progman) to attach to".to_string());
+            }
+            let mut result: usize = 0;
+            SendMessageTimeoutA(progman, SPAWN_WORKERW, 0, 0, SMTO_NORMAL, 1000, &mut result);
+            let mut workerw: HWND = ptr::null_mut();
+            EnumWindows(Some(find_workerw), &mut workerw as *mut HWND as LPARAM);
+            // Without a WorkerW (Windows 7 with Aero off) Progman itself sits behind the icons.
+            SetParent(window, if workerw.is_null() { progman } else { workerw });
+            // Layered and transparent together make the window ignore the mouse.
+            let style = GetWindowLongPtrA(window, GWL_EXSTYLE);
+            let extra = (WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOOLWINDOW) as _;
+            SetWindowLongPtrA(window, GWL_EXSTYLE, style | extra);
+            SetLayeredWindowAttributes(window, 0, 255, LWA_ALPHA);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+mod platform {
+    use ggez::Context;
+
+    pub fn attach(_ctx: &mut Context) -> Result<(), String> {
+        Err("wallpaper mode is only available on Windows and X11".to_string())
+    }
+}
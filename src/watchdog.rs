@@ -0,0 +1,88 @@
+// Filename: watchdog.rs
+// Project: EntropicRust
+// Description: Simulation watchdog for unattended runs. Every frame it is shown whether the
+//              whole cloud has diverged and how long the frame took. A cloud that stays
+//              diverged is reset, and if it diverges again soon after, the parameters that
+//              keep throwing it out are reset too; frames that stay slow for several
+//              seconds make it shed particles. Each recovery is reported in the message log
+//              and appended, with a timestamp, to a log file next to the config, so an
+//              installation left running never sits on a black or frozen screen.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub const LOG_PATH: &str = "watchdog.log";
+// How long every particle has to stay diverged before the cloud is reset.
+const DIVERGED_SECS: f32 = 2.0;
+// A second divergence this soon after a reset resets the parameters as well.
+const RELAPSE_SECS: f32 = 30.0;
+// Frames slower than this, or than twice the frame cap if that is lower, count as slow.
+const SLOW_FRAME_SECS: f32 = 0.1;
+// How long frames have to stay slow before load is shed.
+const SLOW_SECS: f32 = 5.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Recovery {
+    ResetParticles,
+    ResetParameters,
+    ShedLoad,
+}
+
+pub struct Watchdog {
+    diverged_since: Option<Instant>,
+    slow_since: Option<Instant>,
+    last_reset: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new() -> Self {
+        Watchdog { diverged_since: None, slow_since: None, last_reset: None }
+    }
+
+    // Looks at one frame: whether no particle is left finite, how long the frame took in
+    // seconds, and the frame-rate cap. Returns the recovery due, if any.
+    pub fn check(&mut self, all_diverged: bool, frame_secs: f32, max_fps: Option<u32>) -> Option<Recovery> {
+        let now = Instant::now();
+        if !all_diverged {
+            self.diverged_since = None;
+        } else if let Some(since) = self.diverged_since {
+            if since.elapsed().as_secs_f32() >= DIVERGED_SECS {
+                self.diverged_since = None;
+                let relapse = self.last_reset.is_some_and(|at| at.elapsed().as_secs_f32() < RELAPSE_SECS);
+                self.last_reset = Some(now);
+                return Some(if relapse { Recovery::ResetParameters } else { Recovery::ResetParticles });
+            }
+        } else {
+            self.diverged_since = Some(now);
+        }
+
+        let slow_frame = max_fps.map_or(SLOW_FRAME_SECS, |fps| SLOW_FRAME_SECS.max(2.0 / fps as f32));
+        if frame_secs < slow_frame {
+            self.slow_since = None;
+        } else if let Some(since) = self.slow_since {
+            if since.elapsed().as_secs_f32() >= SLOW_SECS {
+                self.slow_since = None;
+                return Some(Recovery::ShedLoad);
+            }
+        } else {
+            self.slow_since = Some(now);
+        }
+        None
+    }
+}
+
+// Appends `text` to the watchdog log, stamped with seconds since the Unix epoch.
+pub fn log(text: &str) -> io::Result<()> {
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let mut file = OpenOptions::new().create(true).append(true).open(LOG_PATH)?;
+    writeln!(file, "{} {}", stamp, text)
+}
@@ -0,0 +1,100 @@
+// Filename: xy_pad.rs
+// Project: EntropicRust
+// Description: XY pad for two-parameter control. A square on the right of the window maps
+//              the pointer's horizontal and vertical position, while dragged inside it,
+//              onto two parameters of the current system at once, so the parameter plane
+//              can be swept continuously instead of one key step at a time. The pad spans
+//              the same neighbourhood around the values it was opened at as the chaos map,
+//              and cycles through the same parameter pairs.
+//
+// Author: Emanuel Lázaro
+// Contact: emanuellzr01@outlook.com
+// Copyright (c) 2025 Emanuel Lázaro
+//
+// License: MIT License
+// See LICENSE file for details.
+//
+
+use ggez::{graphics, mint::Point2, Context, GameResult};
+
+use crate::locale::Locale;
+use crate::main_state::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::param_scan::ScanAxes;
+use crate::particle::SystemType;
+use crate::system_parameters::SystemParameters;
+use crate::theme::Theme;
+
+const PAD_SIZE: f32 = 180.0;
+
+pub struct XyPad {
+    pub axes: ScanAxes,
+    pub pair: usize,
+    // The pointer went down inside the pad and has not been released yet.
+    pub dragging: bool,
+}
+
+impl XyPad {
+    pub fn new(system_type: SystemType, parameters: &SystemParameters, pair: usize) -> Self {
+        XyPad { axes: ScanAxes::for_pair(system_type, parameters, pair), pair, dragging: false }
+    }
+
+    pub fn area() -> graphics::Rect {
+        graphics::Rect::new(SCREEN_WIDTH - PAD_SIZE - 20.0, (SCREEN_HEIGHT - PAD_SIZE) / 2.0, PAD_SIZE, PAD_SIZE)
+    }
+
+    // Position of a window point across the pad, in [0, 1] on both axes with y pointing up.
+    pub fn fraction(x: f32, y: f32) -> (f32, f32) {
+        let area = XyPad::area();
+        (((x - area.x) / area.w).clamp(0.0, 1.0), (1.0 - (y - area.y) / area.h).clamp(0.0, 1.0))
+    }
+
+    // Sets both parameters for a point `(fx, fy)` across the pad.
+    pub fn apply(&self, parameters: &mut SystemParameters, fx: f32, fy: f32) {
+        parameters.set(self.axes.x.name, self.axes.x.value_at(fx));
+        parameters.set(self.axes.y.name, self.axes.y.value_at(fy));
+    }
+
+    // The pad with a marker at the current values, which other controls may have moved.
+    pub fn draw(&self, ctx: &mut Context, parameters: &SystemParameters, theme: &Theme, locale: Locale) -> GameResult {
+        let area = XyPad::area();
+        let mut builder = graphics::MeshBuilder::new();
+        builder.rectangle(graphics::DrawMode::fill(), area, theme.panel)?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), area, theme.frame)?;
+        let (x, y) = (parameters.get(self.axes.x.name), parameters.get(self.axes.y.name));
+        let along = |value: Option<f32>, from: f32, to: f32| {
+            ((value.unwrap_or(from) - from) / (to - from)).clamp(0.0, 1.0)
+        };
+        let marker = Point2 {
+            x: area.x + along(x, self.axes.x.from, self.axes.x.to) * area.w,
+            y: area.y + (1.0 - along(y, self.axes.y.from, self.axes.y.to)) * area.h,
+        };
+        let faint = graphics::Color::new(theme.frame.r, theme.frame.g, theme.frame.b, 0.4);
+        builder.line(&[Point2 { x: marker.x, y: area.y }, Point2 { x: marker.x, y: area.bottom() }], 1.0, faint)?;
+        builder.line(&[Point2 { x: area.x, y: marker.y }, Point2 { x: area.right(), y: marker.y }], 1.0, faint)?;
+        let color = if self.dragging { theme.text } else { theme.frame };
+        builder.circle(graphics::DrawMode::fill(), marker, 4.0, 0.1, color)?;
+        let mesh = builder.build(ctx)?;
+        graphics::draw(ctx, &mesh, graphics::DrawParam::default())?;
+
+        let value = |value: Option<f32>| value.map_or("--".to_string(), |v| locale.significant(v));
+        let label = graphics::Text::new(
+            graphics::TextFragment::new(format!(
+                "{} = {} (→){}{} = {} (↑)",
+                self.axes.x.name,
+                value(x),
+                locale.list_separator(),
+                self.axes.y.name,
+                value(y)
+            ))
+            .font(graphics::Font::default())
+            .scale(graphics::PxScale::from(14.0)),
+        );
+        graphics::draw(
+            ctx,
+            &label,
+            graphics::DrawParam::default()
+                .dest(Point2 { x: area.x, y: area.bottom() + 4.0 })
+                .color(theme.text),
+        )
+    }
+}